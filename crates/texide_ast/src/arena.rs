@@ -4,6 +4,9 @@
 //! All nodes for a single file are allocated in the same arena,
 //! and freed together when processing is complete.
 
+use std::ops::Deref;
+use std::sync::{Mutex, PoisonError};
+
 use bumpalo::Bump;
 
 /// Arena allocator for AST nodes.
@@ -62,6 +65,10 @@ impl AstArena {
     }
 
     /// Allocates a slice in the arena by copying from the input slice.
+    ///
+    /// Elements keep their order, so children allocated with this (or
+    /// [`alloc_slice_clone`](Self::alloc_slice_clone)) appear in the order
+    /// the parser collected them.
     #[inline]
     pub fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] {
         self.bump.alloc_slice_copy(slice)
@@ -94,6 +101,98 @@ impl Default for AstArena {
     }
 }
 
+/// A pool of reusable arenas.
+///
+/// Parsing many files with a fresh [`AstArena`] each allocates and frees
+/// the arena's memory once per file. The pool instead hands out arenas that
+/// are reset (not reallocated) when they are returned, so each worker keeps
+/// reusing the chunk sized for the largest file it has parsed.
+///
+/// At most one arena per concurrent user is ever created.
+///
+/// # Example
+///
+/// ```rust
+/// use texide_ast::ArenaPool;
+///
+/// let pool = ArenaPool::new();
+///
+/// for source in ["first", "second"] {
+///     let arena = pool.get();
+///     let s = arena.alloc_str(source);
+///     assert_eq!(s, source);
+/// } // The arena is reset and returned to the pool here
+///
+/// assert_eq!(pool.idle(), 1);
+/// ```
+#[derive(Default)]
+pub struct ArenaPool {
+    arenas: Mutex<Vec<AstArena>>,
+}
+
+impl ArenaPool {
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes an arena from the pool, creating one if none is idle.
+    ///
+    /// The arena is reset and returned to the pool when the guard is
+    /// dropped. Nodes allocated in it borrow the guard, so they cannot
+    /// outlive the reset.
+    pub fn get(&self) -> PooledArena<'_> {
+        let arena = self
+            .arenas
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop()
+            .unwrap_or_default();
+
+        PooledArena {
+            pool: self,
+            arena: Some(arena),
+        }
+    }
+
+    /// Returns the number of arenas waiting to be reused.
+    pub fn idle(&self) -> usize {
+        self.arenas
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+/// An arena borrowed from an [`ArenaPool`].
+///
+/// Dereferences to [`AstArena`].
+pub struct PooledArena<'p> {
+    pool: &'p ArenaPool,
+    arena: Option<AstArena>,
+}
+
+impl Deref for PooledArena<'_> {
+    type Target = AstArena;
+
+    fn deref(&self) -> &AstArena {
+        self.arena.as_ref().expect("arena is present until drop")
+    }
+}
+
+impl Drop for PooledArena<'_> {
+    fn drop(&mut self) {
+        if let Some(mut arena) = self.arena.take() {
+            arena.reset();
+            self.pool
+                .arenas
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(arena);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +230,35 @@ mod tests {
         // because the arena keeps the memory for reuse
         assert!(arena.allocated_bytes() > 0 || bytes_before > 0);
     }
+
+    #[test]
+    fn test_arena_pool_reuses_arena() {
+        let pool = ArenaPool::new();
+        assert_eq!(pool.idle(), 0);
+
+        let capacity = {
+            let arena = pool.get();
+            arena.alloc_slice_copy(&[0u8; 64 * 1024]);
+            arena.allocated_bytes()
+        };
+        assert_eq!(pool.idle(), 1);
+
+        // The returned arena keeps its memory, so the same workload does not grow it
+        let arena = pool.get();
+        assert_eq!(pool.idle(), 0);
+        arena.alloc_slice_copy(&[1u8; 64 * 1024]);
+        assert_eq!(arena.allocated_bytes(), capacity);
+    }
+
+    #[test]
+    fn test_arena_pool_creates_arena_per_concurrent_user() {
+        let pool = ArenaPool::new();
+        {
+            let first = pool.get();
+            let second = pool.get();
+            assert_eq!(first.alloc(1u32), &1);
+            assert_eq!(second.alloc(2u32), &2);
+        }
+        assert_eq!(pool.idle(), 2);
+    }
 }
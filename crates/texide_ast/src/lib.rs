@@ -29,15 +29,21 @@
 //! ```
 
 mod arena;
+mod line_index;
 mod node;
 mod node_type;
+mod outline;
 mod span;
+mod tree;
 pub mod visitor;
 
-pub use arena::AstArena;
-pub use node::{NodeData, TxtNode};
+pub use arena::{ArenaPool, AstArena, PooledArena};
+pub use line_index::LineIndex;
+pub use node::{NodeData, TableAlign, TxtNode};
 pub use node_type::NodeType;
+pub use outline::{HeadingInfo, TocEntry, headings, toc};
 pub use span::{Location, Position, Span};
+pub use tree::Tree;
 
 // Re-export commonly used visitor items for convenience
 pub use visitor::{MutVisitor, VisitResult, Visitor};
@@ -0,0 +1,145 @@
+//! Conversion from byte offsets to line/column positions.
+
+use crate::{Location, Position, Span};
+
+/// Index of line starts for converting byte offsets to [`Position`]s.
+///
+/// Lines end at `\n`. A `\r` directly before the `\n` is treated as part
+/// of the line ending, so LF and CRLF sources yield the same positions.
+///
+/// # Example
+///
+/// ```rust
+/// use texide_ast::{LineIndex, Position};
+///
+/// let index = LineIndex::new("first\r\nsecond");
+/// assert_eq!(index.position(7), Position::new(2, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    /// The indexed source text.
+    source: &'a str,
+    /// Byte offset of the start of each line.
+    line_starts: Vec<u32>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Builds an index for `source`.
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(
+                source
+                    .match_indices('\n')
+                    .map(|(offset, _)| (offset + 1) as u32),
+            )
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Returns the indexed source text.
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the number of lines.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Converts a byte offset to a position.
+    ///
+    /// Lines are 1-indexed and columns are 0-indexed character counts.
+    /// Offsets past the end of the source are clamped to the end, and
+    /// offsets inside a multi-byte character to the start of that
+    /// character.
+    pub fn position(&self, offset: u32) -> Position {
+        let mut offset = (offset as usize).min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self
+            .line_starts
+            .partition_point(|&start| start as usize <= offset);
+        let line_start = self.line_starts[line - 1] as usize;
+
+        let mut text = &self.source[line_start..offset];
+        // The `\r` of a CRLF ending is not a column
+        if self.source[offset..].starts_with('\n') {
+            text = text.strip_suffix('\r').unwrap_or(text);
+        }
+
+        Position::new(line as u32, text.chars().count() as u32)
+    }
+
+    /// Converts a span to a location.
+    pub fn location(&self, span: Span) -> Location {
+        Location::new(self.position(span.start), self.position(span.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line() {
+        let index = LineIndex::new("hello");
+
+        assert_eq!(index.line_count(), 1);
+        assert_eq!(index.position(0), Position::new(1, 0));
+        assert_eq!(index.position(5), Position::new(1, 5));
+    }
+
+    #[test]
+    fn test_lf_and_crlf_positions_match() {
+        let lf = "one\ntwo\n\nthree";
+        let crlf = "one\r\ntwo\r\n\r\nthree";
+        let lf_index = LineIndex::new(lf);
+        let crlf_index = LineIndex::new(crlf);
+
+        assert_eq!(lf_index.line_count(), crlf_index.line_count());
+        for word in ["one", "two", "three"] {
+            let lf_start = lf.find(word).unwrap() as u32;
+            let crlf_start = crlf.find(word).unwrap() as u32;
+            let len = word.len() as u32;
+
+            assert_eq!(
+                lf_index.location(Span::new(lf_start, lf_start + len)),
+                crlf_index.location(Span::new(crlf_start, crlf_start + len))
+            );
+        }
+
+        // End of the first line, before the line ending
+        assert_eq!(crlf_index.position(3), Position::new(1, 3));
+        assert_eq!(crlf_index.position(4), Position::new(1, 3));
+    }
+
+    #[test]
+    fn test_columns_count_characters() {
+        let index = LineIndex::new("日本語\nテキスト");
+
+        assert_eq!(index.position(9), Position::new(1, 3));
+        assert_eq!(index.position(13), Position::new(2, 1));
+    }
+
+    #[test]
+    fn test_offset_inside_character_is_clamped() {
+        let index = LineIndex::new("日本語\nテキスト");
+
+        assert_eq!(index.position(1), Position::new(1, 0));
+        assert_eq!(index.position(5), Position::new(1, 1));
+        assert_eq!(index.position(11), Position::new(2, 0));
+    }
+
+    #[test]
+    fn test_offset_past_end_is_clamped() {
+        let index = LineIndex::new("ab\n");
+
+        assert_eq!(index.line_count(), 2);
+        assert_eq!(index.position(100), Position::new(2, 0));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! The core AST node type used throughout Texide.
 
+use serde::{Serialize, Serializer};
+
 use crate::{NodeType, Span};
 
 /// A node in the TxtAST.
@@ -36,6 +38,12 @@ use crate::{NodeType, Span};
 ///     children,
 /// );
 /// ```
+///
+/// # Serialization
+///
+/// Nodes serialize to a TxtAST-like JSON object with `type` (see
+/// [`TxtNode::type_name`]), `range`, `children` (when non-empty), `value`
+/// (for text nodes) and any `NodeData` fields that are set.
 #[derive(Debug, Clone, Copy)]
 pub struct TxtNode<'a> {
     /// The type of this node.
@@ -54,29 +62,127 @@ pub struct TxtNode<'a> {
     pub data: NodeData<'a>,
 }
 
+/// Serialized form of a [`TxtNode`].
+#[derive(Serialize)]
+struct SerializedNode<'n, 'a> {
+    #[serde(rename = "type")]
+    node_type: &'a str,
+    #[serde(rename = "range", serialize_with = "serialize_range")]
+    span: Span,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    children: &'a [TxtNode<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<&'a str>,
+    #[serde(flatten)]
+    data: &'n NodeData<'a>,
+}
+
+impl Serialize for TxtNode<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedNode {
+            node_type: self.type_name(),
+            span: self.span,
+            children: self.children,
+            value: self.value,
+            data: &self.data,
+        }
+        .serialize(serializer)
+    }
+}
+
 /// Additional data specific to certain node types.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, Serialize)]
 pub struct NodeData<'a> {
     /// URL for Link/Image nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<&'a str>,
 
     /// Title for Link/Image nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<&'a str>,
 
+    /// Alternative text for Image/ImageReference nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt: Option<&'a str>,
+
     /// Depth for Header nodes (1-6).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub depth: Option<u8>,
 
     /// Whether list is ordered.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ordered: Option<bool>,
 
+    /// Number of the first item for ordered List nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u32>,
+
+    /// Number written before an item of an ordered list, for ListItem nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordinal: Option<u32>,
+
     /// Language for CodeBlock nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<&'a str>,
 
+    /// Info string of fenced CodeBlock nodes as written after the opening
+    /// fence, e.g. `rust title="main.rs"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<&'a str>,
+
+    /// Opening fence of fenced CodeBlock nodes, e.g. `` ``` `` or `~~~~`;
+    /// its first character and length give the fence character and length.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fence: Option<&'a str>,
+
     /// Identifier for reference nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<&'a str>,
 
     /// Label for reference nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<&'a str>,
+
+    /// Per-column alignment for Table nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<&'a [TableAlign]>,
+
+    /// Name of a `Custom` node's type, e.g. `"Admonition"`.
+    ///
+    /// Serialized as the node's `type`.
+    #[serde(skip)]
+    pub custom_type: Option<&'a str>,
+}
+
+/// Column alignment of a table, from the delimiter row (`:--`, `--:`, `:-:`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableAlign {
+    /// `:--`
+    Left,
+    /// `--:`
+    Right,
+    /// `:-:`
+    Center,
+    /// `---` (no alignment).
+    None,
+}
+
+impl TableAlign {
+    /// Returns the mdast name of this alignment.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TableAlign::Left => "left",
+            TableAlign::Right => "right",
+            TableAlign::Center => "center",
+            TableAlign::None => "none",
+        }
+    }
+}
+
+/// Serializes a span as a textlint-style `[start, end]` range.
+fn serialize_range<S: Serializer>(span: &Span, serializer: S) -> Result<S::Ok, S::Error> {
+    [span.start, span.end].serialize(serializer)
 }
 
 impl<'a> TxtNode<'a> {
@@ -128,13 +234,48 @@ impl<'a> TxtNode<'a> {
         self.value.is_some()
     }
 
-    /// Returns the raw text content of this node.
+    /// Returns the name of this node's type: the `custom_type` of `Custom`
+    /// nodes, and the [`NodeType`] name otherwise.
+    ///
+    /// Rules should match node types by this name so they work with nodes
+    /// from any parser.
+    #[inline]
+    pub fn type_name(&self) -> &'a str {
+        match (self.node_type, self.data.custom_type) {
+            (NodeType::Custom, Some(name)) => name,
+            (node_type, _) => node_type.as_str(),
+        }
+    }
+
+    /// Returns this node and its descendants whose [`type_name`] is
+    /// `type_name`, in document order.
     ///
-    /// For text nodes, returns the value.
-    /// For parent nodes, this returns None (use a visitor to collect text).
+    /// [`type_name`]: TxtNode::type_name
+    pub fn select<'n>(&'n self, type_name: &str) -> Vec<&'n TxtNode<'a>> {
+        let mut selected = Vec::new();
+        self.select_into(type_name, &mut selected);
+        selected
+    }
+
+    fn select_into<'n>(&'n self, type_name: &str, selected: &mut Vec<&'n TxtNode<'a>>) {
+        if self.type_name() == type_name {
+            selected.push(self);
+        }
+        for child in self.children {
+            child.select_into(type_name, selected);
+        }
+    }
+
+    /// Returns the part of `source` this node covers.
+    ///
+    /// Unlike `value`, this includes any markup, e.g. the `**` around
+    /// `Strong` text. Returns an empty string instead of panicking if the
+    /// span lies outside `source` or splits a character.
     #[inline]
-    pub const fn text(&self) -> Option<&'a str> {
-        self.value
+    pub fn text<'s>(&self, source: &'s str) -> &'s str {
+        source
+            .get(self.span.start as usize..self.span.end as usize)
+            .unwrap_or_default()
     }
 }
 
@@ -145,11 +286,18 @@ impl<'a> NodeData<'a> {
         Self {
             url: None,
             title: None,
+            alt: None,
             depth: None,
             ordered: None,
+            start: None,
+            ordinal: None,
             lang: None,
+            info: None,
+            fence: None,
             identifier: None,
             label: None,
+            align: None,
+            custom_type: None,
         }
     }
 
@@ -172,6 +320,17 @@ impl<'a> NodeData<'a> {
         }
     }
 
+    /// Creates node data for an image.
+    #[inline]
+    pub const fn image(url: &'a str, title: Option<&'a str>, alt: &'a str) -> Self {
+        Self {
+            url: Some(url),
+            title,
+            alt: Some(alt),
+            ..Self::new()
+        }
+    }
+
     /// Creates node data for a code block.
     #[inline]
     pub const fn code_block(lang: Option<&'a str>) -> Self {
@@ -189,6 +348,24 @@ impl<'a> NodeData<'a> {
             ..Self::new()
         }
     }
+
+    /// Creates node data for a table.
+    #[inline]
+    pub const fn table(align: &'a [TableAlign]) -> Self {
+        Self {
+            align: Some(align),
+            ..Self::new()
+        }
+    }
+
+    /// Creates node data for a `Custom` node named `custom_type`.
+    #[inline]
+    pub const fn custom(custom_type: &'a str) -> Self {
+        Self {
+            custom_type: Some(custom_type),
+            ..Self::new()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -214,7 +391,7 @@ mod tests {
 
         assert_eq!(node.node_type, NodeType::Str);
         assert!(node.is_text());
-        assert_eq!(node.text(), Some("hello"));
+        assert_eq!(node.value, Some("hello"));
         assert!(!node.has_children());
     }
 
@@ -238,6 +415,21 @@ mod tests {
         assert!(data.title.is_none());
     }
 
+    #[test]
+    fn test_node_data_image() {
+        let data = NodeData::image("cat.png", None, "A cat");
+        assert_eq!(data.url, Some("cat.png"));
+        assert!(data.title.is_none());
+        assert_eq!(data.alt, Some("A cat"));
+    }
+
+    #[test]
+    fn test_node_data_table() {
+        let align = [TableAlign::Left, TableAlign::None];
+        let data = NodeData::table(&align);
+        assert_eq!(data.align, Some(&align[..]));
+    }
+
     #[test]
     fn test_node_data_code_block() {
         let data = NodeData::code_block(Some("rust"));
@@ -336,11 +528,21 @@ mod tests {
 
     #[test]
     fn test_node_text_method() {
-        let text_node = TxtNode::new_text(NodeType::Str, Span::new(0, 5), "hello");
-        let parent_node = TxtNode::new_parent(NodeType::Paragraph, Span::new(0, 5), &[]);
+        let source = "Say **hello**.";
+        let text_node = TxtNode::new_text(NodeType::Str, Span::new(6, 11), "hello");
+        let parent_node = TxtNode::new_parent(NodeType::Strong, Span::new(4, 13), &[]);
+
+        assert_eq!(text_node.text(source), "hello");
+        assert_eq!(parent_node.text(source), "**hello**");
+    }
+
+    #[test]
+    fn test_node_text_out_of_bounds() {
+        let past_end = TxtNode::new_leaf(NodeType::Str, Span::new(3, 20));
+        let inside_char = TxtNode::new_leaf(NodeType::Str, Span::new(1, 2));
 
-        assert_eq!(text_node.text(), Some("hello"));
-        assert_eq!(parent_node.text(), None);
+        assert_eq!(past_end.text("short"), "");
+        assert_eq!(inside_char.text("é"), "");
     }
 
     #[test]
@@ -387,4 +589,72 @@ mod tests {
         assert_eq!(node.data.lang, Some("rust"));
         assert_eq!(node.value, Some(code));
     }
+
+    #[test]
+    fn test_node_serialization() {
+        let arena = AstArena::new();
+        let text = arena.alloc(TxtNode::new_text(NodeType::Str, Span::new(2, 7), "Title"));
+        let children = arena.alloc_slice_copy(&[*text]);
+        let mut header = TxtNode::new_parent(NodeType::Header, Span::new(0, 7), children);
+        header.data = NodeData::header(1);
+
+        let json = serde_json::to_value(header).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "Header",
+                "range": [0, 7],
+                "depth": 1,
+                "children": [
+                    { "type": "Str", "range": [2, 7], "value": "Title" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_table_align_serialization() {
+        let align = [
+            TableAlign::Left,
+            TableAlign::Right,
+            TableAlign::Center,
+            TableAlign::None,
+        ];
+        let mut table = TxtNode::new_leaf(NodeType::Table, Span::new(0, 10));
+        table.data = NodeData::table(&align);
+
+        let json = serde_json::to_value(table).unwrap();
+        assert_eq!(
+            json["align"],
+            serde_json::json!(["left", "right", "center", "none"])
+        );
+    }
+
+    #[test]
+    fn test_custom_node_type_name() {
+        let arena = AstArena::new();
+        let text =
+            arena.alloc_slice_copy(&[TxtNode::new_text(NodeType::Str, Span::new(10, 14), "Note")]);
+        let mut directive = TxtNode::new_parent(NodeType::Custom, Span::new(0, 14), text);
+        directive.data = NodeData::custom("Admonition");
+
+        assert_eq!(directive.type_name(), "Admonition");
+        assert_eq!(directive.children[0].type_name(), "Str");
+        assert_eq!(
+            serde_json::to_value(directive).unwrap(),
+            serde_json::json!({
+                "type": "Admonition",
+                "range": [0, 14],
+                "children": [
+                    { "type": "Str", "range": [10, 14], "value": "Note" }
+                ]
+            })
+        );
+
+        // Without a name, the node is just "Custom"
+        assert_eq!(
+            TxtNode::new_leaf(NodeType::Custom, Span::new(0, 0)).type_name(),
+            "Custom"
+        );
+    }
 }
@@ -34,6 +34,8 @@ pub enum NodeType {
     HorizontalRule,
     /// Raw HTML block.
     Html,
+    /// Document metadata (YAML frontmatter); `value` holds the raw YAML.
+    FrontMatter,
 
     // Inline elements
     /// Plain text string.
@@ -72,6 +74,14 @@ pub enum NodeType {
     FootnoteDefinition,
     /// Footnote reference.
     FootnoteReference,
+
+    // Parser-defined elements
+    /// A construct of another format (e.g. a reStructuredText directive)
+    /// with no equivalent here; its name is in `NodeData::custom_type`.
+    ///
+    /// Custom nodes may have children and values, but count as neither
+    /// block nor inline nodes.
+    Custom,
 }
 
 impl NodeType {
@@ -89,6 +99,7 @@ impl NodeType {
                 | NodeType::CodeBlock
                 | NodeType::HorizontalRule
                 | NodeType::Html
+                | NodeType::FrontMatter
                 | NodeType::Table
                 | NodeType::TableRow
                 | NodeType::FootnoteDefinition
@@ -133,20 +144,26 @@ impl NodeType {
                 | NodeType::TableRow
                 | NodeType::TableCell
                 | NodeType::FootnoteDefinition
+                | NodeType::Custom
         )
     }
 
     /// Returns true if this node type is a text node (has value).
     #[inline]
     pub const fn is_text(&self) -> bool {
-        matches!(self, NodeType::Str | NodeType::Code | NodeType::CodeBlock)
+        matches!(
+            self,
+            NodeType::Str | NodeType::Code | NodeType::CodeBlock | NodeType::FrontMatter
+        )
     }
-}
 
-impl std::fmt::Display for NodeType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Use the same casing as serde serialization
-        let name = match self {
+    /// Returns the name of this node type, as used in serialized ASTs.
+    ///
+    /// Custom nodes are serialized with their own name instead; see
+    /// `TxtNode::type_name`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
             NodeType::Document => "Document",
             NodeType::Paragraph => "Paragraph",
             NodeType::Header => "Header",
@@ -156,6 +173,7 @@ impl std::fmt::Display for NodeType {
             NodeType::CodeBlock => "CodeBlock",
             NodeType::HorizontalRule => "HorizontalRule",
             NodeType::Html => "Html",
+            NodeType::FrontMatter => "FrontMatter",
             NodeType::Str => "Str",
             NodeType::Break => "Break",
             NodeType::Emphasis => "Emphasis",
@@ -172,8 +190,14 @@ impl std::fmt::Display for NodeType {
             NodeType::TableCell => "TableCell",
             NodeType::FootnoteDefinition => "FootnoteDefinition",
             NodeType::FootnoteReference => "FootnoteReference",
-        };
-        write!(f, "{}", name)
+            NodeType::Custom => "Custom",
+        }
+    }
+}
+
+impl std::fmt::Display for NodeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -241,6 +265,7 @@ mod tests {
             (NodeType::TableCell, "TableCell"),
             (NodeType::FootnoteDefinition, "FootnoteDefinition"),
             (NodeType::FootnoteReference, "FootnoteReference"),
+            (NodeType::Custom, "Custom"),
         ];
 
         for (node_type, expected) in types {
@@ -0,0 +1,231 @@
+//! Document outline: headings and table of contents.
+//!
+//! Built from the `Header` nodes of a parsed document and their `depth`.
+//!
+//! ```rust
+//! use texide_ast::{AstArena, NodeData, NodeType, Span, TxtNode, toc};
+//!
+//! let arena = AstArena::new();
+//! let text = arena.alloc_slice_copy(&[TxtNode::new_text(
+//!     NodeType::Str,
+//!     Span::new(2, 7),
+//!     "Intro",
+//! )]);
+//! let mut header = TxtNode::new_parent(NodeType::Header, Span::new(0, 7), text);
+//! header.data = NodeData::header(1);
+//! let children = arena.alloc_slice_copy(&[header]);
+//! let document = TxtNode::new_parent(NodeType::Document, Span::new(0, 7), children);
+//!
+//! let entries = toc(&document);
+//! assert_eq!(entries[0].heading.text, "Intro");
+//! assert!(entries[0].children.is_empty());
+//! ```
+
+use serde::Serialize;
+
+use crate::{NodeType, Span, TxtNode};
+
+/// A heading of a document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeadingInfo {
+    /// Heading level (1-6).
+    pub depth: u8,
+    /// Text of the heading, without markup.
+    pub text: String,
+    /// Byte span of the heading in the source text.
+    pub span: Span,
+}
+
+/// An entry of a table of contents: a heading and the headings nested under it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TocEntry {
+    /// The heading.
+    #[serde(flatten)]
+    pub heading: HeadingInfo,
+    /// Deeper headings that follow, up to the next heading of the same or a
+    /// lower depth.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TocEntry>,
+}
+
+/// Returns every heading under `node`, in document order.
+///
+/// Headings inside block quotes and lists are included.
+pub fn headings(node: &TxtNode<'_>) -> Vec<HeadingInfo> {
+    let mut headings = Vec::new();
+    collect_headings(node, &mut headings);
+    headings
+}
+
+fn collect_headings(node: &TxtNode<'_>, headings: &mut Vec<HeadingInfo>) {
+    if node.node_type == NodeType::Header {
+        let mut text = String::new();
+        collect_text(node, &mut text);
+        headings.push(HeadingInfo {
+            depth: node.data.depth.unwrap_or(1),
+            text,
+            span: node.span,
+        });
+        return;
+    }
+    for child in node.children {
+        collect_headings(child, headings);
+    }
+}
+
+/// Appends the text of `Str` and inline `Code` nodes under `node`.
+fn collect_text(node: &TxtNode<'_>, text: &mut String) {
+    match node.node_type {
+        NodeType::Str | NodeType::Code => text.push_str(node.value.unwrap_or("")),
+        _ => {
+            for child in node.children {
+                collect_text(child, text);
+            }
+        }
+    }
+}
+
+/// Builds the table of contents of the document `node`.
+///
+/// Each heading is nested under the closest preceding heading of a lower
+/// depth, so skipped levels (`#` followed by `###`) still nest.
+pub fn toc(node: &TxtNode<'_>) -> Vec<TocEntry> {
+    let mut entries = Vec::new();
+    for heading in headings(node) {
+        insert_entry(&mut entries, heading);
+    }
+    entries
+}
+
+fn insert_entry(entries: &mut Vec<TocEntry>, heading: HeadingInfo) {
+    match entries.last_mut() {
+        Some(last) if last.heading.depth < heading.depth => {
+            insert_entry(&mut last.children, heading);
+        }
+        _ => entries.push(TocEntry {
+            heading,
+            children: Vec::new(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AstArena, NodeData};
+
+    /// Builds a document of headings given as `(depth, text)`; each heading
+    /// occupies 10 bytes.
+    fn document<'a>(arena: &'a AstArena, headings: &[(u8, &'a str)]) -> TxtNode<'a> {
+        let nodes: Vec<TxtNode<'a>> = headings
+            .iter()
+            .enumerate()
+            .map(|(i, &(depth, text))| {
+                let start = i as u32 * 10;
+                let span = Span::new(start, start + 10);
+                let children =
+                    arena.alloc_slice_copy(&[TxtNode::new_text(NodeType::Str, span, text)]);
+                let mut header = TxtNode::new_parent(NodeType::Header, span, children);
+                header.data = NodeData::header(depth);
+                header
+            })
+            .collect();
+        let end = nodes.len() as u32 * 10;
+        TxtNode::new_parent(
+            NodeType::Document,
+            Span::new(0, end),
+            arena.alloc_slice_copy(&nodes),
+        )
+    }
+
+    fn outline(entries: &[TocEntry]) -> Vec<(String, Vec<String>)> {
+        entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.heading.text.clone(),
+                    entry
+                        .children
+                        .iter()
+                        .map(|child| child.heading.text.clone())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_headings() {
+        let arena = AstArena::new();
+        let doc = document(&arena, &[(1, "Guide"), (2, "Install")]);
+
+        assert_eq!(
+            headings(&doc),
+            vec![
+                HeadingInfo {
+                    depth: 1,
+                    text: "Guide".to_string(),
+                    span: Span::new(0, 10),
+                },
+                HeadingInfo {
+                    depth: 2,
+                    text: "Install".to_string(),
+                    span: Span::new(10, 20),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toc_nesting() {
+        let arena = AstArena::new();
+        let doc = document(
+            &arena,
+            &[
+                (1, "Guide"),
+                (2, "Install"),
+                (3, "Linux"),
+                (3, "macOS"),
+                (2, "Usage"),
+                (1, "Reference"),
+                (3, "Options"),
+            ],
+        );
+
+        let entries = toc(&doc);
+        assert_eq!(
+            outline(&entries),
+            vec![
+                (
+                    "Guide".to_string(),
+                    vec!["Install".to_string(), "Usage".to_string()]
+                ),
+                ("Reference".to_string(), vec!["Options".to_string()]),
+            ]
+        );
+        assert_eq!(
+            outline(&entries[0].children),
+            vec![
+                (
+                    "Install".to_string(),
+                    vec!["Linux".to_string(), "macOS".to_string()]
+                ),
+                ("Usage".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heading_text_includes_inline_code() {
+        let arena = AstArena::new();
+        let children = arena.alloc_slice_copy(&[
+            TxtNode::new_text(NodeType::Str, Span::new(2, 6), "The "),
+            TxtNode::new_text(NodeType::Code, Span::new(6, 12), "lint"),
+            TxtNode::new_text(NodeType::Str, Span::new(12, 20), " command"),
+        ]);
+        let mut header = TxtNode::new_parent(NodeType::Header, Span::new(0, 20), children);
+        header.data = NodeData::header(2);
+
+        assert_eq!(headings(&header)[0].text, "The lint command");
+    }
+}
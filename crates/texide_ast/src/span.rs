@@ -61,6 +61,23 @@ impl Span {
         self.start <= offset && offset < self.end
     }
 
+    /// Returns true if this span fully contains the other span.
+    ///
+    /// An empty span at either boundary is contained.
+    #[inline]
+    pub const fn contains_span(&self, other: &Span) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Returns true if the two spans share at least one byte.
+    ///
+    /// Adjacent spans (one ends where the other starts) do not overlap.
+    /// An empty span overlaps a span that strictly surrounds its offset.
+    #[inline]
+    pub const fn overlaps(&self, other: &Span) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
     /// Merges two spans into one that covers both.
     #[inline]
     pub const fn merge(&self, other: &Span) -> Span {
@@ -159,6 +176,58 @@ mod tests {
         assert!(!span.contains(20)); // End is exclusive
     }
 
+    #[test]
+    fn test_span_adjacent() {
+        let left = Span::new(0, 5);
+        let right = Span::new(5, 10);
+
+        assert!(!left.overlaps(&right));
+        assert!(!right.overlaps(&left));
+        assert!(!left.contains_span(&right));
+        assert_eq!(left.merge(&right), Span::new(0, 10));
+    }
+
+    #[test]
+    fn test_span_nested() {
+        let outer = Span::new(0, 100);
+        let inner = Span::new(20, 30);
+
+        assert!(outer.overlaps(&inner));
+        assert!(inner.overlaps(&outer));
+        assert!(outer.contains_span(&inner));
+        assert!(!inner.contains_span(&outer));
+        assert!(outer.contains_span(&outer));
+    }
+
+    #[test]
+    fn test_span_disjoint() {
+        let first = Span::new(0, 5);
+        let second = Span::new(10, 15);
+
+        assert!(!first.overlaps(&second));
+        assert!(!second.overlaps(&first));
+        assert!(!first.contains_span(&second));
+    }
+
+    #[test]
+    fn test_span_partial_overlap() {
+        let span1 = Span::new(10, 20);
+        let span2 = Span::new(15, 30);
+
+        assert!(span1.overlaps(&span2));
+        assert!(!span1.contains_span(&span2));
+    }
+
+    #[test]
+    fn test_empty_span_overlap() {
+        let span = Span::new(3, 8);
+
+        assert!(span.overlaps(&Span::new(5, 5)));
+        assert!(!span.overlaps(&Span::new(3, 3)));
+        assert!(!Span::new(5, 5).overlaps(&Span::new(5, 5)));
+        assert!(span.contains_span(&Span::new(8, 8)));
+    }
+
     #[test]
     fn test_span_merge_non_overlapping() {
         let span1 = Span::new(0, 5);
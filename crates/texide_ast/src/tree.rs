@@ -0,0 +1,141 @@
+//! Canonical text form of an AST.
+//!
+//! One line per node, indented two spaces per level: the type name, its
+//! span, the `NodeData` fields that are set, and the quoted value of text
+//! nodes. Children are listed in the order they appear in the slice, which
+//! the parsers fill in source order, so the same input always gives the
+//! same text. This makes it suitable for snapshot tests.
+//!
+//! ```text
+//! Document [0..9]
+//!   Header [0..9] depth=1
+//!     Str [2..9] "Heading"
+//! ```
+
+use std::fmt;
+
+use crate::TxtNode;
+
+/// Displays a node and its descendants in the canonical text form.
+///
+/// Created by [`TxtNode::tree`].
+#[derive(Debug, Clone, Copy)]
+pub struct Tree<'n, 'a> {
+    node: &'n TxtNode<'a>,
+}
+
+impl<'a> TxtNode<'a> {
+    /// Returns a value that displays this node and its descendants in the
+    /// canonical text form.
+    ///
+    /// ```rust
+    /// use texide_ast::{AstArena, NodeType, Span, TxtNode};
+    ///
+    /// let arena = AstArena::new();
+    /// let text = arena.alloc_slice_copy(&[TxtNode::new_text(
+    ///     NodeType::Str,
+    ///     Span::new(0, 5),
+    ///     "Hello",
+    /// )]);
+    /// let paragraph = TxtNode::new_parent(NodeType::Paragraph, Span::new(0, 5), text);
+    ///
+    /// assert_eq!(
+    ///     paragraph.tree().to_string(),
+    ///     "Paragraph [0..5]\n  Str [0..5] \"Hello\"\n"
+    /// );
+    /// ```
+    pub fn tree(&self) -> Tree<'_, 'a> {
+        Tree { node: self }
+    }
+}
+
+impl fmt::Display for Tree<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_node(self.node, 0, f)
+    }
+}
+
+/// Writes `node` at `depth` and then its children one level deeper.
+fn write_node(node: &TxtNode, depth: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+        f,
+        "{}{} [{}..{}]",
+        "  ".repeat(depth),
+        node.type_name(),
+        node.span.start,
+        node.span.end
+    )?;
+
+    let data = &node.data;
+    if let Some(depth) = data.depth {
+        write!(f, " depth={}", depth)?;
+    }
+    if let Some(ordered) = data.ordered {
+        write!(f, " ordered={}", ordered)?;
+    }
+    if let Some(start) = data.start {
+        write!(f, " start={}", start)?;
+    }
+    if let Some(ordinal) = data.ordinal {
+        write!(f, " ordinal={}", ordinal)?;
+    }
+    if let Some(lang) = data.lang {
+        write!(f, " lang={}", lang)?;
+    }
+    if let Some(fence) = data.fence {
+        write!(f, " fence={}", fence)?;
+    }
+    if let Some(info) = data.info {
+        write!(f, " info={:?}", info)?;
+    }
+    if let Some(align) = data.align {
+        let align: Vec<&str> = align.iter().map(|a| a.as_str()).collect();
+        write!(f, " align=[{}]", align.join(", "))?;
+    }
+    if let Some(url) = data.url {
+        write!(f, " url={}", url)?;
+    }
+    if let Some(title) = data.title {
+        write!(f, " title={:?}", title)?;
+    }
+    if let Some(alt) = data.alt {
+        write!(f, " alt={:?}", alt)?;
+    }
+    if let Some(identifier) = data.identifier {
+        write!(f, " identifier={}", identifier)?;
+    }
+    if let Some(label) = data.label {
+        write!(f, " label={:?}", label)?;
+    }
+    if let Some(value) = node.value {
+        write!(f, " {:?}", value)?;
+    }
+    writeln!(f)?;
+
+    for child in node.children {
+        write_node(child, depth + 1, f)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AstArena, NodeData, NodeType, Span, TableAlign};
+
+    #[test]
+    fn test_tree_lists_data_and_children_in_order() {
+        let arena = AstArena::new();
+        let cells = arena.alloc_slice_copy(&[
+            TxtNode::new_text(NodeType::Str, Span::new(0, 1), "a"),
+            TxtNode::new_text(NodeType::Str, Span::new(2, 3), "b"),
+        ]);
+        let mut table = TxtNode::new_parent(NodeType::Table, Span::new(0, 3), cells);
+        table.data = NodeData::table(arena.alloc_slice_copy(&[TableAlign::Left, TableAlign::None]));
+
+        assert_eq!(
+            table.tree().to_string(),
+            "Table [0..3] align=[left, none]\n  Str [0..1] \"a\"\n  Str [2..3] \"b\"\n"
+        );
+    }
+}
@@ -131,6 +131,11 @@ pub trait Visitor<'a>: Sized {
         ControlFlow::Continue(()) // Leaf node
     }
 
+    /// Visit a FrontMatter node.
+    fn visit_front_matter(&mut self, _node: &TxtNode<'a>) -> VisitResult {
+        ControlFlow::Continue(()) // Leaf node
+    }
+
     // === Inline-level node visitors ===
 
     /// Visit a Str (text) node.
@@ -218,4 +223,11 @@ pub trait Visitor<'a>: Sized {
     fn visit_footnote_reference(&mut self, _node: &TxtNode<'a>) -> VisitResult {
         ControlFlow::Continue(()) // Leaf node
     }
+
+    // === Parser-defined node visitors ===
+
+    /// Visit a Custom node; `TxtNode::type_name` tells which kind.
+    fn visit_custom(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        walk_children(self, node)
+    }
 }
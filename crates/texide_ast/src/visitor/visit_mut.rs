@@ -109,6 +109,11 @@ pub trait MutVisitor<'a>: Sized {
         None // No change by default
     }
 
+    /// Transform a FrontMatter node.
+    fn visit_front_matter_mut(&mut self, _node: &TxtNode<'a>) -> VisitMutResult<'a> {
+        None // No change by default
+    }
+
     // === Inline-level node visitors ===
 
     /// Transform a Str (text) node.
@@ -196,6 +201,13 @@ pub trait MutVisitor<'a>: Sized {
     fn visit_footnote_reference_mut(&mut self, _node: &TxtNode<'a>) -> VisitMutResult<'a> {
         None // No change by default
     }
+
+    // === Parser-defined node visitors ===
+
+    /// Transform a Custom node; `TxtNode::type_name` tells which kind.
+    fn visit_custom_mut(&mut self, node: &TxtNode<'a>) -> VisitMutResult<'a> {
+        walk_children_mut(self, node)
+    }
 }
 
 /// Walks a node for mutation, returning a new node if any changes were made.
@@ -216,6 +228,7 @@ where
         NodeType::CodeBlock => visitor.visit_code_block_mut(node),
         NodeType::HorizontalRule => visitor.visit_horizontal_rule_mut(node),
         NodeType::Html => visitor.visit_html_mut(node),
+        NodeType::FrontMatter => visitor.visit_front_matter_mut(node),
 
         // Inline-level nodes
         NodeType::Str => visitor.visit_str_mut(node),
@@ -240,6 +253,9 @@ where
         // Footnote nodes
         NodeType::FootnoteDefinition => visitor.visit_footnote_definition_mut(node),
         NodeType::FootnoteReference => visitor.visit_footnote_reference_mut(node),
+
+        // Parser-defined nodes
+        NodeType::Custom => visitor.visit_custom_mut(node),
     }
 }
 
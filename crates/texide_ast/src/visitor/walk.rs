@@ -43,6 +43,7 @@ where
         NodeType::CodeBlock => visitor.visit_code_block(node),
         NodeType::HorizontalRule => visitor.visit_horizontal_rule(node),
         NodeType::Html => visitor.visit_html(node),
+        NodeType::FrontMatter => visitor.visit_front_matter(node),
 
         // Inline-level nodes
         NodeType::Str => visitor.visit_str(node),
@@ -67,6 +68,9 @@ where
         // Footnote nodes
         NodeType::FootnoteDefinition => visitor.visit_footnote_definition(node),
         NodeType::FootnoteReference => visitor.visit_footnote_reference(node),
+
+        // Parser-defined nodes
+        NodeType::Custom => visitor.visit_custom(node),
     };
 
     result?;
@@ -38,6 +38,10 @@ pub struct CacheEntry {
     #[serde(default)]
     pub blocks: Vec<BlockCacheEntry>,
 
+    /// State collected for project rules, by rule name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub project_state: HashMap<String, serde_json::Value>,
+
     /// Timestamp when this entry was created.
     pub created_at: u64,
 }
@@ -57,6 +61,7 @@ impl CacheEntry {
             rule_versions,
             diagnostics,
             blocks,
+            project_state: HashMap::new(),
             created_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .map(|d| d.as_secs())
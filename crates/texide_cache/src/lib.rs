@@ -12,13 +12,16 @@
 //!
 //! ## Storage
 //!
-//! Cache is stored using `rkyv` for zero-copy deserialization,
-//! providing fast cache reads without parsing overhead.
+//! Entries are persisted through a [`CacheStore`]. The default
+//! [`FileCacheStore`] writes them to a local directory; embedders can
+//! supply their own to keep the cache elsewhere.
 
 pub mod entry;
 mod error;
 mod manager;
+mod store;
 
 pub use entry::CacheEntry;
 pub use error::CacheError;
 pub use manager::CacheManager;
+pub use store::{CacheStore, FileCacheStore};
@@ -1,23 +1,29 @@
 //! Cache manager for file-level caching.
 
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use texide_ast::Span;
 use texide_plugin::Diagnostic;
-use tracing::{debug, info};
+use tracing::info;
 
-use crate::{CacheEntry, CacheError, entry::BlockCacheEntry};
+use crate::{CacheEntry, CacheError, CacheStore, FileCacheStore, entry::BlockCacheEntry};
 
 /// Manages the lint cache for all files.
+///
+/// Entries are kept in memory and persisted through a [`CacheStore`],
+/// which is a [`FileCacheStore`] unless another is given.
 pub struct CacheManager {
-    /// Directory where cache files are stored.
-    cache_dir: PathBuf,
+    /// Where entries are loaded from and saved to.
+    store: Box<dyn CacheStore>,
     /// In-memory cache entries.
     entries: HashMap<PathBuf, CacheEntry>,
     /// Whether cache is enabled.
     enabled: bool,
+    /// Files whose entries were set since the last load or save.
+    changed: HashSet<PathBuf>,
+    /// Whether entries were removed since the last load or save.
+    removed: bool,
 }
 
 impl CacheManager {
@@ -27,10 +33,17 @@ impl CacheManager {
     ///
     /// * `cache_dir` - Directory to store cache files
     pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self::with_store(Box::new(FileCacheStore::new(cache_dir)))
+    }
+
+    /// Creates a cache manager persisting entries through `store`.
+    pub fn with_store(store: Box<dyn CacheStore>) -> Self {
         Self {
-            cache_dir: cache_dir.into(),
+            store,
             entries: HashMap::new(),
             enabled: true,
+            changed: HashSet::new(),
+            removed: false,
         }
     }
 
@@ -49,6 +62,11 @@ impl CacheManager {
         self.enabled
     }
 
+    /// Returns the local directory the store writes to, if any.
+    pub fn local_dir(&self) -> Option<&Path> {
+        self.store.local_dir()
+    }
+
     /// Computes the BLAKE3 hash of content.
     pub fn hash_content(content: &str) -> String {
         blake3::hash(content.as_bytes()).to_hex().to_string()
@@ -176,6 +194,18 @@ impl CacheManager {
                         fix.span = Span::new(fix_start, fix_end);
                     }
 
+                    // Shift related places that moved with the block; places
+                    // elsewhere in the file are kept as they were
+                    let block = matched_block.span;
+                    for related in &mut new_diag.related {
+                        if related.span.start < block.start || related.span.end > block.end {
+                            continue;
+                        }
+                        let related_start = (related.span.start as i64 + shift) as u32;
+                        let related_end = (related.span.end as i64 + shift) as u32;
+                        related.span = Span::new(related_start, related_end);
+                    }
+
                     // Note: Location (line/col) would need recalculation, but it's derived from source + span.
                     // We clear it so it gets recomputed if needed, or we rely on Span.
                     new_diag.loc = None;
@@ -219,64 +249,59 @@ impl CacheManager {
     /// Stores a cache entry for a file.
     pub fn set(&mut self, path: PathBuf, entry: CacheEntry) {
         if self.enabled {
+            self.changed.insert(path.clone());
             self.entries.insert(path, entry);
         }
     }
 
     /// Removes a cache entry.
     pub fn remove(&mut self, path: &Path) {
-        self.entries.remove(path);
+        self.removed |= self.entries.remove(path).is_some();
+        self.changed.remove(path);
     }
 
     /// Clears all cache entries.
     pub fn clear(&mut self) {
+        self.removed |= !self.entries.is_empty();
         self.entries.clear();
+        self.changed.clear();
     }
 
-    /// Loads cache from disk.
+    /// Loads the cache from the store, replacing the entries in memory.
     pub fn load(&mut self) -> Result<(), CacheError> {
         if !self.enabled {
             return Ok(());
         }
 
-        let cache_file = self.cache_dir.join("cache.json");
-
-        if !cache_file.exists() {
-            debug!("No cache file found at {}", cache_file.display());
-            return Ok(());
-        }
-
-        let content = fs::read_to_string(&cache_file)?;
-        let entries: HashMap<PathBuf, CacheEntry> =
-            serde_json::from_str(&content).map_err(|e| CacheError::corrupted(e.to_string()))?;
-
-        info!("Loaded {} cache entries", entries.len());
-        self.entries = entries;
+        self.entries = self.store.load()?;
+        self.changed.clear();
+        self.removed = false;
+        info!("Loaded {} cache entries", self.entries.len());
 
         Ok(())
     }
 
-    /// Saves cache to disk.
-    pub fn save(&self) -> Result<(), CacheError> {
-        if !self.enabled {
+    /// Saves the entries in memory to the store.
+    ///
+    /// Does nothing if no entry changed since the last load or save, so
+    /// saving often during a run only writes when there is progress.
+    /// Only the changed entries are sent to a remote store, through
+    /// [`CacheStore::set`]; a local store, or any store after entries were
+    /// removed, is rewritten with every entry.
+    pub fn save(&mut self) -> Result<(), CacheError> {
+        if !self.enabled || (self.changed.is_empty() && !self.removed) {
             return Ok(());
         }
 
-        // Ensure cache directory exists
-        fs::create_dir_all(&self.cache_dir)?;
-
-        let cache_file = self.cache_dir.join("cache.json");
-        let content = serde_json::to_string_pretty(&self.entries)
-            .map_err(|e| CacheError::Serialization(e.to_string()))?;
-
-        fs::write(&cache_file, content)?;
-
-        info!(
-            "Saved {} cache entries to {}",
-            self.entries.len(),
-            cache_file.display()
-        );
-
+        if self.removed || self.store.local_dir().is_some() {
+            self.store.save(&self.entries)?;
+        } else {
+            for path in &self.changed {
+                self.store.set(path, &self.entries[path])?;
+            }
+        }
+        self.changed.clear();
+        self.removed = false;
         Ok(())
     }
 
@@ -300,6 +325,35 @@ impl Default for CacheManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    /// A store kept in memory, shared by the managers it is cloned into.
+    #[derive(Clone, Default)]
+    struct MemoryStore(Arc<Mutex<HashMap<PathBuf, CacheEntry>>>);
+
+    impl CacheStore for MemoryStore {
+        fn load(&mut self) -> Result<HashMap<PathBuf, CacheEntry>, CacheError> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn save(&mut self, entries: &HashMap<PathBuf, CacheEntry>) -> Result<(), CacheError> {
+            *self.0.lock().unwrap() = entries.clone();
+            Ok(())
+        }
+
+        fn get(&mut self, path: &Path) -> Result<Option<CacheEntry>, CacheError> {
+            Ok(self.0.lock().unwrap().get(path).cloned())
+        }
+
+        fn set(&mut self, path: &Path, entry: &CacheEntry) -> Result<(), CacheError> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf(), entry.clone());
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_cache_manager_new() {
@@ -506,6 +560,275 @@ mod tests {
         assert_ne!(hash1, hash3);
     }
 
+    #[test]
+    fn test_save_replaces_cache_file_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = CacheManager::new(dir.path());
+        manager.set(
+            PathBuf::from("a.md"),
+            CacheEntry::new(
+                "hash_a".to_string(),
+                "config".to_string(),
+                HashMap::new(),
+                vec![],
+                vec![],
+            ),
+        );
+
+        manager.save().unwrap();
+        manager.save().unwrap();
+
+        // Only the cache file remains, and it loads back
+        let files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec!["cache.json"]);
+
+        let mut loaded = CacheManager::new(dir.path());
+        loaded.load().unwrap();
+        assert_eq!(loaded.len(), 1);
+    }
+
+    #[test]
+    fn test_load_recovers_from_truncated_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = CacheManager::new(dir.path());
+        manager.set(
+            PathBuf::from("a.md"),
+            CacheEntry::new(
+                "hash_a".to_string(),
+                "config".to_string(),
+                HashMap::new(),
+                vec![],
+                vec![],
+            ),
+        );
+        manager.save().unwrap();
+
+        // Cut the file short, as a crash during a non-atomic write would
+        let cache_file = dir.path().join("cache.json");
+        let content = fs::read_to_string(&cache_file).unwrap();
+        fs::write(&cache_file, &content[..content.len() / 2]).unwrap();
+
+        let mut recovered = CacheManager::new(dir.path());
+        recovered.load().unwrap();
+        assert!(recovered.is_empty());
+
+        // The next save replaces the corrupt file
+        recovered.set(
+            PathBuf::from("b.md"),
+            CacheEntry::new(
+                "hash_b".to_string(),
+                "config".to_string(),
+                HashMap::new(),
+                vec![],
+                vec![],
+            ),
+        );
+        recovered.save().unwrap();
+        let mut reloaded = CacheManager::new(dir.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.len(), 1);
+        assert!(reloaded.get(Path::new("b.md")).is_some());
+    }
+
+    #[test]
+    fn test_load_recovers_from_garbage_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("cache.json"), b"\x00\x01not json").unwrap();
+
+        let mut manager = CacheManager::new(dir.path());
+        assert!(manager.load().is_ok());
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn test_save_writes_only_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_file = dir.path().join("cache.json");
+        let mut manager = CacheManager::new(dir.path());
+        manager.save().unwrap();
+        assert!(!cache_file.exists());
+
+        manager.set(
+            PathBuf::from("a.md"),
+            CacheEntry::new(
+                "hash_a".to_string(),
+                "config".to_string(),
+                HashMap::new(),
+                vec![],
+                vec![],
+            ),
+        );
+        manager.save().unwrap();
+        assert!(!fs::read_to_string(&cache_file).unwrap().contains('\n'));
+
+        fs::remove_file(&cache_file).unwrap();
+        manager.save().unwrap();
+        assert!(!cache_file.exists());
+
+        manager.remove(Path::new("a.md"));
+        manager.save().unwrap();
+        assert_eq!(fs::read_to_string(&cache_file).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_load_recovers_from_non_utf8_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("cache.json"), b"{\"a.md\": \xff\xfe}").unwrap();
+
+        let mut manager = CacheManager::new(dir.path());
+        assert!(manager.load().is_ok());
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn test_custom_store_round_trip() {
+        let store = MemoryStore::default();
+        let versions = HashMap::from([("rule".to_string(), "1.0.0".to_string())]);
+
+        let mut manager = CacheManager::with_store(Box::new(store.clone()));
+        manager.load().unwrap();
+        assert!(manager.is_empty());
+        manager.set(
+            PathBuf::from("a.md"),
+            CacheEntry::new(
+                "hash_a".to_string(),
+                "config".to_string(),
+                versions.clone(),
+                vec![],
+                vec![],
+            ),
+        );
+        manager.save().unwrap();
+
+        // A second manager, as on another runner, sees the saved entry
+        let mut shared = CacheManager::with_store(Box::new(store.clone()));
+        shared.load().unwrap();
+        assert_eq!(shared.len(), 1);
+        assert!(shared.is_valid(Path::new("a.md"), "hash_a", "config", &versions));
+        assert!(!shared.is_valid(Path::new("a.md"), "hash_b", "config", &versions));
+
+        // Single entries go through the store directly
+        let mut direct = store.clone();
+        let entry = direct.get(Path::new("a.md")).unwrap().unwrap();
+        assert_eq!(entry.content_hash, "hash_a");
+        direct.set(Path::new("b.md"), &entry).unwrap();
+        shared.load().unwrap();
+        assert_eq!(shared.len(), 2);
+    }
+
+    #[test]
+    fn test_save_sends_changed_entries_through_set() {
+        let store = MemoryStore::default();
+        let entry = |hash: &str| {
+            CacheEntry::new(
+                hash.to_string(),
+                "config".to_string(),
+                HashMap::new(),
+                vec![],
+                vec![],
+            )
+        };
+
+        let mut manager = CacheManager::with_store(Box::new(store.clone()));
+        manager.load().unwrap();
+        manager.set(PathBuf::from("a.md"), entry("hash_a"));
+
+        // An entry written by another runner meanwhile is kept
+        store
+            .0
+            .lock()
+            .unwrap()
+            .insert(PathBuf::from("b.md"), entry("hash_b"));
+        manager.save().unwrap();
+        assert_eq!(store.0.lock().unwrap().len(), 2);
+
+        // Removing an entry rewrites the store with the entries held
+        manager.remove(Path::new("a.md"));
+        manager.save().unwrap();
+        assert!(store.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_store_single_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = FileCacheStore::new(dir.path());
+        let entry = CacheEntry::new(
+            "hash_a".to_string(),
+            "config".to_string(),
+            HashMap::new(),
+            vec![],
+            vec![],
+        );
+
+        assert!(store.get(Path::new("a.md")).unwrap().is_none());
+        store.set(Path::new("a.md"), &entry).unwrap();
+        store.set(Path::new("b.md"), &entry).unwrap();
+        assert_eq!(store.load().unwrap().len(), 2);
+        assert_eq!(
+            store.get(Path::new("a.md")).unwrap().unwrap().content_hash,
+            "hash_a"
+        );
+    }
+
+    #[test]
+    fn test_disabled_manager_skips_store() {
+        let store = MemoryStore::default();
+        let mut manager = CacheManager::with_store(Box::new(store.clone()));
+        manager.set(
+            PathBuf::from("a.md"),
+            CacheEntry::new(
+                "hash".to_string(),
+                "config".to_string(),
+                HashMap::new(),
+                vec![],
+                vec![],
+            ),
+        );
+        manager.disable();
+        manager.save().unwrap();
+
+        assert!(store.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_shifts_related_spans_inside_block() {
+        use texide_plugin::RelatedSpan;
+
+        let versions = HashMap::new();
+        let diagnostic = Diagnostic::new("dup", "Duplicate", Span::new(12, 14)).with_related(vec![
+            RelatedSpan::new(Span::new(10, 12), "Inside"),
+            RelatedSpan::new(Span::new(0, 2), "Before"),
+        ]);
+        let block = |start: u32, diagnostics: Vec<Diagnostic>| BlockCacheEntry {
+            hash: "block".to_string(),
+            span: Span::new(start, start + 5),
+            diagnostics,
+        };
+
+        let mut manager = CacheManager::new("/tmp/test-cache");
+        manager.set(
+            PathBuf::from("a.md"),
+            CacheEntry::new(
+                "hash".to_string(),
+                "config".to_string(),
+                versions.clone(),
+                vec![],
+                vec![block(10, vec![diagnostic])],
+            ),
+        );
+
+        // The block moved down by 5 bytes
+        let (reused, matched) =
+            manager.reconcile_blocks(Path::new("a.md"), &[block(15, vec![])], "config", &versions);
+        assert_eq!(matched, vec![true]);
+        assert_eq!(reused[0].span, Span::new(17, 19));
+        assert_eq!(reused[0].related[0].span, Span::new(15, 17));
+        assert_eq!(reused[0].related[1].span, Span::new(0, 2));
+    }
+
     #[test]
     fn test_cache_manager_multiple_files() {
         let mut manager = CacheManager::new("/tmp/test-cache");
@@ -0,0 +1,144 @@
+//! Persistent storage for cache entries.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info, warn};
+
+use crate::{CacheEntry, CacheError};
+
+/// Where a [`CacheManager`](crate::CacheManager) persists its entries.
+///
+/// The manager keeps the entries in memory and decides which are valid;
+/// a store only reads and writes them. [`FileCacheStore`] keeps them in a
+/// local directory. Embedders can implement this trait to share the cache
+/// between machines, e.g. CI runners backed by Redis or S3.
+pub trait CacheStore: Send {
+    /// Reads every stored entry.
+    ///
+    /// Called once when the linter starts. A store with nothing in it
+    /// returns an empty map, not an error.
+    fn load(&mut self) -> Result<HashMap<PathBuf, CacheEntry>, CacheError>;
+
+    /// Writes `entries`, replacing everything stored before.
+    ///
+    /// The manager saves every entry it holds after entries were removed,
+    /// and always for a store with a [`local_dir`](Self::local_dir).
+    fn save(&mut self, entries: &HashMap<PathBuf, CacheEntry>) -> Result<(), CacheError>;
+
+    /// Reads the entry of one file.
+    ///
+    /// The default implementation loads every entry; stores with keyed
+    /// access should override it.
+    fn get(&mut self, path: &Path) -> Result<Option<CacheEntry>, CacheError> {
+        Ok(self.load()?.remove(path))
+    }
+
+    /// Writes the entry of one file, keeping the others.
+    ///
+    /// The manager sends the entries that changed through this method
+    /// when none were removed and the store has no local directory. The
+    /// default implementation loads and saves every entry; stores with
+    /// keyed access should override it.
+    fn set(&mut self, path: &Path, entry: &CacheEntry) -> Result<(), CacheError> {
+        let mut entries = self.load()?;
+        entries.insert(path.to_path_buf(), entry.clone());
+        self.save(&entries)
+    }
+
+    /// Returns the local directory the store writes to, if any.
+    ///
+    /// The linter keeps this directory out of file discovery when the
+    /// include patterns would reach it.
+    fn local_dir(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Stores the cache as `cache.json` in a directory.
+#[derive(Debug, Clone)]
+pub struct FileCacheStore {
+    /// Directory where cache files are stored.
+    cache_dir: PathBuf,
+}
+
+impl FileCacheStore {
+    /// Creates a store writing to `cache_dir`, which is created on the
+    /// first save.
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    /// Returns the directory the cache is stored in.
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+}
+
+impl CacheStore for FileCacheStore {
+    /// Reads `cache.json`.
+    ///
+    /// A missing cache file leaves the cache empty. So does a corrupt one
+    /// (e.g. truncated by a crash, or not valid UTF-8), with a warning; it
+    /// is replaced by the next save.
+    fn load(&mut self) -> Result<HashMap<PathBuf, CacheEntry>, CacheError> {
+        let cache_file = self.cache_dir.join("cache.json");
+
+        if !cache_file.exists() {
+            debug!("No cache file found at {}", cache_file.display());
+            return Ok(HashMap::new());
+        }
+
+        let content = fs::read(&cache_file)?;
+        match serde_json::from_slice(&content) {
+            Ok(entries) => Ok(entries),
+            Err(e) => {
+                warn!(
+                    "Ignoring corrupt cache file {}: {}",
+                    cache_file.display(),
+                    CacheError::corrupted(e.to_string())
+                );
+                Ok(HashMap::new())
+            }
+        }
+    }
+
+    /// Writes `cache.json`.
+    ///
+    /// The cache is written to a temporary file that then replaces
+    /// `cache.json`, so a crash mid-write leaves the previous cache intact
+    /// and concurrent writers never interleave.
+    fn save(&mut self, entries: &HashMap<PathBuf, CacheEntry>) -> Result<(), CacheError> {
+        // Ensure cache directory exists
+        fs::create_dir_all(&self.cache_dir)?;
+
+        let cache_file = self.cache_dir.join("cache.json");
+        let content =
+            serde_json::to_string(entries).map_err(|e| CacheError::Serialization(e.to_string()))?;
+
+        // Unique per process; writers within a process hold the manager lock
+        let temp_file = self
+            .cache_dir
+            .join(format!("cache.json.{}.tmp", std::process::id()));
+        fs::write(&temp_file, content)?;
+        if let Err(e) = fs::rename(&temp_file, &cache_file) {
+            let _ = fs::remove_file(&temp_file);
+            return Err(e.into());
+        }
+
+        info!(
+            "Saved {} cache entries to {}",
+            entries.len(),
+            cache_file.display()
+        );
+
+        Ok(())
+    }
+
+    fn local_dir(&self) -> Option<&Path> {
+        Some(&self.cache_dir)
+    }
+}
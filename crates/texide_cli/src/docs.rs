@@ -0,0 +1,255 @@
+//! Rule reference documentation generated from rule manifests.
+//!
+//! A [`RuleDoc`] collects what users need to configure a rule: its
+//! description, the severity of its diagnostics, and the options declared
+//! by the `schema` of its [`RuleManifest`]. Docs render as Markdown with
+//! [`to_markdown`], or serialize to JSON.
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{RuleManifest, Severity};
+
+/// Reference documentation of one rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleDoc {
+    /// Rule identifier.
+    pub name: String,
+    /// Rule version.
+    pub version: String,
+    /// Human-readable description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Severity of the rule's diagnostics unless the rule or the
+    /// configuration sets another one.
+    pub severity: Severity,
+    /// Whether the rule can provide auto-fixes.
+    pub fixable: bool,
+    /// Options declared by the rule's options schema.
+    pub options: Vec<OptionDoc>,
+    /// An example `options` entry enabling the rule with its defaults.
+    pub example: Value,
+}
+
+/// Documentation of one rule option.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OptionDoc {
+    /// Option name.
+    pub name: String,
+    /// JSON type of the option (e.g. `integer`, `string | array`).
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    /// Value used when the option is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// What the option does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the option must be set.
+    pub required: bool,
+}
+
+impl RuleDoc {
+    /// Builds the documentation of a rule from its manifest.
+    ///
+    /// Options are read from the `properties` of the manifest's options
+    /// schema, in name order.
+    pub fn from_manifest(manifest: &RuleManifest) -> Self {
+        let schema = manifest.schema.as_ref();
+        let required: Vec<&str> = schema
+            .and_then(|schema| schema.get("required"))
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut options: Vec<OptionDoc> = schema
+            .and_then(|schema| schema.get("properties"))
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| OptionDoc {
+                        name: name.clone(),
+                        ty: schema_type(property),
+                        default: property.get("default").cloned(),
+                        description: property
+                            .get("description")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        required: required.contains(&name.as_str()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        options.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let example = if options.iter().any(|option| option.default.is_some()) {
+            let defaults: Map<String, Value> = options
+                .iter()
+                .filter_map(|option| Some((option.name.clone(), option.default.clone()?)))
+                .collect();
+            Value::Object(defaults)
+        } else {
+            Value::Bool(true)
+        };
+
+        Self {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            description: manifest.description.clone(),
+            severity: Severity::default(),
+            fixable: manifest.fixable,
+            options,
+            example: serde_json::json!({ "options": { manifest.name.as_str(): example } }),
+        }
+    }
+}
+
+/// Returns the `type` of a schema property, joining type lists with `|`.
+fn schema_type(property: &Value) -> Option<String> {
+    match property.get("type")? {
+        Value::String(ty) => Some(ty.clone()),
+        Value::Array(types) => Some(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" | "),
+        ),
+        _ => None,
+    }
+}
+
+/// Renders rule docs as a Markdown reference, one section per rule.
+pub fn to_markdown(docs: &[RuleDoc]) -> String {
+    let mut out = String::new();
+    for (i, doc) in docs.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        // Writing to a String cannot fail
+        let _ = write_rule_markdown(doc, &mut out);
+    }
+    out
+}
+
+fn write_rule_markdown(doc: &RuleDoc, out: &mut String) -> std::fmt::Result {
+    writeln!(out, "## {}", doc.name)?;
+    writeln!(out)?;
+    if let Some(description) = &doc.description {
+        writeln!(out, "{}", description)?;
+        writeln!(out)?;
+    }
+    writeln!(out, "- Version: {}", doc.version)?;
+    writeln!(
+        out,
+        "- Default severity: {}",
+        crate::format::severity_label(doc.severity)
+    )?;
+    writeln!(out, "- Fixable: {}", if doc.fixable { "yes" } else { "no" })?;
+    writeln!(out)?;
+
+    writeln!(out, "### Options")?;
+    writeln!(out)?;
+    if doc.options.is_empty() {
+        writeln!(out, "This rule has no options.")?;
+    } else {
+        writeln!(out, "| Option | Type | Default | Description |")?;
+        writeln!(out, "|--------|------|---------|-------------|")?;
+        for option in &doc.options {
+            let default = match &option.default {
+                Some(default) => format!("`{}`", default),
+                None if option.required => "required".to_string(),
+                None => "-".to_string(),
+            };
+            writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                option.name,
+                option.ty.as_deref().unwrap_or("-").replace('|', "\\|"),
+                default,
+                option
+                    .description
+                    .as_deref()
+                    .unwrap_or("")
+                    .replace('|', "\\|")
+            )?;
+        }
+    }
+    writeln!(out)?;
+
+    writeln!(out, "### Example")?;
+    writeln!(out)?;
+    writeln!(out, "```json")?;
+    writeln!(
+        out,
+        "{}",
+        serde_json::to_string_pretty(&doc.example).unwrap_or_default()
+    )?;
+    writeln!(out, "```")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> RuleManifest {
+        let mut manifest = RuleManifest::new("sentence-length", "1.2.0")
+            .with_description("Limit the length of sentences")
+            .with_fixable(true);
+        manifest.schema = Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "max": {
+                    "type": "integer",
+                    "default": 100,
+                    "description": "Maximum number of characters"
+                },
+                "skip": { "type": ["string", "array"] },
+                "dictionary": { "type": "string", "description": "Path to a word list" }
+            },
+            "required": ["dictionary"]
+        }));
+        manifest
+    }
+
+    #[test]
+    fn test_markdown_contains_options_table() {
+        let markdown = to_markdown(&[RuleDoc::from_manifest(&manifest())]);
+
+        assert!(markdown.starts_with("## sentence-length\n\nLimit the length of sentences\n"));
+        assert!(markdown.contains("- Default severity: error\n- Fixable: yes\n"));
+        assert!(markdown.contains(
+            "| Option | Type | Default | Description |\n\
+             |--------|------|---------|-------------|\n\
+             | dictionary | string | required | Path to a word list |\n\
+             | max | integer | `100` | Maximum number of characters |\n\
+             | skip | string \\| array | - |  |\n"
+        ));
+        assert!(markdown.contains("\"sentence-length\": {\n      \"max\": 100\n    }"));
+    }
+
+    #[test]
+    fn test_rule_without_schema() {
+        let doc = RuleDoc::from_manifest(&RuleManifest::new("no-todo", "1.0.0"));
+        assert!(doc.options.is_empty());
+        assert_eq!(
+            doc.example,
+            serde_json::json!({ "options": { "no-todo": true } })
+        );
+        assert!(to_markdown(&[doc]).contains("This rule has no options.\n"));
+    }
+
+    #[test]
+    fn test_json() {
+        let value = serde_json::to_value(RuleDoc::from_manifest(&manifest())).unwrap();
+        assert_eq!(value["name"], "sentence-length");
+        assert_eq!(value["severity"], "error");
+        assert_eq!(value["options"][1]["name"], "max");
+        assert_eq!(value["options"][1]["type"], "integer");
+        assert_eq!(value["options"][1]["default"], 100);
+        assert_eq!(value["options"][0]["required"], true);
+    }
+}
@@ -0,0 +1,521 @@
+//! Output formatters for lint results.
+//!
+//! A [`Formatter`] writes a set of results to any [`Write`] sink. The
+//! built-in `pretty`, `json` and `sarif` formatters live in a
+//! [`FormatterRegistry`], which embedders can extend with their own formats:
+//!
+//! ```rust
+//! use std::io::{self, Write};
+//! use texide::LintResult;
+//! use texide::format::{Formatter, FormatterRegistry};
+//!
+//! struct Count;
+//!
+//! impl Formatter for Count {
+//!     fn format(&self, results: &[LintResult], out: &mut dyn Write) -> io::Result<()> {
+//!         writeln!(out, "{} files", results.len())
+//!     }
+//! }
+//!
+//! let mut registry = FormatterRegistry::new();
+//! registry.register("count", Count);
+//!
+//! let mut out = Vec::new();
+//! registry.get("count").unwrap().format(&[], &mut out)?;
+//! assert_eq!(out, b"0 files\n");
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
+//! Formatters show each result's path as is; rewrite `LintResult::path`
+//! beforehand to display paths differently.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::{LintResult, LintSummary, Severity};
+
+/// Writes lint results in a particular output format.
+pub trait Formatter: Send + Sync {
+    /// Writes `results` to `out`.
+    fn format(&self, results: &[LintResult], out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Formatters selectable by name.
+pub struct FormatterRegistry {
+    formatters: BTreeMap<String, Box<dyn Formatter>>,
+}
+
+impl FormatterRegistry {
+    /// Creates a registry with the built-in `pretty`, `json` and `sarif`
+    /// formatters. `text` is kept as an alias of `pretty`.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            formatters: BTreeMap::new(),
+        };
+        registry.register("pretty", Pretty::default());
+        registry.register("text", Pretty::default());
+        registry.register("json", Json);
+        registry.register("sarif", Sarif);
+        registry
+    }
+
+    /// Registers a formatter, replacing any formatter with the same name.
+    pub fn register(&mut self, name: impl Into<String>, formatter: impl Formatter + 'static) {
+        self.formatters.insert(name.into(), Box::new(formatter));
+    }
+
+    /// Returns the formatter registered as `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Formatter> {
+        self.formatters.get(name).map(Box::as_ref)
+    }
+
+    /// Returns the registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.formatters.keys().map(String::as_str)
+    }
+}
+
+impl Default for FormatterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Human-readable output: diagnostics grouped by file, followed by a summary.
+///
+/// Files without diagnostics are omitted, but the summary is written even
+/// when nothing was found, so a clean run still shows it checked files. A
+/// diagnostic's help URL follows its message, dimmed when `color` is set.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Pretty {
+    /// Style the output with ANSI escape codes.
+    pub color: bool,
+    /// Leave out the summary, writing only diagnostics.
+    pub quiet: bool,
+}
+
+impl Formatter for Pretty {
+    fn format(&self, results: &[LintResult], out: &mut dyn Write) -> io::Result<()> {
+        for result in results {
+            if result.diagnostics.is_empty() {
+                continue;
+            }
+
+            writeln!(out, "\n{}:", result.path.display())?;
+            for diag in &result.diagnostics {
+                write!(
+                    out,
+                    "  {}:{} {} [{}]: {}",
+                    diag.span.start,
+                    diag.span.end,
+                    severity_label(diag.severity),
+                    diag.rule_id,
+                    diag.message
+                )?;
+                match &diag.help_url {
+                    Some(url) if self.color => writeln!(out, " \x1b[2m({})\x1b[0m", url)?,
+                    Some(url) => writeln!(out, " ({})", url)?,
+                    None => writeln!(out)?,
+                }
+            }
+        }
+
+        if self.quiet {
+            return Ok(());
+        }
+
+        let summary = LintSummary::from_results(results);
+        writeln!(out)?;
+        writeln!(
+            out,
+            "Checked {} files ({} from cache), found {} issues",
+            summary.files_checked, summary.files_from_cache, summary.total_diagnostics
+        )?;
+
+        if summary.total_diagnostics > 0 {
+            writeln!(
+                out,
+                "{} errors, {} warnings, {} info",
+                summary.severity_count(Severity::Error),
+                summary.severity_count(Severity::Warning),
+                summary.severity_count(Severity::Info)
+            )?;
+            writeln!(out, "\nIssues by Rule:")?;
+            writeln!(out, "{:<30} | {:<10}", "Rule", "Count")?;
+            writeln!(out, "{:-<30}-+-{:-<10}", "", "")?;
+            for (rule, count) in summary.rules_by_frequency() {
+                writeln!(out, "{:<30} | {:<10}", rule, count)?;
+            }
+
+            let tags = summary.tags_by_frequency();
+            if !tags.is_empty() {
+                writeln!(out, "\nIssues by Tag:")?;
+                writeln!(out, "{:<30} | {:<10}", "Tag", "Count")?;
+                writeln!(out, "{:-<30}-+-{:-<10}", "", "")?;
+                for (tag, count) in tags {
+                    writeln!(out, "{:<30} | {:<10}", tag, count)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A pretty-printed JSON object with `results`, an array of
+/// `{ "path": ..., "diagnostics": [...] }` objects, and a `summary` of the
+/// run.
+///
+/// Files linted from disk also have `bytes` (their size) and `duration_ms`
+/// (the time spent on them, in milliseconds). The summary is present even
+/// when no file was checked, so consumers can tell a clean run from one
+/// that did not happen:
+///
+/// ```json
+/// {
+///   "results": [],
+///   "summary": {
+///     "files_checked": 0,
+///     "files_from_cache": 0,
+///     "files_with_errors": 0,
+///     "total_diagnostics": 0,
+///     "errors": 0,
+///     "warnings": 0,
+///     "info": 0
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Json;
+
+impl Formatter for Json {
+    fn format(&self, results: &[LintResult], out: &mut dyn Write) -> io::Result<()> {
+        let output: Vec<_> = results
+            .iter()
+            .map(|r| {
+                let mut value = serde_json::json!({
+                    "path": r.path.display().to_string(),
+                    "diagnostics": r.diagnostics,
+                });
+                if let Some(bytes) = r.bytes {
+                    value["bytes"] = bytes.into();
+                }
+                if let Some(duration) = r.duration {
+                    value["duration_ms"] = (duration.as_secs_f64() * 1000.0).into();
+                }
+                value
+            })
+            .collect();
+        let summary = LintSummary::from_results(results);
+        let output = serde_json::json!({
+            "results": output,
+            "summary": {
+                "files_checked": summary.files_checked,
+                "files_from_cache": summary.files_from_cache,
+                "files_with_errors": summary.files_with_errors,
+                "total_diagnostics": summary.total_diagnostics,
+                "errors": summary.severity_count(Severity::Error),
+                "warnings": summary.severity_count(Severity::Warning),
+                "info": summary.severity_count(Severity::Info),
+            },
+        });
+        serde_json::to_writer_pretty(&mut *out, &output)?;
+        writeln!(out)
+    }
+}
+
+/// A [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+/// log for code scanning tools.
+///
+/// Regions are given as byte offsets into each file, related places are
+/// `relatedLocations`, and diagnostic tags are result `properties.tags`.
+/// Rules are listed in `tool.driver.rules` with the help URL of their
+/// diagnostics as `helpUri`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Sarif;
+
+impl Formatter for Sarif {
+    fn format(&self, results: &[LintResult], out: &mut dyn Write) -> io::Result<()> {
+        let sarif_results: Vec<_> = results
+            .iter()
+            .flat_map(|r| {
+                let uri = r.path.display().to_string();
+                r.diagnostics.iter().map(move |diag| {
+                    let mut result = serde_json::json!({
+                        "ruleId": diag.rule_id,
+                        "level": match diag.severity {
+                            Severity::Error => "error",
+                            Severity::Warning => "warning",
+                            Severity::Info => "note",
+                        },
+                        "message": { "text": diag.message },
+                        "locations": [{
+                            "physicalLocation": {
+                                "artifactLocation": { "uri": uri },
+                                "region": {
+                                    "byteOffset": diag.span.start,
+                                    "byteLength": diag.span.end - diag.span.start,
+                                },
+                            },
+                        }],
+                    });
+                    if !diag.related.is_empty() {
+                        let related: Vec<_> = diag
+                            .related
+                            .iter()
+                            .enumerate()
+                            .map(|(id, related)| {
+                                serde_json::json!({
+                                    "id": id,
+                                    "message": { "text": related.message },
+                                    "physicalLocation": {
+                                        "artifactLocation": { "uri": uri },
+                                        "region": {
+                                            "byteOffset": related.span.start,
+                                            "byteLength": related.span.end - related.span.start,
+                                        },
+                                    },
+                                })
+                            })
+                            .collect();
+                        result["relatedLocations"] = related.into();
+                    }
+                    if !diag.tags.is_empty() {
+                        result["properties"] = serde_json::json!({ "tags": diag.tags });
+                    }
+                    result
+                })
+            })
+            .collect();
+
+        // One descriptor per rule, with the first help URL seen for it
+        let mut rules: BTreeMap<&str, Option<&str>> = BTreeMap::new();
+        for diag in results.iter().flat_map(|r| &r.diagnostics) {
+            let help_url = rules.entry(&diag.rule_id).or_default();
+            if help_url.is_none() {
+                *help_url = diag.help_url.as_deref();
+            }
+        }
+        let rules: Vec<_> = rules
+            .into_iter()
+            .map(|(id, help_url)| {
+                let mut rule = serde_json::json!({ "id": id });
+                if let Some(help_url) = help_url {
+                    rule["helpUri"] = help_url.into();
+                }
+                rule
+            })
+            .collect();
+
+        let log = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "texide",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    },
+                },
+                "results": sarif_results,
+            }],
+        });
+        serde_json::to_writer_pretty(&mut *out, &log)?;
+        writeln!(out)
+    }
+}
+
+/// Returns the lowercase label for a severity.
+pub fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Span;
+    use crate::{Diagnostic, RelatedSpan};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn results() -> Vec<LintResult> {
+        vec![
+            LintResult::new(PathBuf::from("clean.md"), Vec::new()),
+            LintResult::new(
+                PathBuf::from("doc.md"),
+                vec![
+                    Diagnostic::new("no-todo", "Found TODO", Span::new(4, 8))
+                        .with_severity(Severity::Warning)
+                        .with_tags(vec!["style".to_string()])
+                        .with_help_url("https://example.com/rules/no-todo"),
+                ],
+            ),
+        ]
+    }
+
+    fn render(formatter: &dyn Formatter) -> String {
+        let mut out = Vec::new();
+        formatter.format(&results(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_pretty() {
+        let output = render(&Pretty::default());
+        assert!(output.starts_with(
+            "\ndoc.md:\n  4:8 warning [no-todo]: Found TODO (https://example.com/rules/no-todo)\n"
+        ));
+        assert!(output.contains("Checked 2 files (0 from cache), found 1 issues\n"));
+        assert!(output.contains("0 errors, 1 warnings, 0 info\n"));
+        assert!(output.contains("Issues by Tag:\n"));
+        assert!(output.contains("\nstyle "));
+    }
+
+    #[test]
+    fn test_pretty_clean_run() {
+        let clean = [LintResult::new(PathBuf::from("clean.md"), Vec::new())];
+        let mut out = Vec::new();
+        Pretty::default().format(&clean, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nChecked 1 files (0 from cache), found 0 issues\n"
+        );
+
+        let mut out = Vec::new();
+        let quiet = Pretty {
+            quiet: true,
+            ..Pretty::default()
+        };
+        quiet.format(&clean, &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_pretty_dims_help_url_with_color() {
+        let output = render(&Pretty {
+            color: true,
+            ..Pretty::default()
+        });
+        assert!(output.contains("Found TODO \x1b[2m(https://example.com/rules/no-todo)\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_json() {
+        let value: serde_json::Value = serde_json::from_str(&render(&Json)).unwrap();
+        assert_eq!(value["results"][0]["path"], "clean.md");
+        assert_eq!(value["results"][1]["diagnostics"][0]["rule_id"], "no-todo");
+        assert!(value["results"][0].get("bytes").is_none());
+        assert_eq!(value["summary"]["files_checked"], 2);
+        assert_eq!(value["summary"]["total_diagnostics"], 1);
+        assert_eq!(value["summary"]["warnings"], 1);
+    }
+
+    #[test]
+    fn test_json_clean_run_has_summary() {
+        let mut out = Vec::new();
+        Json.format(&[], &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value["results"], serde_json::json!([]));
+        assert_eq!(
+            value["summary"],
+            serde_json::json!({
+                "files_checked": 0,
+                "files_from_cache": 0,
+                "files_with_errors": 0,
+                "total_diagnostics": 0,
+                "errors": 0,
+                "warnings": 0,
+                "info": 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_duration_and_size() {
+        let mut result = LintResult::new(PathBuf::from("doc.md"), Vec::new());
+        result.bytes = Some(42);
+        result.duration = Some(Duration::from_micros(1500));
+
+        let mut out = Vec::new();
+        Json.format(&[result], &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value["results"][0]["bytes"], 42);
+        assert_eq!(value["results"][0]["duration_ms"], 1.5);
+    }
+
+    #[test]
+    fn test_sarif() {
+        let value: serde_json::Value = serde_json::from_str(&render(&Sarif)).unwrap();
+        assert_eq!(value["version"], "2.1.0");
+
+        let results = &value["runs"][0]["results"];
+        assert_eq!(results.as_array().unwrap().len(), 1);
+        assert_eq!(results[0]["ruleId"], "no-todo");
+        assert_eq!(results[0]["level"], "warning");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "doc.md");
+        assert_eq!(location["region"]["byteOffset"], 4);
+        assert_eq!(location["region"]["byteLength"], 4);
+        assert_eq!(
+            results[0]["properties"]["tags"],
+            serde_json::json!(["style"])
+        );
+        assert!(results[0].get("relatedLocations").is_none());
+        assert_eq!(
+            value["runs"][0]["tool"]["driver"]["rules"],
+            serde_json::json!([{ "id": "no-todo", "helpUri": "https://example.com/rules/no-todo" }])
+        );
+    }
+
+    #[test]
+    fn test_sarif_related_locations() {
+        let diagnostic = Diagnostic::new("dup", "Duplicate", Span::new(20, 25))
+            .with_related(vec![RelatedSpan::new(Span::new(2, 7), "First here")]);
+        let mut out = Vec::new();
+        Sarif
+            .format(
+                &[LintResult::new(PathBuf::from("doc.md"), vec![diagnostic])],
+                &mut out,
+            )
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let related = &value["runs"][0]["results"][0]["relatedLocations"][0];
+        assert_eq!(related["message"]["text"], "First here");
+        assert_eq!(related["physicalLocation"]["region"]["byteOffset"], 2);
+        assert_eq!(related["physicalLocation"]["region"]["byteLength"], 5);
+    }
+
+    /// Writes a one-line summary.
+    struct OneLine;
+
+    impl Formatter for OneLine {
+        fn format(&self, results: &[LintResult], out: &mut dyn Write) -> io::Result<()> {
+            let summary = LintSummary::from_results(results);
+            writeln!(
+                out,
+                "{} issues in {} files",
+                summary.total_diagnostics, summary.files_checked
+            )
+        }
+    }
+
+    #[test]
+    fn test_custom_formatter() {
+        let mut registry = FormatterRegistry::new();
+        registry.register("one-line", OneLine);
+
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec!["json", "one-line", "pretty", "sarif", "text"]
+        );
+        let formatter = registry.get("one-line").unwrap();
+        assert_eq!(render(formatter), "1 issues in 2 files\n");
+        assert!(registry.get("protobuf").is_none());
+    }
+}
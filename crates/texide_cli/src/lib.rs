@@ -0,0 +1,93 @@
+//! # texide
+//!
+//! High-performance natural language linter written in Rust.
+//!
+//! This crate is the stable library API for embedding Texide. It re-exports
+//! the types needed to configure a linter, run it, and report its results;
+//! everything else in the `texide_*` crates is an implementation detail and
+//! may change between minor releases.
+//!
+//! The default `cli` feature builds the command-line tool and pulls in its
+//! dependencies (argument parsing, the language server, the rule
+//! registry). Embedders only need the library:
+//!
+//! ```toml
+//! texide = { version = "0.1", default-features = false }
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use texide::{LinterConfig, Severity, lint_str};
+//!
+//! let config = LinterConfig::from_json(r#"{ "options": { "image-alt-text": true } }"#)?;
+//! let diagnostics = lint_str("# Pets\n\n![](cat.png)\n", "pets.md", config)?;
+//!
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].rule_id, "image-alt-text");
+//! assert_eq!(diagnostics[0].severity, Severity::Warning);
+//! # Ok::<(), texide::LinterError>(())
+//! ```
+
+#![warn(missing_docs)]
+
+use std::path::Path;
+
+pub use texide_core::{
+    ChangeSet, DedupStrategy, Diagnostic, Encoding, Fix, LintResult, LintSummary, Linter,
+    LinterBuilder, LinterConfig, LinterError, NormalizationForm, ParagraphMode,
+    ParserOptionsOverride, PathStyle, RelatedSpan, RuleManifest, RuleOption, RuleProfile,
+    RuleProfiler, Severity, StrictMode, apply_fixes_to_content, changed_files,
+};
+pub use texide_parser::{
+    MarkdownParser, ParseError, Parser, ParserOptions, ParserRegistry, PlainTextParser, RawParser,
+};
+
+/// The syntax tree produced by [`Parser`]s.
+///
+/// [`headings`](ast::headings) and [`toc`](ast::toc) extract a document's
+/// outline:
+///
+/// ```rust
+/// use texide::ast::{AstArena, toc};
+/// use texide::{MarkdownParser, Parser};
+///
+/// let arena = AstArena::new();
+/// let source = "# Guide\n\n## Install\n\n## Usage\n\n# Reference\n";
+/// let document = MarkdownParser::new().parse(&arena, source)?;
+///
+/// let entries = toc(&document);
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].heading.text, "Guide");
+/// assert_eq!(entries[0].children[1].heading.text, "Usage");
+/// assert_eq!(entries[0].children[1].heading.depth, 2);
+/// # Ok::<(), texide::ParseError>(())
+/// ```
+pub mod ast {
+    pub use texide_ast::{
+        AstArena, HeadingInfo, NodeData, NodeType, Span, TableAlign, TocEntry, TxtNode, headings,
+        toc,
+    };
+}
+
+pub mod docs;
+pub mod format;
+pub mod migrate;
+
+/// Lints `source` as if it were the contents of `path`.
+///
+/// `path` only selects the parser (by extension) and is passed to rules;
+/// nothing is read from disk. The result cache is not used.
+pub fn lint_str(
+    source: &str,
+    path: impl AsRef<Path>,
+    mut config: LinterConfig,
+) -> Result<Vec<Diagnostic>, LinterError> {
+    config.cache = false;
+    Linter::new(config)?.lint_content(source, path.as_ref())
+}
+
+/// Lints the file at `path`.
+pub fn lint_path(path: impl AsRef<Path>, config: LinterConfig) -> Result<LintResult, LinterError> {
+    Linter::new(config)?.lint_path(path)
+}
@@ -3,16 +3,27 @@
 //! High-performance natural language linter written in Rust.
 
 use std::collections::HashMap;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::Arc;
 use std::time::Duration;
 
 use clap::{Parser, Subcommand};
 use miette::{IntoDiagnostic, Result};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::EnvFilter;
 
-use texide_core::{LintResult, Linter, LinterConfig, Severity, apply_fixes_to_file};
+use texide::docs::{self, RuleDoc};
+use texide::format::{FormatterRegistry, Pretty};
+use texide::migrate;
+use texide_ast::AstArena;
+use texide_core::{
+    ChangeSet, Diagnostic, LintResult, LintSummary, Linter, LinterConfig, ParserSelector,
+    PathStyle, PluginPolicy, RuleDefinition, RuleDefinitionDetail, RuleProfiler, Severity,
+    StrictMode, apply_fixes_to_content, apply_fixes_to_file, changed_files,
+};
+use texide_registry::{Installer, PluginSource};
 
 /// Texide - High-performance natural language linter
 #[derive(Parser)]
@@ -26,47 +37,125 @@ struct Cli {
     #[arg(short, long, global = true)]
     config: Option<PathBuf>,
 
-    /// Enable verbose output
-    #[arg(short, long, global = true)]
-    verbose: bool,
+    /// Enable verbose output (-vv also prints rule logs)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
 
     /// Disable caching
     #[arg(long, global = true)]
     no_cache: bool,
 }
 
+#[derive(clap::Args)]
+struct LintArgs {
+    /// File patterns to lint
+    #[arg(required = true)]
+    patterns: Vec<String>,
+
+    /// Output format (pretty, json, sarif)
+    #[arg(short, long, default_value = "pretty")]
+    format: String,
+
+    /// Print only diagnostics, without the closing summary (pretty format)
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Lint only files changed since this git commit, branch or tag, including uncommitted and untracked ones
+    #[arg(long, value_name = "REF", conflicts_with = "staged")]
+    since: Option<String>,
+
+    /// Lint only files staged for the next git commit
+    #[arg(long)]
+    staged: bool,
+
+    #[command(flatten)]
+    overrides: ConfigOverrides,
+
+    /// Auto-fix errors
+    #[arg(long)]
+    fix: bool,
+
+    /// Print fixes as a unified diff instead of applying them; exits nonzero if any would apply
+    #[arg(long, requires = "fix")]
+    dry_run: bool,
+
+    /// Maximum lint-and-fix passes per file before giving up on convergence
+    #[arg(long, default_value_t = 10, requires = "fix")]
+    max_fix_passes: usize,
+}
+
+/// Settings given on the command line that override the configuration.
+#[derive(clap::Args)]
+struct ConfigOverrides {
+    /// Directory to discover files in (repeatable; overrides `roots` in the config)
+    #[arg(long = "root", value_name = "DIR")]
+    roots: Vec<String>,
+
+    /// Exit nonzero when more than this many warnings are reported (-1 for unlimited)
+    #[arg(long, allow_negative_numbers = true)]
+    max_warnings: Option<i64>,
+
+    /// Exit nonzero only when a diagnostic has this tag (repeatable; overrides `fail_on_tags` in the config)
+    #[arg(long = "fail-on-tag", value_name = "TAG")]
+    fail_on_tags: Vec<String>,
+
+    /// Exit nonzero on warnings too, still showing them as warnings (`strict: "exit"`)
+    #[arg(long)]
+    strict: bool,
+
+    /// Enable a rule regardless of the config (repeatable)
+    #[arg(long = "enable-rule", value_name = "RULE")]
+    enable_rules: Vec<String>,
+
+    /// Disable a rule regardless of the config (repeatable; wins over --enable-rule)
+    #[arg(long = "disable-rule", value_name = "RULE")]
+    disable_rules: Vec<String>,
+
+    /// Run only this rule, skipping every other rule and, when possible, other plugins
+    #[arg(long, value_name = "RULE")]
+    only_rule: Option<String>,
+
+    /// Report time per rule and the slowest files
+    #[arg(long)]
+    timings: bool,
+
+    /// Report total time, invocations and average time per rule across all files
+    #[arg(long)]
+    profile_rules: bool,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Lint files
-    Lint {
-        /// File patterns to lint
-        #[arg(required = true)]
-        patterns: Vec<String>,
-
-        /// Output format (text, json)
-        #[arg(short, long, default_value = "text")]
-        format: String,
+    Lint(LintArgs),
 
-        /// Auto-fix errors
+    /// Initialize configuration
+    Init {
+        /// Force overwrite existing config
         #[arg(long)]
-        fix: bool,
-
-        /// Preview fixes without applying them
-        #[arg(long, requires = "fix")]
-        dry_run: bool,
+        force: bool,
+    },
 
-        /// Measure performance
+    /// Convert another linter's configuration to .texide.json
+    Migrate {
+        /// Linter the configuration belongs to (markdownlint, textlint)
         #[arg(long)]
-        timings: bool,
-    },
+        from: String,
+
+        /// Configuration file to convert
+        path: PathBuf,
 
-    /// Initialize configuration
-    Init {
         /// Force overwrite existing config
         #[arg(long)]
         force: bool,
     },
 
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
     /// Manage rules
     Rules {
         #[command(subcommand)]
@@ -75,6 +164,29 @@ enum Commands {
 
     /// Start the LSP server
     Lsp,
+
+    /// Print the parsed AST of a file
+    Ast {
+        /// File to parse
+        file: PathBuf,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the configuration in effect after applying the overrides, as JSON
+    Show {
+        #[command(flatten)]
+        overrides: ConfigOverrides,
+
+        /// Show where each setting comes from
+        #[arg(long)]
+        origin: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -90,16 +202,34 @@ enum RulesCommands {
         /// Path to WASM file
         path: PathBuf,
     },
+
+    /// Download the configured remote rules into the plugin cache
+    Install {
+        /// Number of rules to download at the same time
+        #[arg(short, long, default_value_t = texide_registry::install::DEFAULT_JOBS)]
+        jobs: usize,
+    },
+
+    /// Print reference documentation of the configured rules
+    Docs {
+        /// Only document this rule
+        name: Option<String>,
+
+        /// Output format (markdown, json)
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+    },
 }
 
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
-    // Initialize logging
-    let filter = if cli.verbose {
-        EnvFilter::new("debug")
-    } else {
-        EnvFilter::new("info")
+    // Initialize logging; verbose levels only apply to Texide's own
+    // crates, so dependencies such as the WASM runtime stay quiet
+    let filter = match cli.verbose {
+        0 => EnvFilter::new("info"),
+        1 => EnvFilter::new("info,texide=debug"),
+        _ => EnvFilter::new("info,texide=trace"),
     };
 
     tracing_subscriber::fmt()
@@ -125,17 +255,21 @@ fn main() -> ExitCode {
 
 fn run(cli: Cli) -> Result<bool> {
     match cli.command {
-        Commands::Lint {
-            ref patterns,
-            ref format,
-            fix,
-            dry_run,
-            timings,
-        } => run_lint(&cli, patterns, format, fix, dry_run, timings),
+        Commands::Lint(ref args) => run_lint(&cli, args),
         Commands::Init { force } => {
             run_init(force)?;
             Ok(false)
         }
+        Commands::Migrate { from, path, force } => {
+            run_migrate(&from, &path, force)?;
+            Ok(false)
+        }
+        Commands::Config { ref command } => match command {
+            ConfigCommands::Show { overrides, origin } => {
+                run_config_show(&cli, overrides, *origin)?;
+                Ok(false)
+            }
+        },
         Commands::Rules { command } => match command {
             RulesCommands::Create { name } => {
                 run_create_rule(&name)?;
@@ -145,14 +279,54 @@ fn run(cli: Cli) -> Result<bool> {
                 run_add_rule(&path)?;
                 Ok(false)
             }
+            RulesCommands::Install { jobs } => {
+                run_install_rules(cli.config.as_deref(), jobs)?;
+                Ok(false)
+            }
+            RulesCommands::Docs { name, format } => {
+                run_rule_docs(cli.config.as_deref(), name.as_deref(), &format)?;
+                Ok(false)
+            }
         },
         Commands::Lsp => {
             run_lsp()?;
             Ok(false)
         }
+        Commands::Ast {
+            ref file,
+            ref format,
+        } => {
+            run_ast(cli.config.as_deref(), file, format)?;
+            Ok(false)
+        }
     }
 }
 
+fn run_ast(config_path: Option<&Path>, file: &Path, format: &str) -> Result<()> {
+    let config = if let Some(path) = config_path {
+        LinterConfig::from_file(path)?
+    } else {
+        find_config()?
+    };
+    let content = std::fs::read_to_string(file).into_diagnostic()?;
+    // Parse the way `lint` would, honoring `raw_files` and `parser_options`
+    let parser = ParserSelector::new(&config)?.select(file);
+
+    let arena = AstArena::new();
+    let ast = parser
+        .parse(&arena, &content)
+        .map_err(|e| miette::miette!("Failed to parse {}: {}", file.display(), e))?;
+
+    match format {
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(&ast).into_diagnostic()?);
+        }
+        _ => print!("{}", ast.tree()),
+    }
+
+    Ok(())
+}
+
 fn run_lsp() -> Result<()> {
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -164,69 +338,297 @@ fn run_lsp() -> Result<()> {
     Ok(())
 }
 
-fn run_lint(
-    cli: &Cli,
-    patterns: &[String],
-    format: &str,
-    fix: bool,
-    dry_run: bool,
-    timings: bool,
-) -> Result<bool> {
-    // Load configuration
+fn run_lint(cli: &Cli, args: &LintArgs) -> Result<bool> {
+    let config = load_config(cli, &args.overrides)?;
+
+    // Capture settings before config is moved
+    let timings_enabled = config.timings;
+    let max_warnings = config.max_warnings.filter(|&max| max >= 0);
+    let fail_on_tags = config.fail_on_tags.clone();
+    let strict = config.strict;
+    let paths = PathFormatter {
+        style: config.path_style,
+        root: config.base_dir.clone().unwrap_or_default(),
+    };
+
+    // Create linter
+    let linter = Arc::new(Linter::new(config)?);
+    save_cache_on_interrupt(Arc::clone(&linter));
+
+    // Run linting, on the changed files only if asked
+    let changes = match (&args.since, args.staged) {
+        (Some(reference), _) => Some(ChangeSet::Since(reference.clone())),
+        (None, true) => Some(ChangeSet::Staged),
+        (None, false) => None,
+    };
+    let mut results = match changes {
+        Some(changes) => {
+            let changed = changed_files(&changes, Path::new("."))?;
+            linter.lint_changed(&args.patterns, &changed)?
+        }
+        None => linter.lint_patterns(&args.patterns)?,
+    };
+
+    // Apply fixes if requested
+    if args.fix {
+        let dry_run = args.dry_run;
+        let fix_summary = apply_fixes(&linter, &mut results, &paths, dry_run, args.max_fix_passes)?;
+        output_fix_summary(&fix_summary, dry_run);
+
+        if !fix_summary.unconverged.is_empty() {
+            return Ok(true);
+        }
+
+        // Judge the run by the diagnostics left after fixing
+        let has_errors = results.iter().any(|r| r.has_errors());
+        let failed = run_failed(&results, has_errors, strict, &fail_on_tags, max_warnings);
+
+        if dry_run {
+            // The diff has been printed; also fail if anything would change
+            return Ok(failed || fix_summary.files_fixed > 0);
+        }
+        return Ok(failed);
+    }
+
+    // Output results
+    let has_errors = output_results(
+        &linter,
+        &mut results,
+        &paths,
+        &args.format,
+        args.quiet,
+        timings_enabled,
+    )?;
+    if let Some(profile) = linter.rule_profile() {
+        output_rule_profile(&profile);
+    }
+
+    Ok(run_failed(
+        &results,
+        has_errors,
+        strict,
+        &fail_on_tags,
+        max_warnings,
+    ))
+}
+
+/// Returns whether a run reporting `results` fails.
+fn run_failed(
+    results: &[LintResult],
+    has_errors: bool,
+    strict: StrictMode,
+    fail_on_tags: &[String],
+    max_warnings: Option<i64>,
+) -> bool {
+    // With failing tags, only diagnostics carrying one of them fail the run;
+    // the config rejects combining them with `strict: "exit"`
+    let warnings = LintSummary::from_results(results).severity_count(Severity::Warning);
+    let failed = if fail_on_tags.is_empty() {
+        has_errors || (strict != StrictMode::Off && warnings > 0)
+    } else {
+        results.iter().any(|r| r.has_tags(fail_on_tags))
+    };
+
+    // Fail on too many warnings, independently of errors
+    failed || too_many_warnings(warnings, max_warnings)
+}
+
+/// Returns whether `warnings` exceeds `max_warnings`, reporting it if so.
+fn too_many_warnings(warnings: usize, max_warnings: Option<i64>) -> bool {
+    match max_warnings {
+        Some(max) if warnings as i64 > max => {
+            eprintln!(
+                "Too many warnings ({}). Maximum allowed is {}.",
+                warnings, max
+            );
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Exit code of a run interrupted with Ctrl-C (128 + SIGINT).
+const EXIT_INTERRUPTED: i32 = 130;
+
+/// Saves the results cached so far and exits when the run is interrupted,
+/// so the next run can reuse them.
+fn save_cache_on_interrupt(linter: Arc<Linter>) {
+    let result = ctrlc::set_handler(move || {
+        eprintln!("Interrupted, saving cache...");
+        if let Err(e) = linter.save_cache() {
+            warn!("Failed to save cache: {}", e);
+        }
+        std::process::exit(EXIT_INTERRUPTED);
+    });
+    if let Err(e) = result {
+        warn!("Failed to install the interrupt handler: {}", e);
+    }
+}
+
+/// Loads the configuration given by `--config` or found in the current
+/// directory, and applies the command-line overrides.
+fn load_config(cli: &Cli, overrides: &ConfigOverrides) -> Result<LinterConfig> {
     let mut config = if let Some(ref path) = cli.config {
-        LinterConfig::from_file(path).into_diagnostic()?
+        LinterConfig::from_file(path)?
     } else {
         // Try to find config file
         find_config()?
     };
 
-    // Override timings from CLI
-    if timings {
-        config.timings = true;
+    if cli.no_cache {
+        config.cache = false;
     }
+    overrides.apply(&mut config);
+    Ok(config)
+}
 
-    // Capture timings flag before config is moved
-    let timings_enabled = config.timings;
+impl ConfigOverrides {
+    /// Applies the overrides to `config`.
+    fn apply(&self, config: &mut LinterConfig) {
+        if self.timings {
+            config.timings = true;
+        }
+        if self.profile_rules {
+            config.profile_rules = true;
+        }
+        if !self.roots.is_empty() {
+            config.roots = self.roots.clone();
+        }
+        if self.max_warnings.is_some() {
+            config.max_warnings = self.max_warnings;
+        }
+        if !self.fail_on_tags.is_empty() {
+            config.fail_on_tags = self.fail_on_tags.clone();
+        }
+        if self.strict && config.strict == StrictMode::Off {
+            config.strict = StrictMode::Exit;
+        }
+        // Rule overrides apply to the loaded config; disabling wins, and
+        // --only-rule wins over both
+        for rule in &self.enable_rules {
+            config.set_rule_enabled(rule, true);
+        }
+        for rule in &self.disable_rules {
+            config.set_rule_enabled(rule, false);
+        }
+        if let Some(rule) = &self.only_rule {
+            config.retain_only_rule(rule);
+        }
+    }
 
-    // Create linter
-    let linter = Linter::new(config).into_diagnostic()?;
+    /// Returns the settings the overrides change: top-level keys, and
+    /// `options.<rule>` for rules.
+    fn overridden_keys(&self) -> Vec<String> {
+        let flags = [
+            ("timings", self.timings),
+            ("profile_rules", self.profile_rules),
+            ("roots", !self.roots.is_empty()),
+            ("max_warnings", self.max_warnings.is_some()),
+            ("fail_on_tags", !self.fail_on_tags.is_empty()),
+            ("strict", self.strict),
+        ];
+        flags
+            .into_iter()
+            .filter(|(_, set)| *set)
+            .map(|(key, _)| key.to_string())
+            .chain(
+                self.enable_rules
+                    .iter()
+                    .chain(&self.disable_rules)
+                    .chain(&self.only_rule)
+                    .map(|rule| format!("options.{}", rule)),
+            )
+            .collect()
+    }
+}
 
-    // Run linting
-    let results = linter.lint_patterns(patterns).into_diagnostic()?;
+/// Origin of settings set by neither the configuration file nor the
+/// command line.
+const DEFAULT_ORIGIN: &str = "default";
 
-    // Apply fixes if requested
-    if fix {
-        let fix_summary = apply_fixes(&results, dry_run)?;
-        output_fix_summary(&fix_summary, dry_run);
+/// Origin of settings overridden by command-line flags.
+const COMMAND_LINE_ORIGIN: &str = "command line";
 
-        if dry_run {
-            // In dry-run mode, still output diagnostics
-            let has_errors = output_results(&results, format, timings_enabled)?;
-            return Ok(has_errors);
-        }
+fn run_config_show(cli: &Cli, overrides: &ConfigOverrides, origin: bool) -> Result<()> {
+    let config = load_config(cli, overrides)?;
+    let mut value = serde_json::to_value(&config).into_diagnostic()?;
 
-        // After fixing, return based on whether there were unfixable errors
-        let unfixable_errors = results
-            .iter()
-            .any(|r| r.diagnostics.iter().any(|d| d.fix.is_none()));
-        return Ok(unfixable_errors);
+    if origin {
+        let mut command_line = overrides.overridden_keys();
+        if cli.no_cache {
+            command_line.push("cache".to_string());
+        }
+        value = annotate_origins(value, config.source.as_deref(), &command_line)?;
     }
 
-    // Output results
-    let has_errors = output_results(&results, format, timings_enabled)?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&value).into_diagnostic()?
+    );
+    Ok(())
+}
 
-    Ok(has_errors)
+/// Replaces each setting of the serialized configuration `value` with
+/// `{ "value": ..., "origin": ... }`, where the origin is the command line
+/// for the `command_line` keys, the configuration file `source` for the
+/// settings it sets, and otherwise the default. Rules in `options` are
+/// annotated one by one.
+fn annotate_origins(
+    value: serde_json::Value,
+    source: Option<&Path>,
+    command_line: &[String],
+) -> Result<serde_json::Value> {
+    use serde_json::{Map, Value, json};
+
+    let file_settings = match source {
+        Some(path) => {
+            let content = std::fs::read_to_string(path).into_diagnostic()?;
+            let mut settings: Value = serde_json::from_str(&content).into_diagnostic()?;
+            // package.json keeps the configuration under the `texide` key
+            if path.file_name().is_some_and(|name| name == "package.json") {
+                settings = settings["texide"].take();
+            }
+            settings
+        }
+        None => Value::Null,
+    };
+    let file_origin = source.map(|path| path.display().to_string());
+    let origin = |key: &str, in_file: bool| match &file_origin {
+        _ if command_line.iter().any(|k| k == key) => COMMAND_LINE_ORIGIN.to_string(),
+        Some(file) if in_file => file.clone(),
+        _ => DEFAULT_ORIGIN.to_string(),
+    };
+
+    let Value::Object(settings) = value else {
+        return Ok(value);
+    };
+    let annotated: Map<String, Value> = settings
+        .into_iter()
+        .map(|(key, value)| {
+            let annotated = match value {
+                Value::Object(rules) if key == "options" => rules
+                    .into_iter()
+                    .map(|(rule, value)| {
+                        let in_file = file_settings["options"].get(&rule).is_some();
+                        let origin = origin(&format!("options.{}", rule), in_file);
+                        (rule, json!({ "value": value, "origin": origin }))
+                    })
+                    .collect::<Map<_, _>>()
+                    .into(),
+                value => {
+                    let origin = origin(&key, file_settings.get(&key).is_some());
+                    json!({ "value": value, "origin": origin })
+                }
+            };
+            (key, annotated)
+        })
+        .collect();
+    Ok(Value::Object(annotated))
 }
 
 fn find_config() -> Result<LinterConfig> {
-    let config_files = [".texide.jsonc", ".texide.json"];
-
-    for name in config_files {
-        let path = PathBuf::from(name);
-        if path.exists() {
-            info!("Using config: {}", name);
-            return LinterConfig::from_file(&path).into_diagnostic();
-        }
+    if let Some(config) = LinterConfig::discover("")? {
+        return Ok(config);
     }
 
     // Return default config if no file found
@@ -234,84 +636,129 @@ fn find_config() -> Result<LinterConfig> {
     Ok(LinterConfig::new())
 }
 
-fn output_results(results: &[LintResult], format: &str, timings: bool) -> Result<bool> {
-    let has_errors = results.iter().any(|r| r.has_errors());
+/// Formats file paths for output according to the configured style.
+struct PathFormatter {
+    style: PathStyle,
+    /// Directory relative paths are shown against.
+    root: PathBuf,
+}
 
-    match format {
-        "json" => {
-            let output: Vec<_> = results
-                .iter()
-                .map(|r| {
-                    serde_json::json!({
-                        "path": r.path.display().to_string(),
-                        "diagnostics": r.diagnostics,
-                    })
-                })
-                .collect();
-            println!("{}", serde_json::to_string_pretty(&output).unwrap());
-        }
-        _ => {
-            // Text format
-            for result in results {
-                if result.diagnostics.is_empty() {
-                    continue;
-                }
+impl PathFormatter {
+    fn format(&self, path: &Path) -> String {
+        self.style.display(path, &self.root).display().to_string()
+    }
+}
 
-                println!("\n{}:", result.path.display());
-                for diag in &result.diagnostics {
-                    let severity = match diag.severity {
-                        Severity::Error => "error",
-                        Severity::Warning => "warning",
-                        Severity::Info => "info",
-                    };
-                    println!(
-                        "  {}:{} {} [{}]: {}",
-                        diag.span.start, diag.span.end, severity, diag.rule_id, diag.message
-                    );
-                }
-            }
+/// Prints the `--profile-rules` report, slowest rule first.
+///
+/// Written to stderr so it can be combined with machine-readable formats.
+fn output_rule_profile(profile: &RuleProfiler) {
+    let rules = profile.sorted();
+    if rules.is_empty() {
+        return;
+    }
 
-            // Summary
-            let total_files = results.len();
-            let total_errors: usize = results.iter().map(|r| r.diagnostics.len()).sum();
-            let cached = results.iter().filter(|r| r.from_cache).count();
+    eprintln!("\nRule Profile:");
+    eprintln!(
+        "{:<30} | {:<15} | {:<10} | {:<15}",
+        "Rule", "Total", "Calls", "Avg"
+    );
+    eprintln!("{:-<30}-+-{:-<15}-+-{:-<10}-+-{:-<15}", "", "", "", "");
+    for (rule, stats) in rules {
+        eprintln!(
+            "{:<30} | {:<15?} | {:<10} | {:<15?}",
+            rule,
+            stats.total,
+            stats.invocations,
+            stats.average()
+        );
+    }
+}
 
-            println!();
-            println!(
-                "Checked {} files ({} from cache), found {} issues",
-                total_files, cached, total_errors
-            );
+/// Number of files listed in the `--timings` report of the slowest files.
+const SLOWEST_FILES: usize = 10;
 
-            if timings {
-                let mut total_duration = Duration::new(0, 0);
-                let mut rule_timings: HashMap<String, Duration> = HashMap::new();
+fn output_results(
+    linter: &Linter,
+    results: &mut [LintResult],
+    paths: &PathFormatter,
+    format: &str,
+    quiet: bool,
+    timings: bool,
+) -> Result<bool> {
+    let has_errors = results.iter().any(|r| r.has_errors());
 
-                for result in results {
-                    for (rule, duration) in &result.timings {
-                        *rule_timings.entry(rule.clone()).or_default() += *duration;
-                        total_duration += *duration;
-                    }
-                }
+    let mut registry = FormatterRegistry::new();
+    // Dim secondary text such as help URLs on terminals, unless NO_COLOR is set
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    registry.register("pretty", Pretty { color, quiet });
+    registry.register("text", Pretty { color, quiet });
+    let Some(formatter) = registry.get(format) else {
+        let available: Vec<_> = registry.names().collect();
+        return Err(miette::miette!(
+            "Unknown output format '{}' (available: {})",
+            format,
+            available.join(", ")
+        ));
+    };
 
-                if !rule_timings.is_empty() {
-                    println!("\nPerformance Timings:");
-                    println!("{:<30} | {:<15} | {:<10}", "Rule", "Duration", "%");
-                    println!("{:-<30}-+-{:-<15}-+-{:-<10}", "", "", "");
-
-                    let mut sorted_timings: Vec<_> = rule_timings.into_iter().collect();
-                    sorted_timings.sort_by(|a, b| b.1.cmp(&a.1));
-
-                    for (rule, duration) in sorted_timings {
-                        let percentage = if total_duration.as_secs_f64() > 0.0 {
-                            (duration.as_secs_f64() / total_duration.as_secs_f64()) * 100.0
-                        } else {
-                            0.0
-                        };
-                        println!("{:<30} | {:<15?} | {:<10.1}%", rule, duration, percentage);
-                    }
-                    println!("{:-<30}-+-{:-<15}-+-{:-<10}", "", "", "");
-                    println!("{:<30} | {:<15?}", "Total", total_duration);
-                }
+    for result in results.iter_mut() {
+        result.path = PathBuf::from(paths.format(&result.path));
+        linter.localize(&mut result.diagnostics);
+    }
+    let mut stdout = std::io::stdout().lock();
+    formatter.format(results, &mut stdout).into_diagnostic()?;
+
+    if timings && matches!(format, "pretty" | "text") {
+        let mut total_duration = Duration::new(0, 0);
+        let mut rule_timings: HashMap<String, Duration> = HashMap::new();
+
+        for result in results.iter() {
+            for (rule, duration) in &result.timings {
+                *rule_timings.entry(rule.clone()).or_default() += *duration;
+                total_duration += *duration;
+            }
+        }
+
+        if !rule_timings.is_empty() {
+            println!("\nPerformance Timings:");
+            println!("{:<30} | {:<15} | {:<10}", "Rule", "Duration", "%");
+            println!("{:-<30}-+-{:-<15}-+-{:-<10}", "", "", "");
+
+            let mut sorted_timings: Vec<_> = rule_timings.into_iter().collect();
+            sorted_timings.sort_by(|a, b| b.1.cmp(&a.1));
+
+            for (rule, duration) in sorted_timings {
+                let percentage = if total_duration.as_secs_f64() > 0.0 {
+                    (duration.as_secs_f64() / total_duration.as_secs_f64()) * 100.0
+                } else {
+                    0.0
+                };
+                println!("{:<30} | {:<15?} | {:<10.1}%", rule, duration, percentage);
+            }
+            println!("{:-<30}-+-{:-<15}-+-{:-<10}", "", "", "");
+            println!("{:<30} | {:<15?}", "Total", total_duration);
+        }
+
+        let mut slowest: Vec<_> = results
+            .iter()
+            .filter_map(|r| Some((r, r.duration?)))
+            .collect();
+        if !slowest.is_empty() {
+            slowest.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+            slowest.truncate(SLOWEST_FILES);
+
+            println!("\nSlowest Files:");
+            println!("{:<50} | {:<15} | {:<10}", "File", "Duration", "Bytes");
+            println!("{:-<50}-+-{:-<15}-+-{:-<10}", "", "", "");
+            for (result, duration) in slowest {
+                let bytes = result.bytes.map(|b| b.to_string()).unwrap_or_default();
+                println!(
+                    "{:<50} | {:<15?} | {:<10}",
+                    result.path.display(),
+                    duration,
+                    bytes
+                );
             }
         }
     }
@@ -341,6 +788,38 @@ fn run_init(force: bool) -> Result<()> {
     Ok(())
 }
 
+fn run_migrate(from: &str, path: &Path, force: bool) -> Result<()> {
+    let config_path = PathBuf::from(".texide.json");
+
+    if config_path.exists() && !force {
+        return Err(miette::miette!(
+            "Config file already exists. Use --force to overwrite."
+        ));
+    }
+
+    let content = std::fs::read_to_string(path).into_diagnostic()?;
+    let migration = match from {
+        "markdownlint" => migrate::from_markdownlint(&content),
+        "textlint" => migrate::from_textlint(&content),
+        _ => {
+            return Err(miette::miette!(
+                "Unknown migration source '{}' (available: markdownlint, textlint)",
+                from
+            ));
+        }
+    }
+    .into_diagnostic()?;
+
+    for warning in &migration.warnings {
+        warn!("{}", warning);
+    }
+    let json = serde_json::to_string_pretty(&migration.config).into_diagnostic()?;
+    std::fs::write(&config_path, json + "\n").into_diagnostic()?;
+    info!("Created {} from {}", config_path.display(), path.display());
+
+    Ok(())
+}
+
 fn run_create_rule(name: &str) -> Result<()> {
     let rule_dir = PathBuf::from(name);
 
@@ -476,83 +955,320 @@ fn run_add_rule(path: &Path) -> Result<()> {
     Ok(())
 }
 
+fn run_rule_docs(config_path: Option<&Path>, name: Option<&str>, format: &str) -> Result<()> {
+    let mut config = if let Some(path) = config_path {
+        LinterConfig::from_file(path)?
+    } else {
+        find_config()?
+    };
+    config.cache = false;
+
+    let linter = Linter::new(config)?;
+    let docs: Vec<RuleDoc> = linter
+        .rule_manifests()
+        .iter()
+        .filter(|manifest| name.is_none_or(|name| manifest.name == name))
+        .map(RuleDoc::from_manifest)
+        .collect();
+    if let Some(name) = name
+        && docs.is_empty()
+    {
+        return Err(miette::miette!("Rule '{}' is not loaded", name));
+    }
+
+    match format {
+        "markdown" => print!("{}", docs::to_markdown(&docs)),
+        "json" => println!("{}", serde_json::to_string_pretty(&docs).into_diagnostic()?),
+        _ => {
+            return Err(miette::miette!(
+                "Unknown docs format '{}' (available: markdown, json)",
+                format
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn run_install_rules(config_path: Option<&Path>, jobs: usize) -> Result<()> {
+    let config = if let Some(path) = config_path {
+        LinterConfig::from_file(path)?
+    } else {
+        find_config()?
+    };
+
+    let policy = PluginPolicy::new(&config.allowed_plugins, &config.denied_plugins)?;
+    let mut sources = Vec::new();
+    for rule in &config.rules {
+        match rule {
+            RuleDefinition::Detail(RuleDefinitionDetail {
+                url: Some(url),
+                r#as: Some(name),
+                ..
+            }) => {
+                policy.check(name, &[name, url])?;
+                sources.push(PluginSource {
+                    name: name.clone(),
+                    manifest_url: url.clone(),
+                });
+            }
+            RuleDefinition::Detail(RuleDefinitionDetail {
+                url: Some(url),
+                r#as: None,
+                ..
+            }) => {
+                return Err(miette::miette!(
+                    "Rule from {} needs an `as` name to be installed under",
+                    url
+                ));
+            }
+            RuleDefinition::Detail(RuleDefinitionDetail { path: Some(_), .. }) => {}
+            RuleDefinition::Simple(github)
+            | RuleDefinition::Detail(RuleDefinitionDetail {
+                github: Some(github),
+                ..
+            }) => {
+                warn!("GitHub rule fetching not yet implemented: {}", github);
+            }
+            RuleDefinition::Detail(_) => {}
+        }
+    }
+
+    if sources.is_empty() {
+        info!("No remote rules to install");
+        return Ok(());
+    }
+
+    let cache_dir = Installer::default_cache_dir()
+        .ok_or_else(|| miette::miette!("Could not determine the home directory"))?;
+    let installer = Installer::new(cache_dir)
+        .with_jobs(jobs)
+        .with_mirrors(config.mirrors)
+        .with_progress(|progress| match progress.result {
+            Ok(plugin) => info!(
+                "[{}/{}] Installed {} {}",
+                progress.completed, progress.total, plugin.name, plugin.version
+            ),
+            Err(e) => error!(
+                "[{}/{}] Failed to install {}: {}",
+                progress.completed, progress.total, progress.name, e
+            ),
+        });
+
+    let installed = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .into_diagnostic()?
+        .block_on(installer.install_all(sources))
+        .into_diagnostic()?;
+
+    info!("Installed {} rules", installed.len());
+    Ok(())
+}
+
 /// Summary of applied fixes.
 struct FixSummary {
     total_fixes: usize,
     files_fixed: usize,
-    fixes_by_file: Vec<(PathBuf, usize)>,
+    fixes_by_file: Vec<(String, usize)>,
+    /// Files whose fixes kept producing new fixes, with the rules responsible.
+    unconverged: Vec<(String, Vec<String>)>,
+}
+
+/// Outcome of fixing a single file until no more fixes apply.
+enum FixOutcome {
+    /// The fixes settled.
+    Converged {
+        /// The fixed content.
+        content: String,
+        /// The number of fixes applied.
+        fixes_applied: usize,
+        /// The diagnostics fixed in each pass.
+        passes: Vec<Vec<Diagnostic>>,
+        /// The diagnostics left in the fixed content.
+        remaining: Vec<Diagnostic>,
+    },
+    /// Fixes were still being produced after the last pass; holds the rules producing them.
+    Unconverged(Vec<String>),
 }
 
 /// Applies fixes to all files with fixable diagnostics.
-fn apply_fixes(results: &[LintResult], dry_run: bool) -> Result<FixSummary> {
+///
+/// Each file is fixed and re-linted in memory until no further fixes are
+/// produced, then the passes are written with [`apply_fixes_to_file`].
+/// Files that still produce fixes after `max_passes`, or that fail to
+/// re-lint, are left untouched.
+///
+/// The diagnostics of each fixed file in `results` are replaced by the
+/// ones left after fixing (or, in dry-run mode, that would be left).
+fn apply_fixes(
+    linter: &Linter,
+    results: &mut [LintResult],
+    paths: &PathFormatter,
+    dry_run: bool,
+    max_passes: usize,
+) -> Result<FixSummary> {
     let mut total_fixes = 0;
     let mut files_fixed = 0;
     let mut fixes_by_file = Vec::new();
+    let mut unconverged = Vec::new();
 
     for result in results {
-        // Count fixable diagnostics
-        let fixable_count = result
-            .diagnostics
-            .iter()
-            .filter(|d| d.fix.is_some())
-            .count();
-
-        if fixable_count == 0 {
+        if !result.diagnostics.iter().any(|d| d.fix.is_some()) {
             continue;
         }
 
-        if dry_run {
-            // In dry-run mode, just count the fixes
-            fixes_by_file.push((result.path.clone(), fixable_count));
-            total_fixes += fixable_count;
-            files_fixed += 1;
-        } else {
-            // Actually apply the fixes
-            match apply_fixes_to_file(&result.path, &result.diagnostics) {
-                Ok(fixer_result) => {
-                    if fixer_result.modified {
-                        fixes_by_file.push((result.path.clone(), fixer_result.fixes_applied));
-                        total_fixes += fixer_result.fixes_applied;
-                        files_fixed += 1;
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to fix {}: {}", result.path.display(), e);
-                }
+        let content = match std::fs::read_to_string(&result.path) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Failed to read {}: {}", result.path.display(), e);
+                continue;
+            }
+        };
+
+        let outcome = fix_until_stable(&content, &result.diagnostics, max_passes, |fixed| {
+            linter.lint_content(fixed, &result.path)
+        });
+
+        let (fixed_content, fixes_applied, passes, remaining) = match outcome {
+            Ok(FixOutcome::Converged {
+                content: fixed,
+                fixes_applied,
+                passes,
+                remaining,
+            }) if fixed != content => (fixed, fixes_applied, passes, remaining),
+            Ok(FixOutcome::Converged { .. }) => continue,
+            Ok(FixOutcome::Unconverged(rules)) => {
+                unconverged.push((paths.format(&result.path), rules));
+                continue;
+            }
+            Err(e) => {
+                error!("Failed to re-lint {}: {}", result.path.display(), e);
+                continue;
             }
+        };
+
+        let display_path = paths.format(&result.path);
+        if dry_run {
+            // In dry-run mode, print what would change instead of writing it
+            print!("{}", unified_diff(&display_path, &content, &fixed_content));
+        } else if let Err(e) = passes
+            .iter()
+            .try_for_each(|pass| apply_fixes_to_file(&result.path, pass).map(drop))
+        {
+            error!("{}", e);
+            continue;
         }
+
+        result.diagnostics = remaining;
+        fixes_by_file.push((display_path, fixes_applied));
+        total_fixes += fixes_applied;
+        files_fixed += 1;
     }
 
     Ok(FixSummary {
         total_fixes,
         files_fixed,
         fixes_by_file,
+        unconverged,
     })
 }
 
+/// Applies `diagnostics` to `content` and re-lints with `lint` until no
+/// fixes remain, for at most `max_passes` passes.
+fn fix_until_stable<E>(
+    content: &str,
+    diagnostics: &[Diagnostic],
+    max_passes: usize,
+    mut lint: impl FnMut(&str) -> std::result::Result<Vec<Diagnostic>, E>,
+) -> std::result::Result<FixOutcome, E> {
+    let mut content = content.to_string();
+    let mut diagnostics = diagnostics.to_vec();
+    let mut fixes_applied = 0;
+    let mut passes = Vec::new();
+
+    for _ in 0..max_passes {
+        let result = apply_fixes_to_content(&content, &diagnostics);
+        if !result.modified {
+            return Ok(FixOutcome::Converged {
+                content,
+                fixes_applied,
+                passes,
+                remaining: diagnostics,
+            });
+        }
+
+        fixes_applied += result.fixes_applied;
+        content = result.fixed_content;
+        passes.push(std::mem::replace(&mut diagnostics, lint(&content)?));
+    }
+
+    if !diagnostics.iter().any(|d| d.fix.is_some()) {
+        return Ok(FixOutcome::Converged {
+            content,
+            fixes_applied,
+            passes,
+            remaining: diagnostics,
+        });
+    }
+
+    let mut rules: Vec<String> = diagnostics
+        .into_iter()
+        .filter(|d| d.fix.is_some())
+        .map(|d| d.rule_id)
+        .collect();
+    rules.sort();
+    rules.dedup();
+    Ok(FixOutcome::Unconverged(rules))
+}
+
+/// Renders the change from `old` to `new` as a unified diff for `path`.
+fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(path, path)
+        .to_string()
+}
+
 /// Outputs the fix summary.
+///
+/// In dry-run mode the summary goes to stderr so stdout holds only the patch.
 fn output_fix_summary(summary: &FixSummary, dry_run: bool) {
+    for (path, rules) in &summary.unconverged {
+        error!(
+            "Fixes for {} did not converge; rules still producing fixes: {}",
+            path,
+            rules.join(", ")
+        );
+    }
+
     if summary.total_fixes == 0 {
-        println!("No fixable issues found.");
+        if !summary.unconverged.is_empty() {
+            return;
+        }
+        if dry_run {
+            eprintln!("No fixable issues found.");
+        } else {
+            println!("No fixable issues found.");
+        }
         return;
     }
 
     if dry_run {
-        println!(
+        eprintln!(
             "\nWould fix {} issues in {} files:",
             summary.total_fixes, summary.files_fixed
         );
         for (path, count) in &summary.fixes_by_file {
-            println!("  {}: {} fixes", path.display(), count);
+            eprintln!("  {}: {} fixes", path, count);
         }
-        println!("\nRun without --dry-run to apply fixes.");
+        eprintln!("\nRun without --dry-run to apply fixes.");
     } else {
         println!(
             "\nFixed {} issues in {} files:",
             summary.total_fixes, summary.files_fixed
         );
         for (path, count) in &summary.fixes_by_file {
-            println!("  {}: {} fixes", path.display(), count);
+            println!("  {}: {} fixes", path, count);
         }
     }
 }
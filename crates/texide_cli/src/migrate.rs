@@ -0,0 +1,425 @@
+//! Conversion of other linters' configuration to Texide configuration.
+//!
+//! Rules with a Texide counterpart are enabled in the `options` map of the
+//! converted configuration, with their options translated where Texide
+//! supports them. Everything that cannot be carried over is reported as a
+//! warning in the returned [`Migration`].
+
+use serde_json::{Map, Value};
+
+use crate::LinterError;
+
+/// The result of converting a configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    /// The converted Texide configuration.
+    pub config: Value,
+    /// Settings that have no Texide equivalent and were dropped.
+    pub warnings: Vec<String>,
+}
+
+/// markdownlint rules with a Texide counterpart, as
+/// `(id, alias, Texide rule)`.
+const MARKDOWNLINT_RULES: [(&str, &str, &str); 7] = [
+    ("MD012", "no-multiple-blanks", "no-multiple-blank-lines"),
+    (
+        "MD026",
+        "no-trailing-punctuation",
+        "no-trailing-punctuation",
+    ),
+    ("MD029", "ol-prefix", "ordered-list-style"),
+    ("MD034", "no-bare-urls", "no-bare-urls"),
+    ("MD040", "fenced-code-language", "code-block-language"),
+    ("MD045", "no-alt-text", "image-alt-text"),
+    ("MD056", "table-column-count", "consistent-table-columns"),
+];
+
+/// Converts a markdownlint configuration (`.markdownlint.json` or
+/// `.markdownlintrc`) to Texide configuration.
+///
+/// Rules may be named by id (`MD012`) or alias (`no-multiple-blanks`), in
+/// any case. As in markdownlint, rules that are not mentioned are enabled
+/// unless `default` is `false`. Disabled rules without a Texide counterpart
+/// are dropped silently; `extends` is not followed.
+///
+/// # Errors
+///
+/// Returns a configuration error if `json` is not a JSON object.
+pub fn from_markdownlint(json: &str) -> Result<Migration, LinterError> {
+    let source: Value = serde_json::from_str(json)
+        .map_err(|e| LinterError::config(format!("Invalid markdownlint config: {}", e)))?;
+    let Value::Object(source) = source else {
+        return Err(LinterError::config(
+            "Invalid markdownlint config: expected an object",
+        ));
+    };
+
+    let mut options = Map::new();
+    let mut warnings = Vec::new();
+    let mut mentioned = Vec::new();
+    let default = source.get("default").and_then(Value::as_bool) != Some(false);
+
+    for (key, value) in &source {
+        match key.as_str() {
+            "default" | "$schema" => continue,
+            "extends" => {
+                warnings.push(format!(
+                    "\"extends\" is not followed; migrate {} separately",
+                    value
+                ));
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some(&(id, _, rule)) = MARKDOWNLINT_RULES
+            .iter()
+            .find(|(id, alias, _)| key.eq_ignore_ascii_case(id) || key.eq_ignore_ascii_case(alias))
+        else {
+            if value != &Value::Bool(false) {
+                warnings.push(format!("{}: no equivalent Texide rule", key));
+            }
+            continue;
+        };
+        mentioned.push(id);
+
+        let option = match value {
+            Value::Bool(false) => continue,
+            Value::Bool(true) => Value::Bool(true),
+            Value::String(severity) if matches!(severity.as_str(), "error" | "warning") => {
+                Value::String(severity.clone())
+            }
+            Value::Object(settings) => {
+                let translated = markdownlint_options(id, settings, &mut warnings);
+                if translated.is_empty() {
+                    Value::Bool(true)
+                } else {
+                    Value::Object(translated)
+                }
+            }
+            other => {
+                warnings.push(format!("{}: unsupported value {}", key, other));
+                continue;
+            }
+        };
+        options.insert(rule.to_string(), option);
+    }
+
+    if default {
+        for &(id, _, rule) in &MARKDOWNLINT_RULES {
+            if !mentioned.contains(&id) {
+                options.insert(rule.to_string(), Value::Bool(true));
+            }
+        }
+    }
+
+    let mut config = Map::new();
+    config.insert("options".to_string(), Value::Object(options));
+    Ok(Migration {
+        config: Value::Object(config),
+        warnings,
+    })
+}
+
+/// Translates the options of markdownlint rule `id` to the options of its
+/// Texide counterpart.
+fn markdownlint_options(
+    id: &str,
+    settings: &Map<String, Value>,
+    warnings: &mut Vec<String>,
+) -> Map<String, Value> {
+    let mut options = Map::new();
+    for (name, value) in settings {
+        let translated = match (id, name.as_str(), value.as_str()) {
+            ("MD012", "maximum", _) => Some(("max", value.clone())),
+            ("MD026", "punctuation", _) => Some(("punctuation", value.clone())),
+            ("MD029", "style", Some("one")) => Some(("style", Value::from("one"))),
+            ("MD029", "style", Some("ordered")) => Some(("style", Value::from("ordinal"))),
+            ("MD029", "style", Some("zero")) => Some(("style", Value::from("zero"))),
+            ("MD040", "allowed_languages", _) => Some(("allowed", value.clone())),
+            _ => None,
+        };
+        match translated {
+            Some((name, value)) => {
+                options.insert(name.to_string(), value);
+            }
+            None => warnings.push(format!(
+                "{}: option {}={} is not supported",
+                id, name, value
+            )),
+        }
+    }
+    options
+}
+
+/// textlint rules with a built-in Texide counterpart, as
+/// `(textlint rule, Texide rule)`.
+const TEXTLINT_RULES: [(&str, &str); 1] = [("no-dead-link", "no-invalid-links")];
+
+/// textlint plugins whose file types Texide parses without a plugin.
+const TEXTLINT_BUILTIN_PLUGINS: [&str; 4] =
+    ["markdown", "text", "@textlint/markdown", "@textlint/text"];
+
+/// Converts a textlint configuration (`.textlintrc`, in JSON or YAML) to
+/// Texide configuration.
+///
+/// Rules keep their name without the `textlint-rule-` prefix, except for
+/// the few with a built-in Texide counterpart; the others need a Texide
+/// port loaded through `rules`, which is reported as a warning. A rule set
+/// to `true`, an options object (which may hold a `severity`), or a
+/// `[severity, options]` array is enabled; `false` and `"off"` leave it
+/// out. The `allowlist` filter becomes `ignore_patterns`; presets, other
+/// filters and plugins for file types Texide does not parse are reported
+/// as warnings.
+///
+/// # Errors
+///
+/// Returns a configuration error if `source` is not a JSON or YAML object.
+pub fn from_textlint(source: &str) -> Result<Migration, LinterError> {
+    let source: Value = match serde_json::from_str(source) {
+        Ok(value) => value,
+        Err(_) => serde_yaml_ng::from_str(source)
+            .map_err(|e| LinterError::config(format!("Invalid textlint config: {}", e)))?,
+    };
+    let Value::Object(source) = source else {
+        return Err(LinterError::config(
+            "Invalid textlint config: expected an object",
+        ));
+    };
+
+    let mut config = Map::new();
+    let mut options = Map::new();
+    let mut warnings = Vec::new();
+
+    if let Some(rules) = source.get("rules").and_then(Value::as_object) {
+        for (name, value) in rules {
+            let name = name.replacen("textlint-rule-", "", 1);
+            let short_name = name.rsplit('/').next().unwrap_or(&name);
+            if short_name.starts_with("preset-") {
+                warnings.push(format!(
+                    "{}: presets are not supported; enable their rules one by one",
+                    name
+                ));
+                continue;
+            }
+
+            let Some(option) = textlint_rule_option(&name, value, &mut warnings) else {
+                continue;
+            };
+            let rule = match TEXTLINT_RULES
+                .iter()
+                .find(|(textlint, _)| *textlint == name)
+            {
+                Some(&(_, rule)) => rule.to_string(),
+                None => {
+                    warnings.push(format!(
+                        "{}: not built into Texide; add a Texide port of it to \"rules\"",
+                        name
+                    ));
+                    name
+                }
+            };
+            options.insert(rule, option);
+        }
+    }
+
+    if let Some(filters) = source.get("filters").and_then(Value::as_object) {
+        for (name, value) in filters {
+            let name = name.replacen("textlint-filter-rule-", "", 1);
+            if value == &Value::Bool(false) {
+                continue;
+            }
+            match name.as_str() {
+                "allowlist" | "whitelist" => {
+                    let allow = value.get("allow").and_then(Value::as_array);
+                    config.insert(
+                        "ignore_patterns".to_string(),
+                        Value::Array(allow.cloned().unwrap_or_default()),
+                    );
+                    if let Some(settings) = value.as_object() {
+                        for key in settings.keys().filter(|key| *key != "allow") {
+                            warnings.push(format!("{}: option {} is not supported", name, key));
+                        }
+                    }
+                }
+                _ => warnings.push(format!("{}: no equivalent Texide filter", name)),
+            }
+        }
+    }
+
+    let plugins: Vec<&str> = match source.get("plugins") {
+        Some(Value::Array(plugins)) => plugins.iter().filter_map(Value::as_str).collect(),
+        Some(Value::Object(plugins)) => plugins
+            .iter()
+            .filter(|(_, value)| *value != &Value::Bool(false))
+            .map(|(name, _)| name.as_str())
+            .collect(),
+        _ => Vec::new(),
+    };
+    for plugin in plugins {
+        let plugin = plugin.replacen("textlint-plugin-", "", 1);
+        if !TEXTLINT_BUILTIN_PLUGINS.contains(&plugin.as_str()) {
+            warnings.push(format!("{}: plugins are not supported", plugin));
+        }
+    }
+
+    config.insert("options".to_string(), Value::Object(options));
+    Ok(Migration {
+        config: Value::Object(config),
+        warnings,
+    })
+}
+
+/// Translates the textlint setting of rule `name` to a Texide rule option,
+/// or `None` if the rule is disabled.
+fn textlint_rule_option(name: &str, value: &Value, warnings: &mut Vec<String>) -> Option<Value> {
+    match value {
+        Value::Bool(false) => None,
+        Value::Bool(true) => Some(Value::Bool(true)),
+        Value::Object(settings) => {
+            if settings.get("severity").and_then(Value::as_str) == Some("off") {
+                return None;
+            }
+            Some(value.clone())
+        }
+        Value::Array(items) => match items.as_slice() {
+            [Value::String(severity), ..] if severity == "off" => None,
+            [Value::String(severity)] => Some(Value::String(severity.clone())),
+            [Value::String(severity), options @ Value::Object(_)] => Some(Value::Array(vec![
+                Value::String(severity.clone()),
+                options.clone(),
+            ])),
+            _ => {
+                warnings.push(format!("{}: unsupported value {}", name, value));
+                None
+            }
+        },
+        other => {
+            warnings.push(format!("{}: unsupported value {}", name, other));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_markdownlint() {
+        let migration = from_markdownlint(
+            r#"{
+                "$schema": "https://example.com/markdownlint.schema.json",
+                "default": false,
+                "MD012": { "maximum": 2 },
+                "no-trailing-punctuation": { "punctuation": ".,;:" },
+                "ol-prefix": { "style": "ordered" },
+                "md040": { "allowed_languages": ["rust"], "language_only": true },
+                "no-alt-text": "warning",
+                "MD034": false,
+                "MD013": { "line_length": 100 },
+                "MD041": false
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            migration.config,
+            json!({
+                "options": {
+                    "no-multiple-blank-lines": { "max": 2 },
+                    "no-trailing-punctuation": { "punctuation": ".,;:" },
+                    "ordered-list-style": { "style": "ordinal" },
+                    "code-block-language": { "allowed": ["rust"] },
+                    "image-alt-text": "warning"
+                }
+            })
+        );
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "MD013: no equivalent Texide rule",
+                "MD040: option language_only=true is not supported",
+            ]
+        );
+        crate::LinterConfig::from_json(&migration.config.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_markdownlint_default_enables_unmentioned_rules() {
+        let migration = from_markdownlint(r#"{ "MD034": false, "MD045": true }"#).unwrap();
+
+        let options = migration.config["options"].as_object().unwrap();
+        assert_eq!(options.len(), MARKDOWNLINT_RULES.len() - 1);
+        assert!(!options.contains_key("no-bare-urls"));
+        assert_eq!(options["image-alt-text"], true);
+        assert!(migration.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_markdownlint_requires_object() {
+        assert!(from_markdownlint("[]").is_err());
+        assert!(from_markdownlint("{").is_err());
+    }
+
+    #[test]
+    fn test_textlint() {
+        let migration = from_textlint(
+            r#"{
+                "plugins": ["@textlint/markdown", "html"],
+                "filters": {
+                    "allowlist": { "allow": ["ACME", "/\\d+px/"] },
+                    "comments": true
+                },
+                "rules": {
+                    "textlint-rule-no-todo": true,
+                    "sentence-length": ["warning", { "max": 100 }],
+                    "max-ten": { "severity": "info", "max": 3 },
+                    "no-dead-link": ["error"],
+                    "no-exclamation-question-mark": false,
+                    "preset-ja-technical-writing": { "ja-no-weak-phrase": true }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            migration.config,
+            json!({
+                "ignore_patterns": ["ACME", "/\\d+px/"],
+                "options": {
+                    "no-todo": true,
+                    "sentence-length": ["warning", { "max": 100 }],
+                    "max-ten": { "severity": "info", "max": 3 },
+                    "no-invalid-links": "error"
+                }
+            })
+        );
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "max-ten: not built into Texide; add a Texide port of it to \"rules\"",
+                "preset-ja-technical-writing: presets are not supported; enable their rules one by one",
+                "sentence-length: not built into Texide; add a Texide port of it to \"rules\"",
+                "no-todo: not built into Texide; add a Texide port of it to \"rules\"",
+                "comments: no equivalent Texide filter",
+                "html: plugins are not supported",
+            ]
+        );
+        crate::LinterConfig::from_json(&migration.config.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_textlint_yaml() {
+        let migration = from_textlint(
+            "rules:\n  sentence-length:\n    - warning\n    - max: 80\n  no-todo: [\"off\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            migration.config,
+            json!({ "options": { "sentence-length": ["warning", { "max": 80 }] } })
+        );
+    }
+}
@@ -65,3 +65,990 @@ mod lint_command {
             .stdout(predicate::str::contains("Checked 0 files"));
     }
 }
+
+mod max_warnings {
+    use super::*;
+
+    /// Writes a Markdown file with two table rows missing a cell, which
+    /// `consistent-table-columns` reports as two warnings.
+    fn write_project(dir: &std::path::Path, config: &str) {
+        std::fs::write(dir.join(".texide.json"), config).unwrap();
+        std::fs::write(dir.join("doc.md"), "| a | b |\n| - | - |\n| 1 |\n| 2 |\n").unwrap();
+    }
+
+    const CONFIG: &str = r#"{ "options": { "consistent-table-columns": true } }"#;
+
+    #[test]
+    fn warnings_alone_do_not_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "**/*.md"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("0 errors, 2 warnings"));
+    }
+
+    #[test]
+    fn passes_under_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--max-warnings", "2", "**/*.md"])
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn fails_over_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--max-warnings", "1", "**/*.md"])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains(
+                "Too many warnings (2). Maximum allowed is 1.",
+            ));
+    }
+
+    #[test]
+    fn applies_threshold_after_fixing() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "**/*.md"])
+            .assert()
+            .success();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--max-warnings", "0", "**/*.md"])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains(
+                "Too many warnings (2). Maximum allowed is 0.",
+            ));
+    }
+
+    #[test]
+    fn reads_threshold_from_config_and_flag_overrides_it() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": true }, "max_warnings": 0 }"#,
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "**/*.md"])
+            .assert()
+            .code(1);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--max-warnings", "-1", "**/*.md"])
+            .assert()
+            .success();
+    }
+}
+
+mod fail_on_tags {
+    use super::*;
+
+    /// `no-bare-urls` reports errors tagged `style`; `image-alt-text`
+    /// reports diagnostics tagged `accessibility`.
+    const CONFIG: &str = r#"{
+        "options": { "no-bare-urls": "error", "image-alt-text": true },
+        "fail_on_tags": ["accessibility"]
+    }"#;
+
+    fn write_project(dir: &std::path::Path, config: &str, markdown: &str) {
+        std::fs::write(dir.join(".texide.json"), config).unwrap();
+        std::fs::write(dir.join("doc.md"), markdown).unwrap();
+    }
+
+    #[test]
+    fn passes_on_style_only_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG, "See https://example.com\n");
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "**/*.md"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("1 errors, 0 warnings"))
+            .stdout(predicate::str::contains("Issues by Tag:"));
+    }
+
+    #[test]
+    fn fails_on_accessibility_diagnostic() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            CONFIG,
+            "See https://example.com\n\n![](diagram.png)\n",
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "**/*.md"])
+            .assert()
+            .code(1);
+    }
+
+    #[test]
+    fn applies_after_fixing() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            CONFIG,
+            "See https://example.com\n\n![](diagram.png)\n",
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "**/*.md"])
+            .assert()
+            .code(1);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("doc.md")).unwrap(),
+            "See <https://example.com>\n\n![](diagram.png)\n"
+        );
+    }
+
+    #[test]
+    fn flag_overrides_config() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            CONFIG,
+            "See https://example.com\n\n![](diagram.png)\n",
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fail-on-tag", "correctness", "**/*.md"])
+            .assert()
+            .success();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fail-on-tag", "style", "**/*.md"])
+            .assert()
+            .code(1);
+    }
+
+    #[test]
+    fn strict_exit_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG, "See https://example.com\n");
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--strict", "**/*.md"])
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains(
+                "cannot be combined with fail_on_tags",
+            ));
+    }
+}
+
+mod strict {
+    use super::*;
+
+    /// Writes a Markdown file with two table rows missing a cell, which
+    /// `consistent-table-columns` reports as two warnings.
+    fn write_project(dir: &std::path::Path, config: &str) {
+        std::fs::write(dir.join(".texide.json"), config).unwrap();
+        std::fs::write(dir.join("doc.md"), "| a | b |\n| - | - |\n| 1 |\n| 2 |\n").unwrap();
+    }
+
+    const CONFIG: &str = r#"{ "options": { "consistent-table-columns": true } }"#;
+
+    #[test]
+    fn warnings_fail_only_with_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "**/*.md"])
+            .assert()
+            .success();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--strict", "**/*.md"])
+            .assert()
+            .code(1)
+            .stdout(predicate::str::contains("0 errors, 2 warnings"));
+    }
+
+    #[test]
+    fn clean_files_pass_with_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+        std::fs::write(
+            dir.path().join("doc.md"),
+            "| a | b |\n| - | - |\n| 1 | 2 |\n",
+        )
+        .unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--strict", "**/*.md"])
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn promote_reports_warnings_as_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": true }, "strict": "promote" }"#,
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--strict", "**/*.md"])
+            .assert()
+            .code(1)
+            .stdout(predicate::str::contains("2 errors, 0 warnings"));
+    }
+
+    #[test]
+    fn applies_after_fixing() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), CONFIG);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--strict", "**/*.md"])
+            .assert()
+            .code(1);
+
+        write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": true }, "strict": "promote" }"#,
+        );
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--strict", "**/*.md"])
+            .assert()
+            .code(1);
+    }
+}
+
+mod rule_overrides {
+    use super::*;
+
+    /// Writes a Markdown file with two table rows missing a cell, which
+    /// `consistent-table-columns` reports as two warnings.
+    fn write_project(dir: &std::path::Path, config: &str) {
+        std::fs::write(dir.join(".texide.json"), config).unwrap();
+        std::fs::write(dir.join("doc.md"), "| a | b |\n| - | - |\n| 1 |\n| 2 |\n").unwrap();
+    }
+
+    #[test]
+    fn disable_rule_overrides_config() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": true } }"#,
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args([
+                "lint",
+                "--disable-rule",
+                "consistent-table-columns",
+                "**/*.md",
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("found 0 issues"));
+    }
+
+    #[test]
+    fn enable_rule_overrides_config() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": "off" } }"#,
+        );
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args([
+                "lint",
+                "--enable-rule",
+                "consistent-table-columns",
+                "**/*.md",
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("0 errors, 2 warnings"));
+    }
+
+    #[test]
+    fn disable_wins_over_enable() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), "{}");
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args([
+                "lint",
+                "--enable-rule",
+                "consistent-table-columns",
+                "--disable-rule",
+                "consistent-table-columns",
+                "**/*.md",
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("found 0 issues"));
+    }
+}
+
+mod only_rule {
+    use super::*;
+
+    /// Writes a Markdown file with a bare URL and extra blank lines, both
+    /// of which are reported by enabled rules.
+    fn write_project(dir: &std::path::Path) {
+        std::fs::write(
+            dir.join(".texide.json"),
+            r#"{ "options": { "no-bare-urls": true, "no-multiple-blank-lines": true } }"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("doc.md"), "See https://example.com\n\n\n\nEnd\n").unwrap();
+    }
+
+    #[test]
+    fn only_rule_hides_other_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args([
+                "lint",
+                "--format",
+                "json",
+                "--only-rule",
+                "no-bare-urls",
+                "**/*.md",
+            ])
+            .assert()
+            .stdout(predicate::str::contains("no-bare-urls"))
+            .stdout(predicate::str::contains("no-multiple-blank-lines").not());
+    }
+
+    #[test]
+    fn only_rule_fixes_only_that_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--only-rule", "no-bare-urls", "**/*.md"])
+            .assert()
+            .success();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("doc.md")).unwrap(),
+            "See <https://example.com>\n\n\n\nEnd\n"
+        );
+    }
+}
+
+mod summary {
+    use super::*;
+
+    fn clean_project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".texide.json"),
+            r#"{ "options": { "no-bare-urls": true } }"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("doc.md"), "# Title\n\nClean text.\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn clean_run_prints_summary() {
+        let dir = clean_project();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["--no-cache", "lint", "**/*.md"])
+            .assert()
+            .success()
+            .stdout("\nChecked 1 files (0 from cache), found 0 issues\n");
+    }
+
+    #[test]
+    fn quiet_leaves_out_summary() {
+        let dir = clean_project();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["--no-cache", "lint", "--quiet", "**/*.md"])
+            .assert()
+            .success()
+            .stdout("");
+    }
+
+    #[test]
+    fn clean_run_has_json_summary() {
+        let dir = clean_project();
+
+        let output = texide_cmd()
+            .current_dir(dir.path())
+            .args([
+                "--no-cache",
+                "lint",
+                "--format",
+                "json",
+                "--quiet",
+                "**/*.md",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["results"][0]["path"], "doc.md");
+        assert_eq!(json["summary"]["files_checked"], 1);
+        assert_eq!(json["summary"]["total_diagnostics"], 0);
+    }
+}
+
+mod changed_files {
+    use super::*;
+
+    /// Runs git in `dir`, returning whether it succeeded.
+    fn git(dir: &std::path::Path, args: &[&str]) -> bool {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    #[test]
+    fn staged_lints_only_staged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        if !git(dir.path(), &["init", "-q"]) {
+            return;
+        }
+        std::fs::write(
+            dir.path().join(".texide.json"),
+            r#"{ "options": { "no-bare-urls": true } }"#,
+        )
+        .unwrap();
+        for file in ["staged.md", "unstaged.md"] {
+            std::fs::write(dir.path().join(file), "See https://example.com\n").unwrap();
+        }
+        assert!(git(dir.path(), &["add", "staged.md"]));
+
+        let output = texide_cmd()
+            .current_dir(dir.path())
+            .args([
+                "--no-cache",
+                "lint",
+                "--staged",
+                "--format",
+                "json",
+                "**/*.md",
+            ])
+            .output()
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["summary"]["files_checked"], 1);
+        assert_eq!(json["results"][0]["path"], "staged.md");
+    }
+
+    #[test]
+    fn outside_repository_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        // Only testable when the temporary directory is in no repository
+        if git(dir.path(), &["rev-parse", "--show-toplevel"]) {
+            return;
+        }
+        std::fs::write(dir.path().join("doc.md"), "Text\n").unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["--no-cache", "lint", "--since", "main", "**/*.md"])
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains(
+                "Linting changed files needs a git repository",
+            ));
+    }
+}
+
+mod help_url {
+    use super::*;
+
+    #[test]
+    fn help_url_option_is_resolved_in_json_output() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".texide.json"),
+            r#"{
+                "options": {
+                    "no-bare-urls": { "help_url": "https://docs.example.com/rules/{rule_id}.html" }
+                }
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("doc.md"), "See https://example.com\n").unwrap();
+
+        let output = texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--format", "json", "**/*.md"])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let diagnostic = &json["results"][0]["diagnostics"][0];
+        assert_eq!(diagnostic["rule_id"], "no-bare-urls");
+        assert_eq!(
+            diagnostic["help_url"],
+            "https://docs.example.com/rules/no-bare-urls.html"
+        );
+    }
+}
+
+mod path_style {
+    use super::*;
+
+    /// Writes a project whose `docs/doc.md` has one table warning, and
+    /// returns the project's canonical path.
+    fn write_project(dir: &std::path::Path, config: &str) -> std::path::PathBuf {
+        std::fs::write(dir.join(".texide.json"), config).unwrap();
+        std::fs::create_dir(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("docs/doc.md"), "| a | b |\n| - | - |\n| 1 |\n").unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn relative_paths_are_stable_across_working_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": true } }"#,
+        );
+
+        texide_cmd()
+            .current_dir(&root)
+            .args(["lint", "**/*.md"])
+            .assert()
+            .stdout(predicate::str::contains("\ndocs/doc.md:\n"));
+
+        texide_cmd()
+            .current_dir(root.join("docs"))
+            .arg("--config")
+            .arg(root.join(".texide.json"))
+            .args(["lint", "**/*.md"])
+            .assert()
+            .stdout(predicate::str::contains("\ndocs/doc.md:\n"));
+    }
+
+    #[test]
+    fn absolute_and_as_given_styles() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = write_project(
+            dir.path(),
+            r#"{ "options": { "consistent-table-columns": true }, "path_style": "absolute" }"#,
+        );
+
+        texide_cmd()
+            .current_dir(&root)
+            .args(["lint", "**/*.md"])
+            .assert()
+            .stdout(predicate::str::contains(format!(
+                "\n{}:\n",
+                root.join("docs/doc.md").display()
+            )));
+
+        std::fs::write(
+            root.join(".texide.json"),
+            r#"{ "options": { "consistent-table-columns": true }, "path_style": "as-given" }"#,
+        )
+        .unwrap();
+        texide_cmd()
+            .current_dir(&root)
+            .args(["lint", "**/*.md"])
+            .assert()
+            .stdout(predicate::str::contains("\n./docs/doc.md:\n"));
+    }
+}
+
+mod ast_command {
+    use super::*;
+
+    fn write_markdown(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let path = dir.path().join("doc.md");
+        std::fs::write(&path, "# Title\n\nHello *world*.\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn prints_tree_view() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_markdown(&dir);
+
+        let expected = "\
+Document [0..24]
+  Header [0..7] depth=1
+    Str [2..7] \"Title\"
+  Paragraph [9..23]
+    Str [9..15] \"Hello \"
+    Emphasis [15..22]
+      Str [16..21] \"world\"
+    Str [22..23] \".\"
+";
+
+        texide_cmd()
+            .arg("ast")
+            .arg(&path)
+            .assert()
+            .success()
+            .stdout(predicate::eq(expected));
+    }
+
+    #[test]
+    fn prints_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_markdown(&dir);
+
+        let output = texide_cmd()
+            .args(["ast", "--format", "json"])
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["type"], "Document");
+        assert_eq!(json["range"], serde_json::json!([0, 24]));
+        assert_eq!(json["children"][0]["type"], "Header");
+        assert_eq!(json["children"][0]["depth"], 1);
+        assert_eq!(json["children"][0]["children"][0]["value"], "Title");
+    }
+
+    #[test]
+    fn parses_like_lint_does() {
+        let dir = tempfile::tempdir().unwrap();
+        write_markdown(&dir);
+        std::fs::write(
+            dir.path().join(".texide.json"),
+            r#"{ "raw_files": ["*.md"] }"#,
+        )
+        .unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["ast", "doc.md"])
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with(
+                "Document [0..24]\n  Str [0..24]",
+            ));
+    }
+
+    #[test]
+    fn fails_for_missing_file() {
+        texide_cmd()
+            .args(["ast", "nonexistent_file.md"])
+            .assert()
+            .failure();
+    }
+}
+
+mod fix_command {
+    use super::*;
+    use std::path::{Path, PathBuf};
+    use texide_plugin::test_support::RuleFixture;
+
+    /// Builds an Extism rule named `fix-rule` that reports `diagnostics`
+    /// only when the request contains `needle`.
+    fn fix_rule_wasm(needle: &str, diagnostics: &str) -> Vec<u8> {
+        RuleFixture::new("fix-rule")
+            .reports(diagnostics)
+            .when_request_contains(needle)
+            .build()
+    }
+
+    /// A rule that collapses the double space in `Hello  world`.
+    const DOUBLE_SPACE_FIX: &str = r#"[{"message":"Double space","span":{"start":5,"end":7},"fix":{"span":{"start":5,"end":7},"text":" "}}]"#;
+
+    /// Writes a project whose `fix-rule` reports `diagnostics` whenever the
+    /// request contains `needle`, and returns the document path.
+    fn write_project(dir: &Path, needle: &str, diagnostics: &str) -> PathBuf {
+        let plugins = dir.join(".texide/plugins");
+        std::fs::create_dir_all(&plugins).unwrap();
+        std::fs::write(
+            plugins.join("fix-rule.wasm"),
+            fix_rule_wasm(needle, diagnostics),
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.join(".texide.json"),
+            r#"{ "rules": ["fix-rule"], "options": { "fix-rule": true } }"#,
+        )
+        .unwrap();
+
+        let doc = dir.join("doc.txt");
+        std::fs::write(&doc, "Hello  world\nSecond line\n").unwrap();
+
+        doc
+    }
+
+    #[test]
+    fn very_verbose_output_is_limited_to_texide() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), "Hello  world", "[]");
+
+        let output = texide_cmd()
+            .current_dir(dir.path())
+            .args(["-vv", "lint", "**/*.txt"])
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Loaded rule: fix-rule"), "{}", stderr);
+        // wasmtime traces every type it registers
+        assert!(!stderr.contains("RegisteredType"), "{}", stderr);
+    }
+
+    #[test]
+    fn dry_run_prints_diff_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = write_project(dir.path(), "Hello  world", DOUBLE_SPACE_FIX);
+
+        let expected = "\
+--- doc.txt
++++ doc.txt
+@@ -1,2 +1,2 @@
+-Hello  world
++Hello world
+ Second line
+";
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--dry-run", "**/*.txt"])
+            .assert()
+            .failure()
+            .stdout(predicate::eq(expected))
+            .stderr(predicate::str::contains("Would fix 1 issues in 1 files"));
+
+        assert_eq!(
+            std::fs::read_to_string(&doc).unwrap(),
+            "Hello  world\nSecond line\n"
+        );
+    }
+
+    #[test]
+    fn dry_run_succeeds_when_nothing_to_fix() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = write_project(dir.path(), "Hello  world", DOUBLE_SPACE_FIX);
+        std::fs::write(&doc, "Hello world\n").unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--dry-run", "**/*.txt"])
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+    }
+
+    #[test]
+    fn fix_converges_and_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = write_project(dir.path(), "Hello  world", DOUBLE_SPACE_FIX);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "**/*.txt"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Fixed 1 issues in 1 files"));
+
+        assert_eq!(
+            std::fs::read_to_string(&doc).unwrap(),
+            "Hello world\nSecond line\n"
+        );
+    }
+
+    #[test]
+    fn fix_judges_warnings_left_after_fixing() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), "Hello  world", DOUBLE_SPACE_FIX);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--max-warnings", "0", "**/*.txt"])
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn fix_reports_rules_that_never_converge() {
+        let dir = tempfile::tempdir().unwrap();
+        // Prepending "!" keeps "Hello" in the text, so the rule fires forever
+        let diagnostics = r#"[{"message":"Exclaim","span":{"start":0,"end":0},"fix":{"span":{"start":0,"end":0},"text":"!"}}]"#;
+        let doc = write_project(dir.path(), "Hello", diagnostics);
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["lint", "--fix", "--max-fix-passes", "3", "**/*.txt"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("did not converge"))
+            .stderr(predicate::str::contains("fix-rule"));
+
+        assert_eq!(
+            std::fs::read_to_string(&doc).unwrap(),
+            "Hello  world\nSecond line\n"
+        );
+    }
+}
+
+mod migrate_command {
+    use super::*;
+
+    #[test]
+    fn converts_markdownlint_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".markdownlint.json"),
+            r#"{ "default": false, "MD012": { "maximum": 2 }, "MD013": true }"#,
+        )
+        .unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["migrate", "--from", "markdownlint", ".markdownlint.json"])
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("MD013: no equivalent Texide rule"));
+
+        let config = std::fs::read_to_string(dir.path().join(".texide.json")).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&config).unwrap();
+        assert_eq!(
+            config,
+            serde_json::json!({ "options": { "no-multiple-blank-lines": { "max": 2 } } })
+        );
+
+        // An existing config is kept without --force
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["migrate", "--from", "markdownlint", ".markdownlint.json"])
+            .assert()
+            .failure();
+    }
+
+    #[test]
+    fn rejects_unknown_source() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("config.json"), "{}").unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["migrate", "--from", "unknown", "config.json"])
+            .assert()
+            .failure();
+        assert!(!dir.path().join(".texide.json").exists());
+    }
+}
+
+mod config_command {
+    use super::*;
+
+    fn write_config(dir: &tempfile::TempDir) {
+        std::fs::write(
+            dir.path().join(".texide.json"),
+            r#"{
+                "options": { "no-todo": true, "sentence-length": { "max": 80 } },
+                "max_warnings": 5
+            }"#,
+        )
+        .unwrap();
+    }
+
+    fn show(dir: &tempfile::TempDir, args: &[&str]) -> serde_json::Value {
+        let output = texide_cmd()
+            .current_dir(dir.path())
+            .args(["config", "show"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    }
+
+    #[test]
+    fn shows_overrides_merged_over_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(&dir);
+
+        let config = show(&dir, &["--disable-rule", "no-todo", "--max-warnings", "3"]);
+        assert_eq!(config["options"]["no-todo"], false);
+        assert_eq!(config["options"]["sentence-length"]["max"], 80);
+        assert_eq!(config["max_warnings"], 3);
+        assert_eq!(config["cache"], true);
+    }
+
+    #[test]
+    fn shows_origin_of_each_setting() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(&dir);
+
+        let config = show(&dir, &["--origin", "--disable-rule", "no-todo"]);
+        let options = &config["options"];
+        assert_eq!(options["no-todo"]["value"], false);
+        assert_eq!(options["no-todo"]["origin"], "command line");
+        assert_eq!(options["sentence-length"]["origin"], ".texide.json");
+        assert_eq!(config["max_warnings"]["value"], 5);
+        assert_eq!(config["max_warnings"]["origin"], ".texide.json");
+        assert_eq!(config["follow_symlinks"]["origin"], "default");
+    }
+
+    #[test]
+    fn points_at_json_syntax_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".texide.json"),
+            "{\n  \"cache\": false,\n  \"include\": [\"**/*.md\",]\n}\n",
+        )
+        .unwrap();
+
+        texide_cmd()
+            .current_dir(dir.path())
+            .args(["config", "show"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "trailing comma at line 3 column 25",
+            ))
+            .stderr(predicate::str::contains(".texide.json:3:25"));
+    }
+}
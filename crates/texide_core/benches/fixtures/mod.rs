@@ -0,0 +1,69 @@
+//! Deterministic fixture generators shared by the benchmarks.
+//!
+//! Fixtures are generated from fixed templates (no randomness) so results
+//! are comparable between runs and machines.
+
+#![allow(dead_code)]
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use texide_plugin::test_support::RuleFixture;
+
+/// Generates a Markdown document with `sections` sections.
+///
+/// Each section contains a heading, paragraphs with inline markup,
+/// a list, a code block, and a table.
+pub fn markdown_document(sections: usize) -> String {
+    let mut doc = String::from("# Benchmark Document\n\n");
+
+    for i in 0..sections {
+        writeln!(doc, "## Section {}\n", i).unwrap();
+        writeln!(
+            doc,
+            "This is paragraph {i} with **strong text**, *emphasis*, `inline code`, \
+             and a [link](https://example.com/{i}). It is long enough to wrap \
+             across several words and exercise the inline tokenizer.\n"
+        )
+        .unwrap();
+        doc.push_str("- First item\n- Second item with `code`\n  - Nested item\n\n");
+        writeln!(
+            doc,
+            "```rust\nfn section_{i}() -> usize {{\n    {i}\n}}\n```\n"
+        )
+        .unwrap();
+        doc.push_str("| Name | Value |\n| ---- | ----- |\n");
+        writeln!(doc, "| key{i} | {i} |\n").unwrap();
+        doc.push_str("> A quoted line of text.\n\n");
+    }
+
+    doc
+}
+
+/// Creates `dirs` directories under `root`, each holding `files_per_dir`
+/// Markdown files and one text file.
+pub fn file_tree(root: &Path, dirs: usize, files_per_dir: usize) {
+    let content = markdown_document(2);
+
+    for d in 0..dirs {
+        let dir = root.join(format!("dir{}", d)).join("nested");
+        fs::create_dir_all(&dir).unwrap();
+        for f in 0..files_per_dir {
+            fs::write(dir.join(format!("doc{}.md", f)), &content).unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "Plain text notes.\n").unwrap();
+    }
+}
+
+/// Builds a `noop` Extism rule that never reports diagnostics.
+///
+/// This measures the host overhead of calling a rule without any rule logic.
+pub fn noop_rule_wasm() -> Vec<u8> {
+    named_noop_rule_wasm("noop")
+}
+
+/// Builds an Extism rule called `name` that never reports diagnostics.
+pub fn named_noop_rule_wasm(name: &str) -> Vec<u8> {
+    RuleFixture::new(name).build()
+}
@@ -0,0 +1,113 @@
+//! Linter benchmarks covering file discovery and end-to-end linting.
+//!
+//! Run with `cargo bench -p texide_core --bench lint`.
+
+mod fixtures;
+
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tempfile::TempDir;
+
+use texide_ast::AstArena;
+use texide_core::{Linter, LinterConfig};
+use texide_parser::{MarkdownParser, Parser};
+use texide_plugin::PluginHost;
+
+/// Creates a fixture tree and makes it the current directory, since
+/// discovery walks from `.`.
+fn setup_tree(dirs: usize, files_per_dir: usize) -> TempDir {
+    let temp = TempDir::new().unwrap();
+    fixtures::file_tree(temp.path(), dirs, files_per_dir);
+    std::env::set_current_dir(temp.path()).unwrap();
+    temp
+}
+
+fn uncached_config() -> LinterConfig {
+    let mut config = LinterConfig::new();
+    config.cache = false;
+    config
+}
+
+fn bench_discover_files(c: &mut Criterion) {
+    let _temp = setup_tree(50, 20);
+    let linter = Linter::new(uncached_config()).unwrap();
+    let patterns = vec!["**/*.md".to_string()];
+
+    c.bench_function("discover_files/1000", |b| {
+        b.iter(|| {
+            let files = linter.discover_files(black_box(&patterns)).unwrap();
+            assert_eq!(files.len(), 1000);
+        });
+    });
+}
+
+fn bench_lint_files(c: &mut Criterion) {
+    let temp = setup_tree(10, 10);
+    let rule_path = temp.path().join("noop.wasm");
+    std::fs::write(&rule_path, fixtures::noop_rule_wasm()).unwrap();
+
+    let mut config = LinterConfig::from_json(r#"{ "options": { "noop": true } }"#).unwrap();
+    config.cache = false;
+    let linter = Linter::new(config).unwrap();
+    linter.load_rule(&rule_path).unwrap();
+
+    let files: Vec<PathBuf> = linter.discover_files(&["**/*.md".to_string()]).unwrap();
+    assert_eq!(files.len(), 100);
+
+    let mut group = c.benchmark_group("lint_files");
+    group.sample_size(20);
+    group.bench_function("noop_rule/100", |b| {
+        b.iter(|| {
+            let results = linter.lint_files(black_box(&files)).unwrap();
+            assert_eq!(results.len(), files.len());
+        });
+    });
+    group.finish();
+}
+
+/// Number of WASM rules run on the large document.
+const RULES: usize = 8;
+
+/// Runs 8 rules on the AST of a large document, serializing the request
+/// for each rule (`separate`) or once for all of them (`shared`).
+fn bench_wasm_rules(c: &mut Criterion) {
+    let mut host = PluginHost::new();
+    let names: Vec<String> = (0..RULES).map(|i| format!("noop{}", i)).collect();
+    for name in &names {
+        host.load_rule_bytes(&fixtures::named_noop_rule_wasm(name))
+            .unwrap();
+    }
+
+    let source = fixtures::markdown_document(200);
+    let arena = AstArena::new();
+    let ast = MarkdownParser::new().parse(&arena, &source).unwrap();
+    let node = serde_json::to_value(ast).unwrap();
+
+    let mut group = c.benchmark_group("wasm_rules");
+    group.sample_size(20);
+    group.bench_function("separate/x8", |b| {
+        b.iter(|| {
+            for name in &names {
+                host.run_rule(name, black_box(&node), &source, Some("large.md"))
+                    .unwrap();
+            }
+        });
+    });
+    group.bench_function("shared/x8", |b| {
+        b.iter(|| {
+            host.run_rules(&names, black_box(&node), &source, Some("large.md"))
+                .unwrap();
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_discover_files,
+    bench_lint_files,
+    bench_wasm_rules
+);
+criterion_main!(benches);
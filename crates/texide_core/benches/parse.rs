@@ -0,0 +1,93 @@
+//! Parser benchmarks.
+//!
+//! Run with `cargo bench -p texide_core --bench parse`.
+
+mod fixtures;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::Path;
+
+use texide_ast::{ArenaPool, AstArena};
+use texide_core::{LinterConfig, ParserSelector};
+
+fn bench_markdown_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_markdown");
+    let parser = ParserSelector::new(&LinterConfig::new())
+        .unwrap()
+        .select(Path::new("bench.md"));
+
+    for sections in [10, 100, 1000] {
+        let source = fixtures::markdown_document(sections);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(sections),
+            &source,
+            |b, source| {
+                b.iter(|| {
+                    let arena = AstArena::new();
+                    let ast = parser.parse(&arena, black_box(source)).unwrap();
+                    black_box(ast.children.len());
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_plain_text_parse(c: &mut Criterion) {
+    let parser = ParserSelector::new(&LinterConfig::new())
+        .unwrap()
+        .select(Path::new("bench.txt"));
+    let source = fixtures::markdown_document(1000);
+
+    c.bench_function("parse_plain_text/1000", |b| {
+        b.iter(|| {
+            let arena = AstArena::new();
+            let ast = parser.parse(&arena, black_box(&source)).unwrap();
+            black_box(ast.children.len());
+        });
+    });
+}
+
+/// Compares a fresh arena per file with a pooled arena reset between files.
+fn bench_arena_reuse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arena_reuse");
+    // Plain text parsing is cheap enough for arena allocation to show up
+    let parser = ParserSelector::new(&LinterConfig::new())
+        .unwrap()
+        .select(Path::new("bench.txt"));
+    let sources: Vec<String> = (1..=100).map(fixtures::markdown_document).collect();
+
+    group.bench_function("fresh/100", |b| {
+        b.iter(|| {
+            for source in &sources {
+                let arena = AstArena::new();
+                let ast = parser.parse(&arena, black_box(source)).unwrap();
+                black_box(ast.children.len());
+            }
+        });
+    });
+
+    let pool = ArenaPool::new();
+    group.bench_function("pooled/100", |b| {
+        b.iter(|| {
+            for source in &sources {
+                let arena = pool.get();
+                let ast = parser.parse(&arena, black_box(source)).unwrap();
+                black_box(ast.children.len());
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_markdown_parse,
+    bench_plain_text_parse,
+    bench_arena_reuse
+);
+criterion_main!(benches);
@@ -0,0 +1,197 @@
+//! Reading documents out of `.zip` and `.tar(.gz)` archives.
+//!
+//! Entries are streamed one at a time so that large archives can be linted
+//! without extracting them to disk.
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+use crate::LinterError;
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArchiveFormat {
+    /// `.zip`
+    Zip,
+    /// `.tar`
+    Tar,
+    /// `.tar.gz` or `.tgz`
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detects the archive format from a file name.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// A regular file inside an archive.
+pub(crate) struct ArchiveEntry<'r> {
+    /// Path of the entry inside the archive, `/`-separated.
+    pub name: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    /// Reader over the uncompressed content.
+    pub reader: &'r mut dyn Read,
+}
+
+/// Calls `visit` for each regular file in the archive at `path`.
+///
+/// Directories, links and other special entries are skipped.
+pub(crate) fn for_each_entry(
+    path: &Path,
+    mut visit: impl FnMut(ArchiveEntry<'_>) -> Result<(), LinterError>,
+) -> Result<(), LinterError> {
+    let format = ArchiveFormat::from_path(path).ok_or_else(|| {
+        LinterError::file(
+            path,
+            format!("Unsupported archive format: {}", path.display()),
+        )
+    })?;
+    let file = File::open(path).map_err(|e| {
+        LinterError::file(path, format!("Failed to open {}: {}", path.display(), e))
+    })?;
+    let invalid = |e: &dyn std::fmt::Display| {
+        LinterError::file(
+            path,
+            format!("Failed to read archive {}: {}", path.display(), e),
+        )
+    };
+
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive =
+                zip::ZipArchive::new(BufReader::new(file)).map_err(|e| invalid(&e))?;
+            for index in 0..archive.len() {
+                let mut entry = archive.by_index(index).map_err(|e| invalid(&e))?;
+                if !entry.is_file() {
+                    continue;
+                }
+                let name = entry_name(entry.name());
+                let size = entry.size();
+                visit(ArchiveEntry {
+                    name,
+                    size,
+                    reader: &mut entry,
+                })?;
+            }
+        }
+        ArchiveFormat::Tar => visit_tar(tar::Archive::new(BufReader::new(file)), &invalid, visit)?,
+        ArchiveFormat::TarGz => visit_tar(
+            tar::Archive::new(GzDecoder::new(BufReader::new(file))),
+            &invalid,
+            visit,
+        )?,
+    }
+
+    Ok(())
+}
+
+fn visit_tar<R: Read>(
+    mut archive: tar::Archive<R>,
+    invalid: &dyn Fn(&dyn std::fmt::Display) -> LinterError,
+    mut visit: impl FnMut(ArchiveEntry<'_>) -> Result<(), LinterError>,
+) -> Result<(), LinterError> {
+    for entry in archive.entries().map_err(|e| invalid(&e))? {
+        let mut entry = entry.map_err(|e| invalid(&e))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry_name(&entry.path().map_err(|e| invalid(&e))?.to_string_lossy());
+        let size = entry.size();
+        visit(ArchiveEntry {
+            name,
+            size,
+            reader: &mut entry,
+        })?;
+    }
+    Ok(())
+}
+
+/// Normalizes an entry path to `/`-separated form without a leading `./`.
+fn entry_name(name: &str) -> String {
+    let name = name.replace('\\', "/");
+    name.trim_start_matches("./").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use tempfile::tempdir;
+
+    fn collect(path: &Path) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        for_each_entry(path, |entry| {
+            let mut content = String::new();
+            entry.reader.read_to_string(&mut content)?;
+            entries.push((entry.name, content));
+            Ok(())
+        })
+        .unwrap();
+        entries
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("docs.zip")),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("release.TGZ")),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("release.tar.gz")),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("release.tar")),
+            Some(ArchiveFormat::Tar)
+        );
+        assert_eq!(ArchiveFormat::from_path(Path::new("readme.md")), None);
+    }
+
+    #[test]
+    fn test_tar_gz_entries() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("docs.tgz");
+
+        let encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        let content = b"# Hello\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "./docs/hello.md", &content[..])
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert_eq!(
+            collect(&path),
+            vec![("docs/hello.md".to_string(), "# Hello\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        let result = for_each_entry(Path::new("notes.rar"), |_| Ok(()));
+        assert!(matches!(result, Err(LinterError::File { .. })));
+    }
+}
@@ -0,0 +1,338 @@
+//! Builder for [`Linter`].
+
+use std::path::PathBuf;
+
+use crate::config::RuleOption;
+use crate::rules::is_builtin_rule;
+use crate::{Linter, LinterConfig, LinterError, RuleDefinition};
+
+/// Builds a [`Linter`] one setting at a time.
+///
+/// Starts from the defaults of [`LinterConfig::new`], or from an existing
+/// configuration with [`LinterBuilder::from_config`]. The combined settings
+/// are validated by [`LinterBuilder::build`].
+///
+/// ```rust,ignore
+/// let linter = Linter::builder()
+///     .include("docs/**/*.md")
+///     .cache(false)
+///     .add_rule("image-alt-text")
+///     .load_rule_from(".texide/plugins/no-todo.wasm")
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LinterBuilder {
+    config: LinterConfig,
+    /// WASM files to load once the linter is created.
+    rule_paths: Vec<PathBuf>,
+}
+
+impl LinterBuilder {
+    /// Creates a builder with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder starting from `config`.
+    pub fn from_config(config: LinterConfig) -> Self {
+        Self {
+            config,
+            rule_paths: Vec::new(),
+        }
+    }
+
+    /// Adds a glob pattern files must match to be linted.
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.config.include.push(pattern.into());
+        self
+    }
+
+    /// Adds a glob pattern for files to skip.
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.config.exclude.push(pattern.into());
+        self
+    }
+
+    /// Enables or disables the result cache.
+    pub fn cache(mut self, enabled: bool) -> Self {
+        self.config.cache = enabled;
+        self
+    }
+
+    /// Sets the directory the cache is stored in, instead of the OS cache
+    /// directory.
+    pub fn cache_dir(mut self, dir: impl Into<String>) -> Self {
+        self.config.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the directory relative plugin paths are resolved against.
+    pub fn base_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.base_dir = Some(dir.into());
+        self
+    }
+
+    /// Enables a rule by name.
+    ///
+    /// Built-in rules are enabled directly; any other name is also
+    /// registered as a plugin to load from `.texide/plugins/` or the global
+    /// plugin directories. Options already set for the rule are kept.
+    pub fn add_rule(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        let is_builtin = is_builtin_rule(&name);
+        let is_registered =
+            self.config.rules.iter().any(
+                |rule| matches!(rule, RuleDefinition::Simple(registered) if *registered == name),
+            );
+        if !is_builtin && !is_registered {
+            self.config.rules.push(RuleDefinition::Simple(name.clone()));
+        }
+        self.config
+            .options
+            .entry(name)
+            .or_insert(RuleOption::Enabled(true));
+        self
+    }
+
+    /// Sets the options of a rule, replacing any previous ones.
+    pub fn rule_option(mut self, name: impl Into<String>, option: RuleOption) -> Self {
+        self.config.options.insert(name.into(), option);
+        self
+    }
+
+    /// Loads a WASM rule from a file and enables it.
+    ///
+    /// The file is read by [`LinterBuilder::build`].
+    pub fn load_rule_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.rule_paths.push(path.into());
+        self
+    }
+
+    /// Validates the settings and creates the linter.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the cache is enabled with an empty
+    /// cache directory, if a rule name is empty, or if a glob pattern is invalid,
+    /// and a plugin error if a rule file cannot be loaded.
+    pub fn build(self) -> Result<Linter, LinterError> {
+        let empty_cache_dir = self
+            .config
+            .cache_dir
+            .as_deref()
+            .is_some_and(|dir| dir.trim().is_empty());
+        if self.config.cache && empty_cache_dir {
+            return Err(LinterError::config(
+                "Cache is enabled but the cache directory is empty",
+            ));
+        }
+        if self
+            .config
+            .options
+            .keys()
+            .any(|name| name.trim().is_empty())
+        {
+            return Err(LinterError::config("Rule names must not be empty"));
+        }
+
+        let mut linter = Linter::new(self.config)?;
+        for path in &self.rule_paths {
+            let name = linter.load_rule(path)?;
+            linter.enable_rule(name);
+        }
+        Ok(linter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleDefinitionDetail;
+    use std::path::Path;
+    use texide_plugin::test_support::RuleFixture;
+
+    /// Builds a rule named `always` that reports one diagnostic per document.
+    fn always_rule_wasm() -> Vec<u8> {
+        always_rule_wasm_with_message("Always")
+    }
+
+    /// Builds the `always` rule, reporting `message`.
+    fn always_rule_wasm_with_message(message: &str) -> Vec<u8> {
+        RuleFixture::new("always")
+            .reports(&format!(
+                r#"[{{"message":"{}","span":{{"start":0,"end":1}}}}]"#,
+                message
+            ))
+            .build()
+    }
+
+    #[test]
+    fn test_builder_matches_config() {
+        let source = "# Pets\n\n![](cat.png)\n";
+        let path = Path::new("pets.md");
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.include = vec!["**/*.md".to_string()];
+        config
+            .options
+            .insert("image-alt-text".to_string(), RuleOption::Enabled(true));
+        let from_config = Linter::new(config).unwrap();
+
+        let from_builder = Linter::builder()
+            .include("**/*.md")
+            .cache(false)
+            .add_rule("image-alt-text")
+            .build()
+            .unwrap();
+
+        let expected = from_config.lint_content(source, path).unwrap();
+        let actual = from_builder.lint_content(source, path).unwrap();
+        assert_eq!(expected.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap(),
+            serde_json::to_value(&expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_rule() {
+        let builder = LinterBuilder::new()
+            .rule_option(
+                "spelling",
+                RuleOption::Options(serde_json::json!({ "words": ["texide"] })),
+            )
+            .add_rule("spelling")
+            .add_rule("no-todo")
+            .add_rule("no-todo");
+
+        // Built-in rules are not registered as plugins, and options are kept
+        assert_eq!(
+            builder.config.rules,
+            vec![RuleDefinition::Simple("no-todo".to_string())]
+        );
+        assert!(matches!(
+            builder.config.options["spelling"],
+            RuleOption::Options(_)
+        ));
+        assert_eq!(builder.config.options["no-todo"], RuleOption::Enabled(true));
+    }
+
+    #[test]
+    fn test_load_rule_from() {
+        let dir = tempfile::tempdir().unwrap();
+        let rule_path = dir.path().join("always.wasm");
+        std::fs::write(&rule_path, always_rule_wasm()).unwrap();
+
+        let linter = LinterBuilder::new()
+            .cache(false)
+            .load_rule_from(&rule_path)
+            .build()
+            .unwrap();
+
+        let diagnostics = linter.lint_content("Text\n", Path::new("a.md")).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "always");
+    }
+
+    #[test]
+    fn test_rebuilt_rule_invalidates_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let rule_path = dir.path().join("always.wasm");
+        let doc = dir.path().join("doc.md");
+        std::fs::write(&doc, "Text\n").unwrap();
+
+        let lint = || {
+            LinterBuilder::new()
+                .cache(true)
+                .cache_dir(dir.path().join("cache").to_string_lossy())
+                .load_rule_from(&rule_path)
+                .build()
+                .unwrap()
+                .lint_files(std::slice::from_ref(&doc))
+                .unwrap()
+                .pop()
+                .unwrap()
+        };
+
+        std::fs::write(&rule_path, always_rule_wasm_with_message("Old")).unwrap();
+        assert!(!lint().from_cache);
+        assert!(lint().from_cache);
+
+        // Same name and version, different bytes
+        std::fs::write(&rule_path, always_rule_wasm_with_message("New")).unwrap();
+        let result = lint();
+        assert!(!result.from_cache);
+        assert_eq!(result.diagnostics[0].message, "New");
+    }
+
+    #[test]
+    fn test_denied_plugin_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let rule_path = dir.path().join("always.wasm");
+        std::fs::write(&rule_path, always_rule_wasm()).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.rules = vec![RuleDefinition::Detail(RuleDefinitionDetail {
+            github: None,
+            url: None,
+            path: Some(rule_path.to_string_lossy().into_owned()),
+            r#as: None,
+        })];
+        config.allowed_plugins = vec!["**/*.wasm".to_string()];
+        config.denied_plugins = vec!["**/always.wasm".to_string()];
+
+        let error = LinterBuilder::from_config(config.clone())
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(error, LinterError::Config(_)));
+        assert!(error.to_string().contains("is denied by denied_plugins"));
+
+        config.rules.clear();
+        let result = LinterBuilder::from_config(config)
+            .load_rule_from(&rule_path)
+            .build();
+        assert!(matches!(result, Err(LinterError::Config(_))));
+    }
+
+    #[test]
+    fn test_plugin_outside_allow_list_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let rule_path = dir.path().join("always.wasm");
+        std::fs::write(&rule_path, always_rule_wasm()).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.allowed_plugins = vec!["**/approved/*.wasm".to_string()];
+
+        let error = LinterBuilder::from_config(config)
+            .load_rule_from(&rule_path)
+            .build()
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("is not in allowed_plugins"));
+    }
+
+    #[test]
+    fn test_load_rule_from_missing_file() {
+        let result = LinterBuilder::new()
+            .cache(false)
+            .load_rule_from("does-not-exist.wasm")
+            .build();
+        assert!(matches!(result, Err(LinterError::Plugin(_))));
+    }
+
+    #[test]
+    fn test_cache_with_empty_dir_is_rejected() {
+        let result = LinterBuilder::new().cache(true).cache_dir("").build();
+        assert!(matches!(result, Err(LinterError::Config(_))));
+    }
+
+    #[test]
+    fn test_invalid_glob_is_rejected() {
+        let result = LinterBuilder::new().cache(false).exclude("[").build();
+        assert!(matches!(result, Err(LinterError::Config(_))));
+    }
+}
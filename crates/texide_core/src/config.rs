@@ -1,16 +1,27 @@
 //! Linter configuration.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use globset::Glob;
 use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use texide_parser::{ParagraphMode, ParserOptions};
+use texide_plugin::Severity;
 
 use crate::LinterError;
 
 use jsonschema::Validator;
 use std::sync::OnceLock;
 
+/// Dedicated configuration files, in order of precedence.
+const CONFIG_FILE_NAMES: [&str; 2] = [".texide.jsonc", ".texide.json"];
+
+/// Key holding the configuration in `package.json`.
+const PACKAGE_JSON_KEY: &str = "texide";
+
 // Embed the schema
 const SCHEMA_JSON: &str = include_str!("../../../schemas/v1/config.json");
 static CONFIG_SCHEMA: OnceLock<Validator> = OnceLock::new();
@@ -31,37 +42,322 @@ pub struct LinterConfig {
     pub plugins: Vec<String>,
 
     /// File patterns to include.
+    ///
+    /// The last pattern matching a path decides whether it is included. A
+    /// `!` prefix negates a pattern, so `["**/*.md", "!drafts/**"]` lints
+    /// Markdown files outside `drafts/`. When `include` has no plain
+    /// pattern, paths no pattern matches are included.
+    ///
+    /// Patterns are validated when the configuration is loaded, and a
+    /// leading `./` is dropped.
     #[serde(default)]
     pub include: Vec<String>,
 
     /// File patterns to exclude.
+    ///
+    /// The last pattern matching a path decides whether it is excluded. A
+    /// `!` prefix re-includes paths matched by an earlier exclude pattern,
+    /// but only within the paths `include` covers.
     #[serde(default)]
     pub exclude: Vec<String>,
 
+    /// Whether file discovery follows symbolic links.
+    ///
+    /// Links are skipped by default. When followed, links back to an
+    /// ancestor directory are skipped and files reachable through several
+    /// links are linted once.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+
+    /// Directories file discovery walks, relative to the current directory.
+    ///
+    /// Defaults to the current directory. Every root is matched against the
+    /// same patterns and globs, and a file under several roots is linted
+    /// once. Discovered paths start with their root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roots: Vec<String>,
+
     /// Whether to enable caching.
     #[serde(default = "default_cache")]
     pub cache: bool,
 
     /// Cache directory.
-    #[serde(default = "default_cache_dir")]
-    pub cache_dir: String,
+    ///
+    /// When unset, a per-project directory under the OS cache directory is
+    /// used; see [`LinterConfig::resolved_cache_dir`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
+
+    /// Number of linted files after which the cache is saved during a run
+    /// (0 to only count time).
+    #[serde(default = "default_cache_flush_files")]
+    pub cache_flush_files: usize,
+
+    /// Seconds after which the cache is saved during a run (0 to only count
+    /// files).
+    ///
+    /// Whichever of `cache_flush_files` and `cache_flush_secs` is reached
+    /// first triggers a save, so a crash loses little work. With both set
+    /// to 0 the cache is only saved when the run ends.
+    #[serde(default = "default_cache_flush_secs")]
+    pub cache_flush_secs: u64,
 
     /// Whether to enable performance timings.
     #[serde(default)]
     pub timings: bool,
 
+    /// Whether to accumulate the time spent in each rule across all files.
+    ///
+    /// The profile is available from [`crate::Linter::rule_profile`].
+    #[serde(default)]
+    pub profile_rules: bool,
+
+    /// Maximum size in bytes of files to lint.
+    ///
+    /// Larger files are skipped with a warning diagnostic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<u64>,
+
+    /// How to decode files that are not valid UTF-8.
+    #[serde(default)]
+    pub encoding: Encoding,
+
+    /// Accepted strings (product names, jargon).
+    ///
+    /// Diagnostics whose covered text matches one of these are dropped,
+    /// whichever rule reported them. Patterns wrapped in slashes are
+    /// regular expressions.
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+
+    /// How to collapse diagnostics reported by several rules at the same place.
+    #[serde(default)]
+    pub dedup: DedupStrategy,
+
+    /// URL prefix rewrites applied to plugin downloads.
+    ///
+    /// Maps an upstream prefix (e.g. `https://github.com/`) to the mirror
+    /// prefix that replaces it. The longest matching prefix wins.
+    #[serde(default)]
+    pub mirrors: BTreeMap<String, String>,
+
+    /// Glob patterns of the plugins that may be loaded; empty allows every
+    /// plugin.
+    ///
+    /// Matched against the name, path or URL a plugin is referenced by and
+    /// the file it resolves to.
+    #[serde(default)]
+    pub allowed_plugins: Vec<String>,
+
+    /// Glob patterns of the plugins that must never be loaded, even if
+    /// `rules` references them. Wins over `allowed_plugins`.
+    #[serde(default)]
+    pub denied_plugins: Vec<String>,
+
+    /// How file paths are displayed in output.
+    #[serde(default)]
+    pub path_style: PathStyle,
+
+    /// Maximum number of warnings before the run fails; `-1` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_warnings: Option<i64>,
+
+    /// Tags of the diagnostics that fail the run. When set, diagnostics
+    /// with one of these tags fail it and others do not, whatever their
+    /// severity, so it cannot be combined with `strict: "exit"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fail_on_tags: Vec<String>,
+
+    /// Whether warnings fail the run like errors. `fail_on_tags` takes
+    /// precedence in deciding what fails the run.
+    #[serde(default)]
+    pub strict: StrictMode,
+
+    /// How plain text files are split into paragraphs.
+    #[serde(default)]
+    pub paragraph_mode: ParagraphMode,
+
+    /// Markdown parser options for files matching glob patterns.
+    ///
+    /// When several entries match a file, the last one wins; files no
+    /// entry matches use the default options.
+    #[serde(default)]
+    pub parser_options: Vec<ParserOptionsOverride>,
+
+    /// Glob patterns of files parsed with the raw parser, which passes the
+    /// whole file to rules as one text node.
+    ///
+    /// Lets rules that only read the source text, such as `final-newline`,
+    /// run on formats Texide has no parser for (e.g. `**/*.yaml`).
+    #[serde(default)]
+    pub raw_files: Vec<String>,
+
+    /// Whether to convert CRLF line endings to LF before parsing.
+    ///
+    /// Reported spans still refer to the original file.
+    #[serde(default)]
+    pub normalize_line_endings: bool,
+
+    /// Unicode normalization form applied to file content before parsing.
+    ///
+    /// Reported spans still refer to the original file.
+    #[serde(default)]
+    pub unicode_normalize: NormalizationForm,
+
+    /// Locale of diagnostic messages (e.g., "ja").
+    ///
+    /// Messages without a translation in this locale stay in English.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
     /// Base directory for resolving relative paths (plugins, etc.).
     /// This is usually the directory containing the configuration file.
     #[serde(skip)]
     pub base_dir: Option<PathBuf>,
+
+    /// The file the configuration was loaded from, if any.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
 }
 
 fn default_cache() -> bool {
     true
 }
 
-fn default_cache_dir() -> String {
-    ".texide-cache".to_string()
+fn default_cache_flush_files() -> usize {
+    100
+}
+
+fn default_cache_flush_secs() -> u64 {
+    5
+}
+
+/// Cache directory used when the OS has no cache directory.
+const FALLBACK_CACHE_DIR: &str = ".texide-cache";
+
+/// Decoding mode for source files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Encoding {
+    /// Files must be valid UTF-8; other files are reported with an
+    /// `encoding-error` diagnostic.
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+    /// Invalid UTF-8 sequences are replaced with U+FFFD and the file is linted.
+    #[serde(rename = "utf-8-lossy")]
+    Utf8Lossy,
+}
+
+/// Whether warnings fail the run like errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StrictMode {
+    /// Only errors fail the run.
+    #[default]
+    Off,
+    /// Warnings fail the run too, but are still shown as warnings.
+    Exit,
+    /// Warnings are reported as errors.
+    Promote,
+}
+
+/// How file paths are displayed in output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathStyle {
+    /// Relative to the root directory (the config file's directory).
+    #[default]
+    Relative,
+    /// Absolute paths.
+    Absolute,
+    /// Paths exactly as discovered or passed on the command line.
+    AsGiven,
+}
+
+impl PathStyle {
+    /// Formats `path` for display, resolving relative paths against the
+    /// current directory and making them relative to `root` if requested.
+    ///
+    /// Paths outside `root` are shown as absolute paths.
+    pub fn display(self, path: &Path, root: &Path) -> PathBuf {
+        match self {
+            PathStyle::AsGiven => path.to_path_buf(),
+            PathStyle::Absolute => absolute(path),
+            PathStyle::Relative => {
+                let path = absolute(path);
+                match path.strip_prefix(absolute(root)) {
+                    Ok(relative) => relative.to_path_buf(),
+                    Err(_) => path,
+                }
+            }
+        }
+    }
+}
+
+/// Resolves the cache directory from an explicit setting, the project root
+/// and the OS cache directory.
+fn resolve_cache_dir(
+    explicit: Option<&str>,
+    root: &Path,
+    os_cache_dir: Option<PathBuf>,
+) -> PathBuf {
+    if let Some(dir) = explicit {
+        return PathBuf::from(dir);
+    }
+    let Some(os_cache_dir) = os_cache_dir else {
+        return PathBuf::from(FALLBACK_CACHE_DIR);
+    };
+
+    let root = absolute(root);
+    let hash = blake3::hash(root.as_os_str().as_encoded_bytes()).to_hex();
+    os_cache_dir.join("texide").join(&hash.as_str()[..16])
+}
+
+/// Makes `path` absolute without resolving symlinks; empty paths are the
+/// current directory.
+fn absolute(path: &Path) -> PathBuf {
+    let path = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Markdown parser options applied to the files matching some glob patterns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParserOptionsOverride {
+    /// Glob patterns of the files the options apply to.
+    pub files: Vec<String>,
+    /// Parser options; unset fields take their default values.
+    #[serde(default)]
+    pub options: ParserOptions,
+}
+
+/// Unicode normalization form applied to file content before parsing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizationForm {
+    /// Lint the text as written.
+    #[default]
+    None,
+    /// Canonical composition (NFC), e.g. `e` + U+0301 becomes `é`.
+    Nfc,
+    /// Canonical decomposition (NFD), e.g. `é` becomes `e` + U+0301.
+    Nfd,
+}
+
+/// Strategy for collapsing duplicate diagnostics across rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupStrategy {
+    /// Keep every diagnostic.
+    #[default]
+    None,
+    /// Collapse diagnostics with the same span and message.
+    Exact,
+    /// Collapse diagnostics with the same span, whatever their message or rule.
+    Span,
 }
 
 /// Definition of a rule to load.
@@ -94,28 +390,126 @@ pub enum RuleOption {
     Enabled(bool),
     /// Rule is enabled with severity string ("error", "warning", "off").
     Severity(String),
+    /// Rule is enabled with a severity and options, written textlint-style
+    /// as `["warning", { "max": 100 }]`. The options may be left out
+    /// (`["off"]`). The `files`, `exclude_files` and `help_url` keys work
+    /// as in the [`RuleOption::Options`] form.
+    SeverityWithOptions(String, #[serde(default)] serde_json::Value),
     /// Rule is enabled with specific options object.
+    ///
+    /// A `severity` key in the object sets the severity like the
+    /// [`RuleOption::Severity`] form, `files` and `exclude_files` limit
+    /// the files the rule runs on, and `help_url` sets the documentation
+    /// URL of its diagnostics; the other keys are the rule's options.
     Options(serde_json::Value),
 }
 
+/// Key of an options object that holds the rule severity.
+const SEVERITY_KEY: &str = "severity";
+
+/// Key of an options object that lists the files the rule runs on.
+const FILES_KEY: &str = "files";
+
+/// Key of an options object that lists the files the rule skips.
+const EXCLUDE_FILES_KEY: &str = "exclude_files";
+
+/// Key of an options object that holds the documentation URL template.
+const HELP_URL_KEY: &str = "help_url";
+
 impl RuleOption {
     /// Returns whether the rule is enabled.
     pub fn is_enabled(&self) -> bool {
+        self.severity_str() != Some("off")
+            && match self {
+                RuleOption::Enabled(enabled) => *enabled,
+                RuleOption::Severity(_)
+                | RuleOption::SeverityWithOptions(..)
+                | RuleOption::Options(_) => true,
+            }
+    }
+
+    /// Returns the severity set for the rule, if any.
+    ///
+    /// Overrides the severity the rule reports its diagnostics with.
+    pub fn severity(&self) -> Option<Severity> {
+        match self.severity_str()? {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            "info" => Some(Severity::Info),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity string of the `Severity` and
+    /// `SeverityWithOptions` forms, or the `severity` key of the `Options`
+    /// form.
+    fn severity_str(&self) -> Option<&str> {
         match self {
-            RuleOption::Enabled(enabled) => *enabled,
-            RuleOption::Severity(s) => s != "off",
-            RuleOption::Options(_) => true,
+            RuleOption::Enabled(_) => None,
+            RuleOption::Severity(s) | RuleOption::SeverityWithOptions(s, _) => Some(s),
+            RuleOption::Options(v) => v.get(SEVERITY_KEY)?.as_str(),
         }
     }
 
     /// Gets the rule options as JSON value.
+    ///
+    /// The `severity`, `files`, `exclude_files` and `help_url` keys of an
+    /// options object, in either the object or the array form, are not
+    /// passed to the rule.
     pub fn options(&self) -> serde_json::Value {
+        match self.options_value() {
+            Some(serde_json::Value::Object(map)) => {
+                let mut map = map.clone();
+                for key in [SEVERITY_KEY, FILES_KEY, EXCLUDE_FILES_KEY, HELP_URL_KEY] {
+                    map.remove(key);
+                }
+                serde_json::Value::Object(map)
+            }
+            Some(v) => v.clone(),
+            None => serde_json::Value::Null,
+        }
+    }
+
+    /// Returns the options value of the `SeverityWithOptions` and `Options`
+    /// forms.
+    fn options_value(&self) -> Option<&serde_json::Value> {
         match self {
-            RuleOption::Enabled(_) => serde_json::Value::Null,
-            RuleOption::Severity(_) => serde_json::Value::Null,
-            RuleOption::Options(v) => v.clone(),
+            RuleOption::Enabled(_) | RuleOption::Severity(_) => None,
+            RuleOption::SeverityWithOptions(_, v) | RuleOption::Options(v) => Some(v),
         }
     }
+
+    /// Returns the documentation URL template set with the `help_url` key
+    /// of an options object, which overrides the rule manifest's.
+    pub fn help_url(&self) -> Option<&str> {
+        self.options_value()?.get(HELP_URL_KEY)?.as_str()
+    }
+
+    /// Returns the glob patterns of the files the rule runs on, from the
+    /// `files` key of an options object. Empty means every file.
+    pub fn files(&self) -> Vec<String> {
+        self.patterns(FILES_KEY)
+    }
+
+    /// Returns the glob patterns of the files the rule skips, from the
+    /// `exclude_files` key of an options object.
+    pub fn exclude_files(&self) -> Vec<String> {
+        self.patterns(EXCLUDE_FILES_KEY)
+    }
+
+    /// Returns the strings in the array under `key` of an options object.
+    fn patterns(&self, key: &str) -> Vec<String> {
+        self.options_value()
+            .and_then(|options| options.get(key))
+            .and_then(|v| v.as_array())
+            .map(|patterns| {
+                patterns
+                    .iter()
+                    .filter_map(|p| p.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl LinterConfig {
@@ -127,10 +521,33 @@ impl LinterConfig {
             plugins: Vec::new(),
             include: Vec::new(),
             exclude: Vec::new(),
+            follow_symlinks: false,
+            roots: Vec::new(),
             cache: true,
-            cache_dir: ".texide-cache".to_string(),
+            cache_dir: None,
+            cache_flush_files: default_cache_flush_files(),
+            cache_flush_secs: default_cache_flush_secs(),
             timings: false,
+            profile_rules: false,
+            max_file_size: None,
+            encoding: Encoding::default(),
+            ignore_patterns: Vec::new(),
+            dedup: DedupStrategy::default(),
+            mirrors: BTreeMap::new(),
+            allowed_plugins: Vec::new(),
+            denied_plugins: Vec::new(),
+            max_warnings: None,
+            fail_on_tags: Vec::new(),
+            strict: StrictMode::default(),
+            path_style: PathStyle::default(),
+            paragraph_mode: ParagraphMode::default(),
+            parser_options: Vec::new(),
+            raw_files: Vec::new(),
+            normalize_line_endings: false,
+            unicode_normalize: NormalizationForm::default(),
+            locale: None,
             base_dir: None,
+            source: None,
         }
     }
 
@@ -142,22 +559,91 @@ impl LinterConfig {
         let content = fs::read_to_string(path)
             .map_err(|e| LinterError::config(format!("Failed to read config: {}", e)))?;
 
-        let mut config = Self::from_json(&content)?;
+        let mut config = Self::from_json_source(&content, Some(path))?;
 
         // precise parent directory handling
         if let Some(parent) = path.parent() {
             config.base_dir = Some(parent.to_path_buf());
         }
+        config.source = Some(path.to_path_buf());
 
         Ok(config)
     }
 
+    /// Loads configuration from the `texide` key of a `package.json` file.
+    ///
+    /// Returns `None` if the file has no `texide` key.
+    pub fn from_package_json(path: impl AsRef<Path>) -> Result<Option<Self>, LinterError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| LinterError::config(format!("Failed to read package.json: {}", e)))?;
+        let mut package: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| LinterError::config_syntax(&content, Some(path), &e))?;
+
+        let Some(value) = package
+            .get_mut(PACKAGE_JSON_KEY)
+            .map(serde_json::Value::take)
+        else {
+            return Ok(None);
+        };
+        let mut config = Self::from_value(value)?;
+        if let Some(parent) = path.parent() {
+            config.base_dir = Some(parent.to_path_buf());
+        }
+        config.source = Some(path.to_path_buf());
+
+        Ok(Some(config))
+    }
+
+    /// Finds and loads the configuration of the project in `dir`.
+    ///
+    /// Looks for `.texide.jsonc`, then `.texide.json`, then a `texide` key in
+    /// `package.json`. A dedicated config file wins over `package.json`.
+    /// Returns `None` if none of them exists.
+    pub fn discover(dir: impl AsRef<Path>) -> Result<Option<Self>, LinterError> {
+        let dir = dir.as_ref();
+        for name in CONFIG_FILE_NAMES {
+            let path = dir.join(name);
+            if path.exists() {
+                info!("Using config: {}", path.display());
+                return Self::from_file(&path).map(Some);
+            }
+        }
+
+        let package_json = dir.join("package.json");
+        if package_json.exists()
+            && let Some(config) = Self::from_package_json(&package_json)?
+        {
+            info!(
+                "Using config: \"{}\" in {}",
+                PACKAGE_JSON_KEY,
+                package_json.display()
+            );
+            return Ok(Some(config));
+        }
+
+        Ok(None)
+    }
+
     /// Parses configuration from JSON string with schema validation.
+    ///
+    /// JSON syntax errors are reported as [`LinterError::ConfigSyntax`] with
+    /// the line and column of the offending token.
     pub fn from_json(json: &str) -> Result<Self, LinterError> {
+        Self::from_json_source(json, None)
+    }
+
+    /// Parses configuration read from `path`, if any, from JSON string.
+    fn from_json_source(json: &str, path: Option<&Path>) -> Result<Self, LinterError> {
         // Parse into Value first for validation
-        let value: serde_json::Value = serde_json::from_str(json)
-            .map_err(|e| LinterError::config(format!("Invalid JSON: {}", e)))?;
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| LinterError::config_syntax(json, path, &e))?;
 
+        Self::from_value(value)
+    }
+
+    /// Builds configuration from a parsed JSON value with schema validation.
+    fn from_value(value: serde_json::Value) -> Result<Self, LinterError> {
         // Initialize and check schema
         let schema = CONFIG_SCHEMA.get_or_init(|| {
             let schema_json: serde_json::Value =
@@ -173,8 +659,228 @@ impl LinterConfig {
             )));
         }
 
-        serde_json::from_value(value)
-            .map_err(|e| LinterError::config(format!("Invalid config: {}", e)))
+        let mut config: Self = serde_json::from_value(value)
+            .map_err(|e| LinterError::config(format!("Invalid config: {}", e)))?;
+        config.normalize_globs();
+        config.validate_globs()?;
+        config.validate_failure_options()?;
+        Ok(config)
+    }
+
+    /// Normalizes the glob patterns of `include`, `exclude`,
+    /// `parser_options` and `raw_files`.
+    ///
+    /// A leading `./` is removed (after any `!`), since patterns are matched
+    /// against paths without it.
+    pub(crate) fn normalize_globs(&mut self) {
+        let patterns = self
+            .include
+            .iter_mut()
+            .chain(self.exclude.iter_mut())
+            .chain(
+                self.parser_options
+                    .iter_mut()
+                    .flat_map(|entry| entry.files.iter_mut()),
+            )
+            .chain(self.raw_files.iter_mut());
+        for pattern in patterns {
+            let (negation, glob) = match pattern.strip_prefix('!') {
+                Some(glob) => ("!", glob),
+                None => ("", pattern.as_str()),
+            };
+            if let Some(glob) = glob.strip_prefix("./") {
+                *pattern = format!("{}{}", negation, glob.trim_start_matches('/'));
+            }
+        }
+    }
+
+    /// Checks that every glob pattern of `include`, `exclude`,
+    /// `parser_options`, `raw_files`, the `files` and `exclude_files` of rule
+    /// options, `allowed_plugins` and `denied_plugins` is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error naming each invalid pattern and where it
+    /// is set, e.g. `exclude[1]` or `parser_options[0].files[2]`.
+    pub fn validate_globs(&self) -> Result<(), LinterError> {
+        let mut rule_names: Vec<&String> = self.options.keys().collect();
+        rule_names.sort();
+        let rule_patterns: Vec<(String, String)> = rule_names
+            .into_iter()
+            .flat_map(|name| {
+                let option = &self.options[name];
+                let files = option
+                    .files()
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(i, pattern)| (format!("options.{}.files[{}]", name, i), pattern));
+                let exclude_files =
+                    option
+                        .exclude_files()
+                        .into_iter()
+                        .enumerate()
+                        .map(move |(i, pattern)| {
+                            (format!("options.{}.exclude_files[{}]", name, i), pattern)
+                        });
+                files.chain(exclude_files)
+            })
+            .collect();
+
+        let patterns = self
+            .include
+            .iter()
+            .enumerate()
+            .map(|(i, pattern)| (format!("include[{}]", i), pattern))
+            .chain(
+                self.exclude
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pattern)| (format!("exclude[{}]", i), pattern)),
+            )
+            .chain(
+                self.parser_options
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, entry)| {
+                        entry.files.iter().enumerate().map(move |(j, pattern)| {
+                            (format!("parser_options[{}].files[{}]", i, j), pattern)
+                        })
+                    }),
+            )
+            .chain(
+                self.raw_files
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pattern)| (format!("raw_files[{}]", i), pattern)),
+            )
+            .chain(
+                rule_patterns
+                    .iter()
+                    .map(|(location, pattern)| (location.clone(), pattern)),
+            )
+            .chain(
+                self.allowed_plugins
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pattern)| (format!("allowed_plugins[{}]", i), pattern)),
+            )
+            .chain(
+                self.denied_plugins
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pattern)| (format!("denied_plugins[{}]", i), pattern)),
+            );
+
+        let errors: Vec<String> = patterns
+            .filter_map(|(location, pattern)| {
+                let glob = pattern.strip_prefix('!').unwrap_or(pattern);
+                let error = Glob::new(glob).err()?;
+                Some(format!(
+                    "Invalid glob pattern '{}' in {}: {}",
+                    pattern,
+                    location,
+                    error.kind()
+                ))
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(LinterError::config(errors.join("; ")))
+        }
+    }
+
+    /// Checks that `strict` and `fail_on_tags` do not both decide which
+    /// diagnostics fail the run.
+    ///
+    /// With `fail_on_tags`, a diagnostic fails the run by its tags alone,
+    /// so `strict: "exit"` would be silently ignored. `strict: "promote"`
+    /// still changes the reported severity and is allowed.
+    pub fn validate_failure_options(&self) -> Result<(), LinterError> {
+        if self.strict == StrictMode::Exit && !self.fail_on_tags.is_empty() {
+            return Err(LinterError::config(
+                "strict \"exit\" cannot be combined with fail_on_tags, which fail the run \
+                 on tagged diagnostics of any severity; use one or the other",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the absolute directory the `files`, `raw_files` and
+    /// `parser_options` globs are relative to: `base_dir`, or the current
+    /// directory.
+    pub(crate) fn scope_root(&self) -> Result<PathBuf, LinterError> {
+        let root = self
+            .base_dir
+            .as_deref()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        std::path::absolute(root).map_err(|e| {
+            LinterError::config(format!(
+                "Cannot resolve base directory {}: {}",
+                root.display(),
+                e
+            ))
+        })
+    }
+
+    /// Returns the directory the result cache is stored in.
+    ///
+    /// An explicit `cache_dir` is used as is. Otherwise the cache lives in
+    /// `texide/<project hash>` under the OS cache directory
+    /// (`$XDG_CACHE_HOME` or `~/.cache` on Linux, `~/Library/Caches` on
+    /// macOS), where the hash identifies the project root (`base_dir`, or
+    /// the current directory), so projects never share a cache.
+    pub fn resolved_cache_dir(&self) -> PathBuf {
+        resolve_cache_dir(
+            self.cache_dir.as_deref(),
+            self.base_dir.as_deref().unwrap_or(Path::new(".")),
+            dirs::cache_dir(),
+        )
+    }
+
+    /// Enables or disables the rule `name`, e.g. from a command-line override.
+    ///
+    /// Disabling replaces the rule's entry in `options` with `false`.
+    /// Enabling keeps an entry that is already enabled, so its options and
+    /// severity still apply, and otherwise sets it to `true`.
+    pub fn set_rule_enabled(&mut self, name: &str, enabled: bool) {
+        match self.options.get(name) {
+            Some(option) if option.is_enabled() == enabled => {}
+            _ => {
+                self.options
+                    .insert(name.to_string(), RuleOption::Enabled(enabled));
+            }
+        }
+    }
+
+    /// Restricts the configuration to the rule `name`, enabling it.
+    ///
+    /// Every other rule is dropped from `options`. Plugins are only dropped
+    /// from `rules` and `plugins` when one of them can be told to provide
+    /// `name` from its reference alone (its `as` alias, or the last path
+    /// segment without `texide-rule-` prefix, version or `.wasm` extension);
+    /// otherwise all of them are kept, since any could provide the rule.
+    pub fn retain_only_rule(&mut self, name: &str) {
+        self.options.retain(|rule, _| rule == name);
+        self.set_rule_enabled(name, true);
+
+        let provides = |reference: &str| plugin_rule_name(reference) == name;
+        let rule_provides = |rule: &RuleDefinition| match rule {
+            RuleDefinition::Simple(reference) => provides(reference),
+            RuleDefinition::Detail(detail) => match &detail.r#as {
+                Some(alias) => alias == name,
+                None => [&detail.path, &detail.github, &detail.url]
+                    .into_iter()
+                    .flatten()
+                    .any(|reference| provides(reference)),
+            },
+        };
+        if self.rules.iter().any(rule_provides) || self.plugins.iter().any(|p| provides(p)) {
+            self.rules.retain(rule_provides);
+            self.plugins.retain(|p| provides(p));
+        }
     }
 
     /// Returns enabled rules (Iterator over options).
@@ -195,6 +901,15 @@ impl LinterConfig {
     }
 }
 
+/// Guesses the rule name a plugin reference provides: its last path segment
+/// without `texide-rule-` prefix, `@version` and `.wasm` extension.
+fn plugin_rule_name(reference: &str) -> &str {
+    let name = reference.rsplit(['/', '\\']).next().unwrap_or(reference);
+    let name = name.split('@').next().unwrap_or(name);
+    let name = name.strip_suffix(".wasm").unwrap_or(name);
+    name.strip_prefix("texide-rule-").unwrap_or(name)
+}
+
 impl Default for LinterConfig {
     fn default() -> Self {
         Self::new()
@@ -226,6 +941,529 @@ mod tests {
         assert_eq!(config.options.len(), 2);
     }
 
+    #[test]
+    fn test_config_max_file_size() {
+        let config = LinterConfig::from_json(r#"{ "max_file_size": 1048576 }"#).unwrap();
+        assert_eq!(config.max_file_size, Some(1_048_576));
+
+        assert!(LinterConfig::from_json(r#"{ "max_file_size": -1 }"#).is_err());
+        assert_eq!(LinterConfig::new().max_file_size, None);
+    }
+
+    #[test]
+    fn test_config_encoding() {
+        assert_eq!(LinterConfig::new().encoding, Encoding::Utf8);
+
+        let config = LinterConfig::from_json(r#"{ "encoding": "utf-8-lossy" }"#).unwrap();
+        assert_eq!(config.encoding, Encoding::Utf8Lossy);
+
+        assert!(LinterConfig::from_json(r#"{ "encoding": "latin-1" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_normalize_line_endings() {
+        assert!(!LinterConfig::new().normalize_line_endings);
+
+        let config = LinterConfig::from_json(r#"{ "normalize_line_endings": true }"#).unwrap();
+        assert!(config.normalize_line_endings);
+    }
+
+    #[test]
+    fn test_config_unicode_normalize() {
+        assert_eq!(
+            LinterConfig::new().unicode_normalize,
+            NormalizationForm::None
+        );
+
+        let config = LinterConfig::from_json(r#"{ "unicode_normalize": "nfc" }"#).unwrap();
+        assert_eq!(config.unicode_normalize, NormalizationForm::Nfc);
+
+        assert!(LinterConfig::from_json(r#"{ "unicode_normalize": "nfkc" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_locale() {
+        assert!(LinterConfig::new().locale.is_none());
+
+        let config = LinterConfig::from_json(r#"{ "locale": "ja" }"#).unwrap();
+        assert_eq!(config.locale.as_deref(), Some("ja"));
+    }
+
+    #[test]
+    fn test_config_dedup() {
+        assert_eq!(LinterConfig::new().dedup, DedupStrategy::None);
+
+        let config = LinterConfig::from_json(r#"{ "dedup": "span" }"#).unwrap();
+        assert_eq!(config.dedup, DedupStrategy::Span);
+
+        assert!(LinterConfig::from_json(r#"{ "dedup": "fuzzy" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_mirrors() {
+        let config = LinterConfig::from_json(
+            r#"{ "mirrors": { "https://github.com/": "https://mirror.example.com/github/" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.mirrors["https://github.com/"],
+            "https://mirror.example.com/github/"
+        );
+
+        assert!(LinterConfig::from_json(r#"{ "mirrors": { "https://github.com/": 1 } }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_max_warnings() {
+        assert_eq!(LinterConfig::new().max_warnings, None);
+
+        let config = LinterConfig::from_json(r#"{ "max_warnings": 5 }"#).unwrap();
+        assert_eq!(config.max_warnings, Some(5));
+
+        assert!(LinterConfig::from_json(r#"{ "max_warnings": -2 }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_strict() {
+        assert_eq!(LinterConfig::new().strict, StrictMode::Off);
+
+        let config = LinterConfig::from_json(r#"{ "strict": "promote" }"#).unwrap();
+        assert_eq!(config.strict, StrictMode::Promote);
+
+        assert!(LinterConfig::from_json(r#"{ "strict": true }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_fail_on_tags() {
+        assert!(LinterConfig::new().fail_on_tags.is_empty());
+
+        let config = LinterConfig::from_json(r#"{ "fail_on_tags": ["accessibility"] }"#).unwrap();
+        assert_eq!(config.fail_on_tags, vec!["accessibility"]);
+
+        assert!(LinterConfig::from_json(r#"{ "fail_on_tags": "accessibility" }"#).is_err());
+
+        let error = LinterConfig::from_json(r#"{ "fail_on_tags": ["a"], "strict": "exit" }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("cannot be combined with fail_on_tags"),
+            "{error}"
+        );
+        assert!(
+            LinterConfig::from_json(r#"{ "fail_on_tags": ["a"], "strict": "promote" }"#).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_rule_option_severity() {
+        assert_eq!(
+            RuleOption::Severity("warning".to_string()).severity(),
+            Some(Severity::Warning)
+        );
+        assert_eq!(RuleOption::Severity("off".to_string()).severity(), None);
+        assert_eq!(RuleOption::Enabled(true).severity(), None);
+    }
+
+    #[test]
+    fn test_rule_option_array_form() {
+        let config = LinterConfig::from_json(
+            r#"{
+                "options": {
+                    "sentence-length": ["error", { "max": 100 }],
+                    "no-todo": ["off"],
+                    "spelling": ["info"],
+                    "image-alt-text": true,
+                    "no-bare-urls": "warning",
+                    "code-block-language": { "severity": "info", "allowed": ["rust"] }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let option = &config.options["sentence-length"];
+        assert_eq!(
+            *option,
+            RuleOption::SeverityWithOptions("error".to_string(), serde_json::json!({ "max": 100 }))
+        );
+        assert!(option.is_enabled());
+        assert_eq!(option.severity(), Some(Severity::Error));
+        assert_eq!(option.options(), serde_json::json!({ "max": 100 }));
+
+        let option = &config.options["no-todo"];
+        assert!(!option.is_enabled());
+        assert_eq!(option.options(), serde_json::Value::Null);
+
+        let option = &config.options["spelling"];
+        assert!(option.is_enabled());
+        assert_eq!(option.severity(), Some(Severity::Info));
+
+        assert_eq!(config.options["image-alt-text"], RuleOption::Enabled(true));
+        assert_eq!(
+            config.options["no-bare-urls"].severity(),
+            Some(Severity::Warning)
+        );
+        let option = &config.options["code-block-language"];
+        assert_eq!(option.severity(), Some(Severity::Info));
+        assert_eq!(option.options(), serde_json::json!({ "allowed": ["rust"] }));
+
+        for invalid in [
+            r#"[]"#,
+            r#"["warn"]"#,
+            r#"["error", {}, {}]"#,
+            r#"["error", 1]"#,
+        ] {
+            let json = format!(r#"{{ "options": {{ "no-todo": {} }} }}"#, invalid);
+            assert!(LinterConfig::from_json(&json).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_rule_option_severity_in_options() {
+        let config = LinterConfig::from_json(
+            r#"{
+                "options": {
+                    "sentence-length": { "severity": "warning", "max": 100 },
+                    "no-todo": { "severity": "off", "words": ["FIXME"] },
+                    "spelling": { "words": ["texide"] }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let option = &config.options["sentence-length"];
+        assert!(option.is_enabled());
+        assert_eq!(option.severity(), Some(Severity::Warning));
+        assert_eq!(option.options(), serde_json::json!({ "max": 100 }));
+
+        let option = &config.options["no-todo"];
+        assert!(!option.is_enabled());
+        assert_eq!(option.severity(), None);
+
+        let option = &config.options["spelling"];
+        assert!(option.is_enabled());
+        assert_eq!(option.severity(), None);
+        assert_eq!(option.options(), serde_json::json!({ "words": ["texide"] }));
+        assert!(option.files().is_empty());
+
+        assert!(
+            LinterConfig::from_json(r#"{ "options": { "no-todo": { "severity": "warn" } } }"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rule_option_files() {
+        let config = LinterConfig::from_json(
+            r#"{
+                "options": {
+                    "frontmatter-required-fields": {
+                        "files": ["content/**"],
+                        "exclude_files": ["content/drafts/**"],
+                        "fields": ["title"]
+                    },
+                    "spelling": ["warning", { "files": ["docs/**"] }]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let option = &config.options["frontmatter-required-fields"];
+        assert_eq!(option.files(), vec!["content/**"]);
+        assert_eq!(option.exclude_files(), vec!["content/drafts/**"]);
+        assert_eq!(option.options(), serde_json::json!({ "fields": ["title"] }));
+
+        // The array form scopes a rule the same way
+        let option = &config.options["spelling"];
+        assert_eq!(option.files(), vec!["docs/**"]);
+        assert_eq!(option.options(), serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_rule_option_help_url() {
+        let option = RuleOption::Options(serde_json::json!({
+            "help_url": "https://example.com/{rule_id}",
+            "max": 100
+        }));
+        assert_eq!(option.help_url(), Some("https://example.com/{rule_id}"));
+        assert_eq!(option.options(), serde_json::json!({ "max": 100 }));
+        assert_eq!(RuleOption::Enabled(true).help_url(), None);
+    }
+
+    #[test]
+    fn test_config_path_style() {
+        assert_eq!(LinterConfig::new().path_style, PathStyle::Relative);
+
+        let config = LinterConfig::from_json(r#"{ "path_style": "as-given" }"#).unwrap();
+        assert_eq!(config.path_style, PathStyle::AsGiven);
+
+        assert!(LinterConfig::from_json(r#"{ "path_style": "short" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_paragraph_mode() {
+        assert_eq!(LinterConfig::new().paragraph_mode, ParagraphMode::BlankLine);
+
+        let config = LinterConfig::from_json(r#"{ "paragraph_mode": "hard-wrap-join" }"#).unwrap();
+        assert_eq!(config.paragraph_mode, ParagraphMode::HardWrapJoin);
+
+        let config = LinterConfig::from_json(r#"{ "paragraph_mode": "preserve-breaks" }"#).unwrap();
+        assert_eq!(config.paragraph_mode, ParagraphMode::PreserveBreaks);
+
+        assert!(LinterConfig::from_json(r#"{ "paragraph_mode": "sentence" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_parser_options() {
+        assert!(LinterConfig::new().parser_options.is_empty());
+
+        let config = LinterConfig::from_json(
+            r#"{ "parser_options": [{ "files": ["**/*.mdx"], "options": { "mdx": true } }] }"#,
+        )
+        .unwrap();
+        assert_eq!(config.parser_options.len(), 1);
+        assert_eq!(config.parser_options[0].files, vec!["**/*.mdx"]);
+        assert!(config.parser_options[0].options.mdx);
+        assert!(config.parser_options[0].options.gfm_table);
+
+        assert!(
+            LinterConfig::from_json(
+                r#"{ "parser_options": [{ "files": ["*.md"], "options": { "math": true } }] }"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_config_from_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{
+                "name": "docs",
+                "devDependencies": { "textlint": "^14.0.0" },
+                "texide": { "options": { "no-todo": true }, "follow_symlinks": true }
+            }"#,
+        )
+        .unwrap();
+
+        let config = LinterConfig::discover(dir.path()).unwrap().unwrap();
+        assert!(config.follow_symlinks);
+        assert_eq!(config.options["no-todo"], RuleOption::Enabled(true));
+        assert_eq!(config.base_dir.as_deref(), Some(dir.path()));
+        assert_eq!(config.source, Some(dir.path().join("package.json")));
+
+        // A dedicated config file wins over package.json
+        fs::write(
+            dir.path().join(".texide.json"),
+            r#"{ "max_file_size": 1024 }"#,
+        )
+        .unwrap();
+        let config = LinterConfig::discover(dir.path()).unwrap().unwrap();
+        assert_eq!(config.max_file_size, Some(1024));
+        assert!(!config.follow_symlinks);
+        assert!(config.options.is_empty());
+    }
+
+    #[test]
+    fn test_config_from_package_json_without_key() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(LinterConfig::discover(dir.path()).unwrap().is_none());
+
+        let path = dir.path().join("package.json");
+        fs::write(&path, r#"{ "name": "docs" }"#).unwrap();
+        assert!(LinterConfig::from_package_json(&path).unwrap().is_none());
+        assert!(LinterConfig::discover(dir.path()).unwrap().is_none());
+
+        // The texide key is validated like a config file
+        fs::write(&path, r#"{ "texide": { "max_file_size": -1 } }"#).unwrap();
+        assert!(LinterConfig::discover(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_json_syntax_error_reports_location() {
+        let json = "{\n  \"cache\": false,\n  \"include\": [\"**/*.md\",]\n}\n";
+        let Err(error) = LinterConfig::from_json(json) else {
+            panic!("expected a syntax error");
+        };
+        let LinterError::ConfigSyntax(syntax) = &error else {
+            panic!("expected a syntax error, got {:?}", error);
+        };
+        assert_eq!((syntax.line, syntax.column), (3, 25));
+        let span = syntax.span;
+        assert_eq!(&json[span.offset()..span.offset() + 1], "]");
+        assert_eq!(
+            error.to_string(),
+            "Configuration error: trailing comma at line 3 column 25"
+        );
+    }
+
+    #[test]
+    fn test_json_syntax_error_names_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".texide.json");
+        fs::write(&path, "{ \"cache\": fals }").unwrap();
+
+        let Err(LinterError::ConfigSyntax(syntax)) = LinterConfig::from_file(&path) else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(syntax.path.as_deref(), Some(path.as_path()));
+        assert_eq!(syntax.line, 1);
+    }
+
+    #[test]
+    fn test_invalid_globs_are_reported_at_load() {
+        let result = LinterConfig::from_json(
+            r#"{
+                "include": ["**/*.md"],
+                "exclude": ["vendor/**", "!docs/[draft"],
+                "parser_options": [{ "files": ["*.mdx", "{a,b"] }],
+                "options": { "spelling": { "files": ["docs/**"], "exclude_files": ["[x"] } }
+            }"#,
+        );
+        let Err(LinterError::Config(message)) = result else {
+            panic!("expected a configuration error");
+        };
+        assert!(message.contains("Invalid glob pattern '!docs/[draft' in exclude[1]:"));
+        assert!(message.contains("Invalid glob pattern '{a,b' in parser_options[0].files[1]:"));
+        assert!(
+            message.contains("Invalid glob pattern '[x' in options.spelling.exclude_files[0]:")
+        );
+        assert!(!message.contains("vendor"));
+
+        let mut config = LinterConfig::new();
+        config.include.push("[".to_string());
+        let message = config.validate_globs().unwrap_err().to_string();
+        assert!(message.contains("'[' in include[0]"));
+    }
+
+    #[test]
+    fn test_globs_are_normalized_at_load() {
+        let config = LinterConfig::from_json(
+            r#"{
+                "include": ["./docs/**/*.md", "!./docs/drafts/**", "**/*.txt"],
+                "parser_options": [{ "files": ["./*.mdx"] }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.include,
+            vec!["docs/**/*.md", "!docs/drafts/**", "**/*.txt"]
+        );
+        assert_eq!(config.parser_options[0].files, vec!["*.mdx"]);
+    }
+
+    #[test]
+    fn test_config_roots() {
+        assert!(LinterConfig::new().roots.is_empty());
+
+        let config =
+            LinterConfig::from_json(r#"{ "roots": ["docs", "packages/api/docs"] }"#).unwrap();
+        assert_eq!(config.roots, vec!["docs", "packages/api/docs"]);
+
+        assert!(LinterConfig::from_json(r#"{ "roots": "docs" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_follow_symlinks() {
+        assert!(!LinterConfig::new().follow_symlinks);
+
+        let config = LinterConfig::from_json(r#"{ "follow_symlinks": true }"#).unwrap();
+        assert!(config.follow_symlinks);
+    }
+
+    #[test]
+    fn test_resolve_cache_dir() {
+        let home = tempfile::tempdir().unwrap();
+        let os_cache_dir = home.path().join(".cache");
+
+        let dir = resolve_cache_dir(None, Path::new("/work/project"), Some(os_cache_dir.clone()));
+        assert_eq!(dir.parent().unwrap(), os_cache_dir.join("texide"));
+        assert_eq!(dir.file_name().unwrap().len(), 16);
+
+        // Deterministic per project, distinct across projects
+        assert_eq!(
+            resolve_cache_dir(None, Path::new("/work/project"), Some(os_cache_dir.clone())),
+            dir
+        );
+        assert_ne!(
+            resolve_cache_dir(None, Path::new("/work/other"), Some(os_cache_dir.clone())),
+            dir
+        );
+
+        // An explicit directory wins, and a missing OS directory falls back
+        assert_eq!(
+            resolve_cache_dir(
+                Some("build/cache"),
+                Path::new("/work/project"),
+                Some(os_cache_dir)
+            ),
+            Path::new("build/cache")
+        );
+        assert_eq!(
+            resolve_cache_dir(None, Path::new("/work/project"), None),
+            Path::new(FALLBACK_CACHE_DIR)
+        );
+    }
+
+    #[test]
+    fn test_config_cache_flush_interval() {
+        let config = LinterConfig::new();
+        assert_eq!(config.cache_flush_files, 100);
+        assert_eq!(config.cache_flush_secs, 5);
+
+        let config =
+            LinterConfig::from_json(r#"{ "cache_flush_files": 10, "cache_flush_secs": 0 }"#)
+                .unwrap();
+        assert_eq!(config.cache_flush_files, 10);
+        assert_eq!(config.cache_flush_secs, 0);
+
+        assert!(LinterConfig::from_json(r#"{ "cache_flush_files": -1 }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_cache_dir() {
+        assert_eq!(LinterConfig::new().cache_dir, None);
+
+        let config = LinterConfig::from_json(r#"{ "cache_dir": "build/texide" }"#).unwrap();
+        assert_eq!(config.resolved_cache_dir(), Path::new("build/texide"));
+    }
+
+    #[test]
+    fn test_path_style_display() {
+        let cwd = std::env::current_dir().unwrap();
+        let root = cwd.join("project");
+        let path = Path::new("./project/docs/guide.md");
+
+        assert_eq!(
+            PathStyle::Relative.display(path, &root),
+            Path::new("docs/guide.md")
+        );
+        assert_eq!(
+            PathStyle::Relative.display(&root.join("docs/guide.md"), Path::new("project")),
+            Path::new("docs/guide.md")
+        );
+        assert_eq!(
+            PathStyle::Absolute.display(path, &root),
+            root.join("docs/guide.md")
+        );
+        assert_eq!(PathStyle::AsGiven.display(path, &root), path);
+    }
+
+    #[test]
+    fn test_path_style_relative_outside_root_is_absolute() {
+        let cwd = std::env::current_dir().unwrap();
+        let root = cwd.join("project");
+
+        assert_eq!(
+            PathStyle::Relative.display(Path::new("other/a.md"), &root),
+            cwd.join("other/a.md")
+        );
+        assert_eq!(
+            PathStyle::Relative.display(Path::new("./a.md"), Path::new("")),
+            Path::new("a.md")
+        );
+    }
+
     #[test]
     fn test_config_rules_array() {
         let json = r#"{
@@ -284,6 +1522,80 @@ mod tests {
         assert_eq!(enabled.len(), 2); // enabled-rule, options-rule
     }
 
+    #[test]
+    fn test_retain_only_rule() {
+        let mut config = LinterConfig::from_json(
+            r#"{
+                "rules": [
+                    "acme/texide-rule-no-todo@1.0.0",
+                    { "path": "rules/length.wasm", "as": "sentence-length" }
+                ],
+                "options": {
+                    "no-todo": { "allow": ["FIXME"] },
+                    "sentence-length": true,
+                    "no-bare-urls": false
+                }
+            }"#,
+        )
+        .unwrap();
+
+        config.retain_only_rule("no-todo");
+        assert_eq!(config.options.len(), 1);
+        assert_eq!(
+            config.options["no-todo"].options(),
+            serde_json::json!({ "allow": ["FIXME"] })
+        );
+        assert_eq!(
+            config.rules,
+            vec![RuleDefinition::Simple(
+                "acme/texide-rule-no-todo@1.0.0".to_string()
+            )]
+        );
+
+        // A disabled rule is enabled
+        let mut config = LinterConfig::from_json(
+            r#"{ "rules": ["acme/style"], "options": { "no-bare-urls": false } }"#,
+        )
+        .unwrap();
+        config.retain_only_rule("no-bare-urls");
+        assert!(config.options["no-bare-urls"].is_enabled());
+        // No plugin is known to provide the rule, so all are kept
+        assert_eq!(config.rules.len(), 1);
+    }
+
+    #[test]
+    fn test_set_rule_enabled() {
+        let json = r#"{
+            "options": {
+                "no-todo": { "allow": ["FIXME"] },
+                "sentence-length": ["off", { "max": 80 }]
+            }
+        }"#;
+        let mut config = LinterConfig::from_json(json).unwrap();
+
+        config.set_rule_enabled("no-todo", false);
+        config.set_rule_enabled("sentence-length", true);
+        config.set_rule_enabled("no-bare-urls", true);
+
+        assert!(!config.options["no-todo"].is_enabled());
+        assert!(matches!(
+            config.options["sentence-length"],
+            RuleOption::Enabled(true)
+        ));
+        assert!(config.options["no-bare-urls"].is_enabled());
+
+        // Enabling a rule that is already enabled keeps its options
+        config.options.insert(
+            "no-todo".to_string(),
+            RuleOption::Options(serde_json::json!({ "allow": ["FIXME"] })),
+        );
+        config.set_rule_enabled("no-todo", true);
+        assert_eq!(
+            config.options["no-todo"].options(),
+            serde_json::json!({ "allow": ["FIXME"] })
+        );
+    }
+
     #[test]
     fn test_config_default() {
         let config = LinterConfig::default();
@@ -0,0 +1,115 @@
+//! Cross-rule diagnostic deduplication.
+//!
+//! Different rules can report the same problem at the same place. The
+//! `dedup` config option collapses such diagnostics into one, keeping the
+//! most severe.
+
+use std::collections::HashMap;
+
+use texide_plugin::{Diagnostic, Severity};
+
+use crate::DedupStrategy;
+
+/// Collapses duplicate diagnostics according to `strategy`.
+///
+/// The kept diagnostic takes the position of the first duplicate, so the
+/// output order is stable.
+pub(crate) fn dedup_diagnostics(
+    diagnostics: Vec<Diagnostic>,
+    strategy: DedupStrategy,
+) -> Vec<Diagnostic> {
+    let key = |d: &Diagnostic| match strategy {
+        DedupStrategy::None => None,
+        DedupStrategy::Exact => Some((d.span, Some(d.message.clone()))),
+        DedupStrategy::Span => Some((d.span, None)),
+    };
+
+    let mut kept: Vec<Diagnostic> = Vec::with_capacity(diagnostics.len());
+    let mut index = HashMap::new();
+
+    for diagnostic in diagnostics {
+        let Some(key) = key(&diagnostic) else {
+            kept.push(diagnostic);
+            continue;
+        };
+
+        match index.get(&key) {
+            Some(&i) => {
+                let existing: &mut Diagnostic = &mut kept[i];
+                if severity_rank(diagnostic.severity) > severity_rank(existing.severity) {
+                    *existing = diagnostic;
+                }
+            }
+            None => {
+                index.insert(key, kept.len());
+                kept.push(diagnostic);
+            }
+        }
+    }
+
+    kept
+}
+
+fn severity_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Info => 0,
+        Severity::Warning => 1,
+        Severity::Error => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texide_ast::Span;
+
+    fn diagnostic(rule: &str, message: &str, start: u32, severity: Severity) -> Diagnostic {
+        Diagnostic::new(rule, message, Span::new(start, start + 4)).with_severity(severity)
+    }
+
+    fn sample() -> Vec<Diagnostic> {
+        vec![
+            diagnostic("a", "Unknown word", 0, Severity::Warning),
+            diagnostic("b", "Unknown word", 0, Severity::Error),
+            diagnostic("c", "Misspelled", 0, Severity::Info),
+            diagnostic("a", "Unknown word", 10, Severity::Warning),
+        ]
+    }
+
+    #[test]
+    fn test_none_keeps_everything() {
+        assert_eq!(dedup_diagnostics(sample(), DedupStrategy::None).len(), 4);
+    }
+
+    #[test]
+    fn test_exact_collapses_same_span_and_message() {
+        let result = dedup_diagnostics(sample(), DedupStrategy::Exact);
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].rule_id, "b");
+        assert_eq!(result[0].severity, Severity::Error);
+        assert_eq!(result[1].rule_id, "c");
+        assert_eq!(result[2].span.start, 10);
+    }
+
+    #[test]
+    fn test_span_collapses_same_span_across_rules() {
+        let result = dedup_diagnostics(sample(), DedupStrategy::Span);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].rule_id, "b");
+        assert_eq!(result[1].span.start, 10);
+    }
+
+    #[test]
+    fn test_equal_severity_keeps_first() {
+        let diagnostics = vec![
+            diagnostic("a", "First", 0, Severity::Warning),
+            diagnostic("b", "Second", 0, Severity::Warning),
+        ];
+        let result = dedup_diagnostics(diagnostics, DedupStrategy::Span);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].rule_id, "a");
+    }
+}
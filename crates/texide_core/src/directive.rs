@@ -0,0 +1,231 @@
+//! Comments that turn rules on or off for part of a file.
+//!
+//! A rule can be kept off in the configuration, e.g. because it is slow,
+//! and turned on only where a document asks for it:
+//!
+//! ```markdown
+//! <!-- texide-enable spelling -->
+//! Text checked for spelling.
+//! <!-- texide-disable spelling -->
+//! ```
+//!
+//! or, in formats without HTML comments, `// texide-enable spelling`. A
+//! directive is a line of its own naming one or more rules, separated by
+//! spaces or commas. Lines inside fenced code blocks are not directives.
+//!
+//! A diagnostic is kept if its rule is on where the diagnostic starts:
+//!
+//! 1. The last directive naming the rule before that point decides.
+//! 2. With none, the configuration decides.
+//!
+//! So a directive wins over the configuration from where it appears to the
+//! next directive naming the same rule, or the end of the file. It only
+//! switches the rule: options and severity still come from the
+//! configuration (a rule configured `"off"` reports with its own
+//! severity), and a rule never runs on files its `files` or
+//! `exclude_files` options leave out.
+
+use std::collections::BTreeSet;
+
+use texide_plugin::Diagnostic;
+
+/// Comment forms a directive can take, as (prefix, suffix).
+const COMMENTS: [(&str, &str); 2] = [("<!--", "-->"), ("//", "")];
+
+/// The enable and disable directives of a file.
+#[derive(Debug, Default)]
+pub(crate) struct Directives {
+    /// Every directive, in file order.
+    directives: Vec<Directive>,
+}
+
+/// One `texide-enable` or `texide-disable` comment.
+#[derive(Debug)]
+struct Directive {
+    /// Byte offset of the start of its line.
+    offset: u32,
+    /// Whether it turns the rules on.
+    enable: bool,
+    /// The rules it names.
+    rules: Vec<String>,
+}
+
+impl Directives {
+    /// Finds the directives in `content`.
+    pub(crate) fn parse(content: &str) -> Self {
+        let mut directives = Vec::new();
+        // The marker character and length of the open code fence, if any
+        let mut fence: Option<(char, usize)> = None;
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let trimmed = line.trim();
+
+            let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'));
+            if let Some(marker) = marker {
+                let length = trimmed.chars().take_while(|&c| c == marker).count();
+                if length >= 3 {
+                    match fence {
+                        None => fence = Some((marker, length)),
+                        Some((open, open_length))
+                            if open == marker
+                                && length >= open_length
+                                && trimmed.len() == length =>
+                        {
+                            fence = None;
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+            }
+            if fence.is_some() {
+                continue;
+            }
+
+            if let Some(directive) = parse_directive(trimmed, start as u32) {
+                directives.push(directive);
+            }
+        }
+        Self { directives }
+    }
+
+    /// Returns the rules some directive turns on, sorted by name.
+    pub(crate) fn enabled_rules(&self) -> BTreeSet<&str> {
+        self.directives
+            .iter()
+            .filter(|directive| directive.enable)
+            .flat_map(|directive| directive.rules.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Returns whether some directive turns `rule` on.
+    pub(crate) fn enables(&self, rule: &str) -> bool {
+        self.directives
+            .iter()
+            .any(|directive| directive.enable && directive.rules.iter().any(|r| r == rule))
+    }
+
+    /// Returns whether `rule` is on at `offset`, given whether the
+    /// configuration enables it.
+    pub(crate) fn is_enabled_at(&self, rule: &str, offset: u32, configured: bool) -> bool {
+        self.directives
+            .iter()
+            .take_while(|directive| directive.offset <= offset)
+            .filter(|directive| directive.rules.iter().any(|r| r == rule))
+            .last()
+            .map_or(configured, |directive| directive.enable)
+    }
+
+    /// Drops the diagnostics of rules a directive names that are off where
+    /// the diagnostic starts. `configured` tells whether the configuration
+    /// enables a rule.
+    pub(crate) fn filter(
+        &self,
+        mut diagnostics: Vec<Diagnostic>,
+        configured: impl Fn(&str) -> bool,
+    ) -> Vec<Diagnostic> {
+        if self.directives.is_empty() {
+            return diagnostics;
+        }
+        diagnostics.retain(|diagnostic| {
+            let rule = diagnostic.rule_id.as_str();
+            let named = self
+                .directives
+                .iter()
+                .any(|directive| directive.rules.iter().any(|r| r == rule));
+            !named || self.is_enabled_at(rule, diagnostic.span.start, configured(rule))
+        });
+        diagnostics
+    }
+}
+
+/// Parses a trimmed line as a directive starting at `offset`.
+fn parse_directive(line: &str, offset: u32) -> Option<Directive> {
+    let body = COMMENTS
+        .iter()
+        .find_map(|(prefix, suffix)| line.strip_prefix(prefix)?.strip_suffix(suffix))?;
+    let body = body.trim();
+    let (enable, names) = if let Some(names) = body.strip_prefix("texide-enable") {
+        (true, names)
+    } else {
+        (false, body.strip_prefix("texide-disable")?)
+    };
+    if !names.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let rules: Vec<String> = names
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    (!rules.is_empty()).then_some(Directive {
+        offset,
+        enable,
+        rules,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texide_ast::Span;
+
+    #[test]
+    fn test_parse() {
+        let content =
+            "<!-- texide-enable spelling, no-todo -->\nText\n  // texide-disable spelling\n";
+        let directives = Directives::parse(content);
+
+        assert_eq!(
+            directives.enabled_rules().into_iter().collect::<Vec<_>>(),
+            vec!["no-todo", "spelling"]
+        );
+        assert!(directives.enables("spelling"));
+        assert!(!directives.enables("max-line-length"));
+        assert!(directives.is_enabled_at("spelling", 41, false));
+        assert!(!directives.is_enabled_at("spelling", 46, false));
+        assert!(directives.is_enabled_at("no-todo", 46, false));
+        // Rules no directive names keep their configuration
+        assert!(directives.is_enabled_at("max-line-length", 46, true));
+        assert!(!directives.is_enabled_at("max-line-length", 46, false));
+    }
+
+    #[test]
+    fn test_not_directives() {
+        for content in [
+            "<!-- texide-enable -->\n",
+            "<!-- texide-enablespelling -->\n",
+            "Use `<!-- texide-enable spelling -->` to check a section.\n",
+            "```markdown\n<!-- texide-enable spelling -->\n```\n",
+            "~~~~\n```\n<!-- texide-enable spelling -->\n~~~~~\n",
+        ] {
+            assert!(
+                Directives::parse(content).enabled_rules().is_empty(),
+                "{content}"
+            );
+        }
+
+        // The fence is closed again
+        let content = "```\ncode\n```\n<!-- texide-enable spelling -->\n";
+        assert!(Directives::parse(content).enables("spelling"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let content = "<!-- texide-disable no-todo -->\nA\n<!-- texide-enable no-todo -->\nB\n";
+        let directives = Directives::parse(content);
+        let diagnostics = vec![
+            Diagnostic::new("no-todo", "Found TODO", Span::new(32, 33)),
+            Diagnostic::new("no-todo", "Found TODO", Span::new(65, 66)),
+            Diagnostic::new("other", "Other", Span::new(32, 33)),
+        ];
+
+        let kept = directives.filter(diagnostics, |_| true);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].span, Span::new(65, 66));
+        assert_eq!(kept[1].rule_id, "other");
+    }
+}
@@ -1,36 +1,82 @@
 //! Linter error types.
 
+use std::path::{Path, PathBuf};
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
 use thiserror::Error;
 
+/// A JSON syntax error in a configuration, with the text it was found in.
+#[derive(Debug, Error, Diagnostic)]
+#[error("Configuration error: {message} at line {line} column {column}")]
+#[diagnostic(code(texide::config::syntax))]
+pub struct ConfigSyntaxError {
+    /// What is wrong with the configuration.
+    pub message: String,
+    /// The configuration file, if the configuration was read from one.
+    pub path: Option<PathBuf>,
+    /// 1-based line of the offending token.
+    pub line: usize,
+    /// 1-based column of the offending token.
+    pub column: usize,
+    /// The configuration text.
+    #[source_code]
+    pub source_code: NamedSource<String>,
+    /// Where in the configuration text the error is.
+    #[label("{message}")]
+    pub span: SourceSpan,
+}
+
 /// Errors that can occur during linting.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Diagnostic)]
 pub enum LinterError {
     /// Configuration error.
     #[error("Configuration error: {0}")]
+    #[diagnostic(code(texide::config))]
     Config(String),
 
+    /// Configuration that is not valid JSON.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ConfigSyntax(Box<ConfigSyntaxError>),
+
     /// File I/O error.
-    #[error("File error: {0}")]
-    File(String),
+    #[error("File error: {message}")]
+    #[diagnostic(code(texide::file))]
+    File {
+        /// The file that could not be read or written.
+        path: PathBuf,
+        /// What went wrong.
+        message: String,
+    },
 
     /// Parse error.
-    #[error("Parse error: {0}")]
-    Parse(String),
+    #[error("Parse error in {}: {message}", path.display())]
+    #[diagnostic(code(texide::parse))]
+    Parse {
+        /// The file that could not be parsed.
+        path: PathBuf,
+        /// What went wrong.
+        message: String,
+    },
 
     /// Plugin error.
     #[error("Plugin error: {0}")]
+    #[diagnostic(code(texide::plugin))]
     Plugin(#[from] texide_plugin::PluginError),
 
     /// Cache error.
     #[error("Cache error: {0}")]
+    #[diagnostic(code(texide::cache))]
     Cache(#[from] texide_cache::CacheError),
 
     /// I/O error.
     #[error("I/O error: {0}")]
+    #[diagnostic(code(texide::io))]
     Io(#[from] std::io::Error),
 
     /// Internal error.
     #[error("Internal error: {0}")]
+    #[diagnostic(code(texide::internal))]
     Internal(String),
 }
 
@@ -40,13 +86,48 @@ impl LinterError {
         Self::Config(message.into())
     }
 
+    /// Creates a configuration error for JSON that failed to parse, pointing
+    /// at the location `error` reports in `source`.
+    pub fn config_syntax(source: &str, path: Option<&Path>, error: &serde_json::Error) -> Self {
+        let (line, column) = (error.line(), error.column());
+        // serde_json appends the location to its message
+        let message = error.to_string();
+        let message = message
+            .strip_suffix(&format!(" at line {} column {}", line, column))
+            .unwrap_or(&message)
+            .to_string();
+
+        let line_start: usize = source
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum();
+        let offset = (line_start + column.saturating_sub(1)).min(source.len());
+
+        let name = path.map_or_else(|| "config".to_string(), |p| p.display().to_string());
+        Self::ConfigSyntax(Box::new(ConfigSyntaxError {
+            message,
+            path: path.map(Path::to_path_buf),
+            line,
+            column,
+            source_code: NamedSource::new(name, source.to_string()),
+            span: offset.into(),
+        }))
+    }
+
     /// Creates a file error.
-    pub fn file(message: impl Into<String>) -> Self {
-        Self::File(message.into())
+    pub fn file(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::File {
+            path: path.into(),
+            message: message.into(),
+        }
     }
 
     /// Creates a parse error.
-    pub fn parse(message: impl Into<String>) -> Self {
-        Self::Parse(message.into())
+    pub fn parse(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::Parse {
+            path: path.into(),
+            message: message.into(),
+        }
     }
 }
@@ -99,15 +99,9 @@ fn filter_overlapping_fixes(fixes: Vec<&Fix>) -> Vec<&Fix> {
     let mut result: Vec<&Fix> = Vec::with_capacity(fixes.len());
 
     for fix in fixes {
-        let overlaps = result.iter().any(|existing| {
-            let existing_start = existing.span.start;
-            let existing_end = existing.span.end;
-            let fix_start = fix.span.start;
-            let fix_end = fix.span.end;
-
-            // Check if spans overlap
-            !(fix_end <= existing_start || fix_start >= existing_end)
-        });
+        let overlaps = result
+            .iter()
+            .any(|existing| existing.span.overlaps(&fix.span));
 
         if overlaps {
             warn!(
@@ -127,14 +121,16 @@ pub fn apply_fixes_to_file(
     path: &Path,
     diagnostics: &[Diagnostic],
 ) -> Result<FixerResult, LinterError> {
-    let content = fs::read_to_string(path)
-        .map_err(|e| LinterError::file(format!("Failed to read {}: {}", path.display(), e)))?;
+    let content = fs::read_to_string(path).map_err(|e| {
+        LinterError::file(path, format!("Failed to read {}: {}", path.display(), e))
+    })?;
 
     let result = apply_fixes_to_content(&content, diagnostics);
 
     if result.modified {
-        fs::write(path, &result.fixed_content)
-            .map_err(|e| LinterError::file(format!("Failed to write {}: {}", path.display(), e)))?;
+        fs::write(path, &result.fixed_content).map_err(|e| {
+            LinterError::file(path, format!("Failed to write {}: {}", path.display(), e))
+        })?;
     }
 
     Ok(result)
@@ -0,0 +1,172 @@
+//! Listing the files git reports as changed.
+//!
+//! Runs the `git` executable rather than linking a git library, so the
+//! repository is read exactly as the user's own git reads it (hooks,
+//! worktrees, `safe.directory` and so on).
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::LinterError;
+
+/// Which changes to lint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeSet {
+    /// Files changed since a commit, branch or tag, including uncommitted
+    /// and untracked files.
+    Since(String),
+    /// Files staged for the next commit.
+    Staged,
+}
+
+/// Returns the files of the repository containing `dir` that `changes`
+/// selects, as absolute paths. Deleted files are left out.
+///
+/// Fails with a configuration error when `git` is not installed or `dir`
+/// is not inside a git repository.
+pub fn changed_files(changes: &ChangeSet, dir: &Path) -> Result<Vec<PathBuf>, LinterError> {
+    let top_level = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let top_level = PathBuf::from(top_level.trim_end_matches(['\n', '\r']));
+
+    let mut names = match changes {
+        ChangeSet::Since(reference) => {
+            let mut names = git(
+                &top_level,
+                &[
+                    "diff",
+                    "--name-only",
+                    "-z",
+                    "--diff-filter=d",
+                    reference,
+                    "--",
+                ],
+            )?;
+            names.push_str(&git(
+                &top_level,
+                &["ls-files", "--others", "--exclude-standard", "-z"],
+            )?);
+            names
+        }
+        ChangeSet::Staged => git(
+            &top_level,
+            &["diff", "--name-only", "-z", "--diff-filter=d", "--cached"],
+        )?,
+    }
+    .split('\0')
+    .filter(|name| !name.is_empty())
+    .map(|name| top_level.join(name))
+    .collect::<Vec<_>>();
+
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Runs git with `args` in `dir` and returns its output.
+fn git(dir: &Path, args: &[&str]) -> Result<String, LinterError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => {
+                LinterError::config("Linting changed files needs git, which was not found")
+            }
+            _ => LinterError::config(format!("Failed to run git: {}", e)),
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = if stderr.contains("not a git repository") {
+            format!(
+                "Linting changed files needs a git repository, and {} is not in one",
+                dir.display()
+            )
+        } else {
+            format!("git {} failed: {}", args.join(" "), stderr.trim())
+        };
+        return Err(LinterError::config(message));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|_| LinterError::config("git printed a path that is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Runs git in `dir`, panicking on failure.
+    fn run(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Texide",
+                "-c",
+                "user.email=texide@example.com",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn test_changed_files() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        run(&root, &["init", "-q"]);
+        fs::write(root.join("old.md"), "Old\n").unwrap();
+        fs::write(root.join("gone.md"), "Gone\n").unwrap();
+        run(&root, &["add", "."]);
+        run(&root, &["commit", "-q", "-m", "Initial"]);
+
+        fs::write(root.join("old.md"), "Changed\n").unwrap();
+        fs::remove_file(root.join("gone.md")).unwrap();
+        fs::create_dir(root.join("docs")).unwrap();
+        fs::write(root.join("docs/new.md"), "New\n").unwrap();
+        fs::write(root.join("staged.md"), "Staged\n").unwrap();
+        run(&root, &["add", "staged.md"]);
+
+        let since = changed_files(&ChangeSet::Since("HEAD".to_string()), &root.join("docs"));
+        assert_eq!(
+            since.unwrap(),
+            vec![
+                root.join("docs/new.md"),
+                root.join("old.md"),
+                root.join("staged.md")
+            ]
+        );
+        let staged = changed_files(&ChangeSet::Staged, &root).unwrap();
+        assert_eq!(staged, vec![root.join("staged.md")]);
+    }
+
+    #[test]
+    fn test_changed_files_outside_repository() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        // Nothing to check when the temporary directory is in a repository
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        if output.status.success() {
+            return;
+        }
+
+        let error = changed_files(&ChangeSet::Staged, dir.path()).unwrap_err();
+        assert!(
+            error.to_string().contains("needs a git repository"),
+            "{error}"
+        );
+    }
+}
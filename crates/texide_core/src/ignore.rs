@@ -0,0 +1,160 @@
+//! Globally accepted strings.
+//!
+//! Diagnostics whose covered text matches an ignore pattern are dropped,
+//! whichever rule reported them. Patterns come from the `ignore_patterns`
+//! config option and from a `.texideignore-words` file next to the
+//! configuration, one pattern per line (`#` starts a comment line).
+//!
+//! A pattern wrapped in slashes (`/v\d+/`) is a regular expression;
+//! anything else is a literal. Both must match the whole covered text.
+
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use texide_plugin::Diagnostic;
+
+use crate::LinterError;
+
+/// Name of the ignore file.
+pub(crate) const IGNORE_FILE: &str = ".texideignore-words";
+
+/// A set of literal and regex patterns for accepted strings.
+#[derive(Debug, Default)]
+pub(crate) struct IgnoreList {
+    literals: Vec<String>,
+    regexes: Vec<Regex>,
+}
+
+impl IgnoreList {
+    /// Builds the list from config patterns and the ignore file in `base_dir`.
+    pub fn load(patterns: &[String], base_dir: &Path) -> Result<Self, LinterError> {
+        let mut list = Self::new(patterns)?;
+
+        let path = base_dir.join(IGNORE_FILE);
+        if path.is_file() {
+            let content = fs::read_to_string(&path).map_err(|e| {
+                LinterError::config(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            for line in content.lines().map(str::trim) {
+                if !line.is_empty() && !line.starts_with('#') {
+                    list.add(line)?;
+                }
+            }
+        }
+
+        Ok(list)
+    }
+
+    /// Builds the list from patterns.
+    pub fn new(patterns: &[String]) -> Result<Self, LinterError> {
+        let mut list = Self::default();
+        for pattern in patterns {
+            list.add(pattern)?;
+        }
+        Ok(list)
+    }
+
+    fn add(&mut self, pattern: &str) -> Result<(), LinterError> {
+        match pattern
+            .strip_prefix('/')
+            .and_then(|rest| rest.strip_suffix('/'))
+            .filter(|regex| !regex.is_empty())
+        {
+            Some(regex) => {
+                let regex = Regex::new(&format!("^(?:{})$", regex)).map_err(|e| {
+                    LinterError::config(format!("Invalid ignore pattern '{}': {}", pattern, e))
+                })?;
+                self.regexes.push(regex);
+            }
+            None => self.literals.push(pattern.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Returns whether the list has no patterns.
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty() && self.regexes.is_empty()
+    }
+
+    /// Returns whether `text` matches any pattern.
+    pub fn is_ignored(&self, text: &str) -> bool {
+        self.literals.iter().any(|literal| literal == text)
+            || self.regexes.iter().any(|regex| regex.is_match(text))
+    }
+
+    /// Removes diagnostics whose covered text in `content` is ignored.
+    pub fn filter(&self, diagnostics: Vec<Diagnostic>, content: &str) -> Vec<Diagnostic> {
+        if self.is_empty() {
+            return diagnostics;
+        }
+
+        diagnostics
+            .into_iter()
+            .filter(|d| {
+                content
+                    .get(d.span.start as usize..d.span.end as usize)
+                    .is_none_or(|text| text.is_empty() || !self.is_ignored(text))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texide_ast::Span;
+
+    #[test]
+    fn test_literal_pattern() {
+        let list = IgnoreList::new(&["Texide".to_string()]).unwrap();
+        assert!(list.is_ignored("Texide"));
+        assert!(!list.is_ignored("texide"));
+        assert!(!list.is_ignored("Texide rules"));
+    }
+
+    #[test]
+    fn test_regex_pattern() {
+        let list = IgnoreList::new(&[r"/v\d+(\.\d+)*/".to_string()]).unwrap();
+        assert!(list.is_ignored("v2"));
+        assert!(list.is_ignored("v1.10.3"));
+        assert!(!list.is_ignored("version v2"));
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let result = IgnoreList::new(&["/(unclosed/".to_string()]);
+        assert!(matches!(result, Err(LinterError::Config(_))));
+    }
+
+    #[test]
+    fn test_load_ignore_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(IGNORE_FILE),
+            "# Product names\nTexide\n\n/[A-Z]{2,}/\n",
+        )
+        .unwrap();
+
+        let list = IgnoreList::load(&["textlint".to_string()], dir.path()).unwrap();
+        assert!(list.is_ignored("Texide"));
+        assert!(list.is_ignored("AST"));
+        assert!(list.is_ignored("textlint"));
+        assert!(!list.is_ignored("Product names"));
+    }
+
+    #[test]
+    fn test_filter_diagnostics() {
+        let list = IgnoreList::new(&["Texide".to_string()]).unwrap();
+        let content = "Texide is a linter";
+        let diagnostics = vec![
+            Diagnostic::new("spelling", "Unknown word", Span::new(0, 6)),
+            Diagnostic::new("spelling", "Unknown word", Span::new(12, 18)),
+            Diagnostic::new("file-rule", "Whole file", Span::new(0, 0)),
+        ];
+
+        let filtered = list.filter(diagnostics, content);
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0].span, Span::new(12, 18));
+    }
+}
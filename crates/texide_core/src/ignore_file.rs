@@ -0,0 +1,60 @@
+//! Files that opt out of linting.
+//!
+//! A generated file can ask not to be linted with a marker comment on a
+//! line of its own near the top, where a generator can emit it:
+//!
+//! ```markdown
+//! <!-- texide-ignore-file -->
+//! ```
+//!
+//! or, in formats without HTML comments, `// texide-ignore-file`. Unlike
+//! `exclude` patterns, this needs no change to the configuration.
+
+/// Number of lines at the start of a file searched for the marker.
+const MARKER_LINES: usize = 5;
+
+/// Marker comments, compared against trimmed lines.
+const MARKERS: [&str; 2] = ["<!-- texide-ignore-file -->", "// texide-ignore-file"];
+
+/// Returns whether `content` has an ignore-file marker in its first lines.
+pub(crate) fn has_ignore_file_marker(content: &str) -> bool {
+    content
+        .trim_start_matches('\u{FEFF}')
+        .lines()
+        .take(MARKER_LINES)
+        .any(|line| MARKERS.contains(&line.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_at_top() {
+        assert!(has_ignore_file_marker(
+            "<!-- texide-ignore-file -->\n# API\n"
+        ));
+        assert!(has_ignore_file_marker(
+            "\u{FEFF}// texide-ignore-file\r\nText\n"
+        ));
+        assert!(has_ignore_file_marker(
+            "---\ntitle: API\n---\n  <!-- texide-ignore-file -->\n"
+        ));
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert!(!has_ignore_file_marker("# Guide\n\nText\n"));
+        assert!(!has_ignore_file_marker(""));
+        // Mentioning the marker in prose does not count
+        assert!(!has_ignore_file_marker(
+            "Add `<!-- texide-ignore-file -->` to skip a file.\n"
+        ));
+    }
+
+    #[test]
+    fn test_marker_too_far_down() {
+        let content = format!("{}<!-- texide-ignore-file -->\n", "line\n".repeat(5));
+        assert!(!has_ignore_file_marker(&content));
+    }
+}
@@ -6,6 +6,7 @@
 //! - The main `Linter` orchestrator
 //! - Configuration loading
 //! - File discovery and filtering
+//! - Native rules
 //! - Parallel processing
 //!
 //! ## Example
@@ -22,18 +23,43 @@
 //! }
 //! ```
 
+mod archive;
+mod builder;
 mod config;
+mod dedup;
+mod directive;
 mod error;
 mod fixer;
+mod git;
+mod ignore;
+mod ignore_file;
 mod linter;
+mod locale;
+mod newline;
+mod parser_selector;
+mod path_filter;
+mod plugin_policy;
 pub mod resolver;
 mod result;
+pub mod rules;
+mod unicode;
 
-pub use config::LinterConfig;
-pub use error::LinterError;
+pub use builder::LinterBuilder;
+pub use config::{
+    DedupStrategy, Encoding, LinterConfig, NormalizationForm, ParserOptionsOverride, PathStyle,
+    RuleDefinition, RuleDefinitionDetail, RuleOption, StrictMode,
+};
+pub use error::{ConfigSyntaxError, LinterError};
 pub use fixer::{FixerResult, apply_fixes_to_content, apply_fixes_to_file};
+pub use git::{ChangeSet, changed_files};
 pub use linter::Linter;
-pub use result::LintResult;
+pub use parser_selector::ParserSelector;
+pub use plugin_policy::PluginPolicy;
+pub use result::{LintResult, LintSummary};
 
 // Re-export commonly used types
-pub use texide_plugin::{Diagnostic, Fix, Severity};
+pub use texide_cache::{CacheEntry, CacheError, CacheStore, FileCacheStore};
+pub use texide_parser::{ParagraphMode, ParserOptions};
+pub use texide_plugin::{
+    Diagnostic, Fix, RelatedSpan, RuleManifest, RuleProfile, RuleProfiler, Severity,
+};
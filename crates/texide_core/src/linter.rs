@@ -1,22 +1,77 @@
 //! Core linter engine.
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
-use texide_ast::{AstArena, NodeType, TxtNode};
-use texide_cache::{CacheEntry, CacheManager, entry::BlockCacheEntry};
-use texide_parser::{MarkdownParser, Parser, PlainTextParser};
-use texide_plugin::{IsolationLevel, PluginHost};
-
+use texide_ast::{ArenaPool, NodeType, Span, TxtNode};
+use texide_cache::{CacheEntry, CacheManager, CacheStore, FileCacheStore, entry::BlockCacheEntry};
+use texide_parser::Parser;
+use texide_plugin::{
+    Diagnostic, IsolationLevel, LintInput, PluginHost, RuleManifest, RuleProfiler, Severity,
+};
+
+use crate::archive::for_each_entry;
+use crate::config::RuleOption;
+use crate::dedup::dedup_diagnostics;
+use crate::directive::Directives;
+use crate::ignore::IgnoreList;
+use crate::ignore_file::has_ignore_file_marker;
+use crate::locale::MessageBundle;
+use crate::newline::{OffsetMap, normalize_line_endings};
+use crate::parser_selector::ParserSelector;
+use crate::path_filter::PathFilter;
+use crate::plugin_policy::PluginPolicy;
 use crate::resolver::PluginResolver;
-use crate::{LintResult, LinterConfig, LinterError};
+use crate::rules::{
+    CollectedFile, NativeRule, ProjectContext, ProjectRule, RuleContext, builtin_project_rules,
+    builtin_rules,
+};
+use crate::unicode::normalize_unicode;
+use crate::{Encoding, LintResult, LinterBuilder, LinterConfig, LinterError, StrictMode};
+
+/// State collected for the project rules from one file, by rule name.
+type ProjectState = HashMap<String, serde_json::Value>;
+
+/// A linted file, with what its project rule diagnostics still need.
+struct LintedFile {
+    result: LintResult,
+    /// State collected for the project rules.
+    state: ProjectState,
+    /// The text the diagnostics were found in.
+    source: LintedSource,
+}
+
+impl LintedFile {
+    /// A file whose content was not linted, such as one above the size limit.
+    fn skipped(result: LintResult) -> Self {
+        Self {
+            result,
+            state: ProjectState::new(),
+            source: LintedSource::default(),
+        }
+    }
+}
+
+/// The text a file's diagnostics were found in, which they are filtered
+/// against and mapped back from before being reported.
+#[derive(Default)]
+struct LintedSource {
+    /// The content after line ending and Unicode normalization.
+    content: String,
+    directives: Directives,
+    /// Maps from the normalized content back to the file, in the order
+    /// they were applied.
+    offset_maps: Vec<OffsetMap>,
+}
 
 /// The core linter engine.
 ///
@@ -28,44 +83,113 @@ pub struct Linter {
     plugin_host: Mutex<PluginHost>,
     /// Cache manager.
     cache: Mutex<CacheManager>,
-    /// Include glob patterns.
-    include_globs: Option<GlobSet>,
-    /// Exclude glob patterns.
-    exclude_globs: Option<GlobSet>,
+    /// Include/exclude patterns.
+    path_filter: PathFilter,
+    /// The absolute cache directory, when the include patterns reach it
+    /// and discovery has to skip it.
+    excluded_cache_dir: Option<PathBuf>,
+    /// Files each rule with `files` or `exclude_files` options runs on.
+    rule_scopes: HashMap<String, PathFilter>,
+    /// Picks the parser of each file.
+    parsers: ParserSelector,
+    /// Arenas reused across files to avoid reallocating per parse.
+    arenas: ArenaPool,
+    /// Rules built into Texide.
+    native_rules: Vec<Box<dyn NativeRule>>,
+    /// Rules built into Texide that check the linted files together.
+    project_rules: Vec<Box<dyn ProjectRule>>,
+    /// Time spent in native rules, when `profile_rules` is enabled.
+    native_profile: Option<Mutex<RuleProfiler>>,
+    /// Accepted strings whose diagnostics are dropped.
+    ignore_list: IgnoreList,
+    /// Message templates for the configured locale.
+    messages: Option<MessageBundle>,
+    /// Plugins that may be loaded.
+    plugin_policy: PluginPolicy,
 }
 
 impl Linter {
     /// Creates a new linter with the given configuration.
     pub fn new(config: LinterConfig) -> Result<Self, LinterError> {
-        let cache_dir = PathBuf::from(&config.cache_dir);
-        let mut cache = CacheManager::new(cache_dir);
+        let store = Box::new(FileCacheStore::new(config.resolved_cache_dir()));
+        Self::with_cache_store(config, store)
+    }
+
+    /// Creates a new linter that persists its cache through `store` instead
+    /// of the cache directory.
+    ///
+    /// `cache_dir` is then unused, but `cache: false` still disables the
+    /// cache and the store is never read or written.
+    pub fn with_cache_store(
+        mut config: LinterConfig,
+        store: Box<dyn CacheStore>,
+    ) -> Result<Self, LinterError> {
+        config.normalize_globs();
+        config.validate_globs()?;
+        config.validate_failure_options()?;
+
+        let mut cache = CacheManager::with_store(store);
 
         if !config.cache {
             cache.disable();
         }
 
-        // Load cache from disk
+        // Load cache from the store
         if let Err(e) = cache.load() {
             warn!("Failed to load cache: {}", e);
         }
 
         // Build glob patterns
-        let include_globs = Self::build_globset(&config.include)?;
-        let exclude_globs = Self::build_globset(&config.exclude)?;
+        let path_filter = PathFilter::new(&config.include, &config.exclude)?;
+        // Linting the cache's own files would change them on every run
+        let excluded_cache_dir = cache
+            .local_dir()
+            .filter(|dir| config.cache && path_filter.is_match(&dir.join("cache.json")))
+            .map(|dir| {
+                info!(
+                    "Cache directory {} matches the include patterns; skipping it",
+                    dir.display()
+                );
+                std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf())
+            });
+        let mut rule_scopes = HashMap::new();
+        for (name, option) in &config.options {
+            let (files, exclude_files) = (option.files(), option.exclude_files());
+            if !files.is_empty() || !exclude_files.is_empty() {
+                rule_scopes.insert(name.clone(), PathFilter::new(&files, &exclude_files)?);
+            }
+        }
+        let parsers = ParserSelector::new(&config)?;
+
+        let ignore_list = IgnoreList::load(
+            &config.ignore_patterns,
+            config.base_dir.as_deref().unwrap_or(Path::new(".")),
+        )?;
+        let messages = config
+            .locale
+            .as_deref()
+            .map(|locale| {
+                MessageBundle::load(locale, config.base_dir.as_deref().unwrap_or(Path::new(".")))
+            })
+            .transpose()?;
+
+        let plugin_policy = PluginPolicy::new(&config.allowed_plugins, &config.denied_plugins)?;
 
         // Initialize plugin host
         let mut host = PluginHost::new();
 
-        // Helper to load a rule/plugin by name/path
+        // Helper to load a rule/plugin by name/path; only refused plugins are errors
         let load_plugin = |name: &str, host: &mut PluginHost| -> Result<(), LinterError> {
             match PluginResolver::resolve(name, config.base_dir.as_deref()) {
                 Some(path) => {
+                    plugin_policy.check(name, &[name, &path.to_string_lossy()])?;
                     info!("Loading plugin '{}' from {}", name, path.display());
                     if let Err(e) = host.load_rule(&path) {
                         warn!("Failed to load plugin '{}': {}", name, e);
                     }
                 }
                 None => {
+                    plugin_policy.check(name, &[name])?;
                     warn!(
                         "Plugin '{}' not found. Checked .texide/plugins/ and global directories.",
                         name
@@ -77,7 +201,7 @@ impl Linter {
 
         // Load legacy plugins list
         for plugin_name in &config.plugins {
-            let _ = load_plugin(plugin_name, &mut host);
+            load_plugin(plugin_name, &mut host)?;
         }
 
         // Load rules from new rules array
@@ -85,7 +209,7 @@ impl Linter {
             use crate::config::RuleDefinition;
             match rule_def {
                 RuleDefinition::Simple(name) => {
-                    let _ = load_plugin(name, &mut host);
+                    load_plugin(name, &mut host)?;
                 }
                 RuleDefinition::Detail(detail) => {
                     // Prioritize path, then github/url (not fully implemented yet)
@@ -97,28 +221,63 @@ impl Linter {
                             PathBuf::from(path)
                         };
                         let path_str = path_buf.to_string_lossy();
-                        let _ = load_plugin(&path_str, &mut host);
+                        load_plugin(&path_str, &mut host)?;
                     } else if let Some(github) = &detail.github {
+                        plugin_policy
+                            .check(github, &[github, detail.r#as.as_deref().unwrap_or(github)])?;
                         // Placeholder for github fetching
                         warn!("GitHub rule fetching not yet implemented: {}", github);
                     } else if let Some(url) = &detail.url {
-                        warn!("URL rule fetching not yet implemented: {}", url);
+                        let name = detail.r#as.as_deref().ok_or_else(|| {
+                            LinterError::config(format!(
+                                "Rule from {} needs an `as` name to be installed under",
+                                url
+                            ))
+                        })?;
+                        plugin_policy.check(name, &[name, url])?;
+                        match PluginResolver::resolve_installed(name) {
+                            Some(path) => {
+                                info!("Loading rule '{}' from {}", name, path.display());
+                                if let Err(e) = host.load_rule(&path) {
+                                    warn!("Failed to load rule '{}': {}", name, e);
+                                }
+                            }
+                            None => warn!(
+                                "Rule '{}' from {} is not installed; run `texide rules install`",
+                                name, url
+                            ),
+                        }
                     }
                 }
             }
         }
 
+        Self::configure_rules(&config, &mut host);
+        host.set_profiling(config.profile_rules);
+        let native_profile = config
+            .profile_rules
+            .then(|| Mutex::new(RuleProfiler::new()));
+
         Ok(Self {
             config,
             plugin_host: Mutex::new(host),
             cache: Mutex::new(cache),
-            include_globs,
-            exclude_globs,
+            path_filter,
+            excluded_cache_dir,
+            rule_scopes,
+            parsers,
+            arenas: ArenaPool::new(),
+            native_rules: builtin_rules(),
+            project_rules: builtin_project_rules(),
+            native_profile,
+            ignore_list,
+            messages,
+            plugin_policy,
         })
     }
 
     /// Builds a GlobSet from patterns.
-    fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>, LinterError> {
+    pub(crate) fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>, LinterError> {
         if patterns.is_empty() {
             return Ok(None);
         }
@@ -137,11 +296,105 @@ impl Linter {
         Ok(Some(globset))
     }
 
+    /// Passes the configured options of each rule to the plugin host,
+    /// including rules that are off, which directives may turn on.
+    fn configure_rules(config: &LinterConfig, host: &mut PluginHost) {
+        for (name, option) in &config.options {
+            if host.get_manifest(name).is_some()
+                && let Err(e) = host.configure_rule(name, option.options())
+            {
+                warn!("Failed to configure rule '{}': {}", name, e);
+            }
+        }
+    }
+
+    /// Returns a builder for configuring a linter step by step.
+    pub fn builder() -> LinterBuilder {
+        LinterBuilder::new()
+    }
+
     /// Loads a WASM rule.
-    pub fn load_rule(&self, path: impl AsRef<Path>) -> Result<(), LinterError> {
+    ///
+    /// # Returns
+    ///
+    /// The name of the loaded rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if `allowed_plugins` or
+    /// `denied_plugins` refuse the path.
+    pub fn load_rule(&self, path: impl AsRef<Path>) -> Result<String, LinterError> {
+        let path = path.as_ref();
+        let path_str = path.to_string_lossy();
+        self.plugin_policy.check(&path_str, &[&path_str])?;
+
         let mut host = self.plugin_host.lock().unwrap();
-        host.load_rule(path)?;
-        Ok(())
+        let manifest = host.load_rule(path)?;
+        Self::configure_rules(&self.config, &mut host);
+        Ok(manifest.name)
+    }
+
+    /// Enables a rule unless options are already configured for it.
+    pub(crate) fn enable_rule(&mut self, name: String) {
+        self.config
+            .options
+            .entry(name)
+            .or_insert(RuleOption::Enabled(true));
+    }
+
+    /// Returns the manifests of the loaded WASM rules, sorted by name.
+    pub fn rule_manifests(&self) -> Vec<RuleManifest> {
+        let host = self.plugin_host.lock().unwrap();
+        let mut manifests: Vec<RuleManifest> = host
+            .loaded_rules()
+            .into_iter()
+            .filter_map(|name| host.get_manifest(name).cloned())
+            .collect();
+        manifests.sort_by(|a, b| a.name.cmp(&b.name));
+        manifests
+    }
+
+    /// Returns the time spent in each rule so far, across native and WASM
+    /// rules.
+    ///
+    /// Returns `None` unless `profile_rules` is enabled in the configuration.
+    pub fn rule_profile(&self) -> Option<RuleProfiler> {
+        let native_profile = self.native_profile.as_ref()?;
+        let mut profile = native_profile.lock().unwrap().clone();
+        if let Some(host_profile) = self.plugin_host.lock().unwrap().profiler() {
+            profile.merge(host_profile);
+        }
+        Some(profile)
+    }
+
+    /// Reloads every loaded rule whose WASM file is `path`.
+    ///
+    /// Used by watchers when a rule under development is rebuilt. Rules that
+    /// fail to reload keep their previous version.
+    ///
+    /// # Returns
+    ///
+    /// The names of the reloaded rules.
+    pub fn reload_rules_from(&self, path: impl AsRef<Path>) -> Result<Vec<String>, LinterError> {
+        let path = path.as_ref();
+        let mut host = self.plugin_host.lock().unwrap();
+
+        let names: Vec<String> = host
+            .loaded_rules()
+            .into_iter()
+            .filter(|name| {
+                host.rule_source(name)
+                    .is_some_and(|source| same_file(source, path))
+            })
+            .map(|name| name.to_string())
+            .collect();
+
+        for name in &names {
+            host.reload_rule(name)?;
+            info!("Reloaded rule '{}' from {}", name, path.display());
+        }
+
+        Ok(names)
     }
 
     /// Lints files matching the given patterns.
@@ -150,29 +403,162 @@ impl Linter {
         self.lint_files(&files)
     }
 
+    /// Lints the files matching the given patterns that are in `changed`,
+    /// e.g. the files [`changed_files`](crate::changed_files) lists.
+    ///
+    /// Only the changed files are checked against the roots, patterns and
+    /// include/exclude globs, as [`Linter::discover_files`] would see them,
+    /// so the tree is not walked. `changed` may hold absolute paths; the
+    /// results use the paths discovery would give. Project rules such as
+    /// `orphaned-document` are not run, since judging one file needs every
+    /// file of the project.
+    pub fn lint_changed(
+        &self,
+        patterns: &[String],
+        changed: &[PathBuf],
+    ) -> Result<Vec<LintResult>, LinterError> {
+        let matchers = Self::pattern_matchers(patterns)?;
+        let roots: Vec<(PathBuf, PathBuf)> = self
+            .roots()
+            .into_iter()
+            .filter_map(|root| Some((root.canonicalize().ok()?, root)))
+            .collect();
+        let cache_dir = self
+            .excluded_cache_dir
+            .as_deref()
+            .and_then(|dir| dir.canonicalize().ok());
+
+        let mut files = Vec::new();
+        for path in changed {
+            let is_link = path
+                .symlink_metadata()
+                .is_ok_and(|metadata| metadata.file_type().is_symlink());
+            let Ok(canonical) = path.canonicalize() else {
+                continue;
+            };
+            if (is_link && !self.config.follow_symlinks)
+                || !canonical.is_file()
+                || cache_dir
+                    .as_ref()
+                    .is_some_and(|dir| canonical.starts_with(dir))
+            {
+                continue;
+            }
+
+            // The path under the first root containing it, as discovered
+            let Some(path) = roots.iter().find_map(|(canonical_root, root)| {
+                Some(root.join(canonical.strip_prefix(canonical_root).ok()?))
+            }) else {
+                continue;
+            };
+            if matchers.iter().any(|matcher| matcher.is_match(&path))
+                && self.path_filter.is_match(&path)
+            {
+                files.push(path);
+            }
+        }
+        files.sort();
+        files.dedup();
+
+        info!("Linting {} changed files", files.len());
+        let results = Mutex::new(Vec::with_capacity(files.len()));
+        self.lint_files_run(&files, false, |result| results.lock().unwrap().push(result))?;
+        Ok(results.into_inner().unwrap())
+    }
+
     /// Discovers files matching the given patterns.
-    fn discover_files(&self, patterns: &[String]) -> Result<Vec<PathBuf>, LinterError> {
+    ///
+    /// Patterns are matched against paths under each of the configured
+    /// `roots` (the current directory by default) and filtered by the
+    /// configured include/exclude globs (see [`LinterConfig::include`] for
+    /// how `!` negations are ordered). Paths start with their root, so they
+    /// stay relative to the current directory.
+    ///
+    /// A cache directory the include globs would reach is skipped, so the
+    /// cache never lints its own files.
+    ///
+    /// Symbolic links are skipped unless `follow_symlinks` is set. When
+    /// they are followed, links back to an ancestor directory are reported
+    /// and skipped. A file reachable through several links or several
+    /// roots is only returned once.
+    pub fn discover_files(&self, patterns: &[String]) -> Result<Vec<PathBuf>, LinterError> {
+        self.discover_files_in(&self.roots(), patterns)
+    }
+
+    /// Returns the configured `roots`, or the current directory.
+    fn roots(&self) -> Vec<PathBuf> {
+        if self.config.roots.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            self.config.roots.iter().map(PathBuf::from).collect()
+        }
+    }
+
+    /// Compiles the file patterns given to discovery.
+    fn pattern_matchers(patterns: &[String]) -> Result<Vec<GlobMatcher>, LinterError> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                Glob::new(pattern)
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|e| {
+                        LinterError::config(format!("Invalid pattern '{}': {}", pattern, e))
+                    })
+            })
+            .collect()
+    }
+
+    /// Discovers files matching the given patterns under `roots`.
+    fn discover_files_in(
+        &self,
+        roots: &[PathBuf],
+        patterns: &[String],
+    ) -> Result<Vec<PathBuf>, LinterError> {
+        let follow_symlinks = self.config.follow_symlinks;
+        // Roots may overlap, and links may point anywhere
+        let dedup_canonical = follow_symlinks || roots.len() > 1;
         let mut files = Vec::new();
+        // Canonical paths of the files found so far, when deduplicating them
+        let mut seen = HashSet::new();
 
-        for pattern in patterns {
-            let glob = Glob::new(pattern).map_err(|e| {
-                LinterError::config(format!("Invalid pattern '{}': {}", pattern, e))
-            })?;
-            let matcher = glob.compile_matcher();
+        let matchers = Self::pattern_matchers(patterns)?;
 
-            for entry in WalkDir::new(".").into_iter().filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if path.is_file() && matcher.is_match(path) {
-                    // Check exclude patterns
-                    if let Some(ref excludes) = self.exclude_globs
-                        && excludes.is_match(path)
-                    {
+        for root in roots {
+            if !root.exists() {
+                warn!("Skipping missing root directory {}", root.display());
+                continue;
+            }
+
+            // Sorted so the same path wins when a file is reachable twice
+            let walker = WalkDir::new(root)
+                .follow_links(follow_symlinks)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_entry(|entry| !self.is_excluded_cache_dir(entry));
+            for entry in walker {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        if let Some(ancestor) = e.loop_ancestor() {
+                            warn!(
+                                "Skipping symlink loop at {} (points to {})",
+                                e.path().unwrap_or(Path::new("")).display(),
+                                ancestor.display()
+                            );
+                        }
                         continue;
                     }
+                };
 
-                    // Check include patterns (if specified)
-                    if let Some(ref includes) = self.include_globs
-                        && !includes.is_match(path)
+                // Without following, links have their own (non-file) type
+                let path = entry.path();
+                if entry.file_type().is_file()
+                    && matchers.iter().any(|matcher| matcher.is_match(path))
+                    && self.path_filter.is_match(path)
+                {
+                    if dedup_canonical
+                        && let Ok(canonical) = path.canonicalize()
+                        && !seen.insert(canonical)
                     {
                         continue;
                     }
@@ -189,81 +575,409 @@ impl Linter {
         Ok(files)
     }
 
-    /// Lints a list of files.
+    /// Returns whether `entry` is the cache directory that discovery skips.
+    fn is_excluded_cache_dir(&self, entry: &walkdir::DirEntry) -> bool {
+        self.excluded_cache_dir.as_deref().is_some_and(|dir| {
+            entry.file_type().is_dir()
+                && std::path::absolute(entry.path()).is_ok_and(|path| path == dir)
+        })
+    }
+
+    /// Lints a list of files, collecting the results of
+    /// [`Linter::lint_files_with`].
     ///
     /// Note: Currently processes files sequentially. For parallel processing,
     /// parsers need to implement Send + Sync, which requires changes to
     /// the markdown-rs crate's ParseOptions.
     pub fn lint_files(&self, paths: &[PathBuf]) -> Result<Vec<LintResult>, LinterError> {
-        let mut results = Vec::with_capacity(paths.len());
+        let results = Mutex::new(Vec::with_capacity(paths.len()));
+        self.lint_files_with(paths, |result| results.lock().unwrap().push(result))?;
+        Ok(results.into_inner().unwrap())
+    }
+
+    /// Lints a list of files, passing each result to `on_result` as soon as
+    /// the file is done.
+    ///
+    /// Lets callers show results while a long run is still going. Results
+    /// currently arrive in the order of `paths`; since files may be linted
+    /// in parallel in the future, `on_result` must be callable from several
+    /// threads at once. Files that fail to lint are logged and skipped.
+    ///
+    /// The cache is also saved every `cache_flush_files` files or
+    /// `cache_flush_secs` seconds if new results were cached since the
+    /// last save, so an aborted run keeps its progress.
+    ///
+    /// When a project rule such as `orphaned-document` is enabled, its
+    /// diagnostics are only known once every file is linted, so results are
+    /// held back and passed to `on_result` at the end.
+    pub fn lint_files_with<F>(&self, paths: &[PathBuf], on_result: F) -> Result<(), LinterError>
+    where
+        F: Fn(LintResult) + Sync,
+    {
+        self.lint_files_run(paths, true, on_result)
+    }
+
+    /// Lints the file at `path`.
+    ///
+    /// Unlike [`Linter::lint_files`], which logs and skips files it fails
+    /// to lint, this returns the error. The enabled project rules run over
+    /// the file alone, and the cache is saved afterwards.
+    pub fn lint_path(&self, path: impl AsRef<Path>) -> Result<LintResult, LinterError> {
+        let file = self.lint_file(path.as_ref())?;
+        let result = if self.enabled_project_rules().next().is_some() {
+            self.finalize_project_rules(vec![file])
+                .pop()
+                .expect("one result per linted file")
+        } else {
+            file.result
+        };
+
+        if let Err(e) = self.save_cache() {
+            warn!("Failed to save cache: {}", e);
+        }
+
+        Ok(result)
+    }
+
+    /// Lints `paths` like [`Linter::lint_files_with`], running the enabled
+    /// project rules over them if `project_rules` is set.
+    fn lint_files_run<F>(
+        &self,
+        paths: &[PathBuf],
+        project_rules: bool,
+        on_result: F,
+    ) -> Result<(), LinterError>
+    where
+        F: Fn(LintResult) + Sync,
+    {
+        let flush_files = self.config.cache_flush_files;
+        let flush_interval = Duration::from_secs(self.config.cache_flush_secs);
+        let mut files_since_flush = 0;
+        let mut last_flush = Instant::now();
+
+        let has_project_rules = project_rules && self.enabled_project_rules().next().is_some();
+        let mut held_back = Vec::new();
 
         for path in paths {
-            match self.lint_file(path) {
-                Ok(result) => results.push(result),
+            let result = self.lint_file(path);
+
+            files_since_flush += 1;
+            let flush_due = (flush_files > 0 && files_since_flush >= flush_files)
+                || (!flush_interval.is_zero() && last_flush.elapsed() >= flush_interval);
+            if flush_due {
+                if let Err(e) = self.save_cache() {
+                    warn!("Failed to save cache: {}", e);
+                }
+                files_since_flush = 0;
+                last_flush = Instant::now();
+            }
+
+            match result {
+                Ok(file) if has_project_rules => held_back.push(file),
+                Ok(file) => on_result(file.result),
                 Err(e) => {
                     warn!("Failed to lint {}: {}", path.display(), e);
                 }
             }
         }
 
+        if has_project_rules {
+            for result in self.finalize_project_rules(held_back) {
+                on_result(result);
+            }
+        }
+
         // Save cache
-        if let Err(e) = self.cache.lock().unwrap().save() {
+        if let Err(e) = self.save_cache() {
             warn!("Failed to save cache: {}", e);
         }
 
+        Ok(())
+    }
+
+    /// Lints in-memory `content` as if it were the file `name`.
+    ///
+    /// For embedders, such as a CMS, that keep documents in memory under a
+    /// logical name. Nothing is read from disk:
+    /// the parser is chosen from the extension of `name`, rules scoped with
+    /// `files` match against it, and the result's path is `name`. Results
+    /// are cached under `name` like those of a file unless the cache is
+    /// disabled; call [`Linter::save_cache`] to persist them. Project rules,
+    /// which check linted files together, are not run.
+    pub fn lint_virtual(&self, name: &str, content: &str) -> Result<LintResult, LinterError> {
+        let path = Path::new(name);
+        debug!("Linting virtual file {}", name);
+
+        let start = Instant::now();
+        let mut result = match self.check_file_size(path, content.len() as u64) {
+            Some(diagnostic) => {
+                let mut result = LintResult::new(path.to_path_buf(), vec![diagnostic]);
+                result.bytes = Some(content.len());
+                result
+            }
+            None => {
+                self.lint_source(path, content.to_string(), content.len())?
+                    .result
+            }
+        };
+        result.duration = Some(start.elapsed());
+        Ok(result)
+    }
+
+    /// Writes the results cached so far to the cache store.
+    ///
+    /// [`Linter::lint_files`] saves the cache when it finishes; this saves
+    /// the progress of a run that is interrupted. It is safe to call from
+    /// another thread while files are being linted: the cache is saved
+    /// between two files, never halfway through one.
+    pub fn save_cache(&self) -> Result<(), LinterError> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .save()?;
+        Ok(())
+    }
+
+    /// Lints the documents inside a `.zip`, `.tar` or `.tar.gz` archive
+    /// without extracting it.
+    ///
+    /// Entries are filtered by the configured include/exclude globs, and
+    /// entries no parser handles (images, binaries, ...) are skipped. Each
+    /// result's path has the form `archive.tgz!path/inside`.
+    pub fn lint_archive(&self, path: impl AsRef<Path>) -> Result<Vec<LintResult>, LinterError> {
+        let archive = path.as_ref();
+        let mut results = Vec::new();
+
+        for_each_entry(archive, |entry| {
+            let name = Path::new(&entry.name);
+            if !self.is_lintable_entry(name) {
+                debug!("Skipping archive entry {}", entry.name);
+                return Ok(());
+            }
+
+            let display_path = PathBuf::from(format!("{}!{}", archive.display(), entry.name));
+            if let Some(diagnostic) = self.check_file_size(&display_path, entry.size) {
+                results.push(LintResult::new(display_path, vec![diagnostic]));
+                return Ok(());
+            }
+
+            // Entry headers can understate the size, so never read more than
+            // one byte past the limit
+            let limit = self
+                .config
+                .max_file_size
+                .map_or(u64::MAX, |max| max.saturating_add(1));
+            let mut bytes = Vec::new();
+            entry
+                .reader
+                .take(limit)
+                .read_to_end(&mut bytes)
+                .map_err(|e| {
+                    LinterError::file(
+                        &display_path,
+                        format!("Failed to read {}: {}", display_path.display(), e),
+                    )
+                })?;
+            if let Some(diagnostic) = self.check_file_size(&display_path, bytes.len() as u64) {
+                results.push(LintResult::new(display_path, vec![diagnostic]));
+                return Ok(());
+            }
+            let diagnostics = match self.decode(bytes, &display_path) {
+                Ok(content) => self.lint_content(&content, name)?,
+                Err(diagnostic) => vec![*diagnostic],
+            };
+            results.push(LintResult::new(display_path, diagnostics));
+            Ok(())
+        })?;
+
+        info!(
+            "Linted {} files in archive {}",
+            results.len(),
+            archive.display()
+        );
         Ok(results)
     }
 
-    /// Selects an appropriate parser for the file extension.
-    fn select_parser(&self, extension: &str) -> Box<dyn Parser> {
-        let md_parser = MarkdownParser::new();
-        let txt_parser = PlainTextParser::new();
+    /// Returns whether an archive entry matches the configured globs and
+    /// has a parseable file type.
+    fn is_lintable_entry(&self, name: &Path) -> bool {
+        self.parsers.can_parse(name) && self.path_filter.is_match(name)
+    }
 
-        if md_parser.can_parse(extension) {
-            Box::new(md_parser)
-        } else if txt_parser.can_parse(extension) {
-            Box::new(txt_parser)
-        } else {
-            // Default to plain text
-            Box::new(txt_parser)
+    /// Returns `path` as the `files`, `raw_files` and `parser_options`
+    /// globs see it, relative to `base_dir` (see [`relative_to`]).
+    ///
+    /// The LSP and absolute command-line arguments pass absolute paths,
+    /// which the globs would otherwise never match.
+    ///
+    /// [`relative_to`]: crate::path_filter::relative_to
+    fn scoped_path<'p>(&self, path: &'p Path) -> Cow<'p, Path> {
+        self.parsers.scoped_path(path)
+    }
+
+    /// Returns the parser this linter uses for the file at `path` (see
+    /// [`ParserSelector`]).
+    pub fn select_parser(&self, path: &Path) -> Box<dyn Parser> {
+        self.parsers.select(path)
+    }
+
+    /// Returns a `max-file-size` diagnostic if `size` exceeds the configured limit.
+    fn check_file_size(&self, path: &Path, size: u64) -> Option<Diagnostic> {
+        let max_file_size = self.config.max_file_size?;
+        if size <= max_file_size {
+            return None;
+        }
+        warn!(
+            "Skipping {} ({} bytes exceeds max_file_size of {} bytes)",
+            path.display(),
+            size,
+            max_file_size
+        );
+        Some(
+            Diagnostic::new(
+                "max-file-size",
+                format!(
+                    "File skipped: {} bytes exceeds max_file_size of {} bytes",
+                    size, max_file_size
+                ),
+                Span::new(0, 0),
+            )
+            .with_severity(Severity::Warning),
+        )
+    }
+
+    /// Decodes file content according to the configured encoding.
+    ///
+    /// Returns an `encoding-error` diagnostic for invalid UTF-8 in strict mode.
+    fn decode(&self, bytes: Vec<u8>, path: &Path) -> Result<String, Box<Diagnostic>> {
+        match String::from_utf8(bytes) {
+            Ok(content) => Ok(content),
+            Err(e) => match self.config.encoding {
+                Encoding::Utf8Lossy => Ok(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+                Encoding::Utf8 => {
+                    let offset = e.utf8_error().valid_up_to();
+                    warn!("{} is not valid UTF-8", path.display());
+                    Err(Box::new(Diagnostic::new(
+                        "encoding-error",
+                        format!(
+                            "{} is not valid UTF-8 (invalid byte at offset {}); \
+                             set \"encoding\": \"utf-8-lossy\" to lint it anyway",
+                            path.display(),
+                            offset
+                        ),
+                        Span::new(0, 0),
+                    )))
+                }
+            },
         }
     }
 
-    /// Lints a single file.
-    fn lint_file(&self, path: &Path) -> Result<LintResult, LinterError> {
+    /// Lints a single file, recording its size and how long it took.
+    fn lint_file(&self, path: &Path) -> Result<LintedFile, LinterError> {
+        let start = Instant::now();
+        let mut file = self.lint_file_untimed(path)?;
+        file.result.duration = Some(start.elapsed());
+        Ok(file)
+    }
+
+    /// Lints a single file, also returning the state collected for the
+    /// project rules.
+    fn lint_file_untimed(&self, path: &Path) -> Result<LintedFile, LinterError> {
         debug!("Linting {}", path.display());
 
+        // Skip files above the size limit before reading them into memory
+        if self.config.max_file_size.is_some() {
+            let size = fs::metadata(path)
+                .map_err(|e| {
+                    LinterError::file(path, format!("Failed to stat {}: {}", path.display(), e))
+                })?
+                .len();
+            if let Some(diagnostic) = self.check_file_size(path, size) {
+                let mut result = LintResult::new(path.to_path_buf(), vec![diagnostic]);
+                result.bytes = Some(size as usize);
+                return Ok(LintedFile::skipped(result));
+            }
+        }
+
         // Read file content
-        let content = fs::read_to_string(path)
-            .map_err(|e| LinterError::file(format!("Failed to read {}: {}", path.display(), e)))?;
+        let bytes = fs::read(path).map_err(|e| {
+            LinterError::file(path, format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let size = bytes.len();
+        match self.decode(bytes, path) {
+            Ok(content) => self.lint_source(path, content, size),
+            Err(diagnostic) => {
+                let mut result = LintResult::new(path.to_path_buf(), vec![*diagnostic]);
+                result.bytes = Some(size);
+                Ok(LintedFile::skipped(result))
+            }
+        }
+    }
+
+    /// Lints the decoded `content` of the file at `path`, which was `size`
+    /// bytes before decoding.
+    fn lint_source(
+        &self,
+        path: &Path,
+        content: String,
+        size: usize,
+    ) -> Result<LintedFile, LinterError> {
+        // Generated files can opt out with a marker comment
+        if has_ignore_file_marker(&content) {
+            debug!("Skipping {}: marked texide-ignore-file", path.display());
+            let mut result = LintResult::new(path.to_path_buf(), Vec::new());
+            result.bytes = Some(size);
+            return Ok(LintedFile::skipped(result));
+        }
+
+        // Lint normalized text and map spans back to the original at the end
+        let (content, offset_maps) = self.normalize(content);
+        let directives = Directives::parse(&content);
 
         let content_hash = CacheManager::hash_content(&content);
-        let config_hash = self.config.hash();
+        let mut config_hash = self.config.hash();
+        // Blocks linted without the rules directives turn on cannot be reused
+        let directive_rules = directives.enabled_rules();
+        if !directive_rules.is_empty() {
+            let rules: Vec<_> = directive_rules.into_iter().collect();
+            config_hash =
+                CacheManager::hash_content(&format!("{}:{}", config_hash, rules.join(",")));
+        }
         let rule_versions = self.get_rule_versions();
 
         // 1. Check full cache first
-        {
+        let cached = {
             let cache = self.cache.lock().unwrap();
-            if cache.is_valid(path, &content_hash, &config_hash, &rule_versions)
-                && let Some(entry) = cache.get(path)
-            {
-                debug!("Using cached result for {}", path.display());
-                return Ok(LintResult::cached(
-                    path.to_path_buf(),
-                    entry.diagnostics.clone(),
-                ));
-            }
+            cache
+                .get(path)
+                .filter(|_| cache.is_valid(path, &content_hash, &config_hash, &rule_versions))
+                .map(|entry| (entry.diagnostics.clone(), entry.project_state.clone()))
+        };
+        if let Some((diagnostics, state)) = cached {
+            debug!("Using cached result for {}", path.display());
+            let source = LintedSource {
+                content,
+                directives,
+                offset_maps,
+            };
+            let mut result =
+                LintResult::cached(path.to_path_buf(), self.postprocess(diagnostics, &source));
+            result.bytes = Some(size);
+            return Ok(LintedFile {
+                result,
+                state,
+                source,
+            });
         }
 
         // Find appropriate parser
-        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        let parser = self.select_parser(extension);
+        let parser = self.select_parser(path);
 
         // Parse the file
-        let arena = AstArena::new();
+        let arena = self.arenas.get();
         let ast = parser
             .parse(&arena, &content)
-            .map_err(|e| LinterError::parse(e.to_string()))?;
+            .map_err(|e| LinterError::parse(path, e.to_string()))?;
 
         // Extract blocks for incremental analysis
         let current_blocks = self.extract_blocks(&ast, &content);
@@ -281,6 +995,16 @@ impl Linter {
         let mut block_diagnostics = Vec::new();
         let mut timings = HashMap::new();
 
+        // Native rules always see the full document, like global rules
+        global_diagnostics.extend(self.run_native_rules(
+            &ast,
+            &content,
+            path,
+            &directives,
+            &mut timings,
+        ));
+        let project_state = self.collect_project_state(&ast, &content, path);
+
         // Run rules
         {
             let mut host = self.plugin_host.lock().unwrap();
@@ -288,12 +1012,15 @@ impl Linter {
             // A. Run Global Rules
             // Global rules must always run on the full document if anything changed
             // because they depend on the full context.
-            let global_rule_names = self.get_rule_names_by_isolation(&host, IsolationLevel::Global);
+            let global_rule_names =
+                self.get_rule_names_by_isolation(&host, path, &directives, IsolationLevel::Global);
             if !global_rule_names.is_empty() {
+                // Serialized once and shared by every rule
                 let ast_json = self.ast_to_json(&ast, &content);
+                let input = LintInput::new(&ast_json, &content, path.to_str())?;
                 for rule in global_rule_names {
                     let start = Instant::now();
-                    match host.run_rule(&rule, &ast_json, &content, path.to_str()) {
+                    match host.run_rule_on(&rule, &input) {
                         Ok(diags) => global_diagnostics.extend(diags),
                         Err(e) => warn!("Rule '{}' failed: {}", rule, e),
                     }
@@ -304,8 +1031,11 @@ impl Linter {
             }
 
             // B. Run Block Rules on CHANGED/NEW blocks
-            let block_rule_names = self.get_rule_names_by_isolation(&host, IsolationLevel::Block);
+            let block_rule_names =
+                self.get_rule_names_by_isolation(&host, path, &directives, IsolationLevel::Block);
             if !block_rule_names.is_empty() {
+                // The source is serialized once; each changed block adds its node
+                let file_input = LintInput::new(&serde_json::Value::Null, &content, path.to_str())?;
                 // Collect AST nodes for changed blocks
                 // We map `matched_mask` back to actual AST nodes by traversing.
                 let mut block_index = 0;
@@ -314,9 +1044,17 @@ impl Linter {
                         if !matched_mask[block_index] {
                             // This block changed. Run block rules on it.
                             let node_json = self.ast_to_json(node, &content);
+                            let input = match file_input.with_node(&node_json) {
+                                Ok(input) => input,
+                                Err(e) => {
+                                    warn!("Failed to serialize block: {}", e);
+                                    block_index += 1;
+                                    return;
+                                }
+                            };
                             for rule in &block_rule_names {
                                 let start = Instant::now();
-                                match host.run_rule(rule, &node_json, &content, path.to_str()) {
+                                match host.run_rule_on(rule, &input) {
                                     Ok(diags) => block_diagnostics.extend(diags),
                                     Err(e) => warn!("Rule '{}' failed: {}", rule, e),
                                 }
@@ -400,19 +1138,33 @@ impl Linter {
 
         {
             let mut cache = self.cache.lock().unwrap();
-            let entry = CacheEntry::new(
+            let mut entry = CacheEntry::new(
                 content_hash,
                 config_hash,
                 rule_versions,
                 final_diagnostics.clone(),
                 new_blocks,
             );
+            entry.project_state = project_state.clone();
             cache.set(path.to_path_buf(), entry);
         }
 
-        let mut result = LintResult::new(path.to_path_buf(), final_diagnostics);
+        let source = LintedSource {
+            content,
+            directives,
+            offset_maps,
+        };
+        let mut result = LintResult::new(
+            path.to_path_buf(),
+            self.postprocess(final_diagnostics, &source),
+        );
         result.timings = timings;
-        Ok(result)
+        result.bytes = Some(size);
+        Ok(LintedFile {
+            result,
+            state: project_state,
+            source,
+        })
     }
 
     /// Extracts blocks from AST for caching.
@@ -466,103 +1218,466 @@ impl Linter {
         }
     }
 
-    /// Gets rule names filtered by isolation level.
-    fn get_rule_names_by_isolation(&self, host: &PluginHost, level: IsolationLevel) -> Vec<String> {
-        let mut names = Vec::new();
-        // Only run rules that are enabled in options
-        let enabled_rules = self.config.enabled_rules();
-        let enabled_names: HashSet<&str> = enabled_rules.iter().map(|(n, _)| *n).collect();
-
-        for name in host.loaded_rules() {
-            if enabled_names.contains(name)
-                && let Some(manifest) = host.get_manifest(name)
-                && manifest.isolation_level == level
-            {
-                names.push(name.to_string());
-            }
-        }
-        names
-    }
-
-    /// Lints content directly (for LSP or modify-on-save scenarios).
-    pub fn lint_content(
+    /// Runs the enabled native rules on a document.
+    fn run_native_rules(
         &self,
+        ast: &TxtNode,
         content: &str,
         path: &Path,
-    ) -> Result<Vec<texide_plugin::Diagnostic>, LinterError> {
-        // Find appropriate parser
-        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-        let parser = self.select_parser(extension);
+        directives: &Directives,
+        timings: &mut HashMap<String, Duration>,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for rule in &self.native_rules {
+            let enabled = self.is_rule_enabled(rule.name()) || directives.enables(rule.name());
+            if !enabled || !self.rule_applies(rule.name(), path) {
+                continue;
+            }
 
-        // Parse the file
-        let arena = AstArena::new();
-        let ast = parser
-            .parse(&arena, content)
-            .map_err(|e| LinterError::parse(e.to_string()))?;
+            let options = self
+                .config
+                .options
+                .get(rule.name())
+                .map_or(serde_json::Value::Null, RuleOption::options);
+            let context = RuleContext {
+                source: content,
+                path: Some(path),
+                base_dir: self.config.base_dir.as_deref(),
+                options: &options,
+            };
+
+            let start = Instant::now();
+            diagnostics.extend(rule.lint(ast, &context));
+            let elapsed = start.elapsed();
+            if self.config.timings {
+                timings.insert(rule.name().to_string(), elapsed);
+            }
+            if let Some(profile) = &self.native_profile {
+                profile.lock().unwrap().record(rule.name(), elapsed);
+            }
+        }
 
-        // Convert AST to JSON for plugin system
-        let ast_json = self.ast_to_json(&ast, content);
+        diagnostics
+    }
 
-        // Run rules
-        let diagnostics = {
-            let mut host = self
-                .plugin_host
-                .lock()
-                .map_err(|_| LinterError::Internal("Plugin host lock poisoned".to_string()))?;
-            host.run_all_rules(&ast_json, content, path.to_str())?
-        };
+    /// Returns whether the rule `name` is enabled in options.
+    fn is_rule_enabled(&self, name: &str) -> bool {
+        self.config
+            .options
+            .get(name)
+            .is_some_and(RuleOption::is_enabled)
+    }
 
-        Ok(diagnostics)
+    /// Returns the project rules enabled in options.
+    fn enabled_project_rules(&self) -> impl Iterator<Item = &dyn ProjectRule> {
+        self.project_rules
+            .iter()
+            .filter(|rule| self.is_rule_enabled(rule.name()))
+            .map(|rule| rule.as_ref())
     }
 
-    /// Gets the versions of all loaded rules.
-    fn get_rule_versions(&self) -> HashMap<String, String> {
-        let host = self.plugin_host.lock().unwrap();
-        let mut versions = HashMap::new();
+    /// Runs the collect phase of the enabled project rules on a document.
+    fn collect_project_state(&self, ast: &TxtNode, content: &str, path: &Path) -> ProjectState {
+        let mut state = ProjectState::new();
 
-        for name in host.loaded_rules() {
-            if let Some(manifest) = host.get_manifest(name) {
-                versions.insert(name.to_string(), manifest.version.clone());
+        for rule in self.enabled_project_rules() {
+            if !self.rule_applies(rule.name(), path) {
+                continue;
             }
+            let options = self.config.options[rule.name()].options();
+            let context = RuleContext {
+                source: content,
+                path: Some(path),
+                base_dir: self.config.base_dir.as_deref(),
+                options: &options,
+            };
+            state.insert(rule.name().to_string(), rule.collect(ast, &context));
         }
 
-        versions
+        state
     }
 
-    /// Converts a TxtNode to JSON for the plugin system.
-    fn ast_to_json(&self, node: &texide_ast::TxtNode, _source: &str) -> serde_json::Value {
-        // Simplified JSON representation
-        serde_json::json!({
-            "type": format!("{}", node.node_type),
-            "range": [node.span.start, node.span.end],
-            "children": node.children.iter()
-                .map(|c| self.ast_to_json(c, _source))
-                .collect::<Vec<_>>(),
-        })
-    }
-}
+    /// Runs the finalize phase of the enabled project rules over the files
+    /// of a run, adding their diagnostics to the files' results.
+    fn finalize_project_rules(&self, files: Vec<LintedFile>) -> Vec<LintResult> {
+        let mut diagnostics = Vec::new();
+
+        for rule in self.enabled_project_rules() {
+            let collected: Vec<CollectedFile> = files
+                .iter()
+                .filter_map(|file| {
+                    Some(CollectedFile {
+                        path: file.result.path.clone(),
+                        state: file.state.get(rule.name())?.clone(),
+                    })
+                })
+                .collect();
+            let options = self.config.options[rule.name()].options();
+            let context = ProjectContext {
+                base_dir: self.config.base_dir.as_deref(),
+                options: &options,
+            };
+            diagnostics.extend(rule.finalize(&collected, &context));
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut by_file: Vec<Vec<Diagnostic>> = files.iter().map(|_| Vec::new()).collect();
+        for (path, diagnostic) in diagnostics {
+            match files.iter().position(|file| file.result.path == path) {
+                Some(index) => by_file[index].push(diagnostic),
+                None => warn!(
+                    "Project rule reported a diagnostic for {}, which was not linted",
+                    path.display()
+                ),
+            }
+        }
 
-    #[test]
-    fn test_linter_new() {
-        let config = LinterConfig::new();
-        let linter = Linter::new(config);
-        assert!(linter.is_ok());
+        files
+            .into_iter()
+            .zip(by_file)
+            .map(|(file, diagnostics)| {
+                let mut result = file.result;
+                if !diagnostics.is_empty() {
+                    result
+                        .diagnostics
+                        .extend(self.postprocess(diagnostics, &file.source));
+                    result.diagnostics = dedup_diagnostics(
+                        std::mem::take(&mut result.diagnostics),
+                        self.config.dedup,
+                    );
+                }
+                result
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_build_globset() {
-        let patterns = vec!["**/*.md".to_string(), "*.txt".to_string()];
-        let result = Linter::build_globset(&patterns);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_some());
+    /// Returns whether the rule `name` runs on `path`, given its `files` and
+    /// `exclude_files` options.
+    fn rule_applies(&self, name: &str, path: &Path) -> bool {
+        self.rule_scopes
+            .get(name)
+            .is_none_or(|scope| scope.is_match(&self.scoped_path(path)))
     }
 
-    #[test]
+    /// Gets the names of the rules for `path` with the given isolation level.
+    fn get_rule_names_by_isolation(
+        &self,
+        host: &PluginHost,
+        path: &Path,
+        directives: &Directives,
+        level: IsolationLevel,
+    ) -> Vec<String> {
+        self.get_enabled_rule_names(host, path, directives)
+            .into_iter()
+            .filter(|name| {
+                host.get_manifest(name)
+                    .is_some_and(|manifest| manifest.isolation_level == level)
+            })
+            .collect()
+    }
+
+    /// Gets the names of loaded rules that are enabled in options or by a
+    /// directive, and run on `path`.
+    fn get_enabled_rule_names(
+        &self,
+        host: &PluginHost,
+        path: &Path,
+        directives: &Directives,
+    ) -> Vec<String> {
+        host.loaded_rules()
+            .into_iter()
+            .filter(|name| {
+                (self.is_rule_enabled(name) || directives.enables(name))
+                    && self.rule_applies(name, path)
+            })
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Applies severities configured in options (e.g. `"no-todo": "warning"`)
+    /// to the diagnostics of those rules, then turns warnings into errors
+    /// if `strict` is `promote`.
+    fn resolve_severities(&self, mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        for diagnostic in &mut diagnostics {
+            if let Some(severity) = self
+                .config
+                .options
+                .get(&diagnostic.rule_id)
+                .and_then(|option| option.severity())
+            {
+                diagnostic.severity = severity;
+            }
+            if self.config.strict == StrictMode::Promote && diagnostic.severity == Severity::Warning
+            {
+                diagnostic.severity = Severity::Error;
+            }
+        }
+        diagnostics
+    }
+
+    /// Gives diagnostics without tags the tags of the rule that reported
+    /// them: a native rule's `tags()` or the `tags` of a WASM rule manifest.
+    fn resolve_tags(&self, mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        if diagnostics.iter().all(|d| !d.tags.is_empty()) {
+            return diagnostics;
+        }
+
+        let host = self.plugin_host.lock().unwrap();
+        for diagnostic in &mut diagnostics {
+            if !diagnostic.tags.is_empty() {
+                continue;
+            }
+            let builtin = self
+                .native_rules
+                .iter()
+                .map(|rule| (rule.name(), rule.tags()))
+                .chain(
+                    self.project_rules
+                        .iter()
+                        .map(|rule| (rule.name(), rule.tags())),
+                )
+                .find(|(name, _)| *name == diagnostic.rule_id);
+            if let Some((_, tags)) = builtin {
+                diagnostic.tags = tags.iter().map(|tag| tag.to_string()).collect();
+            } else if let Some(manifest) = host.get_manifest(&diagnostic.rule_id) {
+                diagnostic.tags = manifest.tags.clone();
+            }
+        }
+        diagnostics
+    }
+
+    /// Gives diagnostics without a help URL the `help_url` option of their
+    /// rule or else the `help_url` of its WASM rule manifest, with
+    /// `{rule_id}` replaced by the diagnostic's rule ID.
+    fn resolve_help_urls(&self, mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        if diagnostics.iter().all(|d| d.help_url.is_some()) {
+            return diagnostics;
+        }
+
+        let host = self.plugin_host.lock().unwrap();
+        for diagnostic in &mut diagnostics {
+            if diagnostic.help_url.is_some() {
+                continue;
+            }
+            let template = self
+                .config
+                .options
+                .get(&diagnostic.rule_id)
+                .and_then(RuleOption::help_url)
+                .or_else(|| host.get_manifest(&diagnostic.rule_id)?.help_url.as_deref());
+            diagnostic.help_url =
+                template.map(|template| template.replace("{rule_id}", &diagnostic.rule_id));
+        }
+        diagnostics
+    }
+
+    /// Renders the messages of diagnostics with a message key in the
+    /// configured locale.
+    ///
+    /// Lint results keep the rules' English messages, along with the
+    /// `message_key` and `message_params` to translate them by, so the
+    /// output step calls this right before showing diagnostics.
+    pub fn localize(&self, diagnostics: &mut [Diagnostic]) {
+        if let Some(messages) = &self.messages {
+            messages.localize(diagnostics);
+        }
+    }
+
+    /// Normalizes the line endings and Unicode form of `content` as
+    /// configured, returning the text to lint and the maps from it back to
+    /// `content`, in the order they were applied.
+    fn normalize(&self, mut content: String) -> (String, Vec<OffsetMap>) {
+        let mut offset_maps = Vec::new();
+        if self.config.normalize_line_endings
+            && let Some((normalized, map)) = normalize_line_endings(&content)
+        {
+            content = normalized;
+            offset_maps.push(map);
+        }
+        if let Some((normalized, map)) = normalize_unicode(&content, self.config.unicode_normalize)
+        {
+            content = normalized;
+            offset_maps.push(map);
+        }
+        (content, offset_maps)
+    }
+
+    /// Lints content directly (for LSP or modify-on-save scenarios).
+    pub fn lint_content(
+        &self,
+        content: &str,
+        path: &Path,
+    ) -> Result<Vec<texide_plugin::Diagnostic>, LinterError> {
+        if has_ignore_file_marker(content) {
+            return Ok(Vec::new());
+        }
+
+        // Lint normalized text like a file, mapping spans back at the end
+        let (normalized, offset_maps) = self.normalize(content.to_string());
+        let content = normalized.as_str();
+
+        // Find appropriate parser
+        let parser = self.select_parser(path);
+
+        // Parse the file
+        let arena = self.arenas.get();
+        let ast = parser
+            .parse(&arena, content)
+            .map_err(|e| LinterError::parse(path, e.to_string()))?;
+
+        // Convert AST to JSON for plugin system
+        let ast_json = self.ast_to_json(&ast, content);
+
+        // Run rules
+        let directives = Directives::parse(content);
+        let mut diagnostics =
+            self.run_native_rules(&ast, content, path, &directives, &mut HashMap::new());
+        {
+            let mut host = self
+                .plugin_host
+                .lock()
+                .map_err(|_| LinterError::Internal("Plugin host lock poisoned".to_string()))?;
+            // Only run rules that are enabled in options
+            let rule_names = self.get_enabled_rule_names(&host, path, &directives);
+            diagnostics.extend(host.run_rules(&rule_names, &ast_json, content, path.to_str())?);
+        }
+
+        let source = LintedSource {
+            content: normalized,
+            directives,
+            offset_maps,
+        };
+        Ok(self.postprocess(diagnostics, &source))
+    }
+
+    /// Turns the diagnostics rules reported in `source` into the ones the
+    /// file's result reports.
+    ///
+    /// Drops diagnostics of switched-off rules and ignored strings, resolves
+    /// severities, tags and help URLs, removes duplicates and maps spans
+    /// back to the original text.
+    fn postprocess(&self, diagnostics: Vec<Diagnostic>, source: &LintedSource) -> Vec<Diagnostic> {
+        let diagnostics = source
+            .directives
+            .filter(diagnostics, |rule| self.is_rule_enabled(rule));
+        let diagnostics = self.ignore_list.filter(diagnostics, &source.content);
+        let diagnostics = self.resolve_severities(diagnostics);
+        let diagnostics = self.resolve_tags(diagnostics);
+        let diagnostics = self.resolve_help_urls(diagnostics);
+        dedup_diagnostics(diagnostics, self.config.dedup)
+            .into_iter()
+            .map(|d| {
+                source
+                    .offset_maps
+                    .iter()
+                    .rev()
+                    .fold(d, |d, map| map.diagnostic_to_original(d))
+            })
+            .collect()
+    }
+
+    /// Gets the versions of all loaded rules.
+    fn get_rule_versions(&self) -> HashMap<String, String> {
+        let host = self.plugin_host.lock().unwrap();
+        let mut versions = HashMap::new();
+
+        for name in host.loaded_rules() {
+            if let Some(manifest) = host.get_manifest(name) {
+                // Rebuilt and reloaded rules may keep their version, so
+                // include the revision and the hash of the WASM bytes
+                let mut version = match host.rule_revision(name) {
+                    0 => manifest.version.clone(),
+                    revision => format!("{}+reload.{}", manifest.version, revision),
+                };
+                if let Some(hash) = host.rule_hash(name) {
+                    version = format!("{}+wasm.{}", version, hash);
+                }
+                versions.insert(name.to_string(), version);
+            }
+        }
+
+        versions
+    }
+
+    /// Converts a TxtNode to JSON for the plugin system.
+    fn ast_to_json(&self, node: &texide_ast::TxtNode, _source: &str) -> serde_json::Value {
+        // Simplified JSON representation
+        let mut json = serde_json::json!({
+            "type": node.type_name(),
+            "range": [node.span.start, node.span.end],
+            "children": node.children.iter()
+                .map(|c| self.ast_to_json(c, _source))
+                .collect::<Vec<_>>(),
+        });
+        if let Some(alt) = node.data.alt {
+            json["alt"] = serde_json::json!(alt);
+        }
+        if let Some(align) = node.data.align {
+            json["align"] = serde_json::json!(align);
+        }
+        if let Some(start) = node.data.start {
+            json["start"] = serde_json::json!(start);
+        }
+        if let Some(ordinal) = node.data.ordinal {
+            json["ordinal"] = serde_json::json!(ordinal);
+        }
+        if let Some(info) = node.data.info {
+            json["info"] = serde_json::json!(info);
+        }
+        if let Some(fence) = node.data.fence {
+            json["fence"] = serde_json::json!(fence);
+        }
+        json
+    }
+}
+
+/// Returns whether two paths point to the same file.
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linter_new() {
+        let config = LinterConfig::new();
+        let linter = Linter::new(config);
+        assert!(linter.is_ok());
+    }
+
+    #[test]
+    fn test_url_rule_needs_alias() {
+        let mut config = LinterConfig::new();
+        config.rules = vec![crate::RuleDefinition::Detail(crate::RuleDefinitionDetail {
+            github: None,
+            url: Some("https://example.com/texide-rule.json".to_string()),
+            path: None,
+            r#as: None,
+        })];
+        let error = Linter::new(config.clone()).err().unwrap();
+        assert!(error.to_string().contains("needs an `as` name"), "{error}");
+
+        // Not installed yet: a warning, not an error
+        if let crate::RuleDefinition::Detail(detail) = &mut config.rules[0] {
+            detail.r#as = Some("texide-test-not-installed".to_string());
+        }
+        assert!(Linter::new(config).is_ok());
+    }
+
+    #[test]
+    fn test_build_globset() {
+        let patterns = vec!["**/*.md".to_string(), "*.txt".to_string()];
+        let result = Linter::build_globset(&patterns);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_some());
+    }
+
+    #[test]
     fn test_build_globset_empty() {
         let patterns: Vec<String> = vec![];
         let result = Linter::build_globset(&patterns);
@@ -584,7 +1699,72 @@ mod tests {
 
         let linter = Linter::new(config).unwrap();
         // Verify linter was created successfully with cache disabled
-        assert!(linter.include_globs.is_none());
+        assert!(linter.path_filter.is_empty());
+    }
+
+    #[test]
+    fn test_save_cache_keeps_progress_of_interrupted_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cache");
+        let paths: Vec<PathBuf> = ["a.md", "b.md"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, "# Title\n").unwrap();
+                path
+            })
+            .collect();
+
+        let mut config = LinterConfig::new();
+        config.cache_dir = Some(cache_dir.to_string_lossy().into_owned());
+
+        // Interrupted after the first file: lint_files never reaches its save
+        let linter = Linter::new(config.clone()).unwrap();
+        linter.lint_file(&paths[0]).unwrap();
+        linter.save_cache().unwrap();
+        drop(linter);
+
+        let linter = Linter::new(config).unwrap();
+        assert!(linter.lint_file(&paths[0]).unwrap().result.from_cache);
+        assert!(!linter.lint_file(&paths[1]).unwrap().result.from_cache);
+    }
+
+    #[test]
+    fn test_cache_is_flushed_during_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cache");
+        let paths: Vec<PathBuf> = ["a.md", "b.md", "c.md", "d.md"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, "# Title\n").unwrap();
+                path
+            })
+            .collect();
+
+        let mut config = LinterConfig::new();
+        config.cache_dir = Some(cache_dir.to_string_lossy().into_owned());
+        config.cache_flush_files = 2;
+        config.cache_flush_secs = 0;
+        let linter = Linter::new(config).unwrap();
+
+        // Entries on disk when each result arrives
+        let saved = Mutex::new(Vec::new());
+        linter
+            .lint_files_with(&paths, |_| {
+                let content = fs::read_to_string(cache_dir.join("cache.json")).ok();
+                let entries = content.map(|content| {
+                    let cache: serde_json::Value = serde_json::from_str(&content).unwrap();
+                    cache.as_object().unwrap().len()
+                });
+                saved.lock().unwrap().push(entries);
+            })
+            .unwrap();
+
+        assert_eq!(
+            saved.into_inner().unwrap(),
+            vec![None, Some(2), Some(2), Some(4)]
+        );
     }
 
     #[test]
@@ -593,7 +1773,9 @@ mod tests {
         config.include = vec!["**/*.md".to_string()];
 
         let linter = Linter::new(config).unwrap();
-        assert!(linter.include_globs.is_some());
+        assert!(!linter.path_filter.is_empty());
+        assert!(linter.path_filter.is_match(Path::new("docs/guide.md")));
+        assert!(!linter.path_filter.is_match(Path::new("notes.txt")));
     }
 
     #[test]
@@ -602,7 +1784,164 @@ mod tests {
         config.exclude = vec!["**/node_modules/**".to_string()];
 
         let linter = Linter::new(config).unwrap();
-        assert!(linter.exclude_globs.is_some());
+        assert!(!linter.path_filter.is_empty());
+        assert!(
+            !linter
+                .path_filter
+                .is_match(Path::new("node_modules/pkg/README.md"))
+        );
+    }
+
+    #[test]
+    fn test_discover_files_with_negated_include() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["docs/guide.md", "drafts/idea.md", "notes.txt"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "Text\n").unwrap();
+        }
+
+        let mut config = LinterConfig::new();
+        config.include = vec!["**/*.md".to_string(), "!**/drafts/**".to_string()];
+        let linter = Linter::new(config).unwrap();
+
+        let files = linter
+            .discover_files_in(&[dir.path().to_path_buf()], &["**/*".to_string()])
+            .unwrap();
+        assert_eq!(files, vec![dir.path().join("docs/guide.md")]);
+    }
+
+    #[test]
+    fn test_discover_files_skips_cache_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("doc.md"), "Text\n").unwrap();
+        let cache_dir = dir.path().join(".texide-cache");
+
+        let mut config = LinterConfig::new();
+        config.include = vec!["**/*".to_string()];
+        config.cache_dir = Some(cache_dir.to_string_lossy().into_owned());
+        let linter = Linter::new(config.clone()).unwrap();
+        linter.lint_files(&[dir.path().join("doc.md")]).unwrap();
+        assert!(cache_dir.join("cache.json").exists());
+
+        let files = linter
+            .discover_files_in(&[dir.path().to_path_buf()], &["**/*".to_string()])
+            .unwrap();
+        assert_eq!(files, vec![dir.path().join("doc.md")]);
+
+        // An exclude pattern covering it needs no special handling
+        config.exclude = vec!["**/.texide-cache/**".to_string()];
+        let linter = Linter::new(config).unwrap();
+        assert!(linter.excluded_cache_dir.is_none());
+    }
+
+    #[test]
+    fn test_lint_changed_lints_only_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["a.md", "b.md", "c.md", "notes.txt"] {
+            fs::write(dir.path().join(file), "Text\n").unwrap();
+        }
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.roots = vec![dir.path().to_string_lossy().into_owned()];
+        config.options.insert(
+            "orphaned-document".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+        // As `changed_files` would list them, including files outside the patterns
+        let changed = [
+            dir.path().canonicalize().unwrap().join("c.md"),
+            dir.path().join("./a.md"),
+            dir.path().join("notes.txt"),
+            dir.path().join("deleted.md"),
+        ];
+        let results = linter
+            .lint_changed(&["**/*.md".to_string()], &changed)
+            .unwrap();
+
+        let paths: Vec<_> = results.iter().map(|r| r.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![dir.path().join("a.md"), dir.path().join("c.md")]
+        );
+        // Unlinked from each other, but maybe linked from unchanged files
+        assert!(results.iter().all(|r| r.diagnostics.is_empty()));
+    }
+
+    #[test]
+    fn test_discover_files_in_multiple_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "guides/intro.md",
+            "api/docs/endpoints.md",
+            "api/src/main.rs",
+            "notes.md",
+        ] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "Text\n").unwrap();
+        }
+
+        let linter = Linter::new(LinterConfig::new()).unwrap();
+        // Overlapping roots find each file once; missing roots are skipped
+        let roots = [
+            dir.path().join("guides"),
+            dir.path().join("api/docs"),
+            dir.path().join("api"),
+            dir.path().join("missing"),
+        ];
+        let files = linter
+            .discover_files_in(&roots, &["**/*.md".to_string()])
+            .unwrap();
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("api/docs/endpoints.md"),
+                dir.path().join("guides/intro.md"),
+            ]
+        );
+    }
+
+    /// Creates `docs/guide.md`, a link to it, and a link from `docs/loop` back to the root.
+    #[cfg(unix)]
+    fn symlinked_tree() -> tempfile::TempDir {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("docs")).unwrap();
+        fs::write(dir.path().join("docs/guide.md"), "# Guide\n").unwrap();
+        symlink("docs/guide.md", dir.path().join("alias.md")).unwrap();
+        symlink("..", dir.path().join("docs/loop")).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_files_skips_symlinks_by_default() {
+        let dir = symlinked_tree();
+        let linter = Linter::new(LinterConfig::new()).unwrap();
+
+        let files = linter
+            .discover_files_in(&[dir.path().to_path_buf()], &["**/*.md".to_string()])
+            .unwrap();
+        assert_eq!(files, vec![dir.path().join("docs/guide.md")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_files_following_symlinks_terminates_on_loops() {
+        let dir = symlinked_tree();
+        let mut config = LinterConfig::new();
+        config.follow_symlinks = true;
+        let linter = Linter::new(config).unwrap();
+
+        // The guide is reachable as alias.md, docs/guide.md and through the loop
+        let files = linter
+            .discover_files_in(&[dir.path().to_path_buf()], &["**/*.md".to_string()])
+            .unwrap();
+        assert_eq!(files, vec![dir.path().join("alias.md")]);
     }
 
     #[test]
@@ -610,10 +1949,10 @@ mod tests {
         let config = LinterConfig::new();
         let linter = Linter::new(config).unwrap();
 
-        let parser = linter.select_parser("md");
+        let parser = linter.select_parser(Path::new("file.md"));
         assert_eq!(parser.name(), "markdown");
 
-        let parser = linter.select_parser("markdown");
+        let parser = linter.select_parser(Path::new("file.markdown"));
         assert_eq!(parser.name(), "markdown");
     }
 
@@ -622,19 +1961,121 @@ mod tests {
         let config = LinterConfig::new();
         let linter = Linter::new(config).unwrap();
 
-        let parser = linter.select_parser("txt");
+        let parser = linter.select_parser(Path::new("file.txt"));
         assert_eq!(parser.name(), "text");
 
-        let parser = linter.select_parser("text");
+        let parser = linter.select_parser(Path::new("file.text"));
         assert_eq!(parser.name(), "text");
     }
 
+    #[test]
+    fn test_linter_select_parser_uses_paragraph_mode() {
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.paragraph_mode = crate::ParagraphMode::SingleLine;
+        let linter = Linter::new(config).unwrap();
+
+        let arena = texide_ast::AstArena::new();
+        let ast = linter
+            .select_parser(Path::new("file.txt"))
+            .parse(&arena, "One\nTwo\nThree\n")
+            .unwrap();
+        assert_eq!(ast.children.len(), 3);
+    }
+
+    #[test]
+    fn test_linter_select_parser_uses_parser_options() {
+        let mut config = LinterConfig::from_json(
+            r#"{ "parser_options": [{ "files": ["**/*.mdx"], "options": { "mdx": true } }] }"#,
+        )
+        .unwrap();
+        config.cache = false;
+        let linter = Linter::new(config).unwrap();
+
+        let source = "{props.title}\n\n| a |\n|---|\n";
+        let arena = texide_ast::AstArena::new();
+        let node_types = |path: &str| -> Vec<NodeType> {
+            let ast = linter
+                .select_parser(Path::new(path))
+                .parse(&arena, source)
+                .unwrap();
+            ast.children.iter().map(|node| node.node_type).collect()
+        };
+
+        // MDX reads the braces as an expression; GFM tables stay on for both
+        assert_eq!(
+            node_types("docs/page.mdx"),
+            vec![NodeType::Html, NodeType::Table]
+        );
+        assert_eq!(
+            node_types("docs/page.md"),
+            vec![NodeType::Paragraph, NodeType::Table]
+        );
+    }
+
+    #[test]
+    fn test_raw_files_run_source_rules_on_any_file_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        fs::write(&path, "name: texide\nrules:\n  - final-newline").unwrap();
+
+        let mut config = LinterConfig::from_json(r#"{ "raw_files": ["**/*.yaml"] }"#).unwrap();
+        config.cache = false;
+        config.options.insert(
+            "final-newline".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+        assert_eq!(linter.select_parser(&path).name(), "raw");
+        assert_eq!(linter.select_parser(Path::new("notes.txt")).name(), "text");
+
+        let results = linter.lint_files(&[path]).unwrap();
+        let diagnostics = &results[0].diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "final-newline");
+        assert_eq!(diagnostics[0].span, Span::new(37, 37));
+    }
+
+    #[test]
+    fn test_html_text_mode_lints_prose_inside_html() {
+        let source = "<div>\nSee https://example.com\n</div>\n";
+        let diagnostics = |html: &str| {
+            let mut config = LinterConfig::from_json(&format!(
+                r#"{{
+                    "options": {{ "no-bare-urls": true }},
+                    "parser_options": [{{ "files": ["**/*.md"], "options": {{ "html": "{}" }} }}]
+                }}"#,
+                html
+            ))
+            .unwrap();
+            config.cache = false;
+            let linter = Linter::new(config).unwrap();
+            linter.lint_content(source, Path::new("doc.md")).unwrap()
+        };
+
+        assert!(diagnostics("opaque").is_empty());
+        assert!(diagnostics("skip").is_empty());
+        let found = diagnostics("text");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].span, texide_ast::Span::new(10, 29));
+    }
+
+    #[test]
+    fn test_parser_options_need_file_patterns() {
+        let mut config = LinterConfig::new();
+        config.parser_options = vec![crate::ParserOptionsOverride {
+            files: Vec::new(),
+            options: crate::ParserOptions::default(),
+        }];
+        assert!(matches!(Linter::new(config), Err(LinterError::Config(_))));
+    }
+
     #[test]
     fn test_linter_select_parser_unknown_defaults_to_text() {
         let config = LinterConfig::new();
         let linter = Linter::new(config).unwrap();
 
-        let parser = linter.select_parser("unknown");
+        let parser = linter.select_parser(Path::new("file.unknown"));
         assert_eq!(parser.name(), "text");
     }
 
@@ -672,4 +2113,919 @@ mod tests {
         assert!(json["range"].is_array());
         assert!(json["children"].is_array());
     }
+
+    #[test]
+    fn test_linter_ast_to_json_image_alt() {
+        use texide_ast::{NodeData, NodeType, Span, TxtNode};
+
+        let config = LinterConfig::new();
+        let linter = Linter::new(config).unwrap();
+
+        let mut image = TxtNode::new_leaf(NodeType::Image, Span::new(0, 20));
+        image.data = NodeData::image("cat.png", None, "A cat");
+
+        let json = linter.ast_to_json(&image, "");
+        assert_eq!(json["alt"], "A cat");
+    }
+
+    #[test]
+    fn test_linter_ast_to_json_table_align() {
+        use texide_ast::{NodeData, NodeType, Span, TableAlign, TxtNode};
+
+        let config = LinterConfig::new();
+        let linter = Linter::new(config).unwrap();
+
+        let align = [TableAlign::Center, TableAlign::None];
+        let mut table = TxtNode::new_leaf(NodeType::Table, Span::new(0, 10));
+        table.data = NodeData::table(&align);
+
+        let json = linter.ast_to_json(&table, "");
+        assert_eq!(json["align"], serde_json::json!(["center", "none"]));
+    }
+
+    #[test]
+    fn test_linter_ast_to_json_custom_type() {
+        use texide_ast::{NodeData, NodeType, Span, TxtNode};
+
+        let config = LinterConfig::new();
+        let linter = Linter::new(config).unwrap();
+
+        let mut directive = TxtNode::new_leaf(NodeType::Custom, Span::new(0, 12));
+        directive.data = NodeData::custom("Admonition");
+
+        let json = linter.ast_to_json(&directive, "");
+        assert_eq!(json["type"], "Admonition");
+    }
+
+    #[test]
+    fn test_lint_file_exceeding_max_file_size_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.md");
+        fs::write(&path, "# Generated\n\n".repeat(10)).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.max_file_size = Some(16);
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let diagnostics = &results[0].diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "max-file-size");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].message.contains("130 bytes"));
+    }
+
+    #[test]
+    fn test_lint_file_within_max_file_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.md");
+        fs::write(&path, "# Title\n").unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.max_file_size = Some(1024);
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(&[path]).unwrap();
+        assert!(results[0].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_lint_file_records_duration_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.md");
+        fs::write(&path, "# Title\n\nSome text.\n").unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        let linter = Linter::new(config).unwrap();
+
+        let result = linter.lint_file(&path).unwrap().result;
+        assert!(!result.from_cache);
+        assert!(result.duration.is_some());
+        assert_eq!(result.bytes, Some(20));
+    }
+
+    #[test]
+    fn test_rule_profile_counts_one_invocation_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = ["a.md", "b.md", "c.md"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, "# Title\n\n![](cat.png)\n").unwrap();
+                path
+            })
+            .collect();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "image-alt-text".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        assert!(
+            Linter::new(config.clone())
+                .unwrap()
+                .rule_profile()
+                .is_none()
+        );
+
+        config.profile_rules = true;
+        let linter = Linter::new(config).unwrap();
+        linter.lint_files(&paths).unwrap();
+
+        let profile = linter.rule_profile().unwrap();
+        assert_eq!(profile.get("image-alt-text").unwrap().invocations, 3);
+        assert!(profile.get("no-bare-urls").is_none());
+    }
+
+    #[test]
+    fn test_lint_files_with_streams_each_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths = Vec::new();
+        for name in ["c.md", "a.md", "missing.md", "b.md"] {
+            let path = dir.path().join(name);
+            if name != "missing.md" {
+                fs::write(&path, "# Title\n\n![](cat.png)\n").unwrap();
+            }
+            paths.push(path);
+        }
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "image-alt-text".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+
+        let streamed = Mutex::new(Vec::new());
+        linter
+            .lint_files_with(&paths, |result| streamed.lock().unwrap().push(result))
+            .unwrap();
+        let streamed = streamed.into_inner().unwrap();
+
+        // Every readable file, in the given order, with its diagnostics
+        let streamed_paths: Vec<_> = streamed.iter().map(|r| r.path.clone()).collect();
+        assert_eq!(
+            streamed_paths,
+            vec![paths[0].clone(), paths[1].clone(), paths[3].clone()]
+        );
+        assert!(streamed.iter().all(|r| r.diagnostics.len() == 1));
+
+        let batch = linter.lint_files(&paths).unwrap();
+        assert_eq!(
+            batch.iter().map(|r| r.path.clone()).collect::<Vec<_>>(),
+            streamed_paths
+        );
+    }
+
+    #[test]
+    fn test_lint_file_with_ignore_marker_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = "| a | b |\n| - | - |\n| 1 |\n";
+        let marked = dir.path().join("generated.md");
+        fs::write(&marked, format!("<!-- texide-ignore-file -->\n\n{}", table)).unwrap();
+        let unmarked = dir.path().join("written.md");
+        fs::write(&unmarked, table).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "consistent-table-columns".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(&[marked.clone(), unmarked]).unwrap();
+        assert!(results[0].diagnostics.is_empty());
+        assert!(results[0].bytes.is_some());
+        assert_eq!(results[1].diagnostics.len(), 1);
+
+        let content = fs::read_to_string(&marked).unwrap();
+        assert!(linter.lint_content(&content, &marked).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_native_rules_see_unmodified_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        let content = "# Tom &amp; Jerry\t\n\nA \\*literal\\* star. \r\n";
+        fs::write(&path, content).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "no-trailing-spaces".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(&[path]).unwrap();
+        let reported: Vec<&str> = results[0]
+            .diagnostics
+            .iter()
+            .map(|d| &content[d.span.start as usize..d.span.end as usize])
+            .collect();
+        assert_eq!(reported, vec!["\t", " "]);
+    }
+
+    #[test]
+    fn test_lint_content_normalize_line_endings() {
+        use texide_plugin::test_support::RuleFixture;
+
+        // Reports when the source sent to the rule still has a `\r`
+        let dir = tempfile::tempdir().unwrap();
+        let rule_path = dir.path().join("carriage-return.wasm");
+        let wasm = RuleFixture::new("carriage-return")
+            .reports(r#"[{"message":"Found CR","span":{"start":0,"end":1}}]"#)
+            .when_request_contains(r"\r")
+            .build();
+        fs::write(&rule_path, wasm).unwrap();
+
+        let lf = "# Title\n\nSome text\n";
+        let crlf = lf.replace('\n', "\r\n");
+        let lint = |normalize_line_endings: bool, content: &str| {
+            let mut config = LinterConfig::new();
+            config.cache = false;
+            config.normalize_line_endings = normalize_line_endings;
+            config.options.insert(
+                "carriage-return".to_string(),
+                crate::config::RuleOption::Enabled(true),
+            );
+            let linter = Linter::new(config).unwrap();
+            linter.load_rule(&rule_path).unwrap();
+            linter.lint_content(content, Path::new("notes.md")).unwrap()
+        };
+
+        assert_eq!(lint(false, &crlf).len(), 1);
+        assert!(lint(false, lf).is_empty());
+        assert!(lint(true, &crlf).is_empty());
+    }
+
+    #[test]
+    fn test_lint_file_unicode_normalize() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("menu.md");
+        // The second heading is decomposed ("e" + U+0301), as copy-paste from
+        // macOS often produces
+        let content = "# Café\n\n# Cafe\u{301}\n";
+        fs::write(&path, content).unwrap();
+
+        let lint = |form| {
+            let mut config = LinterConfig::new();
+            config.cache = false;
+            config.options.insert(
+                "no-duplicate-headings".to_string(),
+                crate::config::RuleOption::Enabled(true),
+            );
+            config.unicode_normalize = form;
+            let linter = Linter::new(config).unwrap();
+            let diagnostics = linter.lint_files(std::slice::from_ref(&path)).unwrap()[0]
+                .diagnostics
+                .clone();
+            // Content linted in memory is normalized the same way
+            let from_content = linter.lint_content(content, &path).unwrap();
+            assert_eq!(
+                serde_json::to_value(&from_content).unwrap(),
+                serde_json::to_value(&diagnostics).unwrap()
+            );
+            diagnostics
+        };
+
+        assert!(lint(crate::config::NormalizationForm::None).is_empty());
+
+        let diagnostics = lint(crate::config::NormalizationForm::Nfc);
+        assert_eq!(diagnostics.len(), 1);
+        let span = diagnostics[0].span;
+        assert_eq!(
+            &content[span.start as usize..span.end as usize],
+            "# Cafe\u{301}"
+        );
+    }
+
+    #[test]
+    fn test_lint_file_locale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("guide.md");
+        fs::write(&path, "# Guide\n\n\n\nSee below. \n\n![](shot.png)").unwrap();
+
+        let lint = |locale: Option<&str>| {
+            let mut config = LinterConfig::new();
+            config.cache = false;
+            config.base_dir = Some(dir.path().to_path_buf());
+            for rule in [
+                "final-newline",
+                "image-alt-text",
+                "no-multiple-blank-lines",
+                "no-trailing-spaces",
+            ] {
+                config
+                    .options
+                    .insert(rule.to_string(), crate::config::RuleOption::Enabled(true));
+            }
+            config.locale = locale.map(str::to_string);
+            let linter = Linter::new(config).unwrap();
+            let mut diagnostics = linter.lint_files(std::slice::from_ref(&path)).unwrap()[0]
+                .diagnostics
+                .clone();
+            // Results keep the English messages until they are shown
+            assert!(diagnostics.iter().all(|d| d.message.is_ascii()));
+            linter.localize(&mut diagnostics);
+            diagnostics.sort_by_key(|d| d.span.start);
+            diagnostics
+                .into_iter()
+                .map(|d| d.message)
+                .collect::<Vec<_>>()
+        };
+
+        let english = vec![
+            "3 consecutive blank lines (maximum is 1)",
+            "Line has trailing whitespace",
+            "Image has no alt text",
+            "File does not end with a newline",
+        ];
+        assert_eq!(lint(None), english);
+        assert_eq!(lint(Some("en")), english);
+        assert_eq!(
+            lint(Some("ja")),
+            vec![
+                "空行が 3 行続いています（最大 1 行）",
+                "行末に空白があります",
+                "画像に代替テキストがありません",
+                "ファイル末尾に改行がありません",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_virtual() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = LinterConfig::new();
+        config.cache_dir = Some(dir.path().join("cache").to_string_lossy().into_owned());
+        config.options.insert(
+            "no-duplicate-headings".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+        let content = "# Hello\n\nText\n\n# Hello\n";
+
+        let result = linter.lint_virtual("posts/post.md", content).unwrap();
+        assert_eq!(result.path, PathBuf::from("posts/post.md"));
+        assert!(!result.from_cache);
+        assert_eq!(result.bytes, Some(content.len()));
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].span, Span::new(15, 22));
+
+        // Cached under the virtual name
+        let again = linter.lint_virtual("posts/post.md", content).unwrap();
+        assert!(again.from_cache);
+        assert_eq!(again.diagnostics.len(), 1);
+
+        // The name's extension picks the parser: plain text has no headings
+        let text = linter.lint_virtual("post.txt", content).unwrap();
+        assert!(text.diagnostics.is_empty());
+        assert!(!Path::new("posts/post.md").exists());
+    }
+
+    #[test]
+    fn test_lint_file_directives_enable_rule_that_is_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.md");
+        let content = "See https://a.example\n\n\
+                       <!-- texide-enable no-bare-urls -->\n\n\
+                       See https://b.example\n\n\
+                       <!-- texide-disable no-bare-urls -->\n\n\
+                       See https://c.example\n\n\
+                       ```markdown\n<!-- texide-enable no-bare-urls -->\n```\n\n\
+                       See https://d.example\n";
+        fs::write(&path, content).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache_dir = Some(dir.path().join("cache").to_string_lossy().into_owned());
+        config.options.insert(
+            "no-bare-urls".to_string(),
+            RuleOption::Severity("off".to_string()),
+        );
+        let linter = Linter::new(config).unwrap();
+
+        let urls = |diagnostics: &[Diagnostic]| -> Vec<String> {
+            diagnostics
+                .iter()
+                .map(|d| content[d.span.start as usize..d.span.end as usize].to_string())
+                .collect()
+        };
+        let result = linter.lint_file(&path).unwrap().result;
+        assert_eq!(urls(&result.diagnostics), vec!["https://b.example"]);
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+
+        let cached = linter.lint_file(&path).unwrap().result;
+        assert!(cached.from_cache);
+        assert_eq!(urls(&cached.diagnostics), vec!["https://b.example"]);
+
+        let diagnostics = linter.lint_content(content, &path).unwrap();
+        assert_eq!(urls(&diagnostics), vec!["https://b.example"]);
+
+        // Without the enable directive the rule stays off
+        let plain = content.replace("<!-- texide-enable no-bare-urls -->\n\nSee", "See");
+        fs::write(&path, &plain).unwrap();
+        assert!(
+            linter
+                .lint_file(&path)
+                .unwrap()
+                .result
+                .diagnostics
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_with_cache_store() {
+        use std::sync::Arc;
+        use texide_cache::CacheError;
+
+        /// A store kept in memory, shared by the linters it is cloned into.
+        #[derive(Clone, Default)]
+        struct MemoryStore(Arc<Mutex<HashMap<PathBuf, CacheEntry>>>);
+
+        impl CacheStore for MemoryStore {
+            fn load(&mut self) -> Result<HashMap<PathBuf, CacheEntry>, CacheError> {
+                Ok(self.0.lock().unwrap().clone())
+            }
+
+            fn save(&mut self, entries: &HashMap<PathBuf, CacheEntry>) -> Result<(), CacheError> {
+                *self.0.lock().unwrap() = entries.clone();
+                Ok(())
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.md");
+        fs::write(&path, "# Hello\n\nText\n\n# Hello\n").unwrap();
+        let mut config = LinterConfig::new();
+        config.cache_dir = Some(dir.path().join("cache").to_string_lossy().into_owned());
+        config.options.insert(
+            "no-duplicate-headings".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let store = MemoryStore::default();
+
+        let linter = Linter::with_cache_store(config.clone(), Box::new(store.clone())).unwrap();
+        let results = linter.lint_files(std::slice::from_ref(&path)).unwrap();
+        assert!(!results[0].from_cache);
+        assert_eq!(store.0.lock().unwrap().len(), 1);
+        assert!(!dir.path().join("cache").exists());
+
+        // A fresh linter on the same store reuses the result
+        let linter = Linter::with_cache_store(config, Box::new(store)).unwrap();
+        let results = linter.lint_files(std::slice::from_ref(&path)).unwrap();
+        assert!(results[0].from_cache);
+        assert_eq!(results[0].diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_lint_file_invalid_utf8_strict() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("legacy.txt");
+        fs::write(&path, b"caf\xe9 au lait\n").unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let diagnostics = &results[0].diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "encoding-error");
+        assert!(diagnostics[0].message.contains("legacy.txt"));
+        assert!(diagnostics[0].message.contains("not valid UTF-8"));
+        assert!(diagnostics[0].message.contains("offset 3"));
+    }
+
+    #[test]
+    fn test_lint_file_invalid_utf8_lossy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("legacy.txt");
+        fs::write(&path, b"caf\xe9 au lait\n").unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.encoding = Encoding::Utf8Lossy;
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(&[path]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_lint_path_returns_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.md");
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        let linter = Linter::new(config).unwrap();
+
+        // lint_files logs the failure and skips the file
+        assert!(
+            linter
+                .lint_files(std::slice::from_ref(&path))
+                .unwrap()
+                .is_empty()
+        );
+        match linter.lint_path(&path) {
+            Err(LinterError::File { path: failed, .. }) => assert_eq!(failed, path),
+            other => panic!("expected a file error, got {:?}", other),
+        }
+
+        fs::write(&path, "# Title\n").unwrap();
+        let result = linter.lint_path(&path).unwrap();
+        assert_eq!(result.path, path);
+        assert!(result.duration.is_some());
+    }
+
+    #[test]
+    fn test_lint_archive_zip() {
+        use std::io::{Cursor, Write};
+        use zip::write::SimpleFileOptions;
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("docs/guide.md", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"# Guide\n\n![](diagram.png)\n").unwrap();
+        writer
+            .start_file("docs/logo.png", SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .write_all(&[0x89, b'P', b'N', b'G', 0xff, 0x00])
+            .unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("docs.zip");
+        fs::write(&archive, bytes).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "image-alt-text".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_archive(&archive).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].path,
+            PathBuf::from(format!("{}!docs/guide.md", archive.display()))
+        );
+        assert_eq!(results[0].diagnostics.len(), 1);
+        assert_eq!(results[0].diagnostics[0].rule_id, "image-alt-text");
+    }
+
+    #[test]
+    fn test_lint_archive_respects_exclude_globs() {
+        use std::io::{Cursor, Write};
+        use zip::write::SimpleFileOptions;
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for name in ["docs/guide.md", "drafts/wip.md"] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(b"# Title\n").unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("docs.zip");
+        fs::write(&archive, bytes).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.exclude = vec!["drafts/**".to_string()];
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_archive(&archive).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(
+            results[0]
+                .path
+                .to_string_lossy()
+                .ends_with("!docs/guide.md")
+        );
+    }
+
+    #[test]
+    fn test_lint_archive_caps_entries_with_understated_size() {
+        use std::io::{Cursor, Write};
+        use zip::write::SimpleFileOptions;
+
+        let content = b"# Title\n\nA paragraph well past the limit.\n";
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("docs/big.md", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content).unwrap();
+        let mut bytes = writer.finish().unwrap().into_inner();
+
+        // Claim an uncompressed size of 1 byte in the central directory
+        let central = bytes
+            .windows(4)
+            .position(|window| window == b"PK\x01\x02")
+            .unwrap();
+        bytes[central + 24..central + 28].copy_from_slice(&1u32.to_le_bytes());
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("docs.zip");
+        fs::write(&archive, bytes).unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.max_file_size = Some(16);
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_archive(&archive).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].diagnostics.len(), 1);
+        assert_eq!(results[0].diagnostics[0].rule_id, "max-file-size");
+        assert!(results[0].diagnostics[0].message.contains("17 bytes"));
+    }
+
+    #[test]
+    fn test_lint_files_reuses_single_arena() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..50)
+            .map(|i| {
+                let path = dir.path().join(format!("doc{}.md", i));
+                let content = format!("# Doc {}\n\n{}\n", i, "Some text. ".repeat(i * 10));
+                fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        let linter = Linter::new(config).unwrap();
+
+        let results = linter.lint_files(&paths).unwrap();
+        assert_eq!(results.len(), paths.len());
+        for (result, path) in results.iter().zip(&paths) {
+            assert_eq!(&result.path, path);
+            assert!(result.diagnostics.is_empty());
+        }
+        assert_eq!(linter.arenas.idle(), 1);
+
+        let diagnostics = linter
+            .lint_content("# Title\n\nBody\n", Path::new("test.md"))
+            .unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(linter.arenas.idle(), 1);
+    }
+
+    #[test]
+    fn test_native_rule_runs_only_when_enabled() {
+        let source = "| a | b |\n| - | - |\n| 1 |\n";
+        let path = Path::new("table.md");
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        let linter = Linter::new(config.clone()).unwrap();
+        assert!(linter.lint_content(source, path).unwrap().is_empty());
+
+        config.options.insert(
+            "consistent-table-columns".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+        let linter = Linter::new(config).unwrap();
+        let diagnostics = linter.lint_content(source, path).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "consistent-table-columns");
+    }
+
+    #[test]
+    fn test_project_rule_reports_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let guide = dir.path().join("guide.md");
+        fs::write(&guide, "# Guide\n\nSee the [API](api.md).\n").unwrap();
+        let api = dir.path().join("api.md");
+        fs::write(&api, "# API\n").unwrap();
+        let paths = vec![guide, api];
+
+        let mut config = LinterConfig::new();
+        config.cache_dir = Some(dir.path().join("cache").to_string_lossy().into_owned());
+        config.options.insert(
+            "orphaned-document".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+
+        // The second run reads both files' links from the cache
+        for from_cache in [false, true] {
+            let linter = Linter::new(config.clone()).unwrap();
+            let results = linter.lint_files(&paths).unwrap();
+            assert!(results.iter().all(|r| r.from_cache == from_cache));
+            assert_eq!(results[0].diagnostics.len(), 1);
+            assert_eq!(results[0].diagnostics[0].rule_id, "orphaned-document");
+            assert_eq!(results[0].diagnostics[0].tags, vec!["correctness"]);
+            assert!(results[1].diagnostics.is_empty());
+        }
+
+        // Linted alone, a file has no other document to be linked from
+        let linter = Linter::new(config).unwrap();
+        let results = linter.lint_files(&paths[..1]).unwrap();
+        assert!(results[0].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_project_rule_diagnostics_follow_directives() {
+        let dir = tempfile::tempdir().unwrap();
+        let guide = dir.path().join("guide.md");
+        fs::write(
+            &guide,
+            "<!-- texide-disable orphaned-document -->\r\n# Guide\r\n\r\nSee the [API](api.md).\r\n",
+        )
+        .unwrap();
+        let api = dir.path().join("api.md");
+        fs::write(&api, "# API\n").unwrap();
+
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "orphaned-document".to_string(),
+            crate::config::RuleOption::Enabled(true),
+        );
+
+        let linter = Linter::new(config).unwrap();
+        let results = linter.lint_files(&[guide, api]).unwrap();
+        assert!(results[0].diagnostics.is_empty(), "{:?}", results[0]);
+        assert!(results[1].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_get_rule_tags() {
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        for rule in ["image-alt-text", "no-bare-urls"] {
+            config
+                .options
+                .insert(rule.to_string(), crate::config::RuleOption::Enabled(true));
+        }
+
+        let linter = Linter::new(config).unwrap();
+        let mut diagnostics = linter
+            .lint_content("![](a.png) https://example.com\n", Path::new("doc.md"))
+            .unwrap();
+        diagnostics.sort_by(|a, b| a.rule_id.cmp(&b.rule_id));
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule_id, "image-alt-text");
+        assert_eq!(diagnostics[0].tags, vec!["accessibility"]);
+        assert_eq!(diagnostics[1].rule_id, "no-bare-urls");
+        assert_eq!(diagnostics[1].tags, vec!["style"]);
+    }
+
+    #[test]
+    fn test_configured_severity_overrides_rule_severity() {
+        let source = "| a | b |\n| - | - |\n| 1 |\n";
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "consistent-table-columns".to_string(),
+            crate::config::RuleOption::Severity("error".to_string()),
+        );
+
+        let linter = Linter::new(config).unwrap();
+        let diagnostics = linter.lint_content(source, Path::new("table.md")).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, texide_plugin::Severity::Error);
+    }
+
+    #[test]
+    fn test_rule_runs_only_on_its_files() {
+        let source = "# Post without frontmatter\n";
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "frontmatter-required-fields".to_string(),
+            crate::config::RuleOption::Options(serde_json::json!({
+                "files": ["content/**"],
+                "exclude_files": ["content/drafts/**"]
+            })),
+        );
+
+        let linter = Linter::new(config).unwrap();
+        let lint = |path: &str| linter.lint_content(source, Path::new(path)).unwrap();
+
+        let diagnostics = lint("content/posts/hello.md");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "frontmatter-required-fields");
+        assert_eq!(lint("./content/hello.md").len(), 1);
+        assert!(lint("docs/guide.md").is_empty());
+        assert!(lint("content/drafts/idea.md").is_empty());
+    }
+
+    #[test]
+    fn test_scopes_match_absolute_paths_relative_to_base_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "# Post without frontmatter\n";
+        let mut config = LinterConfig::from_json(
+            r#"{
+                "raw_files": ["config/*.yaml"],
+                "options": {
+                    "frontmatter-required-fields": ["warning", { "files": ["content/**"] }]
+                }
+            }"#,
+        )
+        .unwrap();
+        config.cache = false;
+        config.base_dir = Some(dir.path().to_path_buf());
+
+        let linter = Linter::new(config).unwrap();
+        let lint = |path: &Path| linter.lint_content(source, path).unwrap();
+
+        let diagnostics = lint(&dir.path().join("content/post.md"));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "frontmatter-required-fields");
+        assert!(lint(&dir.path().join("docs/guide.md")).is_empty());
+
+        let raw = linter.select_parser(&dir.path().join("config/app.yaml"));
+        assert_eq!(raw.name(), "raw");
+    }
+
+    #[test]
+    fn test_severity_in_options_object() {
+        let mut config = LinterConfig::new();
+        config.cache = false;
+        config.options.insert(
+            "no-bare-urls".to_string(),
+            crate::config::RuleOption::Options(serde_json::json!({
+                "severity": "error",
+                "fix_style": "link"
+            })),
+        );
+
+        let linter = Linter::new(config).unwrap();
+        let diagnostics = linter
+            .lint_content("See https://example.com\n", Path::new("doc.md"))
+            .unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, texide_plugin::Severity::Error);
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(fix.text, "[https://example.com](https://example.com)");
+    }
+
+    #[test]
+    fn test_ignore_patterns_suppress_diagnostics_across_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("words.txt"), "is\na\nlinter\n").unwrap();
+        let path = dir.path().join("intro.md");
+        fs::write(&path, "Texide is a lintr\n").unwrap();
+
+        let mut config = LinterConfig::from_json(
+            r#"{ "options": { "spelling": { "dictionaries": ["words.txt"] } } }"#,
+        )
+        .unwrap();
+        config.cache = false;
+        config.base_dir = Some(dir.path().to_path_buf());
+
+        let linter = Linter::new(config.clone()).unwrap();
+        let results = linter.lint_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(results[0].diagnostics.len(), 2);
+
+        config.ignore_patterns = vec!["Texide".to_string()];
+        let linter = Linter::new(config).unwrap();
+        let results = linter.lint_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(results[0].diagnostics.len(), 1);
+        assert!(results[0].diagnostics[0].message.contains("'lintr'"));
+    }
+
+    #[test]
+    fn test_ignore_file_applies_to_lint_content() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("words.txt"), "is\na\nlinter\n").unwrap();
+        fs::write(dir.path().join(crate::ignore::IGNORE_FILE), "/Tex[a-z]+/\n").unwrap();
+
+        let mut config = LinterConfig::from_json(
+            r#"{ "options": { "spelling": { "dictionaries": ["words.txt"] } } }"#,
+        )
+        .unwrap();
+        config.cache = false;
+        config.base_dir = Some(dir.path().to_path_buf());
+        let linter = Linter::new(config).unwrap();
+
+        let diagnostics = linter
+            .lint_content("Texide is a linter\n", Path::new("intro.md"))
+            .unwrap();
+        assert!(diagnostics.is_empty());
+    }
 }
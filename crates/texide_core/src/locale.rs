@@ -0,0 +1,243 @@
+//! Localized diagnostic messages.
+//!
+//! Rules write diagnostic messages in English and may tag them with a
+//! `message_key` and `message_params`. Lint results keep the English
+//! messages; when the `locale` option is set, [`Linter::localize`] looks
+//! the key up in the message bundle for that locale as diagnostics are
+//! shown, filling in the template's `{name}` placeholders from the params.
+//! Diagnostics without a key, or with a key the bundle does not have, keep
+//! the rule's message.
+//!
+//! A bundle is a JSON object mapping keys to templates. Texide ships `en`
+//! and `ja` bundles for its built-in rules; a project can add messages,
+//! including ones for WASM rules, or override built-in ones in
+//! `.texide/locales/<locale>.json`. A regional locale such as `ja-JP` uses
+//! the bundles for its language (`ja`) too, with its own messages taking
+//! precedence.
+//!
+//! [`Linter::localize`]: crate::Linter::localize
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use texide_plugin::Diagnostic;
+use tracing::warn;
+
+use crate::LinterError;
+
+/// Returns the bundle built into Texide for `locale`.
+fn builtin_bundle(locale: &str) -> Option<&'static str> {
+    match locale {
+        "en" => Some(include_str!("../locales/en.json")),
+        "ja" => Some(include_str!("../locales/ja.json")),
+        _ => None,
+    }
+}
+
+/// Message templates for one locale.
+#[derive(Debug, Default)]
+pub(crate) struct MessageBundle {
+    templates: HashMap<String, String>,
+}
+
+impl MessageBundle {
+    /// Loads the built-in and project bundles for `locale`.
+    pub fn load(locale: &str, base_dir: &Path) -> Result<Self, LinterError> {
+        let mut locales = Vec::new();
+        if let Some((language, _)) = locale.split_once(['-', '_']) {
+            locales.push(language);
+        }
+        locales.push(locale);
+
+        let mut bundle = Self::default();
+        let mut found = false;
+        for locale in locales {
+            if let Some(json) = builtin_bundle(locale) {
+                bundle.extend(json, "built-in")?;
+                found = true;
+            }
+
+            let path = base_dir
+                .join(".texide")
+                .join("locales")
+                .join(format!("{}.json", locale));
+            if path.is_file() {
+                let json = fs::read_to_string(&path).map_err(|e| {
+                    LinterError::config(format!("Failed to read {}: {}", path.display(), e))
+                })?;
+                bundle.extend(&json, &path.display().to_string())?;
+                found = true;
+            }
+        }
+
+        if !found {
+            warn!(
+                "No messages found for locale '{}'; using the rules' messages",
+                locale
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Adds the templates of the bundle `json`, replacing existing ones.
+    fn extend(&mut self, json: &str, origin: &str) -> Result<(), LinterError> {
+        let templates: HashMap<String, String> = serde_json::from_str(json).map_err(|e| {
+            LinterError::config(format!("Invalid message bundle {}: {}", origin, e))
+        })?;
+        self.templates.extend(templates);
+        Ok(())
+    }
+
+    /// Replaces the message of each diagnostic that has a template in this
+    /// bundle.
+    pub fn localize(&self, diagnostics: &mut [Diagnostic]) {
+        for diagnostic in diagnostics {
+            if let Some(template) = diagnostic
+                .message_key
+                .as_ref()
+                .and_then(|key| self.templates.get(key))
+            {
+                diagnostic.message = render(template, &diagnostic.message_params);
+            }
+        }
+    }
+}
+
+/// Fills in the `{name}` placeholders of `template`.
+///
+/// Placeholders without a param are left as they are.
+fn render(template: &str, params: &BTreeMap<String, String>) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        message.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let value = rest
+            .find('}')
+            .and_then(|close| Some((close, params.get(&rest[1..close])?)));
+        match value {
+            Some((close, value)) => {
+                message.push_str(value);
+                rest = &rest[close + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tempfile::TempDir;
+    use texide_ast::Span;
+
+    fn params(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// Returns the placeholder names in `template`.
+    fn placeholders(template: &str) -> HashSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| Some(part.split_once('}')?.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let params = params(&[("width", "90"), ("max", "80")]);
+        assert_eq!(
+            render("Line is {width} columns wide (maximum is {max})", &params),
+            "Line is 90 columns wide (maximum is 80)"
+        );
+        assert_eq!(render("{unknown} and {width}", &params), "{unknown} and 90");
+        assert_eq!(render("Braces {", &params), "Braces {");
+    }
+
+    #[test]
+    fn test_builtin_bundles_match() {
+        let en: HashMap<String, String> =
+            serde_json::from_str(builtin_bundle("en").unwrap()).unwrap();
+        let ja: HashMap<String, String> =
+            serde_json::from_str(builtin_bundle("ja").unwrap()).unwrap();
+
+        let en_keys: HashSet<_> = en.keys().collect();
+        let ja_keys: HashSet<_> = ja.keys().collect();
+        assert_eq!(en_keys, ja_keys);
+        for (key, template) in &en {
+            assert_eq!(placeholders(template), placeholders(&ja[key]), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_localize_falls_back_to_message() {
+        let temp = TempDir::new().unwrap();
+        let bundle = MessageBundle::load("ja", temp.path()).unwrap();
+
+        let mut diagnostics = vec![
+            Diagnostic::new("no-todo", "Found TODO", Span::new(0, 4)),
+            Diagnostic::new("no-todo", "Found TODO", Span::new(0, 4))
+                .with_message_key("no-todo.found"),
+        ];
+        bundle.localize(&mut diagnostics);
+
+        assert_eq!(diagnostics[0].message, "Found TODO");
+        assert_eq!(diagnostics[1].message, "Found TODO");
+    }
+
+    #[test]
+    fn test_regional_locale_and_project_bundle() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(".texide").join("locales");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("ja-JP.json"),
+            r#"{ "no-todo.found": "TODO があります", "final-newline.missing": "改行なし" }"#,
+        )
+        .unwrap();
+
+        let bundle = MessageBundle::load("ja-JP", temp.path()).unwrap();
+        let mut diagnostics = vec![
+            Diagnostic::new("no-todo", "Found TODO", Span::new(0, 4))
+                .with_message_key("no-todo.found"),
+            Diagnostic::new(
+                "final-newline",
+                "File does not end with a newline",
+                Span::new(4, 4),
+            )
+            .with_message_key("final-newline.missing"),
+            Diagnostic::new(
+                "no-trailing-spaces",
+                "Line has trailing whitespace",
+                Span::new(1, 2),
+            )
+            .with_message_key("no-trailing-spaces.trailing"),
+        ];
+        bundle.localize(&mut diagnostics);
+
+        assert_eq!(diagnostics[0].message, "TODO があります");
+        assert_eq!(diagnostics[1].message, "改行なし");
+        assert_eq!(diagnostics[2].message, "行末に空白があります");
+    }
+
+    #[test]
+    fn test_invalid_project_bundle() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(".texide").join("locales");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("ja.json"), "{ not json").unwrap();
+
+        assert!(MessageBundle::load("ja", temp.path()).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! Line ending normalization.
+//!
+//! When `normalize_line_endings` is enabled, CRLF sequences are converted
+//! to LF before parsing so rules see a consistent document. Spans reported
+//! against the normalized text are mapped back to the original file.
+//!
+//! The [`OffsetMap`] is shared with Unicode normalization.
+
+use texide_ast::Span;
+use texide_plugin::Diagnostic;
+
+/// Maps offsets in normalized text back to the original source.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetMap {
+    /// Replaced ranges in order; text between them is unchanged.
+    edits: Vec<Edit>,
+}
+
+/// A range of the original text that normalization replaced.
+#[derive(Debug, Clone, Copy)]
+struct Edit {
+    /// Where the replacement is in the normalized text.
+    normalized: Span,
+    /// Where the replaced text is in the original.
+    original: Span,
+}
+
+impl OffsetMap {
+    /// Records that `original` was replaced by the text at `normalized`.
+    ///
+    /// Edits must be pushed in order.
+    pub fn push(&mut self, normalized: Span, original: Span) {
+        self.edits.push(Edit {
+            normalized,
+            original,
+        });
+    }
+
+    /// Converts an offset in the normalized text to the original offset.
+    ///
+    /// Offsets inside a replaced range map to its start.
+    pub fn to_original(&self, offset: u32) -> u32 {
+        self.map(offset, false)
+    }
+
+    /// Converts a span in the normalized text to the original span, widened
+    /// to whole replaced ranges.
+    pub fn span_to_original(&self, span: Span) -> Span {
+        Span::new(self.to_original(span.start), self.map(span.end, true))
+    }
+
+    /// Converts an offset, rounding offsets inside a replaced range to its
+    /// start or, with `round_up`, its end.
+    fn map(&self, offset: u32, round_up: bool) -> u32 {
+        let i = self
+            .edits
+            .partition_point(|edit| edit.normalized.end <= offset);
+        if let Some(edit) = self.edits.get(i)
+            && edit.normalized.start < offset
+        {
+            return if round_up {
+                edit.original.end
+            } else {
+                edit.original.start
+            };
+        }
+        match i.checked_sub(1).map(|i| self.edits[i]) {
+            Some(edit) => offset - edit.normalized.end + edit.original.end,
+            None => offset,
+        }
+    }
+
+    /// Rewrites the spans of a diagnostic, its fix and its related places to
+    /// original offsets.
+    pub fn diagnostic_to_original(&self, mut diagnostic: Diagnostic) -> Diagnostic {
+        diagnostic.span = self.span_to_original(diagnostic.span);
+        if let Some(fix) = &mut diagnostic.fix {
+            fix.span = self.span_to_original(fix.span);
+        }
+        for related in &mut diagnostic.related {
+            related.span = self.span_to_original(related.span);
+        }
+        diagnostic
+    }
+}
+
+/// Converts CRLF line endings to LF.
+///
+/// Returns `None` if the source has no CRLF line endings.
+pub(crate) fn normalize_line_endings(source: &str) -> Option<(String, OffsetMap)> {
+    if !source.contains("\r\n") {
+        return None;
+    }
+
+    let mut text = String::with_capacity(source.len());
+    let mut map = OffsetMap::default();
+    let mut last = 0;
+    for (offset, _) in source.match_indices("\r\n") {
+        text.push_str(&source[last..offset]);
+        let start = text.len() as u32;
+        map.push(
+            Span::new(start, start + 1),
+            Span::new(offset as u32, offset as u32 + 2),
+        );
+        text.push('\n');
+        last = offset + 2;
+    }
+    text.push_str(&source[last..]);
+
+    Some((text, map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texide_ast::LineIndex;
+    use texide_plugin::Fix;
+
+    #[test]
+    fn test_lf_source_is_unchanged() {
+        assert!(normalize_line_endings("one\ntwo\n").is_none());
+        assert!(normalize_line_endings("lone\rcarriage return").is_none());
+    }
+
+    #[test]
+    fn test_normalize_crlf() {
+        let (text, map) = normalize_line_endings("a\r\nb\r\n\r\nc").unwrap();
+
+        assert_eq!(text, "a\nb\n\nc");
+        assert_eq!(map.to_original(0), 0);
+        assert_eq!(map.to_original(1), 1);
+        assert_eq!(map.to_original(2), 3);
+        assert_eq!(map.to_original(4), 6);
+        assert_eq!(map.to_original(6), 9);
+    }
+
+    #[test]
+    fn test_crlf_and_lf_diagnostics_match() {
+        let lf = "# Title\n\nSome TODO here.\nAnother TODO.\n";
+        let crlf = lf.replace('\n', "\r\n");
+        let (normalized, map) = normalize_line_endings(&crlf).unwrap();
+        assert_eq!(normalized, lf);
+
+        let lf_index = LineIndex::new(lf);
+        let crlf_index = LineIndex::new(&crlf);
+        for (start, _) in lf.match_indices("TODO") {
+            let span = Span::new(start as u32, start as u32 + 4);
+            let diagnostic =
+                Diagnostic::new("no-todo", "Found TODO", span).with_fix(Fix::new(span, "DONE"));
+
+            let mapped = map.diagnostic_to_original(diagnostic);
+            let original = mapped.span.start as usize..mapped.span.end as usize;
+            assert_eq!(&crlf[original], "TODO");
+            assert_eq!(mapped.fix.unwrap().span, mapped.span);
+            assert_eq!(crlf_index.location(mapped.span), lf_index.location(span));
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Choosing the parser for a file.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use globset::GlobSet;
+use texide_parser::{
+    MarkdownParser, ParagraphMode, Parser, ParserOptions, ParserRegistry, PlainTextParser,
+    RawParser,
+};
+
+use crate::path_filter::relative_to;
+use crate::{Linter, LinterConfig, LinterError};
+
+/// Picks the parser for a file from its path and the configuration.
+///
+/// The linter, the LSP and `texide ast` all parse through this, so a file
+/// gets the same tree whichever of them reads it:
+///
+/// 1. Files matching `raw_files` use the raw parser.
+/// 2. Otherwise the extension picks a parser of the [`ParserRegistry`];
+///    Markdown files get the options of the last `parser_options` entry
+///    matching the path, and plain text uses `paragraph_mode`.
+/// 3. Unknown extensions are parsed as plain text.
+///
+/// ```rust
+/// use std::path::Path;
+/// use texide_core::{LinterConfig, ParserSelector};
+///
+/// let config = LinterConfig::from_json(r#"{ "raw_files": ["**/*.yaml"] }"#)?;
+/// let parsers = ParserSelector::new(&config)?;
+///
+/// assert_eq!(parsers.select(Path::new("README.md")).name(), "markdown");
+/// assert_eq!(parsers.select(Path::new("ci/build.yaml")).name(), "raw");
+/// assert_eq!(parsers.select(Path::new("notes.rst")).name(), "text");
+/// # Ok::<(), texide_core::LinterError>(())
+/// ```
+pub struct ParserSelector {
+    /// The parsers extensions are looked up in.
+    registry: ParserRegistry,
+    /// The absolute directory the globs are relative to.
+    scope_root: PathBuf,
+    /// Markdown parser options with the files they apply to, in config order.
+    parser_options: Vec<(GlobSet, ParserOptions)>,
+    /// Files parsed with the raw parser.
+    raw_files: Option<GlobSet>,
+    /// How the plain text parser splits paragraphs.
+    paragraph_mode: ParagraphMode,
+}
+
+impl ParserSelector {
+    /// Creates a selector for the `raw_files`, `parser_options` and
+    /// `paragraph_mode` settings of `config`.
+    pub fn new(config: &LinterConfig) -> Result<Self, LinterError> {
+        let parser_options = config
+            .parser_options
+            .iter()
+            .map(|entry| {
+                let globs = Linter::build_globset(&entry.files)?.ok_or_else(|| {
+                    LinterError::config("parser_options entries need at least one file pattern")
+                })?;
+                Ok((globs, entry.options))
+            })
+            .collect::<Result<Vec<_>, LinterError>>()?;
+
+        Ok(Self {
+            registry: ParserRegistry::new(),
+            scope_root: config.scope_root()?,
+            parser_options,
+            raw_files: Linter::build_globset(&config.raw_files)?,
+            paragraph_mode: config.paragraph_mode,
+        })
+    }
+
+    /// Returns the parser for the file at `path`.
+    pub fn select(&self, path: &Path) -> Box<dyn Parser> {
+        if self.is_raw_file(path) {
+            return Box::new(RawParser::new());
+        }
+
+        match self.registry_parser(path).map(Parser::name) {
+            Some("markdown") => {
+                let scoped = self.scoped_path(path);
+                let options = self
+                    .parser_options
+                    .iter()
+                    .rev()
+                    .find(|(globs, _)| globs.is_match(&scoped))
+                    .map(|(_, options)| *options)
+                    .unwrap_or_default();
+                Box::new(MarkdownParser::with_options(options))
+            }
+            _ => Box::new(PlainTextParser::with_paragraph_mode(self.paragraph_mode)),
+        }
+    }
+
+    /// Returns whether a parser is meant for the file at `path`, rather
+    /// than it only falling back to plain text.
+    pub fn can_parse(&self, path: &Path) -> bool {
+        self.is_raw_file(path) || self.registry_parser(path).is_some()
+    }
+
+    /// Returns whether `path` matches `raw_files`.
+    pub(crate) fn is_raw_file(&self, path: &Path) -> bool {
+        self.raw_files
+            .as_ref()
+            .is_some_and(|globs| globs.is_match(self.scoped_path(path)))
+    }
+
+    /// Returns `path` as the globs see it, relative to `base_dir` (see
+    /// [`relative_to`]).
+    pub(crate) fn scoped_path<'p>(&self, path: &'p Path) -> Cow<'p, Path> {
+        relative_to(path, &self.scope_root)
+    }
+
+    /// Returns the registered parser for the extension of `path`.
+    fn registry_parser(&self, path: &Path) -> Option<&dyn Parser> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        self.registry.for_extension(extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_parse() {
+        let config = LinterConfig::from_json(r#"{ "raw_files": ["**/*.yaml"] }"#).unwrap();
+        let parsers = ParserSelector::new(&config).unwrap();
+
+        assert!(parsers.can_parse(Path::new("docs/guide.MD")));
+        assert!(parsers.can_parse(Path::new("notes.txt")));
+        assert!(parsers.can_parse(Path::new("ci/build.yaml")));
+        assert!(!parsers.can_parse(Path::new("logo.png")));
+        assert_eq!(parsers.select(Path::new("logo.png")).name(), "text");
+    }
+}
@@ -0,0 +1,202 @@
+//! Include/exclude filtering of file paths.
+//!
+//! The `include` and `exclude` lists are ordered lists of gitignore-style
+//! patterns, and a pattern prefixed with `!` is negated. Within each list
+//! the **last** matching pattern decides:
+//!
+//! | Pattern | In `include` | In `exclude` |
+//! |---------|--------------|--------------|
+//! | `pattern` | include the path | exclude the path |
+//! | `!pattern` | leave the path out | re-include the path |
+//!
+//! A path is linted if `include` takes it in and `exclude` does not
+//! exclude it. Paths no `include` pattern matches are taken in only if
+//! `include` has no plain (non-negated) pattern, so an `exclude` negation
+//! never brings in a path outside the include set.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::LinterError;
+
+/// Ordered include/exclude matcher.
+#[derive(Debug)]
+pub(crate) struct PathFilter {
+    /// Every `include` pattern then every `exclude` pattern, without its
+    /// `!` prefix.
+    globs: GlobSet,
+    /// Whether a path matching the pattern at the same index is kept.
+    keep: Vec<bool>,
+    /// Number of `include` patterns at the start of `globs`.
+    include_len: usize,
+    /// Whether paths no `include` pattern matches are included.
+    include_unmatched: bool,
+}
+
+impl PathFilter {
+    /// Builds a filter from the `include` and `exclude` lists.
+    pub(crate) fn new(include: &[String], exclude: &[String]) -> Result<Self, LinterError> {
+        let patterns = include
+            .iter()
+            .map(|pattern| (pattern, true))
+            .chain(exclude.iter().map(|pattern| (pattern, false)));
+
+        let mut builder = GlobSetBuilder::new();
+        let mut keep = Vec::new();
+        let mut has_positive_include = false;
+        for (pattern, in_include) in patterns {
+            let (glob, negated) = match pattern.strip_prefix('!') {
+                Some(glob) => (glob, true),
+                None => (pattern.as_str(), false),
+            };
+            let glob = Glob::new(glob)
+                .map_err(|e| LinterError::config(format!("Invalid glob pattern: {}", e)))?;
+            builder.add(glob);
+            keep.push(!negated);
+            has_positive_include |= in_include && !negated;
+        }
+
+        let globs = builder
+            .build()
+            .map_err(|e| LinterError::config(format!("Failed to build globset: {}", e)))?;
+
+        Ok(Self {
+            globs,
+            keep,
+            include_len: include.len(),
+            include_unmatched: !has_positive_include,
+        })
+    }
+
+    /// Returns whether `path` should be linted.
+    ///
+    /// A leading `./` is ignored, so `docs/**` matches `./docs/guide.md`.
+    pub(crate) fn is_match(&self, path: &Path) -> bool {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let matches = self.globs.matches(path);
+        let (include, exclude): (Vec<usize>, Vec<usize>) = matches
+            .into_iter()
+            .partition(|&index| index < self.include_len);
+
+        let included = match include.into_iter().max() {
+            Some(last) => self.keep[last],
+            None => self.include_unmatched,
+        };
+        let excluded = exclude
+            .into_iter()
+            .max()
+            .is_some_and(|last| self.keep[last]);
+        included && !excluded
+    }
+
+    /// Returns whether the filter has no patterns and so accepts every path.
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.keep.is_empty()
+    }
+}
+
+/// Returns `path` relative to the absolute directory `root`, as globs
+/// written relative to the configuration file expect it.
+///
+/// Relative paths are resolved from the current directory first, so
+/// `docs/a.md`, `./docs/a.md` and `/project/docs/a.md` all give `docs/a.md`
+/// for the root `/project`. Paths outside `root` are returned unchanged.
+pub(crate) fn relative_to<'p>(path: &'p Path, root: &Path) -> Cow<'p, Path> {
+    std::path::absolute(path)
+        .ok()
+        .and_then(|absolute| Some(absolute.strip_prefix(root).ok()?.to_path_buf()))
+        .map_or(Cow::Borrowed(path), Cow::Owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> PathFilter {
+        let to_strings =
+            |patterns: &[&str]| -> Vec<String> { patterns.iter().map(|p| p.to_string()).collect() };
+        PathFilter::new(&to_strings(include), &to_strings(exclude)).unwrap()
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let root = std::path::absolute("project").unwrap();
+        assert_eq!(
+            relative_to(&root.join("docs/a.md"), &root),
+            Path::new("docs/a.md")
+        );
+        assert_eq!(
+            relative_to(Path::new("./project/docs/a.md"), &root),
+            Path::new("docs/a.md")
+        );
+        assert_eq!(
+            relative_to(Path::new("/elsewhere/a.md"), &root),
+            Path::new("/elsewhere/a.md")
+        );
+    }
+
+    #[test]
+    fn test_empty_filter_accepts_everything() {
+        let filter = filter(&[], &[]);
+        assert!(filter.is_empty());
+        assert!(filter.is_match(Path::new("docs/guide.md")));
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let filter = filter(&["**/*.md"], &["**/node_modules/**"]);
+        assert!(filter.is_match(Path::new("docs/guide.md")));
+        assert!(!filter.is_match(Path::new("notes.txt")));
+        assert!(!filter.is_match(Path::new("node_modules/pkg/README.md")));
+    }
+
+    #[test]
+    fn test_negation_overrides_earlier_include() {
+        let filter = filter(&["**/*.md", "!drafts/**"], &[]);
+        assert!(filter.is_match(Path::new("docs/guide.md")));
+        assert!(!filter.is_match(Path::new("drafts/idea.md")));
+        assert!(!filter.is_match(Path::new("./drafts/idea.md")));
+        assert!(!filter.is_match(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn test_later_include_overrides_negation() {
+        let filter = filter(&["**/*.md", "!drafts/**", "drafts/ready/**"], &[]);
+        assert!(!filter.is_match(Path::new("drafts/idea.md")));
+        assert!(filter.is_match(Path::new("drafts/ready/post.md")));
+    }
+
+    #[test]
+    fn test_only_negations_include_everything_else() {
+        let filter = filter(&["!drafts/**"], &[]);
+        assert!(filter.is_match(Path::new("docs/guide.md")));
+        assert!(!filter.is_match(Path::new("drafts/idea.md")));
+    }
+
+    #[test]
+    fn test_exclude_negation_re_includes() {
+        let filter = filter(&["**/*.md"], &["vendor/**", "!vendor/docs/**"]);
+        assert!(!filter.is_match(Path::new("vendor/lib/README.md")));
+        assert!(filter.is_match(Path::new("vendor/docs/guide.md")));
+    }
+
+    #[test]
+    fn test_exclude_negation_stays_within_include() {
+        let docs = filter(&["docs/**"], &["**/*.txt", "!**/keep/**"]);
+        assert!(docs.is_match(Path::new("docs/keep/notes.txt")));
+        assert!(!docs.is_match(Path::new("vendor/keep/notes.txt")));
+        assert!(!docs.is_match(Path::new("vendor/keep/guide.md")));
+
+        let published = filter(&["**/*.md", "!drafts/**"], &["!drafts/ready/**"]);
+        assert!(!published.is_match(Path::new("drafts/ready/post.md")));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let result = PathFilter::new(&["![".to_string()], &[]);
+        assert!(matches!(result, Err(LinterError::Config(_))));
+    }
+}
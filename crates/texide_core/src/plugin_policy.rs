@@ -0,0 +1,150 @@
+//! Allow and deny lists of plugins.
+//!
+//! `allowed_plugins` and `denied_plugins` are glob patterns matched against
+//! the identifiers of a plugin known before it is loaded: the name, path or
+//! URL it is referenced by in the configuration, and the file it resolves
+//! to. A plugin is refused if any identifier matches a `denied_plugins`
+//! pattern, or if `allowed_plugins` is not empty and no identifier matches
+//! one of its patterns. The deny list wins over the allow list.
+//!
+//! Refused plugins are never loaded, so none of their code runs.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::LinterError;
+
+/// Decides which plugins may be loaded.
+#[derive(Debug, Clone)]
+pub struct PluginPolicy {
+    /// Patterns of the permitted plugins; `None` permits every plugin.
+    allowed: Option<GlobSet>,
+    /// Patterns of the forbidden plugins.
+    denied: GlobSet,
+}
+
+impl PluginPolicy {
+    /// Builds a policy from the `allowed_plugins` and `denied_plugins` lists.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if a pattern is not a valid glob.
+    pub fn new(allowed: &[String], denied: &[String]) -> Result<Self, LinterError> {
+        Ok(Self {
+            allowed: if allowed.is_empty() {
+                None
+            } else {
+                Some(build_globset(allowed)?)
+            },
+            denied: build_globset(denied)?,
+        })
+    }
+
+    /// Checks that the plugin `name`, known by `identifiers`, may be loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error naming the plugin and the list that
+    /// refuses it.
+    pub fn check(&self, name: &str, identifiers: &[&str]) -> Result<(), LinterError> {
+        if identifiers.iter().any(|id| self.denied.is_match(id)) {
+            return Err(LinterError::config(format!(
+                "Plugin '{}' is denied by denied_plugins",
+                name
+            )));
+        }
+        if let Some(allowed) = &self.allowed
+            && !identifiers.iter().any(|id| allowed.is_match(id))
+        {
+            return Err(LinterError::config(format!(
+                "Plugin '{}' is not in allowed_plugins",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for PluginPolicy {
+    fn default() -> Self {
+        Self {
+            allowed: None,
+            denied: GlobSet::empty(),
+        }
+    }
+}
+
+/// Builds a GlobSet from plugin patterns.
+fn build_globset(patterns: &[String]) -> Result<GlobSet, LinterError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| LinterError::config(format!("Invalid glob pattern: {}", e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| LinterError::config(format!("Failed to build globset: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> PluginPolicy {
+        let strings =
+            |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        PluginPolicy::new(&strings(allowed), &strings(denied)).unwrap()
+    }
+
+    #[test]
+    fn test_default_allows_everything() {
+        assert!(
+            PluginPolicy::default()
+                .check("no-todo", &["no-todo"])
+                .is_ok()
+        );
+        assert!(policy(&[], &[]).check("no-todo", &["no-todo"]).is_ok());
+    }
+
+    #[test]
+    fn test_allow_list() {
+        let policy = policy(&["acme/*", "**/approved/*.wasm"], &[]);
+
+        assert!(policy.check("acme/style", &["acme/style"]).is_ok());
+        assert!(
+            policy
+                .check("local", &["local", "/repo/approved/local.wasm"])
+                .is_ok()
+        );
+        let error = policy.check("other/style", &["other/style"]).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("'other/style' is not in allowed_plugins")
+        );
+    }
+
+    #[test]
+    fn test_deny_list_wins() {
+        let policy = policy(&["acme/*"], &["acme/untrusted", "**/vendor/**"]);
+
+        let error = policy
+            .check("acme/untrusted", &["acme/untrusted"])
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("'acme/untrusted' is denied by denied_plugins")
+        );
+        assert!(
+            policy
+                .check("acme/style", &["acme/style", "/repo/vendor/style.wasm"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(PluginPolicy::new(&[], &["[".to_string()]).is_err());
+    }
+}
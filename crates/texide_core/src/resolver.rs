@@ -12,17 +12,7 @@ impl PluginResolver {
     /// 1. `$PROJECT_ROOT/.texide/plugins/<name>.wasm`
     /// 2. `$HOME/.texide/plugins/<name>.wasm`
     pub fn resolve(name: &str, project_root: Option<&Path>) -> Option<PathBuf> {
-        // Validate plugin name to prevent path traversal
-        let path = Path::new(name);
-        let mut components = path.components();
-        match (components.next(), components.next()) {
-            (Some(std::path::Component::Normal(_)), None) => {
-                // Good: exactly one normal component
-            }
-            _ => return None,
-        }
-
-        if name.contains(std::path::is_separator) {
+        if !is_plain_name(name) {
             return None;
         }
 
@@ -46,6 +36,66 @@ impl PluginResolver {
 
         None
     }
+
+    /// Resolves a rule installed by `texide rules install` under the alias
+    /// `name` to the path of its WASM artifact.
+    ///
+    /// Looks in `$HOME/.texide/cache/plugins/<name>/<version>/`, taking
+    /// the highest installed version.
+    pub fn resolve_installed(name: &str) -> Option<PathBuf> {
+        let cache_dir = dirs::home_dir()?
+            .join(".texide")
+            .join("cache")
+            .join("plugins");
+        Self::resolve_installed_in(name, &cache_dir)
+    }
+
+    /// Resolves a rule installed under the alias `name` into `cache_dir`.
+    pub fn resolve_installed_in(name: &str, cache_dir: &Path) -> Option<PathBuf> {
+        if !is_plain_name(name) {
+            return None;
+        }
+
+        let version_dir = std::fs::read_dir(cache_dir.join(name))
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .max_by(|a, b| compare_versions(&a.file_name(), &b.file_name()))?
+            .path();
+
+        // The installer writes a single artifact next to the manifest
+        let mut artifacts: Vec<PathBuf> = std::fs::read_dir(version_dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "wasm"))
+            .collect();
+        artifacts.sort();
+        artifacts.into_iter().next()
+    }
+}
+
+/// Returns whether `name` is a single file name, so joining it to a
+/// directory cannot escape it.
+fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(std::path::Component::Normal(_)), None)
+    ) && !name.contains(std::path::is_separator)
+}
+
+/// Compares version directory names by their dot-separated numbers, so
+/// `1.10.0` is newer than `1.9.0`; other parts compare as text.
+fn compare_versions(a: &std::ffi::OsStr, b: &std::ffi::OsStr) -> std::cmp::Ordering {
+    let (a, b) = (a.to_string_lossy(), b.to_string_lossy());
+    let parts = |version: &str| -> Vec<(u64, String)> {
+        version
+            .split('.')
+            .map(|part| (part.parse().unwrap_or(0), part.to_string()))
+            .collect()
+    };
+    parts(&a).cmp(&parts(&b))
 }
 
 #[cfg(test)]
@@ -77,6 +127,30 @@ mod tests {
         assert_eq!(resolved, None);
     }
 
+    #[test]
+    fn test_resolve_installed_takes_highest_version() {
+        let dir = tempdir().unwrap();
+        for version in ["1.9.0", "1.10.0", "1.2.0"] {
+            let version_dir = dir.path().join("external").join(version);
+            fs::create_dir_all(&version_dir).unwrap();
+            fs::write(version_dir.join("rule.wasm"), "").unwrap();
+            fs::write(version_dir.join("texide-rule.json"), "{}").unwrap();
+        }
+
+        assert_eq!(
+            PluginResolver::resolve_installed_in("external", dir.path()),
+            Some(dir.path().join("external/1.10.0/rule.wasm"))
+        );
+        assert_eq!(
+            PluginResolver::resolve_installed_in("missing", dir.path()),
+            None
+        );
+        assert_eq!(
+            PluginResolver::resolve_installed_in("../external", dir.path()),
+            None
+        );
+    }
+
     #[test]
     fn test_resolve_invalid_names() {
         assert_eq!(PluginResolver::resolve("../plugin", None), None);
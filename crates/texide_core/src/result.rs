@@ -5,7 +5,7 @@ use std::time::Duration;
 
 use std::path::PathBuf;
 
-use texide_plugin::Diagnostic;
+use texide_plugin::{Diagnostic, Severity};
 
 /// Result of linting a single file.
 #[derive(Debug)]
@@ -21,6 +21,16 @@ pub struct LintResult {
 
     /// Execution time per rule.
     pub timings: HashMap<String, Duration>,
+
+    /// Time spent linting the file, including reading it from disk.
+    ///
+    /// Only set for files linted from disk.
+    pub duration: Option<Duration>,
+
+    /// Size of the file in bytes.
+    ///
+    /// Only set for files linted from disk.
+    pub bytes: Option<usize>,
 }
 
 impl LintResult {
@@ -31,6 +41,8 @@ impl LintResult {
             diagnostics,
             from_cache: false,
             timings: HashMap::new(),
+            duration: None,
+            bytes: None,
         }
     }
 
@@ -41,12 +53,23 @@ impl LintResult {
             diagnostics,
             from_cache: true,
             timings: HashMap::new(),
+            duration: None,
+            bytes: None,
         }
     }
 
-    /// Returns true if there are any errors.
+    /// Returns true if there are any error-severity diagnostics.
     pub fn has_errors(&self) -> bool {
-        !self.diagnostics.is_empty()
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    /// Returns true if any diagnostic has one of `tags`.
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.tags.iter().any(|tag| tags.contains(tag)))
     }
 
     /// Returns the number of diagnostics.
@@ -56,7 +79,6 @@ impl LintResult {
 }
 
 /// Summary of linting multiple files.
-#[allow(dead_code)]
 #[derive(Debug, Default)]
 pub struct LintSummary {
     /// Total files processed.
@@ -70,11 +92,19 @@ pub struct LintSummary {
 
     /// Files with errors.
     pub files_with_errors: usize,
+
+    /// Diagnostics per rule id.
+    pub per_rule: HashMap<String, usize>,
+
+    /// Diagnostics per severity.
+    pub per_severity: HashMap<Severity, usize>,
+
+    /// Diagnostics per tag; a diagnostic with several tags counts for each.
+    pub per_tag: HashMap<String, usize>,
 }
 
 impl LintSummary {
     /// Creates a summary from results.
-    #[allow(dead_code)]
     pub fn from_results(results: &[LintResult]) -> Self {
         let mut summary = Self::default();
 
@@ -87,10 +117,45 @@ impl LintSummary {
             if result.has_errors() {
                 summary.files_with_errors += 1;
             }
+            for diagnostic in &result.diagnostics {
+                *summary
+                    .per_rule
+                    .entry(diagnostic.rule_id.clone())
+                    .or_default() += 1;
+                *summary.per_severity.entry(diagnostic.severity).or_default() += 1;
+                for tag in &diagnostic.tags {
+                    *summary.per_tag.entry(tag.clone()).or_default() += 1;
+                }
+            }
         }
 
         summary
     }
+
+    /// Returns the per-rule counts, most frequent first (ties by rule id).
+    pub fn rules_by_frequency(&self) -> Vec<(&str, usize)> {
+        by_frequency(&self.per_rule)
+    }
+
+    /// Returns the per-tag counts, most frequent first (ties by tag).
+    pub fn tags_by_frequency(&self) -> Vec<(&str, usize)> {
+        by_frequency(&self.per_tag)
+    }
+
+    /// Returns the number of diagnostics with `severity`.
+    pub fn severity_count(&self, severity: Severity) -> usize {
+        self.per_severity.get(&severity).copied().unwrap_or(0)
+    }
+}
+
+/// Returns the entries of `counts`, most frequent first (ties by key).
+fn by_frequency(counts: &HashMap<String, usize>) -> Vec<(&str, usize)> {
+    let mut entries: Vec<_> = counts
+        .iter()
+        .map(|(key, count)| (key.as_str(), *count))
+        .collect();
+    entries.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    entries
 }
 
 #[cfg(test)]
@@ -194,6 +259,69 @@ mod tests {
         assert_eq!(summary.files_with_errors, 2);
     }
 
+    #[test]
+    fn test_lint_summary_per_rule_and_severity() {
+        let diagnostic = |rule: &str, severity| {
+            Diagnostic::new(rule, "msg", Span::new(0, 1)).with_severity(severity)
+        };
+        let results = vec![
+            LintResult::new(
+                PathBuf::from("a.md"),
+                vec![
+                    diagnostic("spelling", Severity::Warning),
+                    diagnostic("spelling", Severity::Warning),
+                    diagnostic("no-todo", Severity::Error),
+                ],
+            ),
+            LintResult::new(
+                PathBuf::from("b.md"),
+                vec![
+                    diagnostic("spelling", Severity::Info),
+                    diagnostic("image-alt-text", Severity::Error),
+                ],
+            ),
+            LintResult::new(PathBuf::from("c.md"), vec![]),
+        ];
+
+        let summary = LintSummary::from_results(&results);
+
+        assert_eq!(summary.per_rule["spelling"], 3);
+        assert_eq!(summary.per_rule["no-todo"], 1);
+        assert_eq!(summary.per_rule["image-alt-text"], 1);
+        assert_eq!(
+            summary.rules_by_frequency(),
+            [("spelling", 3), ("image-alt-text", 1), ("no-todo", 1)]
+        );
+        assert_eq!(summary.severity_count(Severity::Error), 2);
+        assert_eq!(summary.severity_count(Severity::Warning), 2);
+        assert_eq!(summary.severity_count(Severity::Info), 1);
+    }
+
+    #[test]
+    fn test_lint_summary_per_tag() {
+        let diagnostic = |tags: &[&str]| {
+            Diagnostic::new("rule", "msg", Span::new(0, 1))
+                .with_tags(tags.iter().map(|tag| tag.to_string()).collect())
+        };
+        let results = vec![LintResult::new(
+            PathBuf::from("a.md"),
+            vec![
+                diagnostic(&["style"]),
+                diagnostic(&["style", "accessibility"]),
+                diagnostic(&[]),
+            ],
+        )];
+
+        let summary = LintSummary::from_results(&results);
+
+        assert_eq!(
+            summary.tags_by_frequency(),
+            [("style", 2), ("accessibility", 1)]
+        );
+        assert!(results[0].has_tags(&["accessibility".to_string()]));
+        assert!(!results[0].has_tags(&["correctness".to_string()]));
+    }
+
     #[test]
     fn test_lint_summary_default() {
         let summary = LintSummary::default();
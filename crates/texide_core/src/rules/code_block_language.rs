@@ -0,0 +1,185 @@
+//! code-block-language rule: Require fenced code blocks to declare an
+//! allowed language.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | require_language | boolean | true | Report fenced code blocks without a language |
+//! | allowed | string[] | [] | Allowed languages (case-insensitive); empty allows any language |
+//!
+//! Indented code blocks cannot declare a language and are never reported.
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::TxtNode;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "code-block-language";
+
+/// Flags fenced code blocks with a missing or disallowed language.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CodeBlockLanguage;
+
+/// Configuration for the code-block-language rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Report fenced code blocks without a language.
+    require_language: bool,
+    /// Allowed languages; empty allows any language.
+    allowed: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            require_language: true,
+            allowed: Vec::new(),
+        }
+    }
+}
+
+impl NativeRule for CodeBlockLanguage {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require fenced code blocks to declare an allowed language"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let mut checker = CodeBlockChecker {
+            config: context.options_as(RULE_ID),
+            source: context.source,
+            diagnostics: Vec::new(),
+        };
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+struct CodeBlockChecker<'s> {
+    config: Config,
+    source: &'s str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Visitor<'a> for CodeBlockChecker<'_> {
+    fn visit_code_block(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        if !is_fenced(self.source, node) {
+            return ControlFlow::Continue(());
+        }
+
+        let diagnostic = match node.data.lang {
+            None if self.config.require_language => {
+                Diagnostic::new(RULE_ID, "Code block has no language", node.span)
+                    .with_message_key("code-block-language.missing")
+            }
+            Some(lang)
+                if !self.config.allowed.is_empty()
+                    && !self
+                        .config
+                        .allowed
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(lang)) =>
+            {
+                let allowed = self.config.allowed.join(", ");
+                Diagnostic::new(
+                    RULE_ID,
+                    format!(
+                        "Code block language '{}' is not allowed (allowed: {})",
+                        lang, allowed
+                    ),
+                    node.span,
+                )
+                .with_message_key("code-block-language.not-allowed")
+                .with_message_param("lang", lang)
+                .with_message_param("allowed", allowed)
+            }
+            _ => return ControlFlow::Continue(()),
+        };
+
+        self.diagnostics
+            .push(diagnostic.with_severity(Severity::Warning));
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns whether a code block node was written with ``` or ~~~ fences.
+fn is_fenced(source: &str, node: &TxtNode<'_>) -> bool {
+    let text = node.text(source).trim_start();
+    text.starts_with("```") || text.starts_with("~~~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&CodeBlockLanguage, source, options)
+    }
+
+    #[test]
+    fn test_unlabeled_block() {
+        let source = "# Title\n\n```\nplain\n```\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(diagnostics[0].message, "Code block has no language");
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..diagnostics[0].span.end as usize],
+            "```\nplain\n```"
+        );
+    }
+
+    #[test]
+    fn test_unlabeled_block_allowed_when_not_required() {
+        let source = "```\nplain\n```\n";
+        let diagnostics = lint(source, json!({ "require_language": false }));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_disallowed_language() {
+        let source = "~~~perl\nprint 1;\n~~~\n";
+        let diagnostics = lint(source, json!({ "allowed": ["rust", "sh"] }));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Code block language 'perl' is not allowed (allowed: rust, sh)"
+        );
+    }
+
+    #[test]
+    fn test_allowed_language() {
+        let source = "```Rust\nfn main() {}\n```\n";
+        let diagnostics = lint(source, json!({ "allowed": ["rust", "sh"] }));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_any_language_allowed_by_default() {
+        let source = "```haskell\nmain = pure ()\n```\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_indented_block_is_ignored() {
+        let source = "Paragraph\n\n    indented code\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+}
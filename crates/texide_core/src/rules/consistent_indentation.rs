@@ -0,0 +1,253 @@
+//! consistent-indentation rule: Require one kind of whitespace for indentation.
+//!
+//! Some Markdown parsers expand tabs differently, so a list indented with
+//! tabs in one place and spaces in another can nest differently from one
+//! renderer to the next. The AST drops indentation, so it is read from
+//! `RuleContext::source`; the AST tells which lines belong to code
+//! blocks, HTML and frontmatter, whose whitespace is left alone.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | style | string | "spaces" | `spaces` or `tabs` |
+//! | width | integer | 4 | Columns per tab stop |
+//!
+//! Under `tabs`, indentation is written as one tab per full tab stop
+//! followed by the remaining columns as spaces, so an indent narrower
+//! than `width` is accepted as spaces.
+//!
+//! Each diagnostic carries a fix that rewrites the indentation in the
+//! configured style, keeping its width in columns.
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "consistent-indentation";
+
+/// Flags indentation that mixes in the other kind of whitespace.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsistentIndentation;
+
+/// Whitespace used for indentation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Style {
+    /// Spaces only.
+    #[default]
+    Spaces,
+    /// Tabs, with spaces for what is left of the last tab stop.
+    Tabs,
+}
+
+/// Configuration for the consistent-indentation rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Whitespace used for indentation.
+    style: Style,
+    /// Columns per tab stop.
+    width: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            style: Style::Spaces,
+            width: 4,
+        }
+    }
+}
+
+impl NativeRule for ConsistentIndentation {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require consistent tabs or spaces for indentation"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        let width = config.width.max(1);
+
+        let mut collector = VerbatimCollector::default();
+        let _ = collector.visit_node(ast);
+
+        let mut diagnostics = Vec::new();
+        let mut offset = 0;
+        for line in context.source.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+
+            let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+            let rest = line[indent_len..].trim_end_matches(['\n', '\r']);
+            if indent_len == 0 || rest.is_empty() {
+                continue;
+            }
+
+            // The span of an indented code block starts after the
+            // indentation of its first line
+            let span = Span::new(start as u32, (start + indent_len) as u32);
+            if collector
+                .spans
+                .iter()
+                .any(|block| block.start <= span.end && span.end < block.end)
+            {
+                continue;
+            }
+
+            let indent = &line[..indent_len];
+            let expected = normalize(indent, config.style, width);
+            if indent == expected {
+                continue;
+            }
+
+            let (message, key) = match config.style {
+                Style::Spaces => (
+                    "Indentation should use spaces, not tabs",
+                    "consistent-indentation.spaces",
+                ),
+                Style::Tabs => (
+                    "Indentation should use tabs, not spaces",
+                    "consistent-indentation.tabs",
+                ),
+            };
+            diagnostics.push(
+                Diagnostic::new(RULE_ID, message, span)
+                    .with_message_key(key)
+                    .with_severity(Severity::Warning)
+                    .with_fix(Fix::new(span, expected)),
+            );
+        }
+
+        diagnostics
+    }
+}
+
+/// Returns `indent` rewritten in `style`, with the same width in columns.
+fn normalize(indent: &str, style: Style, width: usize) -> String {
+    let columns = indent.chars().fold(0, |column, c| match c {
+        '\t' => column + width - column % width,
+        _ => column + 1,
+    });
+    match style {
+        Style::Spaces => " ".repeat(columns),
+        Style::Tabs => "\t".repeat(columns / width) + &" ".repeat(columns % width),
+    }
+}
+
+/// Collects the spans of code blocks, HTML and frontmatter, whose
+/// indentation is not Markdown's.
+#[derive(Default)]
+struct VerbatimCollector {
+    spans: Vec<Span>,
+}
+
+impl<'a> Visitor<'a> for VerbatimCollector {
+    fn visit_code_block(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.spans.push(node.span);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_html(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.spans.push(node.span);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_front_matter(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.spans.push(node.span);
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::{fixed_with, lint_with};
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&ConsistentIndentation, source, options)
+    }
+
+    fn fixed(source: &str, options: Value) -> String {
+        fixed_with(&ConsistentIndentation, source, options)
+    }
+
+    #[test]
+    fn test_tab_indented_list_under_spaces() {
+        let source = "- item\n\t- nested\n  \t- deeper\n    - fine\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Indentation should use spaces, not tabs"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(7, 8));
+        assert_eq!(diagnostics[1].span, Span::new(17, 20));
+        assert_eq!(
+            fixed(source, Value::Null),
+            "- item\n    - nested\n    - deeper\n    - fine\n"
+        );
+        assert_eq!(
+            fixed(source, json!({ "width": 2 })),
+            "- item\n  - nested\n    - deeper\n    - fine\n"
+        );
+    }
+
+    #[test]
+    fn test_spaces_under_tabs() {
+        let source = "- item\n    - nested\n  - narrow\n\t- fine\n";
+        let options = json!({ "style": "tabs" });
+
+        let diagnostics = lint(source, options.clone());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Indentation should use tabs, not spaces"
+        );
+        assert_eq!(
+            fixed(source, options),
+            "- item\n\t- nested\n  - narrow\n\t- fine\n"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks_are_left_alone() {
+        let source =
+            "\tindented code\n\n```\n\tcode\n  \tmore\n```\n\n- item\n\n  ```\n  \tcode\n  ```\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_frontmatter_and_html_are_left_alone() {
+        let source =
+            "---\nauthors:\n\t- Ada\n---\n\n<div>\n\t<p>Hi</p>\n</div>\n\n- item\n\t- nested\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..],
+            "\t- nested\n"
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_are_skipped() {
+        assert!(lint("Text\n\t\n  \nMore\n", Value::Null).is_empty());
+    }
+}
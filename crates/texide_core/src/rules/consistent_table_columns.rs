@@ -0,0 +1,186 @@
+//! consistent-table-columns rule: Require every table row to have as many
+//! cells as the header row.
+//!
+//! Markdown renderers silently pad short rows and drop extra cells, so a
+//! ragged table usually means a missing or stray `|`. Tables without any
+//! body rows are also reported.
+
+use std::ops::ControlFlow;
+
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{NodeType, TxtNode};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "consistent-table-columns";
+
+/// Flags table rows whose cell count differs from the header row.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsistentTableColumns;
+
+impl NativeRule for ConsistentTableColumns {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require table rows to have the same number of cells as the header row"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, _context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let mut checker = TableChecker::default();
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+#[derive(Default)]
+struct TableChecker {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Visitor<'a> for TableChecker {
+    fn visit_table(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        let mut rows = node
+            .children
+            .iter()
+            .filter(|child| child.node_type == NodeType::TableRow);
+
+        let Some(header) = rows.next() else {
+            self.report(
+                Diagnostic::new(RULE_ID, "Table is empty", node.span)
+                    .with_message_key("consistent-table-columns.empty"),
+            );
+            return ControlFlow::Continue(());
+        };
+        let expected = cell_count(header);
+
+        let mut body_rows = 0;
+        for row in rows {
+            body_rows += 1;
+            let actual = cell_count(row);
+            if actual != expected {
+                let (cells, key) = if actual == 1 {
+                    ("cell", "consistent-table-columns.row-cell")
+                } else {
+                    ("cells", "consistent-table-columns.row-cells")
+                };
+                self.report(
+                    Diagnostic::new(
+                        RULE_ID,
+                        format!(
+                            "Table row has {} {} but the header has {}",
+                            actual, cells, expected
+                        ),
+                        row.span,
+                    )
+                    .with_message_key(key)
+                    .with_message_param("actual", actual)
+                    .with_message_param("expected", expected),
+                );
+            }
+        }
+
+        if body_rows == 0 {
+            self.report(
+                Diagnostic::new(RULE_ID, "Table has a header but no rows", node.span)
+                    .with_message_key("consistent-table-columns.no-rows"),
+            );
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+impl TableChecker {
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics
+            .push(diagnostic.with_severity(Severity::Warning));
+    }
+}
+
+fn cell_count(row: &TxtNode<'_>) -> usize {
+    row.children
+        .iter()
+        .filter(|child| child.node_type == NodeType::TableCell)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::Value;
+    use texide_ast::Span;
+
+    fn lint(source: &str) -> Vec<Diagnostic> {
+        lint_with(&ConsistentTableColumns, source, Value::Null)
+    }
+
+    #[test]
+    fn test_well_formed_table() {
+        let source = "| a | b |\n| - | - |\n| 1 | 2 |\n| 3 | 4 |\n";
+        assert!(lint(source).is_empty());
+    }
+
+    #[test]
+    fn test_ragged_table() {
+        let source = "| a | b | c |\n| - | - | - |\n| 1 | 2 |\n| 1 | 2 | 3 |\n| 1 | 2 | 3 | 4 |\n";
+        let diagnostics = lint(source);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Table row has 2 cells but the header has 3"
+        );
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..diagnostics[0].span.end as usize],
+            "| 1 | 2 |"
+        );
+        assert_eq!(
+            diagnostics[1].message,
+            "Table row has 4 cells but the header has 3"
+        );
+        assert_eq!(
+            &source[diagnostics[1].span.start as usize..diagnostics[1].span.end as usize],
+            "| 1 | 2 | 3 | 4 |"
+        );
+    }
+
+    #[test]
+    fn test_table_without_rows() {
+        let source = "| a | b |\n| - | - |\n";
+        let diagnostics = lint(source);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Table has a header but no rows");
+    }
+
+    #[test]
+    fn test_empty_table_node() {
+        let table = TxtNode::new_parent(NodeType::Table, Span::new(0, 0), &[]);
+        let doc_children = [table];
+        let doc = TxtNode::new_parent(NodeType::Document, Span::new(0, 0), &doc_children);
+        let context = RuleContext {
+            source: "",
+            path: None,
+            base_dir: None,
+            options: &Value::Null,
+        };
+
+        let diagnostics = ConsistentTableColumns.lint(&doc, &context);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Table is empty");
+    }
+
+    #[test]
+    fn test_ignores_documents_without_tables() {
+        assert!(lint("# Title\n\nSome | pipes | here\n").is_empty());
+    }
+}
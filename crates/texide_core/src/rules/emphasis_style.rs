@@ -0,0 +1,312 @@
+//! emphasis-style rule: Require one marker for emphasis and strong emphasis.
+//!
+//! Markdown writes emphasis as `*text*` or `_text_` and strong emphasis as
+//! `**text**` or `__text__`. The AST does not record which was used, so the
+//! marker is read from the source at the node's span, which covers the
+//! delimiters.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | emphasis | string | "consistent" | `consistent` (the marker of the first emphasis in the file), `asterisk`, or `underscore` |
+//! | strong | string | "consistent" | The same, for strong emphasis |
+//!
+//! Emphasis inside a word (`un*frigging*believable`) only works with
+//! asterisks, so it is never reported for not using underscores.
+//!
+//! Each diagnostic carries a fix that rewrites the markers; the fix of
+//! emphasis nested inside another reported one is part of the outer fix.
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor, walk_children};
+use texide_ast::{NodeType, Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "emphasis-style";
+
+/// Flags emphasis whose marker differs from the configured style.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmphasisStyle;
+
+/// Marker style for one kind of emphasis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Style {
+    /// Whichever marker the first occurrence in the file uses.
+    #[default]
+    Consistent,
+    /// `*` and `**`.
+    Asterisk,
+    /// `_` and `__`.
+    Underscore,
+}
+
+/// Configuration for the emphasis-style rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Marker style for emphasis.
+    emphasis: Style,
+    /// Marker style for strong emphasis.
+    strong: Style,
+}
+
+impl NativeRule for EmphasisStyle {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require a consistent marker for emphasis and strong emphasis"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        let source = context.source;
+
+        let mut collector = EmphasisCollector {
+            source,
+            found: Vec::new(),
+        };
+        let _ = collector.visit_node(ast);
+
+        let expected = |strong: bool| {
+            let style = if strong {
+                config.strong
+            } else {
+                config.emphasis
+            };
+            match style {
+                Style::Asterisk => Some(b'*'),
+                Style::Underscore => Some(b'_'),
+                Style::Consistent => collector
+                    .found
+                    .iter()
+                    .find(|found| found.strong == strong)
+                    .map(|found| found.marker),
+            }
+        };
+        let deviations: Vec<&Found> = collector
+            .found
+            .iter()
+            .filter(|found| {
+                expected(found.strong).is_some_and(|marker| {
+                    marker != found.marker && (marker == b'*' || !found.intraword)
+                })
+            })
+            .collect();
+
+        deviations
+            .iter()
+            .map(|found| {
+                let (kind, width) = if found.strong {
+                    ("Strong emphasis", 2)
+                } else {
+                    ("Emphasis", 1)
+                };
+                let actual = (found.marker as char).to_string().repeat(width);
+                let wanted = (swap(found.marker) as char).to_string().repeat(width);
+                let key = if found.strong {
+                    "emphasis-style.strong"
+                } else {
+                    "emphasis-style.emphasis"
+                };
+                let diagnostic = Diagnostic::new(
+                    RULE_ID,
+                    format!("{} should use '{}' instead of '{}'", kind, wanted, actual),
+                    found.span,
+                )
+                .with_message_key(key)
+                .with_message_param("expected", &wanted)
+                .with_message_param("actual", &actual)
+                .with_severity(Severity::Warning);
+
+                // The fix of the outermost deviation also rewrites the ones
+                // nested inside it, whose fixes would overlap
+                let nested = deviations.iter().any(|outer| {
+                    outer.span != found.span
+                        && outer.span.start <= found.span.start
+                        && found.span.end <= outer.span.end
+                });
+                if nested {
+                    diagnostic
+                } else {
+                    diagnostic.with_fix(Fix::new(
+                        found.span,
+                        rewrite(source, found.span, &deviations),
+                    ))
+                }
+            })
+            .collect()
+    }
+}
+
+/// An emphasis or strong node and the marker it was written with.
+struct Found {
+    span: Span,
+    strong: bool,
+    marker: u8,
+    /// Whether the node touches a letter or digit on either side.
+    intraword: bool,
+}
+
+impl Found {
+    /// Returns the spans of the opening and closing delimiters.
+    fn delimiters(&self) -> [Span; 2] {
+        let width = if self.strong { 2 } else { 1 };
+        [
+            Span::new(self.span.start, self.span.start + width),
+            Span::new(self.span.end - width, self.span.end),
+        ]
+    }
+}
+
+/// Returns the other marker.
+fn swap(marker: u8) -> u8 {
+    if marker == b'*' { b'_' } else { b'*' }
+}
+
+/// Returns the source of `span` with the delimiters of every deviation
+/// inside it swapped.
+fn rewrite(source: &str, span: Span, deviations: &[&Found]) -> String {
+    let mut text = source[span.start as usize..span.end as usize].to_string();
+    for found in deviations {
+        if found.span.start < span.start || found.span.end > span.end {
+            continue;
+        }
+        let marker = (swap(found.marker) as char).to_string();
+        for delimiter in found.delimiters() {
+            let start = (delimiter.start - span.start) as usize;
+            let end = (delimiter.end - span.start) as usize;
+            text.replace_range(start..end, &marker.repeat(end - start));
+        }
+    }
+    text
+}
+
+/// Collects emphasis and strong nodes in document order.
+struct EmphasisCollector<'s> {
+    source: &'s str,
+    found: Vec<Found>,
+}
+
+impl EmphasisCollector<'_> {
+    fn collect(&mut self, node: &TxtNode<'_>) {
+        let strong = node.node_type == NodeType::Strong;
+        let (start, end) = (node.span.start as usize, node.span.end as usize);
+        let Some(&marker) = self
+            .source
+            .as_bytes()
+            .get(start)
+            .filter(|b| matches!(b, b'*' | b'_'))
+        else {
+            return;
+        };
+
+        let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        let intraword = is_word(self.source[..start].chars().next_back())
+            || is_word(self.source[end..].chars().next());
+        self.found.push(Found {
+            span: node.span,
+            strong,
+            marker,
+            intraword,
+        });
+    }
+}
+
+impl<'a> Visitor<'a> for EmphasisCollector<'_> {
+    fn visit_emphasis(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.collect(node);
+        walk_children(self, node)
+    }
+
+    fn visit_strong(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.collect(node);
+        walk_children(self, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::{fixed_with, lint_with};
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&EmphasisStyle, source, options)
+    }
+
+    fn fixed(source: &str, options: Value) -> String {
+        fixed_with(&EmphasisStyle, source, options)
+    }
+
+    #[test]
+    fn test_consistent_follows_first_marker() {
+        let source = "Some *em* and _em_, then __strong__ and **strong**.\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Emphasis should use '*' instead of '_'"
+        );
+        assert_eq!(
+            diagnostics[1].message,
+            "Strong emphasis should use '__' instead of '**'"
+        );
+        assert_eq!(
+            fixed(source, Value::Null),
+            "Some *em* and *em*, then __strong__ and __strong__.\n"
+        );
+    }
+
+    #[test]
+    fn test_configured_markers() {
+        let source = "*a* _b_ **c** __d__\n";
+        let options = json!({ "emphasis": "underscore", "strong": "asterisk" });
+
+        assert_eq!(lint(source, options.clone()).len(), 2);
+        assert_eq!(fixed(source, options.clone()), "_a_ _b_ **c** **d**\n");
+        assert!(lint("_a_ **c**\n", options).is_empty());
+    }
+
+    #[test]
+    fn test_nested_emphasis_is_fixed_in_one_pass() {
+        let source = "__bold _and em_ text__ and ***both***\n";
+        let options = json!({ "emphasis": "asterisk", "strong": "asterisk" });
+
+        let diagnostics = lint(source, options.clone());
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].fix.is_some());
+        assert!(diagnostics[1].fix.is_none());
+        assert_eq!(
+            fixed(source, options),
+            "**bold *and em* text** and ***both***\n"
+        );
+    }
+
+    #[test]
+    fn test_intraword_asterisks_are_kept() {
+        let source = "un*frigging*believable and *plain*\n";
+        assert_eq!(lint(source, json!({ "emphasis": "underscore" })).len(), 1);
+        assert_eq!(
+            fixed(source, json!({ "emphasis": "underscore" })),
+            "un*frigging*believable and _plain_\n"
+        );
+    }
+
+    #[test]
+    fn test_code_is_ignored() {
+        let source = "*a* `_b_`\n\n```\n_c_\n```\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+//! final-newline rule: Control the newline at the end of a file.
+//!
+//! Like no-multiple-blank-lines, this rule scans the raw source text: the
+//! end of the file leaves no trace in the AST. `\r\n` counts as one
+//! newline, and empty files are never reported.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | mode | string | "require" | `require` (end with at least one newline), `single` (end with exactly one newline), or `forbid` (no newline at the end) |
+//!
+//! Each diagnostic carries a fix that adds the missing newline or deletes
+//! the extra ones. Added newlines match the file's line endings.
+
+use serde::Deserialize;
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "final-newline";
+
+/// Flags files whose ending does not match the configured mode.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FinalNewline;
+
+/// What a file may end with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    /// At least one newline.
+    #[default]
+    Require,
+    /// Exactly one newline.
+    Single,
+    /// No newline.
+    Forbid,
+}
+
+/// Configuration for the final-newline rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Required file ending.
+    mode: Mode,
+}
+
+impl NativeRule for FinalNewline {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require or forbid a newline at the end of files"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, _ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        let source = context.source;
+        if source.is_empty() {
+            return Vec::new();
+        }
+
+        let end = source.len() as u32;
+        let (newlines, first) = trailing_newlines(source);
+        let first = first as u32;
+
+        let diagnostic = match config.mode {
+            Mode::Require | Mode::Single if newlines == 0 => {
+                let newline = if source.contains("\r\n") {
+                    "\r\n"
+                } else {
+                    "\n"
+                };
+                Diagnostic::new(
+                    RULE_ID,
+                    "File does not end with a newline",
+                    Span::new(end, end),
+                )
+                .with_message_key("final-newline.missing")
+                .with_fix(Fix::insert(end, newline))
+            }
+            Mode::Single if newlines > 1 => {
+                // Keep the first newline, delete the rest
+                let keep = if source[first as usize..].starts_with("\r\n") {
+                    2
+                } else {
+                    1
+                };
+                let span = Span::new(first + keep, end);
+                Diagnostic::new(
+                    RULE_ID,
+                    format!("File ends with {} newlines (expected 1)", newlines),
+                    span,
+                )
+                .with_message_key("final-newline.extra")
+                .with_message_param("count", newlines)
+                .with_fix(Fix::delete(span))
+            }
+            Mode::Forbid if newlines > 0 => {
+                let span = Span::new(first, end);
+                Diagnostic::new(RULE_ID, "File must not end with a newline", span)
+                    .with_message_key("final-newline.forbidden")
+                    .with_fix(Fix::delete(span))
+            }
+            _ => return Vec::new(),
+        };
+        vec![diagnostic.with_severity(Severity::Warning)]
+    }
+}
+
+/// Counts the newlines at the end of `source`, returning the count and the
+/// offset of the first of them (`source.len()` when there are none).
+fn trailing_newlines(source: &str) -> (usize, usize) {
+    let mut count = 0;
+    let mut rest = source;
+    loop {
+        if let Some(stripped) = rest.strip_suffix("\r\n") {
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_suffix('\n') {
+            rest = stripped;
+        } else {
+            return (count, rest.len());
+        }
+        count += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply_fixes_to_content;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&FinalNewline, source, options)
+    }
+
+    /// Lints `source` in `mode` and returns the fixed text, or `None` if
+    /// nothing was reported.
+    fn fixed(source: &str, mode: &str) -> Option<String> {
+        let diagnostics = lint(source, json!({ "mode": mode }));
+        assert!(diagnostics.len() <= 1);
+        (!diagnostics.is_empty())
+            .then(|| apply_fixes_to_content(source, &diagnostics).fixed_content)
+    }
+
+    #[test]
+    fn test_require() {
+        let diagnostics = lint("# Title", Value::Null);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(diagnostics[0].message, "File does not end with a newline");
+        assert_eq!(diagnostics[0].span, Span::new(7, 7));
+
+        assert_eq!(fixed("# Title", "require").as_deref(), Some("# Title\n"));
+        assert_eq!(fixed("# Title\n", "require"), None);
+        assert_eq!(fixed("# Title\n\n\n", "require"), None);
+    }
+
+    #[test]
+    fn test_single() {
+        assert_eq!(fixed("Text", "single").as_deref(), Some("Text\n"));
+        assert_eq!(fixed("Text\n", "single"), None);
+        assert_eq!(fixed("Text\n\n\n", "single").as_deref(), Some("Text\n"));
+
+        let diagnostics = lint("Text\n\n\n", json!({ "mode": "single" }));
+        assert_eq!(
+            diagnostics[0].message,
+            "File ends with 3 newlines (expected 1)"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(5, 7));
+    }
+
+    #[test]
+    fn test_forbid() {
+        assert_eq!(fixed("Text", "forbid"), None);
+        assert_eq!(fixed("Text\n", "forbid").as_deref(), Some("Text"));
+        assert_eq!(fixed("Text\n\n", "forbid").as_deref(), Some("Text"));
+    }
+
+    #[test]
+    fn test_empty_file() {
+        for mode in ["require", "single", "forbid"] {
+            assert_eq!(fixed("", mode), None);
+        }
+    }
+
+    #[test]
+    fn test_only_newlines() {
+        assert_eq!(fixed("\n", "require"), None);
+        assert_eq!(fixed("\n\n", "single").as_deref(), Some("\n"));
+        assert_eq!(fixed("\n\n", "forbid").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert_eq!(fixed("a\r\nb", "require").as_deref(), Some("a\r\nb\r\n"));
+        assert_eq!(fixed("a\r\n\r\n", "single").as_deref(), Some("a\r\n"));
+        assert_eq!(fixed("a\r\n", "forbid").as_deref(), Some("a"));
+    }
+}
@@ -0,0 +1,261 @@
+//! frontmatter-required-fields rule: Require YAML frontmatter to define
+//! metadata fields.
+//!
+//! Static site generators read titles, dates and similar metadata from the
+//! frontmatter block at the top of a Markdown file.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | fields | string[] | ["title"] | Fields that must be present and non-empty |
+//!
+//! Markdown documents without frontmatter are reported as missing every
+//! field; files with other extensions are only checked when they have
+//! frontmatter.
+
+use serde::Deserialize;
+use serde_yaml_ng::Value;
+use texide_ast::{NodeType, Span, TxtNode};
+use texide_parser::{MarkdownParser, Parser};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "frontmatter-required-fields";
+
+/// Flags frontmatter with missing or empty required fields.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrontmatterRequiredFields;
+
+/// Configuration for the frontmatter-required-fields rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Fields that must be present and non-empty.
+    fields: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            fields: vec!["title".to_string()],
+        }
+    }
+}
+
+impl NativeRule for FrontmatterRequiredFields {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require frontmatter to define non-empty metadata fields"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        if config.fields.is_empty() {
+            return Vec::new();
+        }
+
+        let front_matter = ast
+            .children
+            .iter()
+            .find(|node| node.node_type == NodeType::FrontMatter);
+
+        let Some(node) = front_matter else {
+            if !is_markdown(context) {
+                return Vec::new();
+            }
+            let fields = config.fields.join(", ");
+            return vec![
+                warning(
+                    format!("Document has no frontmatter (required fields: {})", fields),
+                    Span::new(0, 0),
+                )
+                .with_message_key("frontmatter-required-fields.missing")
+                .with_message_param("fields", fields),
+            ];
+        };
+
+        let fields = match serde_yaml_ng::from_str::<Value>(node.value.unwrap_or_default()) {
+            Ok(Value::Mapping(fields)) => fields,
+            Ok(Value::Null) => Default::default(),
+            Ok(_) => {
+                return vec![
+                    warning(
+                        "Frontmatter must be a mapping of fields".to_string(),
+                        node.span,
+                    )
+                    .with_message_key("frontmatter-required-fields.not-mapping"),
+                ];
+            }
+            Err(e) => {
+                return vec![
+                    warning(format!("Invalid frontmatter YAML: {}", e), node.span)
+                        .with_message_key("frontmatter-required-fields.invalid-yaml")
+                        .with_message_param("error", e),
+                ];
+            }
+        };
+
+        config
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let (message, key) = match fields.get(field.as_str()) {
+                    None => (
+                        format!("Frontmatter is missing required field '{}'", field),
+                        "frontmatter-required-fields.missing-field",
+                    ),
+                    Some(value) if is_empty(value) => (
+                        format!("Frontmatter field '{}' is empty", field),
+                        "frontmatter-required-fields.empty-field",
+                    ),
+                    Some(_) => return None,
+                };
+                Some(
+                    warning(message, node.span)
+                        .with_message_key(key)
+                        .with_message_param("field", field),
+                )
+            })
+            .collect()
+    }
+}
+
+fn warning(message: String, span: Span) -> Diagnostic {
+    Diagnostic::new(RULE_ID, message, span).with_severity(Severity::Warning)
+}
+
+/// Returns whether the linted file is Markdown, assuming so when there is no path.
+fn is_markdown(context: &RuleContext<'_>) -> bool {
+    context.path.is_none_or(|path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| MarkdownParser::new().can_parse(ext))
+    })
+}
+
+/// Returns whether a YAML value counts as an empty field.
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Sequence(items) => items.is_empty(),
+        Value::Mapping(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::{lint_context, lint_with};
+    use serde_json::json;
+    use std::path::Path;
+
+    fn lint_at(source: &str, path: Option<&Path>, options: serde_json::Value) -> Vec<Diagnostic> {
+        let context = RuleContext {
+            source,
+            path,
+            base_dir: None,
+            options: &options,
+        };
+        lint_context(&FrontmatterRequiredFields, &MarkdownParser::new(), &context)
+    }
+
+    fn lint(source: &str, options: serde_json::Value) -> Vec<Diagnostic> {
+        lint_with(&FrontmatterRequiredFields, source, options)
+    }
+
+    #[test]
+    fn test_missing_title() {
+        let source = "---\ndate: 2024-01-01\n---\n\n# Post\n";
+        let diagnostics = lint(source, serde_json::Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Frontmatter is missing required field 'title'"
+        );
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..diagnostics[0].span.end as usize],
+            "---\ndate: 2024-01-01\n---"
+        );
+    }
+
+    #[test]
+    fn test_malformed_yaml() {
+        let source = "---\ntitle: [unclosed\n---\n\nBody\n";
+        let diagnostics = lint(source, serde_json::Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(
+            diagnostics[0]
+                .message
+                .starts_with("Invalid frontmatter YAML: ")
+        );
+        assert_eq!(diagnostics[0].span.start, 0);
+    }
+
+    #[test]
+    fn test_all_fields_present() {
+        let source = "---\ntitle: Hello\ndate: 2024-01-01\n---\n";
+        let diagnostics = lint(source, json!({ "fields": ["title", "date"] }));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_empty_fields() {
+        let source = "---\ntitle: \"  \"\ntags: []\ndate:\n---\n";
+        let diagnostics = lint(source, json!({ "fields": ["title", "tags", "date"] }));
+
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Frontmatter field 'title' is empty",
+                "Frontmatter field 'tags' is empty",
+                "Frontmatter field 'date' is empty",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_mapping_frontmatter() {
+        let diagnostics = lint("---\n- title\n---\n", serde_json::Value::Null);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Frontmatter must be a mapping of fields"
+        );
+    }
+
+    #[test]
+    fn test_missing_frontmatter() {
+        let diagnostics = lint_at(
+            "# Post\n",
+            Some(Path::new("post.md")),
+            serde_json::Value::Null,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Document has no frontmatter (required fields: title)"
+        );
+
+        let diagnostics = lint_at(
+            "Plain text\n",
+            Some(Path::new("notes.txt")),
+            serde_json::Value::Null,
+        );
+        assert!(diagnostics.is_empty());
+    }
+}
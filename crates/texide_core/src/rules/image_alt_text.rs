@@ -0,0 +1,173 @@
+//! image-alt-text rule: Require images to have alternative text.
+//!
+//! Screen readers announce images by their alt text, so images without one
+//! are inaccessible.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | forbid_filename | boolean | false | Also report alt text that only repeats the image file name |
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::TxtNode;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "image-alt-text";
+
+/// Flags images with empty, missing, or redundant alt text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImageAltText;
+
+/// Configuration for the image-alt-text rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Report alt text equal to the image file name.
+    forbid_filename: bool,
+}
+
+impl NativeRule for ImageAltText {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require images to have alternative text"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["accessibility"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let mut checker = AltTextChecker {
+            config: context.options_as(RULE_ID),
+            diagnostics: Vec::new(),
+        };
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+struct AltTextChecker {
+    config: Config,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl AltTextChecker {
+    fn check(&mut self, node: &TxtNode<'_>) {
+        let alt = node.data.alt.map(str::trim).unwrap_or_default();
+
+        let diagnostic = if alt.is_empty() {
+            Diagnostic::new(RULE_ID, "Image has no alt text", node.span)
+                .with_message_key("image-alt-text.missing")
+        } else if self.config.forbid_filename
+            && node.data.url.is_some_and(|url| is_file_name(alt, url))
+        {
+            Diagnostic::new(
+                RULE_ID,
+                format!("Image alt text '{}' only repeats the file name", alt),
+                node.span,
+            )
+            .with_message_key("image-alt-text.file-name")
+            .with_message_param("alt", alt)
+        } else {
+            return;
+        };
+
+        self.diagnostics
+            .push(diagnostic.with_severity(Severity::Warning));
+    }
+}
+
+impl<'a> Visitor<'a> for AltTextChecker {
+    fn visit_image(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.check(node);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_image_reference(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.check(node);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns whether `alt` is the file name of `url`, with or without extension.
+fn is_file_name(alt: &str, url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+
+    !file_name.is_empty() && (alt.eq_ignore_ascii_case(file_name) || alt.eq_ignore_ascii_case(stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&ImageAltText, source, options)
+    }
+
+    #[test]
+    fn test_empty_alt() {
+        let source = "Look: ![](images/cat.png)\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(diagnostics[0].message, "Image has no alt text");
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..diagnostics[0].span.end as usize],
+            "![](images/cat.png)"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_alt() {
+        let diagnostics = lint("![  ](cat.png)\n", Value::Null);
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_empty_alt_in_image_reference() {
+        let source = "![][cat]\n\n[cat]: cat.png\n";
+        let diagnostics = lint(source, Value::Null);
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_proper_alt() {
+        let source = "![A sleeping cat](images/cat.png)\n";
+        assert!(lint(source, json!({ "forbid_filename": true })).is_empty());
+    }
+
+    #[test]
+    fn test_file_name_alt_allowed_by_default() {
+        assert!(lint("![cat.png](images/cat.png)\n", Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_file_name_alt_forbidden() {
+        let options = json!({ "forbid_filename": true });
+
+        let diagnostics = lint("![cat.png](images/cat.png?v=2)\n", options.clone());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Image alt text 'cat.png' only repeats the file name"
+        );
+
+        assert_eq!(lint("![Cat](images/cat.png)\n", options).len(), 1);
+    }
+}
@@ -0,0 +1,201 @@
+//! max-line-length rule: Limit the display width of source lines.
+//!
+//! Like no-multiple-blank-lines, this rule scans the raw source text. Width
+//! is counted in display columns, so CJK and other wide characters count
+//! twice.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | max | integer | 80 | Maximum number of columns per line |
+//! | ignore_urls | boolean | true | Leave `http://` and `https://` URLs out of the width |
+//! | ignore_code_blocks | boolean | true | Skip lines inside code blocks |
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::no_bare_urls::find_urls;
+use super::{NativeRule, RuleContext, display_width};
+
+const RULE_ID: &str = "max-line-length";
+
+/// Flags lines wider than the configured maximum.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MaxLineLength;
+
+/// Configuration for the max-line-length rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Maximum number of columns per line.
+    max: usize,
+    /// Leave URLs out of the width.
+    ignore_urls: bool,
+    /// Skip lines inside code blocks.
+    ignore_code_blocks: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max: 80,
+            ignore_urls: true,
+            ignore_code_blocks: true,
+        }
+    }
+}
+
+impl NativeRule for MaxLineLength {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Limit the display width of lines"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        let mut collector = CodeBlockCollector::default();
+        if config.ignore_code_blocks {
+            let _ = collector.visit_node(ast);
+        }
+        // The span of an indented code block starts after the indentation
+        // of its first line, so a line is in code if it overlaps a block
+        let in_code = |start: usize, end: usize| {
+            collector
+                .spans
+                .iter()
+                .any(|span| (span.start as usize) < end && start < span.end as usize)
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut offset = 0;
+        for line in context.source.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+
+            let line = line.trim_end_matches(['\n', '\r']);
+            if in_code(start, start + line.len()) {
+                continue;
+            }
+
+            let mut width = display_width(line);
+            if config.ignore_urls {
+                width = width.saturating_sub(
+                    find_urls(line)
+                        .iter()
+                        .map(|(_, url)| display_width(url))
+                        .sum::<usize>(),
+                );
+            }
+            if width > config.max {
+                let span = Span::new(start as u32, (start + line.len()) as u32);
+                diagnostics.push(
+                    Diagnostic::new(
+                        RULE_ID,
+                        format!("Line is {} columns wide (maximum is {})", width, config.max),
+                        span,
+                    )
+                    .with_message_key("max-line-length.too-long")
+                    .with_message_param("width", width)
+                    .with_message_param("max", config.max)
+                    .with_severity(Severity::Warning),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Collects the spans of all code blocks.
+#[derive(Default)]
+struct CodeBlockCollector {
+    spans: Vec<Span>,
+}
+
+impl<'a> Visitor<'a> for CodeBlockCollector {
+    fn visit_code_block(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.spans.push(node.span);
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&MaxLineLength, source, options)
+    }
+
+    #[test]
+    fn test_long_prose_line() {
+        let source = "# Title\n\nThis sentence is longer than twenty columns.\nShort line.\n";
+        let diagnostics = lint(source, json!({ "max": 20 }));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Line is 44 columns wide (maximum is 20)"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(9, 53));
+    }
+
+    #[test]
+    fn test_wide_characters_count_twice() {
+        // Ten CJK characters take twenty columns
+        let source = "日本語のテキストです\r\n";
+        let diagnostics = lint(source, json!({ "max": 15 }));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Line is 20 columns wide (maximum is 15)"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(0, 30));
+        assert!(lint(source, json!({ "max": 20 })).is_empty());
+    }
+
+    #[test]
+    fn test_long_url_is_ignored() {
+        let source = "See https://example.com/a/very/long/path/to/a/page for details.\n";
+
+        assert!(lint(source, json!({ "max": 20 })).is_empty());
+        let options = json!({ "max": 20, "ignore_urls": false });
+        assert_eq!(lint(source, options).len(), 1);
+    }
+
+    #[test]
+    fn test_long_code_line_is_skipped() {
+        let source = "Intro\n\n```rust\nlet value = compute_something_long(argument);\n```\n";
+
+        assert!(lint(source, json!({ "max": 20 })).is_empty());
+        let options = json!({ "max": 20, "ignore_code_blocks": false });
+        assert_eq!(lint(source, options).len(), 1);
+
+        // The first line of an indented code block too
+        let source = "Intro\n\n    let value = compute_something_long(argument);\n    done();\n";
+        assert!(lint(source, json!({ "max": 20 })).is_empty());
+    }
+
+    #[test]
+    fn test_default_max() {
+        assert!(lint(&"a".repeat(80), Value::Null).is_empty());
+        assert_eq!(lint(&"a".repeat(81), Value::Null).len(), 1);
+    }
+}
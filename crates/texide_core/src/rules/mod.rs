@@ -0,0 +1,240 @@
+//! Native rules.
+//!
+//! Native rules are built into Texide and run directly on the parsed AST,
+//! without going through the WASM plugin host. Like WASM rules, they only
+//! run when enabled in the `options` map of the configuration:
+//!
+//! ```json
+//! {
+//!   "options": {
+//!     "consistent-table-columns": true
+//!   }
+//! }
+//! ```
+//!
+//! Most rules implement [`NativeRule`] and look at one document at a time.
+//! Checks that span files, such as whether a document is linked from any
+//! other, implement [`ProjectRule`] instead.
+
+mod code_block_language;
+mod consistent_indentation;
+mod consistent_table_columns;
+mod emphasis_style;
+mod final_newline;
+mod frontmatter_required_fields;
+mod image_alt_text;
+mod max_line_length;
+mod no_bare_urls;
+mod no_duplicate_headings;
+mod no_empty_file;
+mod no_invalid_links;
+mod no_multiple_blank_lines;
+mod no_trailing_punctuation;
+mod no_trailing_spaces;
+mod ordered_list_style;
+mod orphaned_document;
+mod spelling;
+#[cfg(test)]
+pub(crate) mod test_support;
+
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use texide_ast::TxtNode;
+use texide_plugin::Diagnostic;
+use tracing::warn;
+
+pub use code_block_language::CodeBlockLanguage;
+pub use consistent_indentation::ConsistentIndentation;
+pub use consistent_table_columns::ConsistentTableColumns;
+pub use emphasis_style::EmphasisStyle;
+pub use final_newline::FinalNewline;
+pub use frontmatter_required_fields::FrontmatterRequiredFields;
+pub use image_alt_text::ImageAltText;
+pub use max_line_length::MaxLineLength;
+pub use no_bare_urls::NoBareUrls;
+pub use no_duplicate_headings::NoDuplicateHeadings;
+pub use no_empty_file::NoEmptyFile;
+pub use no_invalid_links::NoInvalidLinks;
+pub use no_multiple_blank_lines::NoMultipleBlankLines;
+pub use no_trailing_punctuation::NoTrailingPunctuation;
+pub use no_trailing_spaces::NoTrailingSpaces;
+pub use ordered_list_style::OrderedListStyle;
+pub use orphaned_document::OrphanedDocument;
+pub use spelling::Spelling;
+
+/// A lint rule implemented in Rust and run in-process.
+pub trait NativeRule: Send + Sync {
+    /// Returns the rule name used in configuration and diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Returns a short description of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// Returns the categories of the rule's diagnostics, like the `tags` of
+    /// a WASM rule manifest.
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Lints a parsed document.
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic>;
+}
+
+/// Inputs available to a native rule besides the AST.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleContext<'a> {
+    /// The source text the AST was parsed from, byte for byte.
+    ///
+    /// Node spans and reported spans are byte offsets into this exact
+    /// text, so a rule can use the AST for structure and read whitespace
+    /// or markup the AST leaves out from here. When `normalize_line_endings`
+    /// or `unicode_normalize` is set this is the normalized text, and the
+    /// linter maps reported spans back to the file.
+    pub source: &'a str,
+    /// The path of the file being linted, if any.
+    pub path: Option<&'a Path>,
+    /// The directory containing the configuration file, if any.
+    pub base_dir: Option<&'a Path>,
+    /// The rule's configured options (`null` when none are set).
+    pub options: &'a serde_json::Value,
+}
+
+impl RuleContext<'_> {
+    /// Deserializes the rule options, falling back to the defaults when
+    /// none are set or they are invalid.
+    pub fn options_as<T: DeserializeOwned + Default>(&self, rule: &str) -> T {
+        options_as(self.options, rule)
+    }
+}
+
+/// A lint rule that checks the linted files together, in two phases.
+///
+/// [`collect`](ProjectRule::collect) runs on each file and returns what
+/// the rule needs to know about it. Once every file has been linted,
+/// [`finalize`](ProjectRule::finalize) gets the collected states and
+/// reports diagnostics for any of the files. States are cached with each
+/// file, so files that did not change are not parsed again.
+///
+/// Project rules run in [`Linter::lint_files`](crate::Linter::lint_files)
+/// and its variants, never when a single document is linted.
+pub trait ProjectRule: Send + Sync {
+    /// Returns the rule name used in configuration and diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Returns a short description of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// Returns the categories of the rule's diagnostics.
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Collects the rule's state for one parsed document.
+    fn collect(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> serde_json::Value;
+
+    /// Reports diagnostics, with the file each belongs to, from the states
+    /// collected for every file.
+    fn finalize(
+        &self,
+        files: &[CollectedFile],
+        context: &ProjectContext<'_>,
+    ) -> Vec<(PathBuf, Diagnostic)>;
+}
+
+/// The state a [`ProjectRule`] collected for one file.
+#[derive(Debug, Clone)]
+pub struct CollectedFile {
+    /// The file, as passed to the linter.
+    pub path: PathBuf,
+    /// What [`ProjectRule::collect`] returned for it.
+    pub state: serde_json::Value,
+}
+
+/// Inputs available to [`ProjectRule::finalize`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectContext<'a> {
+    /// The directory containing the configuration file, if any.
+    pub base_dir: Option<&'a Path>,
+    /// The rule's configured options (`null` when none are set).
+    pub options: &'a serde_json::Value,
+}
+
+impl ProjectContext<'_> {
+    /// Deserializes the rule options, falling back to the defaults when
+    /// none are set or they are invalid.
+    pub fn options_as<T: DeserializeOwned + Default>(&self, rule: &str) -> T {
+        options_as(self.options, rule)
+    }
+}
+
+fn options_as<T: DeserializeOwned + Default>(options: &serde_json::Value, rule: &str) -> T {
+    if options.is_null() {
+        return T::default();
+    }
+    serde_json::from_value(options.clone()).unwrap_or_else(|e| {
+        warn!("Invalid options for rule '{}': {}", rule, e);
+        T::default()
+    })
+}
+
+/// Returns the number of terminal columns `text` takes up.
+///
+/// Wide characters such as CJK ideographs count as two columns.
+pub(crate) fn display_width(text: &str) -> usize {
+    unicode_width::UnicodeWidthStr::width(text)
+}
+
+/// Returns every native rule shipped with Texide.
+pub fn builtin_rules() -> Vec<Box<dyn NativeRule>> {
+    vec![
+        Box::new(CodeBlockLanguage),
+        Box::new(ConsistentIndentation),
+        Box::new(ConsistentTableColumns),
+        Box::new(EmphasisStyle),
+        Box::new(FinalNewline),
+        Box::new(FrontmatterRequiredFields),
+        Box::new(ImageAltText),
+        Box::new(MaxLineLength),
+        Box::new(NoBareUrls),
+        Box::new(NoDuplicateHeadings),
+        Box::new(NoEmptyFile),
+        Box::new(NoInvalidLinks::default()),
+        Box::new(NoMultipleBlankLines),
+        Box::new(NoTrailingPunctuation),
+        Box::new(NoTrailingSpaces),
+        Box::new(OrderedListStyle),
+        Box::new(Spelling::default()),
+    ]
+}
+
+/// Returns every project rule shipped with Texide.
+pub fn builtin_project_rules() -> Vec<Box<dyn ProjectRule>> {
+    vec![Box::new(OrphanedDocument)]
+}
+
+/// Returns whether `name` is a rule shipped with Texide.
+pub(crate) fn is_builtin_rule(name: &str) -> bool {
+    builtin_rules().iter().any(|rule| rule.name() == name)
+        || builtin_project_rules()
+            .iter()
+            .any(|rule| rule.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_builtin_rule_names_are_unique() {
+        let rules = builtin_rules();
+        let project_rules = builtin_project_rules();
+        let names: HashSet<_> = rules
+            .iter()
+            .map(|rule| rule.name())
+            .chain(project_rules.iter().map(|rule| rule.name()))
+            .collect();
+        assert_eq!(names.len(), rules.len() + project_rules.len());
+    }
+}
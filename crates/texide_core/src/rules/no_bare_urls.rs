@@ -0,0 +1,275 @@
+//! no-bare-urls rule: Require URLs in prose to be written as links.
+//!
+//! Reports `http://` and `https://` URLs that appear as plain text, and
+//! GFM literal autolinks (a URL the parser turned into a link although it
+//! is not marked up as one). URLs inside links, code and HTML are left
+//! alone.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | fix_style | string | "angle" | How fixes mark up the URL: `angle` (`<url>`) or `link` (`[url](url)`) |
+//!
+//! Each reported URL carries a fix that marks it up as a link.
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-bare-urls";
+
+/// Flags URLs written as plain text instead of links.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoBareUrls;
+
+/// How a fix marks up a bare URL.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FixStyle {
+    /// `<https://example.com>`
+    #[default]
+    Angle,
+    /// `[https://example.com](https://example.com)`
+    Link,
+}
+
+/// Configuration for the no-bare-urls rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Markup used by fixes.
+    fix_style: FixStyle,
+}
+
+impl NativeRule for NoBareUrls {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow bare URLs that are not marked up as links"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        let mut checker = BareUrlChecker {
+            fix_style: config.fix_style,
+            source: context.source,
+            diagnostics: Vec::new(),
+        };
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+struct BareUrlChecker<'s> {
+    fix_style: FixStyle,
+    source: &'s str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl BareUrlChecker<'_> {
+    /// Reports the URL `url` written as `text` at `span`.
+    fn report(&mut self, span: Span, text: &str, url: &str) {
+        let replacement = match self.fix_style {
+            FixStyle::Angle if text == url => format!("<{}>", url),
+            _ => format!("[{}]({})", text, url),
+        };
+        self.diagnostics.push(
+            Diagnostic::new(
+                RULE_ID,
+                format!("Bare URL '{}' should be a link", text),
+                span,
+            )
+            .with_message_key("no-bare-urls.bare-url")
+            .with_message_param("url", text)
+            .with_severity(Severity::Warning)
+            .with_fix(Fix::new(span, replacement)),
+        );
+    }
+}
+
+impl<'a> Visitor<'a> for BareUrlChecker<'_> {
+    fn visit_link(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        // Links written as `[text](url)` or `<url>` start with their markup;
+        // literal autolinks are just the URL itself
+        let text = node.text(self.source);
+        let is_markup = text.starts_with(['[', '<']);
+        if let Some(url) = node.data.url
+            && !is_markup
+            && (url.starts_with("http://") || url.starts_with("https://"))
+        {
+            self.report(node.span, text, url);
+        }
+        // Text inside a link is never bare
+        ControlFlow::Continue(())
+    }
+
+    fn visit_link_reference(&mut self, _node: &TxtNode<'a>) -> VisitResult {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_str(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        let start = node.span.start as usize;
+        let text = node.text(self.source);
+        for (offset, url) in find_urls(text) {
+            let url_start = (start + offset) as u32;
+            let span = Span::new(url_start, url_start + url.len() as u32);
+            self.report(span, url, url);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Finds `http://` and `https://` URLs in `text`, with their byte offsets.
+///
+/// A URL runs until whitespace or an angle bracket; trailing punctuation
+/// and unbalanced closing parentheses are left out.
+pub(super) fn find_urls(text: &str) -> Vec<(usize, &str)> {
+    let mut urls = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(found) = text[search_from..].find("http") {
+        let start = search_from + found;
+        let rest = &text[start..];
+        let is_scheme = rest.starts_with("http://") || rest.starts_with("https://");
+        let at_word_start = !text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric());
+        if !is_scheme || !at_word_start {
+            search_from = start + "http".len();
+            continue;
+        }
+
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '<' || c == '>')
+            .unwrap_or(rest.len());
+        let url = trim_url_end(&rest[..len]);
+        if url.len() > "https://".len() {
+            urls.push((start, url));
+        }
+        search_from = start + len;
+    }
+
+    urls
+}
+
+/// Strips trailing punctuation that ends the sentence rather than the URL.
+fn trim_url_end(mut url: &str) -> &str {
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '"']);
+        let trimmed = match trimmed.strip_suffix(')') {
+            Some(inner) if inner.matches('(').count() < inner.matches(')').count() + 1 => inner,
+            _ => trimmed,
+        };
+        if trimmed.len() == url.len() {
+            return url;
+        }
+        url = trimmed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply_fixes_to_content;
+    use crate::rules::test_support::{lint_context, lint_with};
+    use serde_json::{Value, json};
+    use texide_parser::{MarkdownParser, ParserOptions};
+
+    fn lint_parsed(parser: MarkdownParser, source: &str, options: Value) -> Vec<Diagnostic> {
+        let context = RuleContext {
+            source,
+            path: None,
+            base_dir: None,
+            options: &options,
+        };
+        lint_context(&NoBareUrls, &parser, &context)
+    }
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&NoBareUrls, source, options)
+    }
+
+    #[test]
+    fn test_bare_url_in_prose() {
+        let source = "See https://example.com/docs for details.\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Bare URL 'https://example.com/docs' should be a link"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(4, 28));
+
+        let fixed = apply_fixes_to_content(source, &diagnostics);
+        assert_eq!(
+            fixed.fixed_content,
+            "See <https://example.com/docs> for details.\n"
+        );
+    }
+
+    #[test]
+    fn test_link_fix_style() {
+        let source = "Visit https://example.com.\n";
+        let diagnostics = lint(source, json!({ "fix_style": "link" }));
+
+        let fixed = apply_fixes_to_content(source, &diagnostics);
+        assert_eq!(
+            fixed.fixed_content,
+            "Visit [https://example.com](https://example.com).\n"
+        );
+    }
+
+    #[test]
+    fn test_urls_inside_links_are_allowed() {
+        let source = "[https://example.com](https://example.com), <https://example.com>, \
+                      [docs][ref] and [the site](https://example.com).\n\n\
+                      [ref]: https://example.com/ref\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_urls_in_code_are_allowed() {
+        let source = "Run `curl https://example.com`.\n\n```sh\ncurl https://example.com\n```\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_bare_url_without_autolink_literals() {
+        let parser = MarkdownParser::with_options(ParserOptions {
+            gfm_autolink_literal: false,
+            ..ParserOptions::default()
+        });
+        let source = "Docs (at http://example.com/a_(b)) and more\n";
+        let diagnostics = lint_parsed(parser, source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        let span = diagnostics[0].span;
+        assert_eq!(
+            &source[span.start as usize..span.end as usize],
+            "http://example.com/a_(b)"
+        );
+    }
+
+    #[test]
+    fn test_find_urls() {
+        assert_eq!(
+            find_urls("a http://x.org, b (https://y.org/p?q=1)! xhttp://z.org"),
+            vec![(2, "http://x.org"), (19, "https://y.org/p?q=1")]
+        );
+        assert!(find_urls("http:// and https:/broken").is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+//! no-duplicate-headings rule: Disallow headings with the same anchor.
+//!
+//! Documentation sites link to a heading through an anchor made from its
+//! text, so two headings that give the same anchor cannot both be linked
+//! to reliably. Each heading after the first with a given anchor is
+//! reported, with the other headings as related places.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | slug_style | string | "github" | How anchors are made from heading text: `github` or `gitlab` |
+//!
+//! Both styles lowercase the text, drop punctuation other than `-` and `_`,
+//! and turn spaces into `-`; `gitlab` also collapses runs of `-`.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use texide_ast::{HeadingInfo, TxtNode, headings};
+use texide_plugin::{Diagnostic, RelatedSpan, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-duplicate-headings";
+
+/// Flags headings whose anchors collide.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoDuplicateHeadings;
+
+/// How anchors are made from heading text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SlugStyle {
+    /// github-slugger, as used by GitHub and many static site generators.
+    #[default]
+    Github,
+    /// GitLab Flavored Markdown.
+    Gitlab,
+}
+
+/// Configuration for the no-duplicate-headings rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Slugification used to make anchors.
+    slug_style: SlugStyle,
+}
+
+impl NativeRule for NoDuplicateHeadings {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow headings that produce the same anchor"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        // Group headings by anchor, keeping document order within each group
+        let mut by_slug: HashMap<String, Vec<HeadingInfo>> = HashMap::new();
+        for heading in headings(ast) {
+            let slug = slugify(&heading.text, config.slug_style);
+            if !slug.is_empty() {
+                by_slug.entry(slug).or_default().push(heading);
+            }
+        }
+
+        let mut diagnostics = Vec::new();
+        for (slug, group) in &by_slug {
+            for (i, heading) in group.iter().enumerate().skip(1) {
+                let related = group
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, other)| {
+                        RelatedSpan::new(
+                            other.span,
+                            format!("Heading '{}' with anchor '#{}'", other.text, slug),
+                        )
+                    })
+                    .collect();
+                diagnostics.push(
+                    Diagnostic::new(
+                        RULE_ID,
+                        format!(
+                            "Heading '{}' has the same anchor '#{}' as an earlier heading",
+                            heading.text, slug
+                        ),
+                        heading.span,
+                    )
+                    .with_message_key("no-duplicate-headings.duplicate")
+                    .with_message_param("heading", heading.text.as_str())
+                    .with_message_param("anchor", slug)
+                    .with_severity(Severity::Warning)
+                    .with_related(related),
+                );
+            }
+        }
+        diagnostics.sort_by_key(|d| d.span.start);
+        diagnostics
+    }
+}
+
+/// Makes the anchor for a heading's text.
+fn slugify(text: &str, style: SlugStyle) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        match c {
+            ' ' => slug.push('-'),
+            '-' | '_' => slug.push(c),
+            c if c.is_alphanumeric() => slug.push(c),
+            _ => {}
+        }
+        if style == SlugStyle::Gitlab && slug.ends_with("--") {
+            slug.pop();
+        }
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&NoDuplicateHeadings, source, options)
+    }
+
+    fn text(source: &str, span: texide_ast::Span) -> &str {
+        &source[span.start as usize..span.end as usize]
+    }
+
+    #[test]
+    fn test_same_slug() {
+        let source = "# Setup\n\n## Install\n\ntext\n\n## Install!\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Heading 'Install!' has the same anchor '#install' as an earlier heading"
+        );
+        assert_eq!(text(source, diagnostics[0].span), "## Install!");
+        assert_eq!(diagnostics[0].related.len(), 1);
+        assert_eq!(text(source, diagnostics[0].related[0].span), "## Install");
+    }
+
+    #[test]
+    fn test_headings_differing_by_case() {
+        let source = "# Getting Started\n\n# getting started\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(text(source, diagnostics[0].span), "# getting started");
+        assert_eq!(
+            text(source, diagnostics[0].related[0].span),
+            "# Getting Started"
+        );
+    }
+
+    #[test]
+    fn test_every_collision_is_related() {
+        let source = "## API\n\n## API\n\n## `api`\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(text(source, diagnostics[1].span), "## `api`");
+        let related: Vec<_> = diagnostics[1]
+            .related
+            .iter()
+            .map(|r| r.span.start)
+            .collect();
+        assert_eq!(related, vec![0, 8]);
+    }
+
+    #[test]
+    fn test_distinct_headings() {
+        let source = "# Usage\n\n## Install\n\n## Installing\n\n## !!!\n\n## ???\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_slug_styles() {
+        assert_eq!(slugify("Foo - Bar", SlugStyle::Github), "foo---bar");
+        assert_eq!(slugify("Foo - Bar", SlugStyle::Gitlab), "foo-bar");
+        assert_eq!(slugify("C++ & Rust_1", SlugStyle::Github), "c--rust_1");
+        assert_eq!(slugify("Über Café", SlugStyle::Github), "über-café");
+
+        let source = "# Foo - Bar\n\n# Foo-Bar\n";
+        assert!(lint(source, Value::Null).is_empty());
+        assert_eq!(lint(source, json!({ "slug_style": "gitlab" })).len(), 1);
+    }
+}
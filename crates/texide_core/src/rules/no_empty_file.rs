@@ -0,0 +1,169 @@
+//! no-empty-file rule: Disallow files without content.
+//!
+//! An empty file parses to a `Document` without children, so no other rule
+//! reports anything about it. This rule flags files that contain nothing
+//! but whitespace (and a byte order mark), and optionally files whose only
+//! content is frontmatter or HTML comments.
+//!
+//! Emptiness is decided from the source, not from the AST, since parser
+//! options such as `"html": "skip"` leave content out of the tree.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | ignore_frontmatter | boolean | false | Treat a file with only frontmatter as empty |
+//! | ignore_comments | boolean | false | Treat a file with only HTML comments as empty |
+
+use serde::Deserialize;
+use texide_ast::{NodeType, Span, TxtNode};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-empty-file";
+
+/// Flags files without meaningful content.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoEmptyFile;
+
+/// Configuration for the no-empty-file rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Treat a file with only frontmatter as empty.
+    ignore_frontmatter: bool,
+    /// Treat a file with only HTML comments as empty.
+    ignore_comments: bool,
+}
+
+impl NativeRule for NoEmptyFile {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow files without content"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        // Frontmatter can only be the first node
+        let body = match ast.children.first() {
+            Some(node) if config.ignore_frontmatter && node.node_type == NodeType::FrontMatter => {
+                &context.source[node.span.end as usize..]
+            }
+            _ => context.source,
+        };
+        let mut rest = body.trim_start_matches('\u{FEFF}').trim();
+        if config.ignore_comments {
+            rest = strip_comments(rest);
+        }
+        if !rest.is_empty() {
+            return Vec::new();
+        }
+
+        vec![
+            Diagnostic::new(RULE_ID, "File is empty", Span::new(0, 0))
+                .with_message_key("no-empty-file.empty")
+                .with_severity(Severity::Warning),
+        ]
+    }
+}
+
+/// Returns `text` without the HTML comments it starts with, and the
+/// whitespace around them.
+fn strip_comments(text: &str) -> &str {
+    let mut rest = text;
+    while let Some(comment) = rest.strip_prefix("<!--") {
+        match comment.split_once("-->") {
+            Some((_, after)) => rest = after.trim_start(),
+            None => break,
+        }
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_context;
+    use serde_json::{Value, json};
+    use texide_parser::{HtmlMode, MarkdownParser, Parser, ParserOptions, PlainTextParser};
+
+    fn lint_with(parser: &dyn Parser, source: &str, options: Value) -> Vec<Diagnostic> {
+        let context = RuleContext {
+            source,
+            path: None,
+            base_dir: None,
+            options: &options,
+        };
+        lint_context(&NoEmptyFile, parser, &context)
+    }
+
+    fn is_empty(source: &str, options: Value) -> bool {
+        let markdown = !lint_with(&MarkdownParser::new(), source, options.clone()).is_empty();
+        let text = !lint_with(&PlainTextParser::new(), source, options).is_empty();
+        assert_eq!(markdown, text, "parsers disagree on {:?}", source);
+        markdown
+    }
+
+    #[test]
+    fn test_empty_file() {
+        let diagnostics = lint_with(&MarkdownParser::new(), "", Value::Null);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(diagnostics[0].message, "File is empty");
+        assert_eq!(diagnostics[0].span, Span::new(0, 0));
+    }
+
+    #[test]
+    fn test_whitespace_only() {
+        assert!(is_empty("\n", Value::Null));
+        assert!(is_empty("\r\n", Value::Null));
+        assert!(is_empty("   \n\t\n  ", Value::Null));
+        assert!(is_empty("\u{FEFF}\n", Value::Null));
+        assert!(!is_empty("a", Value::Null));
+        assert!(!is_empty("\n\n  text  \n", Value::Null));
+    }
+
+    #[test]
+    fn test_frontmatter_only() {
+        let source = "---\ntitle: Draft\n---\n";
+        let parser = MarkdownParser::new();
+        assert!(lint_with(&parser, source, Value::Null).is_empty());
+        let options = json!({ "ignore_frontmatter": true });
+        assert_eq!(lint_with(&parser, source, options.clone()).len(), 1);
+        let with_body = "---\ntitle: Draft\n---\n\nBody\n";
+        assert!(lint_with(&parser, with_body, options).is_empty());
+    }
+
+    #[test]
+    fn test_comments_only() {
+        let source = "<!-- TODO: write this page -->\n";
+        let parser = MarkdownParser::new();
+        assert!(lint_with(&parser, source, Value::Null).is_empty());
+        let options = json!({ "ignore_comments": true });
+        assert_eq!(lint_with(&parser, source, options.clone()).len(), 1);
+        assert!(lint_with(&parser, "<div>Content</div>\n", options.clone()).is_empty());
+        assert!(lint_with(&parser, "<!-- Unclosed\n", options).is_empty());
+    }
+
+    #[test]
+    fn test_content_left_out_of_the_ast() {
+        let parser = MarkdownParser::with_options(ParserOptions {
+            html: HtmlMode::Skip,
+            ..ParserOptions::default()
+        });
+        let options = json!({ "ignore_comments": true, "ignore_frontmatter": true });
+        assert!(lint_with(&parser, "<div>Content</div>\n", options.clone()).is_empty());
+        assert!(lint_with(&parser, "---\ntitle: A\n---\n<p>Hi</p>\n", options.clone()).is_empty());
+        let comments = "---\ntitle: A\n---\n<!-- a -->\n\n<!-- b -->\n";
+        assert_eq!(lint_with(&parser, comments, options).len(), 1);
+    }
+}
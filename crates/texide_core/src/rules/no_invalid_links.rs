@@ -0,0 +1,823 @@
+//! no-invalid-links rule: Report malformed link URLs and dead links.
+//!
+//! Every `Link` and `Definition` URL is checked for syntax errors. Relative
+//! URLs are resolved against the directory of the linted file and must point
+//! to an existing file. External `http(s)` URLs are only requested when
+//! `check_external` is enabled and `offline` is not.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | check_relative | boolean | true | Check that relative links point to existing files |
+//! | check_external | boolean | false | Send HTTP requests to check that external links are reachable |
+//! | offline | boolean | false | Never send HTTP requests, even if `check_external` is set |
+//! | concurrency | integer | 8 | Maximum number of requests running at once |
+//! | timeout | integer | 10 | Seconds to wait for each response |
+//! | host_delay_ms | integer | 100 | Minimum milliseconds between the starts of two requests to the same host |
+//!
+//! External checks require the `link-check` feature. Each URL is requested
+//! once for the lifetime of the linter, however many files link to it.
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor, walk_children};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-invalid-links";
+
+/// Flags malformed, missing, and unreachable link targets.
+#[derive(Debug, Default)]
+pub struct NoInvalidLinks {
+    /// Results of external checks by URL (`Err` holds the failure reason).
+    /// A cell is empty while its URL is being fetched.
+    external: Mutex<HashMap<String, Arc<OnceLock<CheckResult>>>>,
+    /// HTTP client shared by all requests of the run, built on first use.
+    #[cfg(feature = "link-check")]
+    client: OnceLock<Result<reqwest::blocking::Client, String>>,
+}
+
+/// The result of an external check (`Err` holds the failure reason).
+type CheckResult = Result<(), String>;
+
+/// Configuration for the no-invalid-links rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Check that relative links point to existing files.
+    check_relative: bool,
+    /// Check that external links are reachable.
+    check_external: bool,
+    /// Disable all network access.
+    offline: bool,
+    /// Maximum number of requests running at once.
+    concurrency: usize,
+    /// Seconds to wait for each response.
+    timeout: u64,
+    /// Minimum milliseconds between two requests to the same host.
+    host_delay_ms: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            check_relative: true,
+            check_external: false,
+            offline: false,
+            concurrency: 8,
+            timeout: 10,
+            host_delay_ms: 100,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the settings of external checks.
+    fn check_settings(&self) -> CheckSettings {
+        CheckSettings {
+            concurrency: self.concurrency.max(1),
+            timeout: Duration::from_secs(self.timeout),
+            host_delay: Duration::from_millis(self.host_delay_ms),
+        }
+    }
+}
+
+/// How external URLs are requested.
+#[derive(Debug, Clone, Copy)]
+struct CheckSettings {
+    /// Maximum number of requests running at once.
+    concurrency: usize,
+    /// Time to wait for each response.
+    timeout: Duration,
+    /// Minimum time between the starts of two requests to the same host.
+    host_delay: Duration,
+}
+
+impl NativeRule for NoInvalidLinks {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow malformed link URLs and links to missing targets"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        let mut collector = LinkCollector::default();
+        let _ = collector.visit_node(ast);
+
+        let mut diagnostics = Vec::new();
+        let mut external = Vec::new();
+
+        for (url, span) in collector.links {
+            match check_syntax(url) {
+                Err(error) => diagnostics.push(error.diagnostic(url, span)),
+                Ok(UrlKind::External) => external.push((url, span)),
+                Ok(UrlKind::Relative(target)) if config.check_relative => {
+                    let Some(dir) = context.path.and_then(Path::parent) else {
+                        continue;
+                    };
+                    let target = percent_decode(target);
+                    if !dir.join(&target).exists() {
+                        diagnostics.push(
+                            diagnostic(format!("Linked file '{}' does not exist", target), span)
+                                .with_message_key("no-invalid-links.missing-file")
+                                .with_message_param("path", target),
+                        );
+                    }
+                }
+                Ok(_) => {}
+            }
+        }
+
+        if config.check_external && !config.offline && !external.is_empty() {
+            let results = self.check_external(
+                external.iter().map(|(url, _)| *url),
+                config.check_settings(),
+                &|url: &str, timeout| self.check_reachable(url, timeout),
+            );
+            for (url, span) in external {
+                if let Some(Err(reason)) = results.get(url) {
+                    diagnostics.push(
+                        diagnostic(format!("Link '{}' is unreachable: {}", url, reason), span)
+                            .with_message_key("no-invalid-links.unreachable")
+                            .with_message_param("url", url)
+                            .with_message_param("reason", reason),
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl NoInvalidLinks {
+    /// Checks the given URLs with `fetch`, reusing cached results, and
+    /// returns the result of each of them.
+    ///
+    /// The cache is only locked to claim URLs, not while fetching them. A
+    /// URL another file is already fetching is waited for rather than
+    /// requested again.
+    fn check_external<'u, F>(
+        &self,
+        urls: impl Iterator<Item = &'u str>,
+        settings: CheckSettings,
+        fetch: &F,
+    ) -> HashMap<&'u str, CheckResult>
+    where
+        F: Fn(&str, Duration) -> CheckResult + Sync,
+    {
+        let mut cells = HashMap::new();
+        let mut pending = Vec::new();
+        {
+            let mut cache = self.external.lock().unwrap_or_else(|e| e.into_inner());
+            for url in urls {
+                if cells.contains_key(url) {
+                    continue;
+                }
+                let cell = cache.entry(url.to_string()).or_insert_with(|| {
+                    pending.push(url);
+                    Arc::default()
+                });
+                cells.insert(url, Arc::clone(cell));
+            }
+        }
+
+        for (url, result) in fetch_all(&pending, settings, fetch) {
+            let _ = cells[url].set(result);
+        }
+
+        cells
+            .into_iter()
+            .map(|(url, cell)| (url, cell.wait().clone()))
+            .collect()
+    }
+}
+
+/// Fetches `urls` on at most `settings.concurrency` threads, spacing out
+/// requests to the same host by `settings.host_delay`.
+fn fetch_all<'u, F>(
+    urls: &[&'u str],
+    settings: CheckSettings,
+    fetch: &F,
+) -> Vec<(&'u str, CheckResult)>
+where
+    F: Fn(&str, Duration) -> CheckResult + Sync,
+{
+    let next = AtomicUsize::new(0);
+    // Earliest start of the next request to each host
+    let host_slots: Mutex<HashMap<&str, Instant>> = Mutex::new(HashMap::new());
+    let results = Mutex::new(Vec::with_capacity(urls.len()));
+
+    std::thread::scope(|scope| {
+        for _ in 0..settings.concurrency.min(urls.len()) {
+            scope.spawn(|| {
+                while let Some(&url) = urls.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let start = {
+                        let mut slots = host_slots.lock().unwrap_or_else(|e| e.into_inner());
+                        let now = Instant::now();
+                        let slot = slots.entry(host(url)).or_insert(now);
+                        let start = (*slot).max(now);
+                        *slot = start + settings.host_delay;
+                        start
+                    };
+                    std::thread::sleep(start.saturating_duration_since(Instant::now()));
+
+                    let result = fetch(url, settings.timeout);
+                    results
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push((url, result));
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Returns the host (with port) of an `http(s)` URL.
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    authority.rsplit('@').next().unwrap_or(authority)
+}
+
+impl NoInvalidLinks {
+    /// Requests `url` and returns an error for failed or unsuccessful
+    /// responses.
+    #[cfg(feature = "link-check")]
+    fn check_reachable(&self, url: &str, timeout: Duration) -> CheckResult {
+        let client = self
+            .client
+            .get_or_init(|| {
+                reqwest::blocking::Client::builder()
+                    .build()
+                    .map_err(|e| e.to_string())
+            })
+            .as_ref()
+            .map_err(Clone::clone)?;
+
+        let mut response = client
+            .head(url)
+            .timeout(timeout)
+            .send()
+            .map_err(|e| e.to_string())?;
+        // Some servers do not implement HEAD
+        if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED {
+            response = client
+                .get(url)
+                .timeout(timeout)
+                .send()
+                .map_err(|e| e.to_string())?;
+        }
+
+        let status = response.status();
+        if status.is_client_error() || status.is_server_error() {
+            Err(format!("HTTP {}", status))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[cfg(not(feature = "link-check"))]
+    fn check_reachable(&self, _url: &str, _timeout: Duration) -> CheckResult {
+        static WARNING: std::sync::Once = std::sync::Once::new();
+        WARNING.call_once(|| {
+            tracing::warn!(
+                "'{}' was built without the link-check feature; skipping external links",
+                RULE_ID
+            );
+        });
+        Ok(())
+    }
+}
+
+fn diagnostic(message: String, span: Span) -> Diagnostic {
+    Diagnostic::new(RULE_ID, message, span).with_severity(Severity::Warning)
+}
+
+/// Collects the URLs of links and definitions.
+#[derive(Default)]
+pub(super) struct LinkCollector<'a> {
+    pub(super) links: Vec<(&'a str, Span)>,
+}
+
+impl<'a> Visitor<'a> for LinkCollector<'a> {
+    fn visit_link(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        if let Some(url) = node.data.url {
+            self.links.push((url, node.span));
+        }
+        walk_children(self, node)
+    }
+
+    fn visit_definition(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        if let Some(url) = node.data.url {
+            self.links.push((url, node.span));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// What a syntactically valid URL points to.
+#[derive(Debug, PartialEq)]
+pub(super) enum UrlKind<'a> {
+    /// An `http` or `https` URL.
+    External,
+    /// A path relative to the linted file, without query or fragment.
+    Relative(&'a str),
+    /// Anything that is not checked further (fragments, other schemes,
+    /// root-relative paths).
+    Other,
+}
+
+/// Why a URL is malformed.
+#[derive(Debug, PartialEq)]
+pub(super) enum SyntaxError {
+    /// The URL is empty.
+    Empty,
+    /// The URL contains whitespace.
+    Whitespace,
+    /// An `http` or `https` URL has no host.
+    NoHost,
+    /// A `mailto` URL has no `@`.
+    InvalidEmail,
+}
+
+impl SyntaxError {
+    /// Creates the diagnostic for the malformed `url` at `span`.
+    fn diagnostic(&self, url: &str, span: Span) -> Diagnostic {
+        let (message, key) = match self {
+            Self::Empty => ("Link URL is empty".to_string(), "no-invalid-links.empty"),
+            Self::Whitespace => (
+                format!("Link URL '{}' contains whitespace", url),
+                "no-invalid-links.whitespace",
+            ),
+            Self::NoHost => (
+                format!("Link URL '{}' has no host", url),
+                "no-invalid-links.no-host",
+            ),
+            Self::InvalidEmail => (
+                format!("Link URL '{}' is not a valid email address", url),
+                "no-invalid-links.invalid-email",
+            ),
+        };
+        diagnostic(message, span)
+            .with_message_key(key)
+            .with_message_param("url", url)
+    }
+}
+
+/// Validates the syntax of a URL and classifies it.
+pub(super) fn check_syntax(url: &str) -> Result<UrlKind<'_>, SyntaxError> {
+    if url.is_empty() {
+        return Err(SyntaxError::Empty);
+    }
+    if url.chars().any(char::is_whitespace) {
+        return Err(SyntaxError::Whitespace);
+    }
+
+    if let Some((scheme, rest)) = split_scheme(url) {
+        return match scheme.to_ascii_lowercase().as_str() {
+            "http" | "https" => {
+                let host = rest
+                    .strip_prefix("//")
+                    .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+                match host {
+                    Some(host) if !host.is_empty() => Ok(UrlKind::External),
+                    _ => Err(SyntaxError::NoHost),
+                }
+            }
+            "mailto" if !rest.contains('@') => Err(SyntaxError::InvalidEmail),
+            _ => Ok(UrlKind::Other),
+        };
+    }
+
+    if url.starts_with('#') || url.starts_with('/') {
+        return Ok(UrlKind::Other);
+    }
+
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    Ok(UrlKind::Relative(path))
+}
+
+/// Splits `scheme:rest`, following RFC 3986 scheme syntax.
+///
+/// Single-letter schemes are treated as Windows drive letters.
+fn split_scheme(url: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = scheme.len() > 1
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some((scheme, rest))
+}
+
+/// Decodes `%XX` escapes, leaving invalid escapes as they are.
+pub(super) fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_context;
+    use serde_json::{Value, json};
+    use std::fs;
+    use texide_parser::MarkdownParser;
+
+    fn lint(source: &str, path: Option<&Path>, options: Value) -> Vec<Diagnostic> {
+        let context = RuleContext {
+            source,
+            path,
+            base_dir: None,
+            options: &options,
+        };
+        lint_context(&NoInvalidLinks::default(), &MarkdownParser::new(), &context)
+    }
+
+    #[test]
+    fn test_check_syntax() {
+        assert_eq!(check_syntax("https://example.com/a"), Ok(UrlKind::External));
+        assert_eq!(check_syntax("#section"), Ok(UrlKind::Other));
+        assert_eq!(check_syntax("/docs/a.md"), Ok(UrlKind::Other));
+        assert_eq!(check_syntax("ftp://example.com"), Ok(UrlKind::Other));
+        assert_eq!(check_syntax("mailto:a@example.com"), Ok(UrlKind::Other));
+        assert_eq!(
+            check_syntax("docs/a.md?raw=1#intro"),
+            Ok(UrlKind::Relative("docs/a.md"))
+        );
+        assert_eq!(check_syntax(""), Err(SyntaxError::Empty));
+        assert_eq!(check_syntax("a b.md"), Err(SyntaxError::Whitespace));
+        assert_eq!(check_syntax("https:/example.com"), Err(SyntaxError::NoHost));
+        assert_eq!(check_syntax("http://"), Err(SyntaxError::NoHost));
+        assert_eq!(
+            check_syntax("mailto:nobody"),
+            Err(SyntaxError::InvalidEmail)
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("my%20file.md"), "my file.md");
+        assert_eq!(percent_decode("100%.md"), "100%.md");
+    }
+
+    #[test]
+    fn test_malformed_url() {
+        let source = "See [the docs](https:/example.com).\n";
+        let diagnostics = lint(source, None, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Link URL 'https:/example.com' has no host"
+        );
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..diagnostics[0].span.end as usize],
+            "[the docs](https:/example.com)"
+        );
+    }
+
+    #[test]
+    fn test_url_with_whitespace_in_definition() {
+        let source = "[docs]\n\n[docs]: <./my docs.md>\n";
+        let diagnostics = lint(source, None, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("contains whitespace"));
+    }
+
+    #[test]
+    fn test_dead_relative_link() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("exists.md"), "# Exists\n").unwrap();
+        let path = dir.path().join("index.md");
+
+        let source = "[ok](exists.md#intro) [dead](missing.md)\n";
+        let diagnostics = lint(source, Some(&path), Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Linked file 'missing.md' does not exist"
+        );
+    }
+
+    #[test]
+    fn test_relative_check_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.md");
+
+        let source = "[dead](missing.md)\n";
+        let diagnostics = lint(source, Some(&path), json!({ "check_relative": false }));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_offline_skips_external_checks() {
+        let source = "[site](https://invalid.example/)\n";
+        let options = json!({ "check_external": true, "offline": true });
+        assert!(lint(source, None, options).is_empty());
+    }
+
+    /// Settings without a host delay.
+    fn settings(concurrency: usize) -> CheckSettings {
+        CheckSettings {
+            concurrency,
+            timeout: Duration::from_secs(1),
+            host_delay: Duration::ZERO,
+        }
+    }
+
+    /// A fake server that counts requests per URL and the most requests
+    /// in flight at once.
+    #[derive(Default)]
+    struct MockServer {
+        requests: Mutex<HashMap<String, usize>>,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl MockServer {
+        fn fetch(&self, url: &str, _timeout: Duration) -> CheckResult {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            *self
+                .requests
+                .lock()
+                .unwrap()
+                .entry(url.to_string())
+                .or_default() += 1;
+            std::thread::sleep(Duration::from_millis(20));
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if url.contains("missing") {
+                Err("HTTP 404".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_external_results_are_cached() {
+        let rule = NoInvalidLinks::default();
+        rule.external.lock().unwrap().insert(
+            "https://cached.example/".to_string(),
+            Arc::new(OnceLock::from(Err("HTTP 404".to_string()))),
+        );
+
+        let server = MockServer::default();
+        let results = rule.check_external(
+            ["https://cached.example/"].into_iter(),
+            settings(1),
+            &|url: &str, timeout| server.fetch(url, timeout),
+        );
+        assert_eq!(
+            results.get("https://cached.example/"),
+            Some(&Err("HTTP 404".to_string()))
+        );
+        assert!(server.requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_urls_are_fetched_once() {
+        let rule = NoInvalidLinks::default();
+        let server = MockServer::default();
+        let fetch = |url: &str, timeout| server.fetch(url, timeout);
+
+        // Two files linking to overlapping URLs
+        let first = [
+            "https://a.example/",
+            "https://b.example/missing",
+            "https://a.example/",
+        ];
+        let second = ["https://b.example/missing", "https://c.example/"];
+        rule.check_external(first.into_iter(), settings(4), &fetch);
+        let results = rule.check_external(second.into_iter(), settings(4), &fetch);
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.values().all(|&count| count == 1));
+        assert_eq!(
+            results.get("https://b.example/missing"),
+            Some(&Err("HTTP 404".to_string()))
+        );
+        assert_eq!(results.get("https://c.example/"), Some(&Ok(())));
+    }
+
+    #[test]
+    fn test_parallel_files_share_fetches() {
+        let rule = NoInvalidLinks::default();
+        let server = MockServer::default();
+        let fetch = |url: &str, timeout| server.fetch(url, timeout);
+
+        let results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        rule.check_external(
+                            ["https://a.example/missing"].into_iter(),
+                            settings(1),
+                            &fetch,
+                        )
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(
+            server.requests.lock().unwrap()["https://a.example/missing"],
+            1
+        );
+        for result in results {
+            assert_eq!(result.len(), 1);
+            assert_eq!(
+                result["https://a.example/missing"],
+                Err("HTTP 404".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_cache_is_not_locked_while_fetching() {
+        let rule = NoInvalidLinks::default();
+        let (started, wait) = std::sync::mpsc::channel();
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let released = Mutex::new(released);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                rule.check_external(
+                    ["https://slow.example/"].into_iter(),
+                    settings(1),
+                    &|_, _| {
+                        started.send(()).unwrap();
+                        released.lock().unwrap().recv().unwrap();
+                        Ok(())
+                    },
+                )
+            });
+            wait.recv().unwrap();
+
+            // Another file checks a different URL while the first is in flight
+            let results = rule.check_external(
+                ["https://fast.example/"].into_iter(),
+                settings(1),
+                &|_, _| Err("HTTP 500".to_string()),
+            );
+            assert_eq!(
+                results["https://fast.example/"],
+                Err("HTTP 500".to_string())
+            );
+            release.send(()).unwrap();
+        });
+    }
+
+    #[cfg(feature = "link-check")]
+    #[test]
+    fn test_check_reachable_against_local_server() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { return };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let mut parts = request.split_whitespace();
+                let (method, path) = (parts.next(), parts.next());
+                let status = match (method, path) {
+                    (_, Some("/ok")) => "200 OK",
+                    // Only answers GET
+                    (Some("HEAD"), Some("/get-only")) => "405 Method Not Allowed",
+                    (Some("GET"), Some("/get-only")) => "200 OK",
+                    _ => "404 Not Found",
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+            }
+        });
+
+        let rule = NoInvalidLinks::default();
+        let timeout = Duration::from_secs(5);
+        assert_eq!(
+            rule.check_reachable(&format!("{}/ok", base), timeout),
+            Ok(())
+        );
+        assert_eq!(
+            rule.check_reachable(&format!("{}/get-only", base), timeout),
+            Ok(())
+        );
+        assert_eq!(
+            rule.check_reachable(&format!("{}/missing", base), timeout),
+            Err("HTTP 404 Not Found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_concurrency_is_capped() {
+        let server = MockServer::default();
+        let urls: Vec<String> = (0..12)
+            .map(|i| format!("https://host{}.example/", i))
+            .collect();
+        let urls: Vec<&str> = urls.iter().map(String::as_str).collect();
+
+        let results = fetch_all(&urls, settings(3), &|url: &str, timeout| {
+            server.fetch(url, timeout)
+        });
+
+        assert_eq!(results.len(), 12);
+        assert_eq!(server.max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_requests_to_one_host_are_spaced_out() {
+        let starts = Mutex::new(Vec::new());
+        let settings = CheckSettings {
+            host_delay: Duration::from_millis(30),
+            ..settings(4)
+        };
+        let urls = [
+            "https://a.example/1",
+            "https://a.example/2",
+            "https://a.example/3",
+        ];
+
+        let before = Instant::now();
+        fetch_all(&urls, settings, &|_: &str, _| {
+            starts.lock().unwrap().push(Instant::now());
+            Ok(())
+        });
+
+        // The third request waits for two delays, whichever thread runs it
+        let last = starts.into_inner().unwrap().into_iter().max().unwrap();
+        assert!(last - before >= Duration::from_millis(60));
+    }
+
+    #[test]
+    fn test_host() {
+        assert_eq!(host("https://example.com/a?b#c"), "example.com");
+        assert_eq!(host("http://user@example.com:8080"), "example.com:8080");
+    }
+
+    #[test]
+    fn test_check_settings_from_options() {
+        let config: Config =
+            serde_json::from_value(json!({ "concurrency": 0, "timeout": 3, "host_delay_ms": 0 }))
+                .unwrap();
+        let settings = config.check_settings();
+        assert_eq!(settings.concurrency, 1);
+        assert_eq!(settings.timeout, Duration::from_secs(3));
+        assert_eq!(settings.host_delay, Duration::ZERO);
+    }
+}
@@ -0,0 +1,193 @@
+//! no-multiple-blank-lines rule: Limit runs of consecutive blank lines.
+//!
+//! Unlike most rules this one scans the raw source text, since blank lines
+//! leave no trace in the AST. Lines inside code blocks are left alone.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | max | integer | 1 | Maximum number of consecutive blank lines |
+//!
+//! Each reported run carries a fix that deletes the excess lines.
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-multiple-blank-lines";
+
+/// Flags runs of blank lines longer than the configured maximum.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoMultipleBlankLines;
+
+/// Configuration for the no-multiple-blank-lines rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Maximum number of consecutive blank lines.
+    max: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { max: 1 }
+    }
+}
+
+impl NativeRule for NoMultipleBlankLines {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow more than a maximum number of consecutive blank lines"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        let mut collector = CodeBlockCollector::default();
+        let _ = collector.visit_node(ast);
+        let in_code = |offset: usize| {
+            collector
+                .spans
+                .iter()
+                .any(|span| span.start as usize <= offset && offset < span.end as usize)
+        };
+
+        let mut diagnostics = Vec::new();
+        // Byte ranges of the blank lines in the current run
+        let mut run: Vec<(usize, usize)> = Vec::new();
+        let mut offset = 0;
+
+        let lines = context.source.split_inclusive('\n');
+        for line in lines.chain(std::iter::once("")) {
+            let start = offset;
+            offset += line.len();
+
+            let blank = !line.is_empty() && line.trim().is_empty() && !in_code(start);
+            if blank {
+                run.push((start, offset));
+                continue;
+            }
+
+            if run.len() > config.max {
+                let excess_start = run[config.max].0;
+                let excess_end = run[run.len() - 1].1;
+                let span = Span::new(excess_start as u32, excess_end as u32);
+                diagnostics.push(
+                    Diagnostic::new(
+                        RULE_ID,
+                        format!(
+                            "{} consecutive blank lines (maximum is {})",
+                            run.len(),
+                            config.max
+                        ),
+                        span,
+                    )
+                    .with_message_key("no-multiple-blank-lines.too-many")
+                    .with_message_param("count", run.len())
+                    .with_message_param("max", config.max)
+                    .with_severity(Severity::Warning)
+                    .with_fix(Fix::delete(span)),
+                );
+            }
+            run.clear();
+        }
+
+        diagnostics
+    }
+}
+
+/// Collects the spans of all code blocks.
+#[derive(Default)]
+struct CodeBlockCollector {
+    spans: Vec<Span>,
+}
+
+impl<'a> Visitor<'a> for CodeBlockCollector {
+    fn visit_code_block(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.spans.push(node.span);
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply_fixes_to_content;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&NoMultipleBlankLines, source, options)
+    }
+
+    #[test]
+    fn test_triple_blank_lines() {
+        let source = "# Title\n\n\n\nText\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "3 consecutive blank lines (maximum is 1)"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(9, 11));
+
+        let fixed = apply_fixes_to_content(source, &diagnostics);
+        assert_eq!(fixed.fixed_content, "# Title\n\nText\n");
+    }
+
+    #[test]
+    fn test_blank_lines_inside_code_block_are_ignored() {
+        let source = "```text\nfirst\n\n\n\nlast\n```\n";
+        assert!(lint(source, Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_blank_lines_around_code_block() {
+        let source = "Intro\n\n\n```\ncode\n\n\n\n```\n\n\n\nOutro\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 2);
+        let fixed = apply_fixes_to_content(source, &diagnostics);
+        assert_eq!(
+            fixed.fixed_content,
+            "Intro\n\n```\ncode\n\n\n\n```\n\nOutro\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_max() {
+        let source = "A\n\n\nB\n\n\n\nC\n";
+        let diagnostics = lint(source, json!({ "max": 2 }));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "3 consecutive blank lines (maximum is 2)"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_only_lines_and_crlf() {
+        let source = "A\r\n  \r\n\t\r\nB\r\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        let fixed = apply_fixes_to_content(source, &diagnostics);
+        assert_eq!(fixed.fixed_content, "A\r\n  \r\nB\r\n");
+    }
+}
@@ -0,0 +1,220 @@
+//! no-trailing-punctuation rule: Disallow punctuation at the end of headings.
+//!
+//! Headings are titles, not sentences, so most style guides leave off a
+//! final period, colon or comma. The heading's text is the `Str` and
+//! inline code nodes under it; only a final character that comes from a
+//! `Str` is reported, so a heading ending in inline code or an image is
+//! accepted, and one ending in a link or emphasis is checked inside it.
+//! Escaped punctuation (`\.`) is left alone.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | punctuation | string | ".,;:!。，；：！" | Characters a heading may not end with |
+//!
+//! `?` is allowed by default, since a question is a common heading; add
+//! it to `punctuation` to report it too.
+//!
+//! Each diagnostic carries a fix that deletes the character.
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{NodeType, Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-trailing-punctuation";
+
+/// Flags headings that end with punctuation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoTrailingPunctuation;
+
+/// Configuration for the no-trailing-punctuation rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Characters a heading may not end with.
+    punctuation: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            punctuation: ".,;:!。，；：！".to_string(),
+        }
+    }
+}
+
+impl NativeRule for NoTrailingPunctuation {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow punctuation at the end of headings"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        let mut checker = HeadingChecker {
+            source: context.source,
+            punctuation: &config.punctuation,
+            diagnostics: Vec::new(),
+        };
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+/// Reports headings whose text ends with punctuation.
+struct HeadingChecker<'s> {
+    source: &'s str,
+    punctuation: &'s str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl HeadingChecker<'_> {
+    /// Checks the final character of `heading`.
+    fn check(&mut self, heading: &TxtNode<'_>) {
+        let Some(last) = last_str(heading) else {
+            return;
+        };
+        let Some(source) = self
+            .source
+            .get(last.span.start as usize..last.span.end as usize)
+        else {
+            return;
+        };
+        let trimmed = source.trim_end();
+        let Some(punctuation) = trimmed.chars().next_back() else {
+            return;
+        };
+        let end = last.span.start as usize + trimmed.len();
+        // An entity gives a different character than the source, and an
+        // escaped character is written that way on purpose
+        let value_end = last
+            .value
+            .and_then(|value| value.trim_end().chars().next_back());
+        let escaped = trimmed[..trimmed.len() - punctuation.len_utf8()].ends_with('\\');
+        if !self.punctuation.contains(punctuation) || value_end != Some(punctuation) || escaped {
+            return;
+        }
+
+        let span = Span::new((end - punctuation.len_utf8()) as u32, end as u32);
+        let mut text = String::new();
+        collect_text(heading, &mut text);
+        let text = text.trim();
+        self.diagnostics.push(
+            Diagnostic::new(
+                RULE_ID,
+                format!("Heading '{}' ends with punctuation '{}'", text, punctuation),
+                span,
+            )
+            .with_message_key("no-trailing-punctuation.trailing")
+            .with_message_param("heading", text)
+            .with_message_param("punctuation", punctuation)
+            .with_severity(Severity::Warning)
+            .with_fix(Fix::delete(span)),
+        );
+    }
+}
+
+impl<'a> Visitor<'a> for HeadingChecker<'_> {
+    fn visit_header(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.check(node);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the `Str` node the text of `node` ends with, if it ends with
+/// one rather than code, an image or other markup.
+fn last_str<'a, 'b>(node: &'b TxtNode<'a>) -> Option<&'b TxtNode<'a>> {
+    let last = node.children.last()?;
+    match last.node_type {
+        NodeType::Str => Some(last),
+        NodeType::Emphasis | NodeType::Strong | NodeType::Delete | NodeType::Link => last_str(last),
+        _ => None,
+    }
+}
+
+/// Appends the text of `Str` and inline `Code` nodes under `node`.
+fn collect_text(node: &TxtNode<'_>, text: &mut String) {
+    match node.node_type {
+        NodeType::Str | NodeType::Code => text.push_str(node.value.unwrap_or("")),
+        _ => {
+            for child in node.children {
+                collect_text(child, text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::{fixed_with, lint_with};
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&NoTrailingPunctuation, source, options)
+    }
+
+    fn fixed(source: &str, options: Value) -> String {
+        fixed_with(&NoTrailingPunctuation, source, options)
+    }
+
+    #[test]
+    fn test_heading_ending_in_period() {
+        let source = "# Introduction.\n\nText.\n\nSetup:\n------\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Heading 'Introduction.' ends with punctuation '.'"
+        );
+        assert_eq!(diagnostics[0].span, Span::new(14, 15));
+        assert_eq!(
+            fixed(source, Value::Null),
+            "# Introduction\n\nText.\n\nSetup\n------\n"
+        );
+    }
+
+    #[test]
+    fn test_question_mark_is_configurable() {
+        let source = "## Why Texide?\n";
+        assert!(lint(source, Value::Null).is_empty());
+
+        let options = json!({ "punctuation": ".?" });
+        assert_eq!(lint(source, options.clone()).len(), 1);
+        assert_eq!(fixed(source, options), "## Why Texide\n");
+    }
+
+    #[test]
+    fn test_heading_ending_in_code_or_link() {
+        assert!(lint("# Call `init.`\n", Value::Null).is_empty());
+        assert!(lint("# Logo ![Texide.](logo.png)\n", Value::Null).is_empty());
+        assert!(lint("# Version 1.0 ##\n", Value::Null).is_empty());
+        assert_eq!(
+            fixed("# See *the [guide.](g.md)*\n", Value::Null),
+            "# See *the [guide](g.md)*\n"
+        );
+    }
+
+    #[test]
+    fn test_escaped_and_wide_punctuation() {
+        assert!(lint("# Semi &amp;\n", Value::Null).is_empty());
+        assert!(lint("# Dots\\.\n", Value::Null).is_empty());
+        assert_eq!(fixed("# はじめに。\n", Value::Null), "# はじめに\n");
+    }
+}
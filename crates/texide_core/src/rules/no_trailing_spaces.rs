@@ -0,0 +1,191 @@
+//! no-trailing-spaces rule: Disallow whitespace at the end of lines.
+//!
+//! Uses both inputs a native rule gets. The AST drops whitespace, so the
+//! spaces and tabs come from `RuleContext::source`; the AST tells which of
+//! them matter to Markdown, namely code block content and the two or more
+//! spaces that make a hard line break. Spans are byte offsets into that
+//! same source.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | allow_hard_breaks | boolean | true | Accept trailing spaces that make a hard line break |
+//! | ignore_code_blocks | boolean | true | Skip lines inside code blocks |
+//!
+//! Each diagnostic carries a fix that deletes the whitespace.
+
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "no-trailing-spaces";
+
+/// Flags spaces and tabs at the end of lines.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoTrailingSpaces;
+
+/// Configuration for the no-trailing-spaces rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Accept trailing spaces that make a hard line break.
+    allow_hard_breaks: bool,
+    /// Skip lines inside code blocks.
+    ignore_code_blocks: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            allow_hard_breaks: true,
+            ignore_code_blocks: true,
+        }
+    }
+}
+
+impl NativeRule for NoTrailingSpaces {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Disallow whitespace at the end of lines"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+
+        let mut collector = WhitespaceCollector::default();
+        let _ = collector.visit_node(ast);
+        let within = |spans: &[Span], span: Span| {
+            spans
+                .iter()
+                .any(|outer| outer.start <= span.start && span.end <= outer.end)
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut offset = 0;
+        for line in context.source.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+
+            let content = line.trim_end_matches(['\n', '\r']);
+            let trimmed = content.trim_end_matches([' ', '\t']);
+            if trimmed.len() == content.len() {
+                continue;
+            }
+
+            let span = Span::new(
+                (start + trimmed.len()) as u32,
+                (start + content.len()) as u32,
+            );
+            if config.ignore_code_blocks && within(&collector.code_blocks, span) {
+                continue;
+            }
+            if config.allow_hard_breaks && within(&collector.breaks, span) {
+                continue;
+            }
+
+            diagnostics.push(
+                Diagnostic::new(RULE_ID, "Line has trailing whitespace", span)
+                    .with_message_key("no-trailing-spaces.trailing")
+                    .with_severity(Severity::Warning)
+                    .with_fix(Fix::delete(span)),
+            );
+        }
+
+        diagnostics
+    }
+}
+
+/// Collects the spans of code blocks and line breaks.
+#[derive(Default)]
+struct WhitespaceCollector {
+    code_blocks: Vec<Span>,
+    breaks: Vec<Span>,
+}
+
+impl<'a> Visitor<'a> for WhitespaceCollector {
+    fn visit_code_block(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.code_blocks.push(node.span);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_break(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        self.breaks.push(node.span);
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&NoTrailingSpaces, source, options)
+    }
+
+    fn reported(source: &str, options: Value) -> Vec<&str> {
+        lint(source, options)
+            .iter()
+            .map(|d| &source[d.span.start as usize..d.span.end as usize])
+            .collect()
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        let source = "# Title \n\nText\t\n   \nDone\r\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(diagnostics[0].message, "Line has trailing whitespace");
+        assert_eq!(diagnostics[0].span, Span::new(7, 8));
+        assert_eq!(reported(source, Value::Null), vec![" ", "\t", "   "]);
+    }
+
+    #[test]
+    fn test_hard_breaks() {
+        let source = "First line  \nsecond line\n";
+        assert!(lint(source, Value::Null).is_empty());
+        assert_eq!(
+            reported(source, json!({ "allow_hard_breaks": false })),
+            vec!["  "]
+        );
+
+        // A single space makes no hard break
+        assert_eq!(reported("First line \nsecond\n", Value::Null), vec![" "]);
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let source = "```\ncode  \n```\n";
+        assert!(lint(source, Value::Null).is_empty());
+        assert_eq!(
+            reported(source, json!({ "ignore_code_blocks": false })),
+            vec!["  "]
+        );
+    }
+
+    #[test]
+    fn test_offsets_match_source_not_text_values() {
+        // Str values drop the escape and decode the entity; offsets do not
+        let source = "\\*Tom &amp; Jerry\\* \n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span, Span::new(19, 20));
+    }
+}
@@ -0,0 +1,219 @@
+//! ordered-list-style rule: Require ordered list items to be numbered
+//! consistently.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | style | string | "ordinal" | `ordinal` (`1. 2. 3.`, counting up from the list's start), `one` (`1. 1. 1.`), or `zero` (`0. 0. 0.`) |
+//!
+//! Each reported item carries a fix that rewrites its number.
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor, walk_children};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Fix, Severity};
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "ordered-list-style";
+
+/// Flags ordered list items whose number does not follow the configured style.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OrderedListStyle;
+
+/// Numbering style for ordered lists.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Style {
+    /// Items count up by one from the list's start number.
+    #[default]
+    Ordinal,
+    /// Every item is numbered 1.
+    One,
+    /// Every item is numbered 0.
+    Zero,
+}
+
+impl Style {
+    fn as_str(self) -> &'static str {
+        match self {
+            Style::Ordinal => "ordinal",
+            Style::One => "one",
+            Style::Zero => "zero",
+        }
+    }
+
+    /// Returns the number expected for the item at `index` of a list starting at `start`.
+    fn expected(self, start: u32, index: usize) -> u32 {
+        match self {
+            Style::Ordinal => start.saturating_add(index as u32),
+            Style::One => 1,
+            Style::Zero => 0,
+        }
+    }
+}
+
+/// Configuration for the ordered-list-style rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Numbering style to enforce.
+    style: Style,
+}
+
+impl NativeRule for OrderedListStyle {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require ordered list items to follow a consistent numbering style"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["style"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        let mut checker = NumberingChecker {
+            style: config.style,
+            source: context.source,
+            diagnostics: Vec::new(),
+        };
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+struct NumberingChecker<'s> {
+    style: Style,
+    source: &'s str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Visitor<'a> for NumberingChecker<'_> {
+    fn visit_list(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        if node.data.ordered == Some(true) {
+            let start = node.data.start.unwrap_or(1);
+            for (index, item) in node.children.iter().enumerate() {
+                let Some(ordinal) = item.data.ordinal else {
+                    continue;
+                };
+                let expected = self.style.expected(start, index);
+                if ordinal == expected {
+                    continue;
+                }
+
+                let digits = self.source[item.span.start as usize..]
+                    .bytes()
+                    .take_while(u8::is_ascii_digit)
+                    .count();
+                let span = Span::new(item.span.start, item.span.start + digits as u32);
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        RULE_ID,
+                        format!(
+                            "Ordered list item is numbered {} but should be {} (style: {})",
+                            ordinal,
+                            expected,
+                            self.style.as_str()
+                        ),
+                        span,
+                    )
+                    .with_message_key("ordered-list-style.wrong-number")
+                    .with_message_param("number", ordinal)
+                    .with_message_param("expected", expected)
+                    .with_message_param("style", self.style.as_str())
+                    .with_severity(Severity::Warning)
+                    .with_fix(Fix::new(span, expected.to_string())),
+                );
+            }
+        }
+
+        walk_children(self, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_with;
+    use serde_json::{Value, json};
+
+    fn lint(source: &str, options: Value) -> Vec<Diagnostic> {
+        lint_with(&OrderedListStyle, source, options)
+    }
+
+    fn reported(source: &str, diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics
+            .iter()
+            .map(|d| source[d.span.start as usize..d.span.end as usize].to_string())
+            .collect()
+    }
+
+    const ORDINAL: &str = "1. One\n2. Two\n3. Three\n4. Four\n";
+    const ONES: &str = "1. One\n1. Two\n1. Three\n1. Four\n";
+    const ZEROS: &str = "0. One\n0. Two\n0. Three\n0. Four\n";
+
+    #[test]
+    fn test_ordinal_style() {
+        let options = json!({ "style": "ordinal" });
+        assert!(lint(ORDINAL, options.clone()).is_empty());
+
+        let diagnostics = lint(ONES, options);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(reported(ONES, &diagnostics), vec!["1", "1", "1"]);
+        assert_eq!(
+            diagnostics[0].message,
+            "Ordered list item is numbered 1 but should be 2 (style: ordinal)"
+        );
+        assert_eq!(diagnostics[2].fix.as_ref().unwrap().text, "4");
+    }
+
+    #[test]
+    fn test_ordinal_style_counts_from_start() {
+        let source = "3. Three\n4. Four\n6. Six\n7. Seven\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message,
+            "Ordered list item is numbered 6 but should be 5 (style: ordinal)"
+        );
+    }
+
+    #[test]
+    fn test_one_style() {
+        let options = json!({ "style": "one" });
+        assert!(lint(ONES, options.clone()).is_empty());
+
+        let diagnostics = lint(ORDINAL, options);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(reported(ORDINAL, &diagnostics), vec!["2", "3", "4"]);
+        assert_eq!(diagnostics[0].fix.as_ref().unwrap().text, "1");
+    }
+
+    #[test]
+    fn test_zero_style() {
+        let options = json!({ "style": "zero" });
+        assert!(lint(ZEROS, options.clone()).is_empty());
+
+        let diagnostics = lint(ORDINAL, options);
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(
+            diagnostics[3].message,
+            "Ordered list item is numbered 4 but should be 0 (style: zero)"
+        );
+    }
+
+    #[test]
+    fn test_nested_and_unordered_lists() {
+        let source = "- a\n- b\n\n1. One\n   1. Nested\n   3. Nested\n2. Two\n";
+        let diagnostics = lint(source, Value::Null);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(reported(source, &diagnostics), vec!["3"]);
+    }
+}
@@ -0,0 +1,252 @@
+//! orphaned-document rule: Require every document to be linked from
+//! another one.
+//!
+//! A project rule: each file's relative links are collected, and once all
+//! files are linted, documents that no other linted document links to are
+//! reported. Entry points such as `README.md` are never reported, and a
+//! link to a directory counts as a link to its `README.md` or `index.md`.
+//! Links are only known for the linted files, so nothing is reported when
+//! fewer than two files are linted.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | entry_points | string[] | ["**/README.md", "**/index.md"] | Glob patterns of documents that need no incoming link |
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use globset::{Glob, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use texide_ast::visitor::Visitor;
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Severity};
+use tracing::warn;
+
+use super::no_invalid_links::{LinkCollector, UrlKind, check_syntax, percent_decode};
+use super::{CollectedFile, ProjectContext, ProjectRule, RuleContext};
+
+const RULE_ID: &str = "orphaned-document";
+
+/// Names of the documents a link to their directory leads to.
+const INDEX_NAMES: [&str; 2] = ["README.md", "index.md"];
+
+/// Flags documents no other document links to.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OrphanedDocument;
+
+/// Configuration for the orphaned-document rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Documents that need no incoming link.
+    entry_points: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            entry_points: vec!["**/README.md".to_string(), "**/index.md".to_string()],
+        }
+    }
+}
+
+/// What is collected for each file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LinkState {
+    /// Files the document links to, relative to the same directory as the
+    /// document's own path.
+    links: Vec<PathBuf>,
+}
+
+impl ProjectRule for OrphanedDocument {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Require every document to be linked from another document"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn collect(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> serde_json::Value {
+        let mut collector = LinkCollector::default();
+        let _ = collector.visit_node(ast);
+
+        let dir = context.path.and_then(Path::parent).unwrap_or(Path::new(""));
+        let links = collector
+            .links
+            .into_iter()
+            .filter_map(|(url, _)| match check_syntax(url) {
+                Ok(UrlKind::Relative(target)) if !target.is_empty() => {
+                    Some(normalize(&dir.join(percent_decode(target))))
+                }
+                _ => None,
+            })
+            .collect();
+        serde_json::to_value(LinkState { links }).unwrap_or_default()
+    }
+
+    fn finalize(
+        &self,
+        files: &[CollectedFile],
+        context: &ProjectContext<'_>,
+    ) -> Vec<(PathBuf, Diagnostic)> {
+        if files.len() < 2 {
+            return Vec::new();
+        }
+
+        let config: Config = context.options_as(RULE_ID);
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &config.entry_points {
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => warn!("Invalid entry point pattern '{}': {}", pattern, e),
+            }
+        }
+        let entry_points = builder.build().unwrap_or_default();
+
+        // Targets linked from each file, excluding links to itself
+        let mut linked = HashSet::new();
+        for file in files {
+            let own_path = normalize(&file.path);
+            let state: LinkState = serde_json::from_value(file.state.clone()).unwrap_or_default();
+            linked.extend(state.links.into_iter().filter(|link| *link != own_path));
+        }
+
+        files
+            .iter()
+            .filter(|file| !entry_points.is_match(&file.path))
+            .filter(|file| !is_linked(&normalize(&file.path), &linked))
+            .map(|file| {
+                let diagnostic = Diagnostic::new(
+                    RULE_ID,
+                    "Document is not linked from any other document",
+                    Span::new(0, 0),
+                )
+                .with_message_key("orphaned-document.orphaned")
+                .with_severity(Severity::Warning);
+                (file.path.clone(), diagnostic)
+            })
+            .collect()
+    }
+}
+
+/// Returns whether a link in `linked` leads to the document at `path`.
+fn is_linked(path: &Path, linked: &HashSet<PathBuf>) -> bool {
+    if linked.contains(path) {
+        return true;
+    }
+    let is_index = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| INDEX_NAMES.contains(&name));
+    is_index && path.parent().is_some_and(|dir| linked.contains(dir))
+}
+
+/// Resolves `.` and `..` components without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    normalized.pop();
+                } else {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+    use texide_ast::AstArena;
+    use texide_parser::{MarkdownParser, Parser};
+
+    fn collect(path: &str, source: &str) -> CollectedFile {
+        let arena = AstArena::new();
+        let ast = MarkdownParser::new().parse(&arena, source).unwrap();
+        let context = RuleContext {
+            source,
+            path: Some(Path::new(path)),
+            base_dir: None,
+            options: &Value::Null,
+        };
+        CollectedFile {
+            path: PathBuf::from(path),
+            state: OrphanedDocument.collect(&ast, &context),
+        }
+    }
+
+    fn orphans(files: &[CollectedFile], options: Value) -> Vec<PathBuf> {
+        let context = ProjectContext {
+            base_dir: None,
+            options: &options,
+        };
+        OrphanedDocument
+            .finalize(files, &context)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_resolves_relative_links() {
+        let file = collect(
+            "docs/guide/intro.md",
+            "[a](../api.md#top) [b](./setup%20notes.md) [c](https://example.com) [d](#x)\n\n[e]: ../../README.md\n",
+        );
+        assert_eq!(
+            file.state,
+            json!({ "links": ["docs/api.md", "docs/guide/setup notes.md", "README.md"] })
+        );
+    }
+
+    #[test]
+    fn test_unlinked_document_is_reported() {
+        let files = [
+            collect("docs/a.md", "# A\n\n[B](b.md)\n"),
+            collect("docs/b.md", "# B\n"),
+            collect("docs/c.md", "# C\n\n[myself](c.md)\n"),
+        ];
+        assert_eq!(
+            orphans(&files, Value::Null),
+            vec![PathBuf::from("docs/a.md"), PathBuf::from("docs/c.md")]
+        );
+    }
+
+    #[test]
+    fn test_entry_points_and_directory_links() {
+        let files = [
+            collect("README.md", "[Guide](guide/)\n"),
+            collect("guide/index.md", "# Guide\n"),
+            collect("start.md", "# Start\n"),
+        ];
+        assert!(orphans(&files, json!({ "entry_points": ["start.md", "README.md"] })).is_empty());
+        assert_eq!(
+            orphans(&files, Value::Null),
+            vec![PathBuf::from("start.md")]
+        );
+    }
+
+    #[test]
+    fn test_single_file_is_not_reported() {
+        assert!(orphans(&[collect("docs/a.md", "# A\n")], Value::Null).is_empty());
+    }
+}
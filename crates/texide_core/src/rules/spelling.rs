@@ -0,0 +1,539 @@
+//! spelling rule: Report words that are not in the dictionary.
+//!
+//! Words are taken from text (`Str`) nodes only, so inline code, code
+//! blocks, HTML, and link URLs are never checked. Inline math between `$`
+//! signs is skipped as well. Unknown words are reported with the closest
+//! dictionary word as a suggestion.
+//!
+//! # Dictionaries
+//!
+//! Words are loaded, one per line, from:
+//!
+//! 1. `.texide/dictionaries/<language>.txt` in the project, then
+//!    `~/.texide/dictionaries/<language>.txt`
+//! 2. The files listed in `dictionaries`
+//! 3. `.texide-dict.txt` in the project (project-specific words)
+//! 4. The `words` option
+//!
+//! Project paths are relative to the directory of the configuration file.
+//! Lines starting with `#` are comments. Matching is case-insensitive.
+//!
+//! # Configuration
+//!
+//! | Option | Type | Default | Description |
+//! |--------|------|---------|-------------|
+//! | language | string | "en" | Name of the language dictionary to load |
+//! | dictionaries | string[] | [] | Additional word list files |
+//! | words | string[] | [] | Additional accepted words |
+
+use std::collections::HashSet;
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use texide_ast::visitor::{VisitResult, Visitor};
+use texide_ast::{Span, TxtNode};
+use texide_plugin::{Diagnostic, Severity};
+use tracing::warn;
+
+use super::{NativeRule, RuleContext};
+
+const RULE_ID: &str = "spelling";
+
+/// Name of the project dictionary file.
+pub const PROJECT_DICTIONARY: &str = ".texide-dict.txt";
+
+/// Maximum edit distance for suggestions.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Flags words missing from the configured dictionaries.
+#[derive(Debug, Default)]
+pub struct Spelling {
+    /// The last loaded dictionary.
+    dictionary: Mutex<Option<CachedDictionary>>,
+}
+
+/// Configuration for the spelling rule.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Name of the language dictionary.
+    language: String,
+    /// Additional word list files.
+    dictionaries: Vec<String>,
+    /// Additional accepted words.
+    words: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            dictionaries: Vec::new(),
+            words: Vec::new(),
+        }
+    }
+}
+
+impl NativeRule for Spelling {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Report words that are not in the dictionary"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["correctness"]
+    }
+
+    fn lint(&self, ast: &TxtNode<'_>, context: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let config: Config = context.options_as(RULE_ID);
+        let base_dir = context.base_dir.unwrap_or(Path::new("."));
+
+        let dictionary = self.load_dictionary(&config, base_dir);
+        if dictionary.is_empty() {
+            return Vec::new();
+        }
+
+        let mut checker = SpellChecker {
+            source: context.source,
+            dictionary: &dictionary,
+            diagnostics: Vec::new(),
+        };
+        let _ = checker.visit_node(ast);
+        checker.diagnostics
+    }
+}
+
+impl Spelling {
+    /// Loads the dictionary files for `config` and adds its `words`,
+    /// reusing the previous dictionary when the same files and words are
+    /// requested.
+    fn load_dictionary(&self, config: &Config, base_dir: &Path) -> Arc<Dictionary> {
+        let language_file = format!("{}.txt", config.language);
+        let mut paths = Vec::new();
+
+        let local = base_dir
+            .join(".texide")
+            .join("dictionaries")
+            .join(&language_file);
+        if local.is_file() {
+            paths.push(local);
+        } else if let Some(home) = dirs::home_dir() {
+            let global = home
+                .join(".texide")
+                .join("dictionaries")
+                .join(&language_file);
+            if global.is_file() {
+                paths.push(global);
+            }
+        }
+        paths.extend(config.dictionaries.iter().map(|path| base_dir.join(path)));
+        let project = base_dir.join(PROJECT_DICTIONARY);
+        if project.is_file() {
+            paths.push(project);
+        }
+
+        let mut cached = self.dictionary.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cached.as_ref()
+            && cached.paths == paths
+            && cached.words == config.words
+        {
+            return Arc::clone(&cached.dictionary);
+        }
+
+        let mut dictionary = Dictionary::default();
+        for path in &paths {
+            match fs::read_to_string(path) {
+                Ok(content) => dictionary.extend(
+                    content
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty() && !line.starts_with('#')),
+                ),
+                Err(e) => warn!("Failed to read dictionary {}: {}", path.display(), e),
+            }
+        }
+        dictionary.extend(config.words.iter().map(String::as_str));
+
+        // Warned here rather than in `lint`, so once per configuration
+        // instead of once per file
+        if dictionary.is_empty() {
+            warn!(
+                "No dictionary found for '{}' (language '{}'); skipping spell check",
+                RULE_ID, config.language
+            );
+        }
+
+        let dictionary = Arc::new(dictionary);
+        *cached = Some(CachedDictionary {
+            paths,
+            words: config.words.clone(),
+            dictionary: Arc::clone(&dictionary),
+        });
+        dictionary
+    }
+}
+
+/// A loaded dictionary and what it was loaded from.
+#[derive(Debug)]
+struct CachedDictionary {
+    /// The word list files.
+    paths: Vec<PathBuf>,
+    /// The `words` option.
+    words: Vec<String>,
+    dictionary: Arc<Dictionary>,
+}
+
+/// A case-insensitive set of known words.
+#[derive(Debug, Default)]
+struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    fn extend<'w>(&mut self, words: impl IntoIterator<Item = &'w str>) {
+        self.words
+            .extend(words.into_iter().map(|word| word.to_lowercase()));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// Returns the closest known word within [`MAX_SUGGESTION_DISTANCE`].
+    fn suggest(&self, word: &str) -> Option<&str> {
+        let word = word.to_lowercase();
+        let len = word.chars().count();
+
+        self.words
+            .iter()
+            .filter(|candidate| candidate.chars().count().abs_diff(len) <= MAX_SUGGESTION_DISTANCE)
+            .map(|candidate| (edit_distance(&word, candidate), candidate))
+            .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .min_by(|(a, x), (b, y)| a.cmp(b).then_with(|| x.cmp(y)))
+            .map(|(_, candidate)| candidate.as_str())
+    }
+}
+
+struct SpellChecker<'d> {
+    source: &'d str,
+    dictionary: &'d Dictionary,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Visitor<'a> for SpellChecker<'_> {
+    fn visit_str(&mut self, node: &TxtNode<'a>) -> VisitResult {
+        let Some(value) = node.value else {
+            return ControlFlow::Continue(());
+        };
+        // The value has escapes and entities decoded, so its offsets only
+        // match the source when nothing was decoded
+        let text = node.text(self.source);
+        let char_spans = (text != value).then(|| char_spans(value, text));
+
+        for (offset, word) in words(value) {
+            if self.dictionary.contains(word) {
+                continue;
+            }
+
+            let (start, end) = match &char_spans {
+                None => (offset, offset + word.len()),
+                Some(None) => continue,
+                Some(Some(spans)) => {
+                    let last = offset + word.char_indices().last().map_or(0, |(i, _)| i);
+                    (spans[offset].0, spans[last].1)
+                }
+            };
+            let span = Span::new(node.span.start + start as u32, node.span.start + end as u32);
+            let diagnostic = match self.dictionary.suggest(word) {
+                Some(suggestion) => Diagnostic::new(
+                    RULE_ID,
+                    format!("Unknown word '{}' (did you mean '{}'?)", word, suggestion),
+                    span,
+                )
+                .with_message_key("spelling.unknown-suggestion")
+                .with_message_param("suggestion", suggestion),
+                None => Diagnostic::new(RULE_ID, format!("Unknown word '{}'", word), span)
+                    .with_message_key("spelling.unknown"),
+            };
+            self.diagnostics.push(
+                diagnostic
+                    .with_message_param("word", word)
+                    .with_severity(Severity::Warning),
+            );
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+/// Maps the characters of a text node's `value` to the bytes of its
+/// source `text`, which may still contain backslash escapes, entity
+/// references and container markers (`> `) on continuation lines.
+///
+/// Returns the source range of each character, indexed by the byte offset
+/// of the character in `value`, or `None` if the two do not line up.
+fn char_spans(value: &str, text: &str) -> Option<Vec<(usize, usize)>> {
+    let mut spans = vec![(0, 0); value.len()];
+    let mut pos = 0;
+
+    for (i, c) in value.char_indices() {
+        loop {
+            let rest = &text[pos..];
+            let len = if rest.starts_with(c) {
+                Some(c.len_utf8())
+            } else if rest.starts_with('\\') && rest[1..].starts_with(c) {
+                Some(1 + c.len_utf8())
+            } else if rest.starts_with('&') {
+                entity_len(rest)
+            } else {
+                None
+            };
+            if let Some(len) = len {
+                spans[i] = (pos, pos + len);
+                pos += len;
+                break;
+            }
+            // Markup that is not part of the value
+            pos += rest.chars().next()?.len_utf8();
+        }
+    }
+
+    Some(spans)
+}
+
+/// Returns the length of the entity reference (`&amp;`, `&#233;`,
+/// `&#xE9;`) `text` starts with.
+fn entity_len(text: &str) -> Option<usize> {
+    let body = text.strip_prefix('&')?;
+    let (name, _) = body.split_once(';')?;
+    let valid = match name.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => {
+            hex.len() > 1 && hex[1..].chars().all(|c| c.is_ascii_hexdigit())
+        }
+        Some(decimal) => !decimal.is_empty() && decimal.chars().all(|c| c.is_ascii_digit()),
+        None => !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()),
+    };
+    (valid && name.len() <= 32).then_some(name.len() + 2)
+}
+
+/// Splits text into words with their byte offsets.
+///
+/// A word is a run of letters of an alphabetic script (Latin, Greek,
+/// Cyrillic and so on, accents included), optionally joined by apostrophes
+/// (`don't`). Tokens containing digits or characters of other scripts
+/// (`v2`, `日本語`) and text between `$` signs (inline math) are skipped.
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut in_math = false;
+    let mut token_start = None;
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let is_word_char = c.is_alphanumeric()
+            || is_combining_mark(c)
+            || (c == '\''
+                && token_start.is_some()
+                && chars.peek().is_some_and(|(_, n)| n.is_alphabetic()));
+
+        if is_word_char && !in_math {
+            token_start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(start) = token_start.take() {
+            push_word(&mut words, start, &text[start..i]);
+        }
+        if c == '$' {
+            in_math = !in_math;
+        }
+    }
+    if let Some(start) = token_start {
+        push_word(&mut words, start, &text[start..]);
+    }
+
+    words
+}
+
+fn push_word<'t>(words: &mut Vec<(usize, &'t str)>, start: usize, token: &'t str) {
+    if token.chars().all(|c| is_letter(c) || c == '\'') {
+        words.push((start, token));
+    }
+}
+
+/// Returns whether `c` is a letter of a script that separates words with
+/// spaces, or an accent combined with one.
+fn is_letter(c: char) -> bool {
+    (c.is_alphabetic() && (c < '\u{0590}' || ('\u{1E00}'..='\u{1FFF}').contains(&c)))
+        || is_combining_mark(c)
+}
+
+/// Returns whether `c` is a combining accent (`e` followed by U+0301).
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::test_support::lint_context;
+    use serde_json::{Value, json};
+    use texide_parser::MarkdownParser;
+
+    const WORDS: &str =
+        "# Test dictionary\nthe\non\nquick\nbrown\nfox\nreceive\nwe\nletters\nrun\n";
+
+    fn lint_in(base_dir: &Path, source: &str, options: Value) -> Vec<Diagnostic> {
+        let context = RuleContext {
+            source,
+            path: None,
+            base_dir: Some(base_dir),
+            options: &options,
+        };
+        lint_context(&Spelling::default(), &MarkdownParser::new(), &context)
+    }
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("words.txt"), WORDS).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_words() {
+        let found: Vec<_> = words("We don't run v2 of 日本語 $x^2 + y$ ok.")
+            .into_iter()
+            .map(|(_, word)| word)
+            .collect();
+        assert_eq!(found, vec!["We", "don't", "run", "of", "ok"]);
+
+        let found: Vec<_> = words("Un café naïve e\u{301}t Ελλάδα")
+            .into_iter()
+            .map(|(_, word)| word)
+            .collect();
+        assert_eq!(found, vec!["Un", "café", "naïve", "e\u{301}t", "Ελλάδα"]);
+    }
+
+    #[test]
+    fn test_char_spans() {
+        let spans = char_spans("a_b é", "a\\_b &eacute;").unwrap();
+        assert_eq!(spans[0], (0, 1));
+        assert_eq!(spans[1], (1, 3));
+        assert_eq!(spans[2], (3, 4));
+        assert_eq!(spans[4], (5, 13));
+        assert_eq!(char_spans("x\ny", "x\n> y").unwrap()[2], (4, 5));
+        assert_eq!(char_spans("xy", "x"), None);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("recieve", "receive"), 2);
+        assert_eq!(edit_distance("fox", "fox"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_misspelling_with_suggestion() {
+        let dir = project();
+        let source = "We recieve the letters.\n";
+        let diagnostics = lint_in(dir.path(), source, json!({ "dictionaries": ["words.txt"] }));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, RULE_ID);
+        assert_eq!(
+            diagnostics[0].message,
+            "Unknown word 'recieve' (did you mean 'receive'?)"
+        );
+        assert_eq!(
+            &source[diagnostics[0].span.start as usize..diagnostics[0].span.end as usize],
+            "recieve"
+        );
+    }
+
+    #[test]
+    fn test_spans_after_escapes_and_entities() {
+        let dir = project();
+        let source = "The \\*fox\\* &amp; caf&eacute; recieve.\n";
+        let options = json!({ "dictionaries": ["words.txt"] });
+        let diagnostics = lint_in(dir.path(), source, options);
+
+        let reported: Vec<_> = diagnostics
+            .iter()
+            .map(|d| &source[d.span.start as usize..d.span.end as usize])
+            .collect();
+        assert_eq!(reported, vec!["caf&eacute;", "recieve"]);
+        assert_eq!(diagnostics[0].message, "Unknown word 'café'");
+    }
+
+    #[test]
+    fn test_ignores_code_and_urls() {
+        let dir = project();
+        let source = "We run `fmtt` on [the fox](https://exmaple.com/qwzx).\n\n```\nqwzx\n```\n";
+        let diagnostics = lint_in(dir.path(), source, json!({ "dictionaries": ["words.txt"] }));
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_project_dictionary_and_words_option() {
+        let dir = project();
+        fs::write(dir.path().join(PROJECT_DICTIONARY), "Texide\n").unwrap();
+
+        let source = "We run Texide on the Qwzx fox.\n";
+        let options = json!({ "dictionaries": ["words.txt"], "words": ["qwzx"] });
+        assert!(lint_in(dir.path(), source, options).is_empty());
+    }
+
+    #[test]
+    fn test_language_dictionary() {
+        let dir = tempfile::tempdir().unwrap();
+        let dictionaries = dir.path().join(".texide").join("dictionaries");
+        fs::create_dir_all(&dictionaries).unwrap();
+        fs::write(dictionaries.join("en-test.txt"), WORDS).unwrap();
+
+        let source = "The quick brown fox.\n";
+        assert!(lint_in(dir.path(), source, json!({ "language": "en-test" })).is_empty());
+        assert_eq!(
+            lint_in(
+                dir.path(),
+                "The quikc fox.\n",
+                json!({ "language": "en-test" })
+            )
+            .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_no_dictionary_reports_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = json!({ "language": "xx-none" });
+        assert!(lint_in(dir.path(), "Anything goes.\n", options).is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+//! Helpers for the tests of native rules.
+
+use serde_json::Value;
+use texide_ast::AstArena;
+use texide_parser::{MarkdownParser, Parser};
+use texide_plugin::Diagnostic;
+
+use super::{NativeRule, RuleContext};
+use crate::apply_fixes_to_content;
+
+/// Lints `source`, parsed as Markdown, with `rule` configured by `options`.
+pub(crate) fn lint_with(rule: &dyn NativeRule, source: &str, options: Value) -> Vec<Diagnostic> {
+    let context = RuleContext {
+        source,
+        path: None,
+        base_dir: None,
+        options: &options,
+    };
+    lint_context(rule, &MarkdownParser::new(), &context)
+}
+
+/// Lints `context.source`, parsed by `parser`, with `rule`, for tests that
+/// need a path, a base directory or another parser.
+pub(crate) fn lint_context(
+    rule: &dyn NativeRule,
+    parser: &dyn Parser,
+    context: &RuleContext<'_>,
+) -> Vec<Diagnostic> {
+    let arena = AstArena::new();
+    let ast = parser.parse(&arena, context.source).unwrap();
+    rule.lint(&ast, context)
+}
+
+/// Returns `source` with the fixes of the diagnostics [`lint_with`]
+/// reports applied.
+pub(crate) fn fixed_with(rule: &dyn NativeRule, source: &str, options: Value) -> String {
+    apply_fixes_to_content(source, &lint_with(rule, source, options)).fixed_content
+}
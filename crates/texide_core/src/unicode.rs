@@ -0,0 +1,131 @@
+//! Unicode normalization.
+//!
+//! The same text can be written with precomposed characters (`é`) or with
+//! a base character followed by combining marks (`e` + U+0301), and text
+//! from macOS file names or copy-paste often mixes both. When
+//! `unicode_normalize` is set, file content is converted to one form
+//! before parsing so rules that match strings see a consistent document.
+//! Spans are mapped back to the original file like line ending
+//! normalization does.
+//!
+//! Normalization is not free: every file is scanned once with the quick
+//! check, and files that are not already in the requested form are copied
+//! and normalized in a second pass before parsing.
+
+use texide_ast::Span;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick, is_nfd_quick};
+
+use crate::config::NormalizationForm;
+use crate::newline::OffsetMap;
+
+/// Converts `source` to `form`.
+///
+/// Returns `None` if normalization is disabled or the source is already in
+/// `form`.
+pub(crate) fn normalize_unicode(
+    source: &str,
+    form: NormalizationForm,
+) -> Option<(String, OffsetMap)> {
+    let quick = match form {
+        NormalizationForm::None => return None,
+        NormalizationForm::Nfc => is_nfc_quick(source.chars()),
+        NormalizationForm::Nfd => is_nfd_quick(source.chars()),
+    };
+    if quick == IsNormalized::Yes {
+        return None;
+    }
+
+    let mut text = String::with_capacity(source.len());
+    let mut map = OffsetMap::default();
+    for (offset, segment) in segments(source, form) {
+        let start = text.len();
+        match form {
+            NormalizationForm::Nfc => text.extend(segment.nfc()),
+            _ => text.extend(segment.nfd()),
+        }
+        if text[start..] != *segment {
+            map.push(
+                Span::new(start as u32, text.len() as u32),
+                Span::new(offset as u32, (offset + segment.len()) as u32),
+            );
+        }
+    }
+
+    (text != source).then_some((text, map))
+}
+
+/// Splits `source` into pieces that normalize independently, with their
+/// offsets.
+///
+/// A piece starts at a character that normalization never combines with
+/// what comes before it: a starter that, for NFC, cannot be composed onto
+/// a previous character.
+fn segments(source: &str, form: NormalizationForm) -> impl Iterator<Item = (usize, &str)> {
+    let mut boundaries = source
+        .char_indices()
+        .filter(move |&(offset, c)| {
+            offset > 0
+                && canonical_combining_class(c) == 0
+                && (form != NormalizationForm::Nfc
+                    || is_nfc_quick(std::iter::once(c)) == IsNormalized::Yes)
+        })
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(source.len()));
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        let end = boundaries.next()?;
+        let segment = (start, &source[start..end]);
+        start = end;
+        Some(segment)
+    })
+    .filter(|(_, segment)| !segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_source_is_unchanged() {
+        assert!(normalize_unicode("café", NormalizationForm::Nfc).is_none());
+        assert!(normalize_unicode("cafe\u{301}", NormalizationForm::Nfd).is_none());
+        assert!(normalize_unicode("cafe\u{301}", NormalizationForm::None).is_none());
+    }
+
+    #[test]
+    fn test_nfc_maps_offsets() {
+        // "e" + U+0301 (3 bytes) becomes "é" (2 bytes)
+        let source = "cafe\u{301} and cafe\u{301}s";
+        let (text, map) = normalize_unicode(source, NormalizationForm::Nfc).unwrap();
+        assert_eq!(text, "café and cafés");
+
+        let second = text.rfind("cafés").unwrap() as u32;
+        let span = map.span_to_original(Span::new(second, second + "cafés".len() as u32));
+        assert_eq!(
+            &source[span.start as usize..span.end as usize],
+            "cafe\u{301}s"
+        );
+
+        // An offset inside "é" widens to the whole original sequence
+        let inside = Span::new(4, 5);
+        assert_eq!(map.span_to_original(inside), Span::new(3, 6));
+    }
+
+    #[test]
+    fn test_nfd() {
+        let source = "Ångström";
+        let (text, map) = normalize_unicode(source, NormalizationForm::Nfd).unwrap();
+        assert_eq!(text, "A\u{30a}ngstro\u{308}m");
+        assert_eq!(map.to_original(text.len() as u32), source.len() as u32);
+    }
+
+    #[test]
+    fn test_hangul_jamo_compose() {
+        // Conjoining jamo are starters that still compose with each other
+        let source = "\u{1100}\u{1161}\u{11a8}!";
+        let (text, map) = normalize_unicode(source, NormalizationForm::Nfc).unwrap();
+        assert_eq!(text, "\u{ac01}!");
+        assert_eq!(map.to_original(3), 9);
+    }
+}
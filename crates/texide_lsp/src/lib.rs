@@ -11,11 +11,10 @@ use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 use tracing::{debug, error, info};
 
-use texide_ast::{AstArena, NodeType, TxtNode};
+use texide_ast::{AstArena, LineIndex, NodeType, TxtNode};
 use texide_core::{
     Diagnostic as TexideDiagnostic, Linter, LinterConfig, Severity as TexideSeverity,
 };
-use texide_parser::{MarkdownParser, Parser, PlainTextParser};
 
 /// The LSP backend for Texide.
 struct Backend {
@@ -71,9 +70,10 @@ impl Backend {
         let diagnostics = self.lint_text(text, &path);
 
         // Convert to LSP diagnostics
+        let index = LineIndex::new(text);
         let lsp_diagnostics: Vec<Diagnostic> = diagnostics
             .into_iter()
-            .filter_map(|d| self.to_lsp_diagnostic(&d, text))
+            .filter_map(|d| self.to_lsp_diagnostic(&d, uri, &index))
             .collect();
 
         self.client
@@ -102,7 +102,10 @@ impl Backend {
         };
 
         match linter.lint_content(text, path) {
-            Ok(diagnostics) => diagnostics,
+            Ok(mut diagnostics) => {
+                linter.localize(&mut diagnostics);
+                diagnostics
+            }
             Err(e) => {
                 error!("Lint error: {}", e);
                 vec![]
@@ -111,8 +114,14 @@ impl Backend {
     }
 
     /// Converts a Texide diagnostic to an LSP diagnostic.
-    fn to_lsp_diagnostic(&self, diag: &TexideDiagnostic, text: &str) -> Option<Diagnostic> {
-        let range = self.offset_to_range(diag.span.start as usize, diag.span.end as usize, text)?;
+    fn to_lsp_diagnostic(
+        &self,
+        diag: &TexideDiagnostic,
+        uri: &Url,
+        index: &LineIndex,
+    ) -> Option<Diagnostic> {
+        let range =
+            self.offset_to_range(diag.span.start as usize, diag.span.end as usize, index)?;
 
         let severity = match diag.severity {
             TexideSeverity::Error => DiagnosticSeverity::ERROR,
@@ -120,49 +129,57 @@ impl Backend {
             TexideSeverity::Info => DiagnosticSeverity::INFORMATION,
         };
 
+        let code_description = diag
+            .help_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .map(|href| CodeDescription { href });
+
+        let related_information: Vec<_> = diag
+            .related
+            .iter()
+            .filter_map(|related| {
+                let range = self.offset_to_range(
+                    related.span.start as usize,
+                    related.span.end as usize,
+                    index,
+                )?;
+                Some(DiagnosticRelatedInformation {
+                    location: Location::new(uri.clone(), range),
+                    message: related.message.clone(),
+                })
+            })
+            .collect();
+
         Some(Diagnostic {
             range,
             severity: Some(severity),
             code: Some(NumberOrString::String(diag.rule_id.clone())),
+            code_description,
             source: Some("texide".to_string()),
             message: diag.message.clone(),
+            related_information: (!related_information.is_empty()).then_some(related_information),
             ..Default::default()
         })
     }
 
     /// Converts byte offsets to an LSP range.
-    fn offset_to_range(&self, start: usize, end: usize, text: &str) -> Option<Range> {
-        let start_pos = self.offset_to_position(start, text)?;
-        let end_pos = self.offset_to_position(end, text)?;
+    fn offset_to_range(&self, start: usize, end: usize, index: &LineIndex) -> Option<Range> {
+        let start_pos = self.offset_to_position(start, index)?;
+        let end_pos = self.offset_to_position(end, index)?;
         Some(Range::new(start_pos, end_pos))
     }
 
     /// Converts a byte offset to an LSP position.
-    fn offset_to_position(&self, offset: usize, text: &str) -> Option<Position> {
-        if offset > text.len() {
+    ///
+    /// The `\r` of a CRLF line ending is not counted as a column.
+    fn offset_to_position(&self, offset: usize, index: &LineIndex) -> Option<Position> {
+        if !index.source().is_char_boundary(offset) {
             return None;
         }
 
-        let mut line = 0u32;
-        let mut col = 0u32;
-        let mut current_offset = 0;
-
-        for ch in text.chars() {
-            if current_offset >= offset {
-                break;
-            }
-
-            if ch == '\n' {
-                line += 1;
-                col = 0;
-            } else {
-                col += 1;
-            }
-
-            current_offset += ch.len_utf8();
-        }
-
-        Some(Position::new(line, col))
+        let position = index.position(offset as u32);
+        Some(Position::new(position.line - 1, position.column))
     }
 
     /// Helper to compare Positions (p1 <= p2)
@@ -171,7 +188,8 @@ impl Backend {
     }
 
     /// Extracts document symbols from AST.
-    fn extract_symbols(&self, node: &TxtNode, text: &str) -> Vec<DocumentSymbol> {
+    fn extract_symbols(&self, node: &TxtNode, index: &LineIndex) -> Vec<DocumentSymbol> {
+        let text = index.source();
         let mut symbols = Vec::new();
 
         // We only care about specific block elements for the outline
@@ -194,7 +212,7 @@ impl Backend {
 
             // Convert range
             if let Some(range) =
-                self.offset_to_range(child.span.start as usize, child.span.end as usize, text)
+                self.offset_to_range(child.span.start as usize, child.span.end as usize, index)
             {
                 // For selection range, ideally we want just the header text, but full range is fine for now
                 let selection_range = range;
@@ -253,33 +271,56 @@ impl Backend {
             }
         };
 
-        let config_files = [".texide.jsonc", ".texide.json"];
-        for name in config_files {
-            let config_path = path.join(name);
-            if config_path.exists() {
-                info!("Found config file: {}", config_path.display());
-                match LinterConfig::from_file(&config_path) {
-                    Ok(config) => {
-                        info!("Loaded configuration from workspace");
-                        match self.linter.write() {
-                            Ok(mut linter_guard) => match Linter::new(config) {
-                                Ok(new_linter) => {
-                                    *linter_guard = Some(new_linter);
-                                    info!("Linter re-initialized with new config");
-                                }
-                                Err(e) => {
-                                    error!("Failed to create new linter: {}", e);
-                                    *linter_guard = None;
-                                }
-                            },
-                            Err(e) => error!("Linter lock poisoned: {}", e),
+        match LinterConfig::discover(path) {
+            Ok(Some(config)) => {
+                info!("Loaded configuration from workspace");
+                match self.linter.write() {
+                    Ok(mut linter_guard) => match Linter::new(config) {
+                        Ok(new_linter) => {
+                            *linter_guard = Some(new_linter);
+                            info!("Linter re-initialized with new config");
                         }
-                    }
-                    Err(e) => {
-                        error!("Failed to load config: {}", e);
-                    }
+                        Err(e) => {
+                            error!("Failed to create new linter: {}", e);
+                            *linter_guard = None;
+                        }
+                    },
+                    Err(e) => error!("Linter lock poisoned: {}", e),
                 }
-                break;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error!("Failed to load config: {}", e);
+            }
+        }
+    }
+
+    /// Reloads loaded rules whose WASM file is `path`.
+    async fn reload_rule(&self, path: &std::path::Path) {
+        let result = match self.linter.read() {
+            Ok(guard) => match guard.as_ref() {
+                Some(linter) => linter.reload_rules_from(path),
+                None => return,
+            },
+            Err(e) => {
+                error!("Linter lock poisoned: {}", e);
+                return;
+            }
+        };
+
+        match result {
+            Ok(names) if !names.is_empty() => {
+                info!("Reloaded rules: {}", names.join(", "));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Failed to reload rule from {}: {}", path.display(), e);
+                self.client
+                    .show_message(
+                        MessageType::ERROR,
+                        format!("Failed to reload rule {}: {}", path.display(), e),
+                    )
+                    .await;
             }
         }
     }
@@ -422,12 +463,26 @@ impl LanguageServer for Backend {
         // Check if any config files changed
         let config_changed = params.changes.iter().any(|change| {
             let path = change.uri.path();
-            path.ends_with(".texide.json") || path.ends_with(".texide.jsonc")
+            path.ends_with(".texide.json")
+                || path.ends_with(".texide.jsonc")
+                || path.ends_with("/package.json")
         });
 
         if config_changed {
             info!("Configuration file changed, reloading...");
             self.reload_config();
+            return;
+        }
+
+        // Reload rebuilt WASM rules
+        for change in &params.changes {
+            if change.typ == FileChangeType::DELETED || !change.uri.path().ends_with(".wasm") {
+                continue;
+            }
+            let Ok(path) = change.uri.to_file_path() else {
+                continue;
+            };
+            self.reload_rule(&path).await;
         }
     }
 
@@ -479,6 +534,7 @@ impl LanguageServer for Backend {
         // Re-run linting to get diagnostics with fixes
         // Note: In a real implementation, we should cache diagnostics map to avoid re-linting
         let diagnostics = self.lint_text(&text, &path);
+        let index = LineIndex::new(&text);
 
         let mut actions = Vec::new();
 
@@ -515,7 +571,7 @@ impl LanguageServer for Backend {
             for diag in fixable_diags {
                 if let Some(ref fix) = diag.fix
                     && let Some(range) =
-                        self.offset_to_range(fix.span.start as usize, fix.span.end as usize, &text)
+                        self.offset_to_range(fix.span.start as usize, fix.span.end as usize, &index)
                 {
                     edits.push(TextEdit {
                         range,
@@ -548,7 +604,7 @@ impl LanguageServer for Backend {
         for diag in &diagnostics {
             if let Some(ref fix) = diag.fix
                 && let Some(range) =
-                    self.offset_to_range(fix.span.start as usize, fix.span.end as usize, &text)
+                    self.offset_to_range(fix.span.start as usize, fix.span.end as usize, &index)
                 && self.positions_le(range.start, params.range.end)
                 && self.positions_le(params.range.start, range.end)
             {
@@ -602,13 +658,19 @@ impl LanguageServer for Backend {
             Err(_) => std::path::PathBuf::from("untitled"),
         };
 
-        // Select parser
-        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        let parser: Box<dyn Parser> = if extension == "md" || extension == "markdown" {
-            Box::new(MarkdownParser::new())
-        } else {
-            // For plain text, maybe no symbols? Or just return None
-            Box::new(PlainTextParser::new())
+        // Parse the way the linter does
+        let parser = {
+            let linter = match self.linter.read() {
+                Ok(guard) => guard,
+                Err(e) => {
+                    error!("Linter lock poisoned: {}", e);
+                    return Ok(None);
+                }
+            };
+            match linter.as_ref() {
+                Some(linter) => linter.select_parser(&path),
+                None => return Ok(None),
+            }
         };
 
         let arena = AstArena::new();
@@ -620,7 +682,7 @@ impl LanguageServer for Backend {
             }
         };
 
-        let symbols = self.extract_symbols(&ast, &text);
+        let symbols = self.extract_symbols(&ast, &LineIndex::new(&text));
         Ok(Some(DocumentSymbolResponse::Nested(symbols)))
     }
 }
@@ -18,6 +18,15 @@ pub enum ParseError {
     #[error("Unsupported feature: {0}")]
     Unsupported(String),
 
+    /// No parser is registered under the requested name.
+    #[error("Unknown parser '{name}' (available: {available})")]
+    UnknownParser {
+        /// Requested parser name.
+        name: String,
+        /// Comma-separated names of the registered parsers.
+        available: String,
+    },
+
     /// An internal parser error occurred.
     #[error("Internal parser error: {0}")]
     Internal(String),
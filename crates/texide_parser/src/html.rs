@@ -0,0 +1,143 @@
+//! Text extraction from raw HTML.
+//!
+//! A deliberately small scanner rather than a full HTML parser: it finds
+//! the text between tags so prose inside embedded HTML can be linted.
+
+use std::ops::Range;
+
+/// Elements whose content is not prose.
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// Returns the byte ranges of the text runs in `html`.
+///
+/// Tags, comments, declarations and the content of `<script>` and
+/// `<style>` elements are skipped. Each run is trimmed of surrounding
+/// whitespace, and whitespace-only runs are left out. A `<` that does not
+/// start a tag is text.
+pub(crate) fn text_ranges(html: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(found) = html[pos..].find('<') {
+        let tag_start = pos + found;
+        let Some(tag_end) = markup_end(html, tag_start) else {
+            pos = tag_start + 1;
+            continue;
+        };
+        push_trimmed(&mut ranges, html, text_start..tag_start);
+
+        pos = match raw_text_element(&html[tag_start..tag_end]) {
+            Some(name) => closing_tag_end(html, tag_end, name),
+            None => tag_end,
+        };
+        text_start = pos;
+    }
+    push_trimmed(&mut ranges, html, text_start..html.len());
+
+    ranges
+}
+
+/// Returns the end of the tag, comment or declaration starting at `start`,
+/// or `None` if the `<` there does not start markup.
+fn markup_end(html: &str, start: usize) -> Option<usize> {
+    let rest = &html[start..];
+    if let Some(comment) = rest.strip_prefix("<!--") {
+        let end = comment
+            .find("-->")
+            .map_or(html.len(), |i| start + 4 + i + 3);
+        return Some(end);
+    }
+
+    let next = rest[1..].chars().next()?;
+    if !(next.is_ascii_alphabetic() || matches!(next, '/' | '!' | '?')) {
+        return None;
+    }
+
+    // Find the closing `>`, ignoring any inside quoted attribute values
+    let mut quote = None;
+    for (i, c) in rest.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(start + i + 1),
+            _ => {}
+        }
+    }
+    Some(html.len())
+}
+
+/// Returns the name of the raw text element `tag` opens, if any.
+fn raw_text_element(tag: &str) -> Option<&'static str> {
+    let name: String = tag[1..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    RAW_TEXT_ELEMENTS
+        .into_iter()
+        .find(|element| element.eq_ignore_ascii_case(&name))
+}
+
+/// Returns the end of the `</name>` tag closing a raw text element whose
+/// content starts at `from`, or the end of `html` if it is not closed.
+fn closing_tag_end(html: &str, from: usize, name: &str) -> usize {
+    let closing = format!("</{}", name);
+    let lower = html[from..].to_ascii_lowercase();
+    match lower.find(&closing) {
+        Some(i) => {
+            let tag_start = from + i;
+            markup_end(html, tag_start).unwrap_or(html.len())
+        }
+        None => html.len(),
+    }
+}
+
+/// Pushes `range` of `html` without surrounding whitespace, unless empty.
+fn push_trimmed(ranges: &mut Vec<Range<usize>>, html: &str, range: Range<usize>) {
+    let text = &html[range.clone()];
+    let trimmed = text.trim_start();
+    let start = range.start + (text.len() - trimmed.len());
+    let end = start + trimmed.trim_end().len();
+    if start < end {
+        ranges.push(start..end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(html: &str) -> Vec<&str> {
+        text_ranges(html)
+            .into_iter()
+            .map(|range| &html[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_text_between_tags() {
+        assert_eq!(
+            texts("<div class=\"note\">\n  Some <b>bold</b> prose.\n</div>\n"),
+            vec!["Some", "bold", "prose."]
+        );
+    }
+
+    #[test]
+    fn test_skips_comments_and_raw_text() {
+        assert_eq!(
+            texts("<!-- note -->Intro<script>let a = 1 < 2;</script><STYLE>p{}</STYLE>End"),
+            vec!["Intro", "End"]
+        );
+    }
+
+    #[test]
+    fn test_quoted_angle_bracket_and_plain_less_than() {
+        assert_eq!(texts("<a title=\"a > b\">1 < 2</a>"), vec!["1 < 2"]);
+    }
+
+    #[test]
+    fn test_tags_only() {
+        assert!(text_ranges("<br/>\n<hr>").is_empty());
+    }
+}
@@ -6,6 +6,9 @@
 //! - A `Parser` trait for implementing custom parsers
 //! - Built-in Markdown parser using `markdown-rs`
 //! - Built-in plain text parser
+//! - A raw parser that passes the whole source through as one text node
+//! - A `ParserRegistry` for selecting parsers by name
+//! - Span verification for parser output
 //!
 //! ## Architecture
 //!
@@ -26,11 +29,18 @@
 //! ```
 
 mod error;
+mod html;
 mod markdown;
+mod raw;
+mod registry;
 mod text;
 mod traits;
+mod verify;
 
 pub use error::ParseError;
-pub use markdown::MarkdownParser;
-pub use text::PlainTextParser;
+pub use markdown::{HtmlMode, MarkdownParser, ParserOptions};
+pub use raw::RawParser;
+pub use registry::ParserRegistry;
+pub use text::{ParagraphMode, PlainTextParser};
 pub use traits::Parser;
+pub use verify::{SpanError, verify_spans};
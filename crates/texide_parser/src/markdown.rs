@@ -3,10 +3,98 @@
 //! This parser converts Markdown to TxtAST using the `markdown` crate,
 //! which provides mdast-compatible AST output.
 
-use markdown::{ParseOptions, to_mdast};
-use texide_ast::{AstArena, NodeData, NodeType, Span, TxtNode};
+use markdown::{MdxSignal, ParseOptions, to_mdast};
+use serde::{Deserialize, Serialize};
+use texide_ast::{AstArena, NodeData, NodeType, Span, TableAlign, TxtNode};
 
-use crate::{ParseError, Parser};
+use crate::{ParseError, Parser, html};
+
+/// Markdown syntax extensions to recognize.
+///
+/// The defaults parse GitHub Flavored Markdown with YAML frontmatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParserOptions {
+    /// GFM tables.
+    pub gfm_table: bool,
+    /// GFM strikethrough (`~~text~~`).
+    pub gfm_strikethrough: bool,
+    /// GFM literal autolinks (`https://example.com`, `www.example.com` and
+    /// email addresses), parsed as `Link` nodes like `<autolinks>`.
+    pub gfm_autolink_literal: bool,
+    /// GFM footnotes.
+    pub gfm_footnote: bool,
+    /// YAML frontmatter.
+    pub frontmatter: bool,
+    /// MDX: JSX, `{expressions}` and `import`/`export` statements.
+    ///
+    /// As in MDX itself, HTML, `<autolinks>` and indented code are then
+    /// not recognized.
+    pub mdx: bool,
+    /// How embedded HTML is represented.
+    pub html: HtmlMode,
+}
+
+/// How embedded HTML (blocks and inline tags) is represented in the AST.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HtmlMode {
+    /// Leave HTML out of the AST.
+    Skip,
+    /// Keep each piece of HTML as one opaque `Html` node.
+    #[default]
+    Opaque,
+    /// Replace HTML with `Str` nodes for the text between its tags, so prose
+    /// rules see it. The text of an HTML block is wrapped in a `Paragraph`;
+    /// inline tags hold no text and are dropped.
+    ///
+    /// HTML inside block quotes and list items, whose value differs from its
+    /// source text, stays opaque.
+    Text,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            gfm_table: true,
+            gfm_strikethrough: true,
+            gfm_autolink_literal: true,
+            gfm_footnote: true,
+            frontmatter: true,
+            mdx: false,
+            html: HtmlMode::Opaque,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// Converts to markdown-rs parse options.
+    fn to_parse_options(self) -> ParseOptions {
+        let mut options = ParseOptions::gfm();
+        let constructs = &mut options.constructs;
+        constructs.gfm_table = self.gfm_table;
+        constructs.gfm_strikethrough = self.gfm_strikethrough;
+        constructs.gfm_autolink_literal = self.gfm_autolink_literal;
+        constructs.gfm_footnote_definition = self.gfm_footnote;
+        constructs.gfm_label_start_footnote = self.gfm_footnote;
+        constructs.frontmatter = self.frontmatter;
+        if self.mdx {
+            // Statements are not linted, so any ESM is accepted as is
+            options.mdx_esm_parse = Some(Box::new(|_| MdxSignal::Ok));
+            let constructs = &mut options.constructs;
+            constructs.mdx_esm = true;
+            constructs.mdx_expression_flow = true;
+            constructs.mdx_expression_text = true;
+            constructs.mdx_jsx_flow = true;
+            constructs.mdx_jsx_text = true;
+            constructs.html_flow = false;
+            constructs.html_text = false;
+            constructs.autolink = false;
+            constructs.code_indented = false;
+        }
+        options
+    }
+}
 
 /// Markdown parser implementation.
 ///
@@ -14,19 +102,49 @@ use crate::{ParseError, Parser};
 /// - CommonMark
 /// - GFM (GitHub Flavored Markdown)
 /// - MDX (optional)
-/// - Math (optional)
-/// - Frontmatter (optional)
-pub struct MarkdownParser;
+/// - Frontmatter
+///
+/// Which extensions are recognized is set by [`ParserOptions`].
+///
+/// # Text values
+///
+/// `Str` values hold the text the author intended: character references
+/// (`&copy;`, `&#169;`, `&#xA9;`) are decoded and backslash escapes
+/// (`\_`) are removed. Spans still cover the original source, so a
+/// node's span can be longer than its value; rules that report positions
+/// inside a value must map them through the source text.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownParser {
+    options: ParserOptions,
+}
 
 impl MarkdownParser {
     /// Creates a new Markdown parser with default options.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Creates a Markdown parser using the given options.
+    pub fn with_options(options: ParserOptions) -> Self {
+        Self { options }
     }
 
-    /// Gets default parse options (GFM).
-    fn default_options() -> ParseOptions {
-        ParseOptions::gfm()
+    /// Returns the options used by [`Parser::parse`].
+    pub fn options(&self) -> ParserOptions {
+        self.options
+    }
+
+    /// Parses `source` with `options` instead of the parser's own options.
+    pub fn parse_with_options<'a>(
+        &self,
+        arena: &'a AstArena,
+        source: &str,
+        options: &ParserOptions,
+    ) -> Result<TxtNode<'a>, ParseError> {
+        let mdast = to_mdast(source, &options.to_parse_options())
+            .map_err(|e| ParseError::invalid_source(e.to_string()))?;
+
+        Ok(Self::with_options(*options).convert_node(arena, &mdast, source))
     }
 
     /// Converts an mdast node to TxtNode.
@@ -61,6 +179,7 @@ impl MarkdownParser {
 
             Node::Text(text) => {
                 let span = self.node_span(node, source);
+                // markdown-rs has already resolved references and escapes
                 let value = arena.alloc_str(&text.value);
                 TxtNode::new_text(NodeType::Str, span, value)
             }
@@ -87,8 +206,12 @@ impl MarkdownParser {
                 let span = self.node_span(node, source);
                 let value = arena.alloc_str(&code.value);
                 let mut node = TxtNode::new_text(NodeType::CodeBlock, span, value);
-                if let Some(lang) = &code.lang {
-                    node.data = NodeData::code_block(Some(arena.alloc_str(lang)));
+                let lang = code.lang.as_deref().map(|lang| arena.alloc_str(lang));
+                node.data = NodeData::code_block(lang);
+                // The span covers the fences, so the info string can be read as written
+                if let Some((fence, info)) = code_fence(node.text(source)) {
+                    node.data.fence = Some(arena.alloc_str(fence));
+                    node.data.info = (!info.is_empty()).then(|| arena.alloc_str(info));
                 }
                 node
             }
@@ -108,7 +231,8 @@ impl MarkdownParser {
                 let mut node = TxtNode::new_leaf(NodeType::Image, span);
                 let url = arena.alloc_str(&image.url);
                 let title = image.title.as_ref().map(|t| arena.alloc_str(t));
-                node.data = NodeData::link(url, title);
+                let alt = arena.alloc_str(&image.alt);
+                node.data = NodeData::image(url, title, alt);
                 node
             }
 
@@ -117,13 +241,16 @@ impl MarkdownParser {
                 let span = self.node_span(node, source);
                 let mut node = TxtNode::new_parent(NodeType::List, span, children);
                 node.data = NodeData::list(list.ordered);
+                node.data.start = list.start;
                 node
             }
 
             Node::ListItem(item) => {
                 let children = self.convert_children(arena, &item.children, source);
                 let span = self.node_span(node, source);
-                TxtNode::new_parent(NodeType::ListItem, span, children)
+                let mut node = TxtNode::new_parent(NodeType::ListItem, span, children);
+                node.data.ordinal = item_ordinal(&source[span.start as usize..]);
+                node
             }
 
             Node::Blockquote(quote) => {
@@ -156,9 +283,23 @@ impl MarkdownParser {
 
             // Table support (GFM)
             Node::Table(table) => {
+                use markdown::mdast::AlignKind;
+
                 let children = self.convert_children(arena, &table.children, source);
                 let span = self.node_span(node, source);
-                TxtNode::new_parent(NodeType::Table, span, children)
+                let align: Vec<TableAlign> = table
+                    .align
+                    .iter()
+                    .map(|kind| match kind {
+                        AlignKind::Left => TableAlign::Left,
+                        AlignKind::Right => TableAlign::Right,
+                        AlignKind::Center => TableAlign::Center,
+                        AlignKind::None => TableAlign::None,
+                    })
+                    .collect();
+                let mut node = TxtNode::new_parent(NodeType::Table, span, children);
+                node.data = NodeData::table(arena.alloc_slice_copy(&align));
+                node
             }
 
             Node::TableRow(row) => {
@@ -210,6 +351,7 @@ impl MarkdownParser {
             Node::ImageReference(ref_node) => {
                 let span = self.node_span(node, source);
                 let mut node = TxtNode::new_leaf(NodeType::ImageReference, span);
+                node.data.alt = Some(arena.alloc_str(&ref_node.alt));
                 node.data.identifier = Some(arena.alloc_str(&ref_node.identifier));
                 if let Some(label) = &ref_node.label {
                     node.data.label = Some(arena.alloc_str(label));
@@ -231,6 +373,12 @@ impl MarkdownParser {
                 node
             }
 
+            Node::Yaml(yaml) => {
+                let span = self.node_span(node, source);
+                let value = arena.alloc_str(&yaml.value);
+                TxtNode::new_text(NodeType::FrontMatter, span, value)
+            }
+
             // Fallback for unsupported nodes
             _ => {
                 let span = self.node_span(node, source);
@@ -246,14 +394,55 @@ impl MarkdownParser {
         children: &[markdown::mdast::Node],
         source: &str,
     ) -> &'a [TxtNode<'a>] {
-        let nodes: Vec<TxtNode<'a>> = children
-            .iter()
-            .map(|child| self.convert_node(arena, child, source))
-            .collect();
+        let mut nodes: Vec<TxtNode<'a>> = Vec::with_capacity(children.len());
+        for child in children {
+            match child {
+                markdown::mdast::Node::Html(html) if self.options.html != HtmlMode::Opaque => {
+                    self.convert_html(arena, child, &html.value, source, &mut nodes);
+                }
+                _ => nodes.push(self.convert_node(arena, child, source)),
+            }
+        }
 
         arena.alloc_slice_clone(&nodes)
     }
 
+    /// Converts an `Html` node according to [`ParserOptions::html`],
+    /// pushing the resulting nodes (if any) to `nodes`.
+    fn convert_html<'a>(
+        &self,
+        arena: &'a AstArena,
+        node: &markdown::mdast::Node,
+        value: &str,
+        source: &str,
+        nodes: &mut Vec<TxtNode<'a>>,
+    ) {
+        if self.options.html == HtmlMode::Skip {
+            return;
+        }
+
+        let span = self.node_span(node, source);
+        let start = span.start as usize;
+        if source.get(start..span.end as usize) != Some(value) {
+            // Offsets into the value would not map onto the source
+            nodes.push(self.convert_node(arena, node, source));
+            return;
+        }
+
+        let texts: Vec<TxtNode<'a>> = html::text_ranges(value)
+            .into_iter()
+            .map(|range| {
+                let span = Span::new((start + range.start) as u32, (start + range.end) as u32);
+                TxtNode::new_text(NodeType::Str, span, arena.alloc_str(&value[range]))
+            })
+            .collect();
+        if let (Some(first), Some(last)) = (texts.first(), texts.last()) {
+            let span = Span::new(first.span.start, last.span.end);
+            let children = arena.alloc_slice_copy(&texts);
+            nodes.push(TxtNode::new_parent(NodeType::Paragraph, span, children));
+        }
+    }
+
     /// Gets the span for an mdast node.
     fn node_span(&self, node: &markdown::mdast::Node, _source: &str) -> Span {
         if let Some(pos) = node.position() {
@@ -264,10 +453,34 @@ impl MarkdownParser {
     }
 }
 
-impl Default for MarkdownParser {
-    fn default() -> Self {
-        Self::new()
+/// Reads the number of an ordered list item (`3.` or `3)`) from the start of
+/// its source text.
+fn item_ordinal(item: &str) -> Option<u32> {
+    let digits = item.len() - item.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if !item[digits..].starts_with(['.', ')']) {
+        return None;
     }
+    item[..digits].parse().ok()
+}
+
+/// Reads the opening fence (`` ``` `` or `~~~`, three or more) and the
+/// trimmed info string from the start of a code block's source text.
+///
+/// Returns `None` for indented code blocks. Their span starts at the
+/// indentation, so a fence-like first line is indented four or more
+/// columns, which a fence cannot be.
+fn code_fence(block: &str) -> Option<(&str, &str)> {
+    let first = block.lines().next()?;
+    let line = first.trim_start_matches(' ');
+    if first.len() - line.len() > 3 {
+        return None;
+    }
+    let marker = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = line.len() - line.trim_start_matches(marker).len();
+    if len < 3 {
+        return None;
+    }
+    Some((&line[..len], line[len..].trim()))
 }
 
 impl Parser for MarkdownParser {
@@ -276,21 +489,18 @@ impl Parser for MarkdownParser {
     }
 
     fn extensions(&self) -> &[&str] {
-        &["md", "markdown", "mdown", "mkdn", "mkd"]
+        &["md", "markdown", "mdown", "mkdn", "mkd", "mdx"]
     }
 
     fn parse<'a>(&self, arena: &'a AstArena, source: &str) -> Result<TxtNode<'a>, ParseError> {
-        let options = Self::default_options();
-        let mdast =
-            to_mdast(source, &options).map_err(|e| ParseError::invalid_source(e.to_string()))?;
-
-        Ok(self.convert_node(arena, &mdast, source))
+        self.parse_with_options(arena, source, &self.options)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::verify_spans;
 
     #[test]
     fn test_parse_simple_markdown() {
@@ -342,6 +552,7 @@ mod tests {
         assert!(parser.can_parse("md"));
         assert!(parser.can_parse("markdown"));
         assert!(parser.can_parse("MD"));
+        assert!(parser.can_parse("mdx"));
         assert!(!parser.can_parse("txt"));
     }
 
@@ -357,6 +568,18 @@ mod tests {
         assert!(ast.children.is_empty());
     }
 
+    #[test]
+    fn test_parse_blank_document() {
+        let parser = MarkdownParser::new();
+        for source in ["\n", "\r\n", "   \n\t\n  ", "\u{FEFF}\n"] {
+            let arena = AstArena::new();
+            let ast = parser.parse(&arena, source).unwrap();
+
+            assert!(ast.children.is_empty(), "{:?}", source);
+            assert_eq!(ast.span, Span::new(0, source.len() as u32));
+        }
+    }
+
     #[test]
     fn test_parse_emphasis() {
         let arena = AstArena::new();
@@ -396,6 +619,87 @@ mod tests {
         assert!(code_block.value.is_some());
     }
 
+    #[test]
+    fn test_code_block_span_covers_fences_and_info() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "Intro\n\n```rust title=\"main.rs\"\nfn main() {}\n```\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let code_block = &ast.children[1];
+        assert_eq!(
+            code_block.text(source),
+            "```rust title=\"main.rs\"\nfn main() {}\n```"
+        );
+        assert_eq!(code_block.value, Some("fn main() {}"));
+        assert_eq!(code_block.data.lang, Some("rust"));
+        assert_eq!(code_block.data.info, Some("rust title=\"main.rs\""));
+        assert_eq!(code_block.data.fence, Some("```"));
+    }
+
+    #[test]
+    fn test_code_block_fence_kinds() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "~~~~ \ncode\n~~~~\n\n> ```sh\n> ls\n> ```\n\n    indented\n\n- item\n\n      ```not-a-fence\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let tilde = &ast.children[0];
+        assert_eq!(tilde.data.fence, Some("~~~~"));
+        assert_eq!(tilde.data.info, None);
+
+        let quoted = &ast.children[1].children[0];
+        assert_eq!(quoted.text(source), "```sh\n> ls\n> ```");
+        assert_eq!(quoted.data.fence, Some("```"));
+        assert_eq!(quoted.data.info, Some("sh"));
+
+        let indented = &ast.children[2];
+        assert_eq!(indented.node_type, NodeType::CodeBlock);
+        assert_eq!(indented.data.fence, None);
+        assert_eq!(indented.data.info, None);
+
+        let fence_like = &ast.children[3].children[0].children[1];
+        assert_eq!(fence_like.node_type, NodeType::CodeBlock);
+        assert_eq!(fence_like.data.fence, None);
+        assert_eq!(fence_like.data.info, None);
+    }
+
+    #[test]
+    fn test_inline_code_span_covers_backticks() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "Run `` `ls` `` now";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let code = &ast.children[0].children[1];
+        assert_eq!(code.node_type, NodeType::Code);
+        assert_eq!(code.text(source), "`` `ls` ``");
+        assert_eq!(code.value, Some("`ls`"));
+    }
+
+    #[test]
+    fn test_emphasis_spans_cover_delimiters() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "_a_ *b* __c__ **d** ***e***";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let spans: Vec<_> = ast.children[0]
+            .children
+            .iter()
+            .filter(|node| node.node_type != NodeType::Str)
+            .map(|node| node.text(source))
+            .collect();
+        assert_eq!(spans, vec!["_a_", "*b*", "__c__", "**d**", "***e***"]);
+
+        let nested = ast.children[0].children.last().unwrap();
+        assert_eq!(nested.children[0].text(source), "**e**");
+    }
+
     #[test]
     fn test_parse_code_block_no_language() {
         let arena = AstArena::new();
@@ -409,6 +713,65 @@ mod tests {
         assert!(code_block.data.lang.is_none());
     }
 
+    #[test]
+    fn test_parse_front_matter() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "---\ntitle: Hello\n---\n\n# Body\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let front_matter = &ast.children[0];
+        assert_eq!(front_matter.node_type, NodeType::FrontMatter);
+        assert_eq!(front_matter.value, Some("title: Hello"));
+        assert_eq!(front_matter.span.start, 0);
+        assert_eq!(front_matter.span.end, 20);
+        assert_eq!(ast.children[1].node_type, NodeType::Header);
+    }
+
+    fn paragraph_text<'a>(arena: &'a AstArena, source: &str) -> TxtNode<'a> {
+        let ast = MarkdownParser::new().parse(arena, source).unwrap();
+        verify_spans(&ast, source).unwrap();
+        ast.children[0].children[0]
+    }
+
+    #[test]
+    fn test_named_entity_is_decoded() {
+        let arena = AstArena::new();
+        let source = "Copyright &copy; 2024\n";
+
+        let text = paragraph_text(&arena, source);
+        assert_eq!(text.value, Some("Copyright © 2024"));
+        assert_eq!(text.span, Span::new(0, 21));
+        assert_eq!(
+            &source[text.span.start as usize..text.span.end as usize],
+            "Copyright &copy; 2024"
+        );
+    }
+
+    #[test]
+    fn test_backslash_escapes_are_removed() {
+        let arena = AstArena::new();
+        let source = "A \\_literal\\_ word\n";
+
+        let text = paragraph_text(&arena, source);
+        assert_eq!(text.value, Some("A _literal_ word"));
+        assert_eq!(
+            &source[text.span.start as usize..text.span.end as usize],
+            "A \\_literal\\_ word"
+        );
+    }
+
+    #[test]
+    fn test_numeric_entities_are_decoded() {
+        let arena = AstArena::new();
+        let source = "Snow &#9731; and &#x263A;\n";
+
+        let text = paragraph_text(&arena, source);
+        assert_eq!(text.value, Some("Snow ☃ and ☺"));
+        assert_eq!(text.span, Span::new(0, 25));
+    }
+
     #[test]
     fn test_parse_inline_code() {
         let arena = AstArena::new();
@@ -466,6 +829,23 @@ mod tests {
         let list = &ast.children[0];
         assert_eq!(list.node_type, NodeType::List);
         assert_eq!(list.data.ordered, Some(true));
+        assert_eq!(list.data.start, Some(1));
+        let ordinals: Vec<_> = list.children.iter().map(|item| item.data.ordinal).collect();
+        assert_eq!(ordinals, vec![Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn test_parse_ordered_list_ordinals() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "7) Seven\n7) Seven again\n10) Ten\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let list = &ast.children[0];
+        assert_eq!(list.data.start, Some(7));
+        let ordinals: Vec<_> = list.children.iter().map(|item| item.data.ordinal).collect();
+        assert_eq!(ordinals, vec![Some(7), Some(7), Some(10)]);
     }
 
     #[test]
@@ -495,6 +875,24 @@ mod tests {
         assert_eq!(image.data.title, Some("Title"));
     }
 
+    #[test]
+    fn test_parse_image_alt() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "![A *tiny* cat](cat.png) ![][dog]\n\n[dog]: dog.png\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let paragraph = &ast.children[0];
+        let image = &paragraph.children[0];
+        assert_eq!(image.node_type, NodeType::Image);
+        assert_eq!(image.data.alt, Some("A tiny cat"));
+
+        let reference = &paragraph.children[2];
+        assert_eq!(reference.node_type, NodeType::ImageReference);
+        assert_eq!(reference.data.alt, Some(""));
+    }
+
     #[test]
     fn test_parse_link_with_title() {
         let arena = AstArena::new();
@@ -511,6 +909,91 @@ mod tests {
         assert_eq!(link.data.title, Some("Example Title"));
     }
 
+    /// Returns the URL, text and span of each link in the first paragraph.
+    fn links<'a>(ast: &TxtNode<'a>) -> Vec<(&'a str, &'a str, Span)> {
+        ast.children[0]
+            .children
+            .iter()
+            .filter(|node| node.node_type == NodeType::Link)
+            .map(|link| {
+                let text = link.children[0].value.unwrap();
+                (link.data.url.unwrap(), text, link.span)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_gfm_autolink_literals() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "See https://example.com/docs, www.example.com or mail@example.com.";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        assert_eq!(
+            links(&ast),
+            vec![
+                (
+                    "https://example.com/docs",
+                    "https://example.com/docs",
+                    Span::new(4, 28)
+                ),
+                (
+                    "http://www.example.com",
+                    "www.example.com",
+                    Span::new(30, 45)
+                ),
+                (
+                    "mailto:mail@example.com",
+                    "mail@example.com",
+                    Span::new(49, 65)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_autolinks() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "<https://example.com> and <mail@example.com>";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        assert_eq!(
+            links(&ast),
+            vec![
+                (
+                    "https://example.com",
+                    "https://example.com",
+                    Span::new(0, 21)
+                ),
+                (
+                    "mailto:mail@example.com",
+                    "mail@example.com",
+                    Span::new(26, 44)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_autolink_literals_can_be_disabled() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::with_options(ParserOptions {
+            gfm_autolink_literal: false,
+            ..ParserOptions::default()
+        });
+
+        let ast = parser.parse(&arena, "See https://example.com").unwrap();
+
+        assert!(links(&ast).is_empty());
+        assert_eq!(
+            ast.children[0].children[0].value,
+            Some("See https://example.com")
+        );
+    }
+
     #[test]
     fn test_parse_strikethrough() {
         let arena = AstArena::new();
@@ -544,6 +1027,73 @@ mod tests {
         assert_eq!(first_row.node_type, NodeType::TableRow);
     }
 
+    #[test]
+    fn test_parse_table_disabled() {
+        let arena = AstArena::new();
+        let options = ParserOptions {
+            gfm_table: false,
+            ..ParserOptions::default()
+        };
+        let source = "| a | b |\n|---|---|\n| 1 | 2 |";
+
+        let ast = MarkdownParser::new()
+            .parse_with_options(&arena, source, &options)
+            .unwrap();
+        assert_eq!(ast.children[0].node_type, NodeType::Paragraph);
+
+        // The parser's own options apply to `parse`
+        let ast = MarkdownParser::with_options(options)
+            .parse(&arena, source)
+            .unwrap();
+        assert_eq!(ast.children[0].node_type, NodeType::Paragraph);
+    }
+
+    #[test]
+    fn test_parse_mdx() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::with_options(ParserOptions {
+            mdx: true,
+            ..ParserOptions::default()
+        });
+        let source =
+            "import Chart from './chart.js'\n\n{1 + 1}\n\n<Chart year={2024} />\n\n| a |\n|---|\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+        let types: Vec<_> = ast.children.iter().map(|n| n.node_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                NodeType::Html,
+                NodeType::Html,
+                NodeType::Html,
+                NodeType::Table
+            ]
+        );
+        verify_spans(&ast, source).unwrap();
+    }
+
+    #[test]
+    fn test_parse_table_alignment() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "| a | b | c | d |\n|:--|--:|:-:|---|\n| 1 | 2 | 3 | 4 |";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        let table = &ast.children[0];
+        assert_eq!(
+            table.data.align,
+            Some(
+                &[
+                    TableAlign::Left,
+                    TableAlign::Right,
+                    TableAlign::Center,
+                    TableAlign::None
+                ][..]
+            )
+        );
+    }
+
     #[test]
     fn test_parse_html_inline() {
         let arena = AstArena::new();
@@ -556,6 +1106,73 @@ mod tests {
         assert!(has_html);
     }
 
+    const HTML_SOURCE: &str =
+        "Intro.\n\n<div class=\"note\">\n  Read the <b>guide</b> first.\n</div>\n\nA <br> break.\n";
+
+    fn parse_html(arena: &AstArena, html: HtmlMode) -> TxtNode<'_> {
+        let parser = MarkdownParser::with_options(ParserOptions {
+            html,
+            ..ParserOptions::default()
+        });
+        parser.parse(arena, HTML_SOURCE).unwrap()
+    }
+
+    #[test]
+    fn test_html_opaque() {
+        let arena = AstArena::new();
+        let ast = parse_html(&arena, HtmlMode::Opaque);
+
+        let div = &ast.children[1];
+        assert_eq!(div.node_type, NodeType::Html);
+        assert!(div.value.unwrap().starts_with("<div"));
+        assert_eq!(ast.children[2].children[1].node_type, NodeType::Html);
+    }
+
+    #[test]
+    fn test_html_skip() {
+        let arena = AstArena::new();
+        let ast = parse_html(&arena, HtmlMode::Skip);
+
+        let types: Vec<_> = ast.children.iter().map(|n| n.node_type).collect();
+        assert_eq!(types, vec![NodeType::Paragraph, NodeType::Paragraph]);
+        let inline: Vec<_> = ast.children[1].children.iter().map(|n| n.value).collect();
+        assert_eq!(inline, vec![Some("A "), Some(" break.")]);
+    }
+
+    #[test]
+    fn test_html_text() {
+        let arena = AstArena::new();
+        let ast = parse_html(&arena, HtmlMode::Text);
+
+        let div = &ast.children[1];
+        assert_eq!(div.node_type, NodeType::Paragraph);
+        let texts: Vec<_> = div.children.iter().map(|n| n.value.unwrap()).collect();
+        assert_eq!(texts, vec!["Read the", "guide", "first."]);
+        for text in div.children {
+            assert_eq!(text.node_type, NodeType::Str);
+            assert_eq!(
+                &HTML_SOURCE[text.span.start as usize..text.span.end as usize],
+                text.value.unwrap()
+            );
+        }
+        assert_eq!(ast.children[2].children.len(), 2);
+        verify_spans(&ast, HTML_SOURCE).unwrap();
+    }
+
+    #[test]
+    fn test_html_text_in_block_quote_stays_opaque() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::with_options(ParserOptions {
+            html: HtmlMode::Text,
+            ..ParserOptions::default()
+        });
+        let ast = parser
+            .parse(&arena, "> <div>\n> Quoted\n> </div>\n")
+            .unwrap();
+
+        assert_eq!(ast.children[0].children[0].node_type, NodeType::Html);
+    }
+
     #[test]
     fn test_parse_multiple_headings() {
         let arena = AstArena::new();
@@ -593,7 +1210,7 @@ mod tests {
 
     #[test]
     fn test_parser_default() {
-        let parser = MarkdownParser;
+        let parser = MarkdownParser::default();
         assert_eq!(parser.name(), "markdown");
     }
 
@@ -608,4 +1225,61 @@ mod tests {
         assert_eq!(ast.span.start, 0);
         assert_eq!(ast.span.end, 5);
     }
+
+    #[test]
+    fn test_spans_match_source() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "\
+# Title with `code`
+
+Paragraph with *emphasis*, **strong**, ~~strike~~ and a [link](https://example.com \"T\").
+Escaped \\* and entity &amp; and ![image](a.png).
+
+> Quoted
+> text
+
+- item 1
+- item 2
+  continued
+
+1. first
+2. second
+
+```rust
+fn main() {}
+```
+
+| a | b |
+|---|---|
+| 1 | 2 |
+
+<div>html</div>
+
+---
+";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        verify_spans(&ast, source).unwrap();
+    }
+
+    #[test]
+    fn test_leading_bom() {
+        let arena = AstArena::new();
+        let parser = MarkdownParser::new();
+        let source = "\u{FEFF}# Title\n\nText\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        verify_spans(&ast, source).unwrap();
+        let heading = &ast.children[0];
+        assert_eq!(heading.node_type, NodeType::Header);
+        assert_eq!(heading.data.depth, Some(1));
+        // Spans point at the bytes of the original source, after the BOM
+        assert_eq!(heading.span, Span::new(3, 10));
+        assert_eq!(heading.children[0].value, Some("Title"));
+        assert_eq!(&source[5..10], "Title");
+        assert_eq!(ast.children[1].span, Span::new(12, 16));
+    }
 }
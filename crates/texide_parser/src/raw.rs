@@ -0,0 +1,83 @@
+//! Raw (passthrough) parser.
+//!
+//! This parser does not interpret the source at all: the whole file becomes
+//! a single text node. It lets rules that only look at the raw text, such as
+//! line length or trailing newlines, run on any file type without paying
+//! for a format-specific parse.
+
+use texide_ast::{AstArena, NodeType, Span, TxtNode};
+
+use crate::{ParseError, Parser};
+
+/// Raw parser implementation.
+///
+/// Produces a `Document` with one `Str` child spanning the entire source.
+/// It handles no file extensions, so it is only used when selected by name
+/// or by configuration.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RawParser;
+
+impl RawParser {
+    /// Creates a new raw parser.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for RawParser {
+    fn name(&self) -> &str {
+        "raw"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[]
+    }
+
+    fn parse<'a>(&self, arena: &'a AstArena, source: &str) -> Result<TxtNode<'a>, ParseError> {
+        let span = Span::new(0, source.len() as u32);
+        let text = TxtNode::new_text(NodeType::Str, span, arena.alloc_str(source));
+        Ok(TxtNode::new_parent(
+            NodeType::Document,
+            span,
+            arena.alloc_slice_copy(&[text]),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify_spans;
+
+    #[test]
+    fn test_parse_whole_source_as_one_node() {
+        let arena = AstArena::new();
+        let source = "key: value\nlist:\n  - a\n";
+
+        let ast = RawParser::new().parse(&arena, source).unwrap();
+
+        assert_eq!(ast.node_type, NodeType::Document);
+        assert_eq!(ast.children.len(), 1);
+        assert_eq!(ast.children[0].node_type, NodeType::Str);
+        assert_eq!(ast.children[0].span, Span::new(0, source.len() as u32));
+        assert_eq!(ast.children[0].value, Some(source));
+        verify_spans(&ast, source).unwrap();
+    }
+
+    #[test]
+    fn test_parse_empty_source() {
+        let arena = AstArena::new();
+
+        let ast = RawParser::new().parse(&arena, "").unwrap();
+
+        assert_eq!(ast.children.len(), 1);
+        assert_eq!(ast.children[0].span, Span::new(0, 0));
+    }
+
+    #[test]
+    fn test_handles_no_extensions() {
+        let parser = RawParser::new();
+        assert!(!parser.can_parse("yaml"));
+        assert!(!parser.can_parse(""));
+    }
+}
@@ -0,0 +1,217 @@
+//! Parsers selectable by name.
+
+use texide_ast::{AstArena, TxtNode};
+
+use crate::{MarkdownParser, ParseError, Parser, PlainTextParser, RawParser};
+
+/// Parsers looked up by their [`Parser::name`].
+///
+/// Complements extension-based selection for programmatic use, where the
+/// caller knows the format but not the concrete parser type:
+///
+/// ```rust
+/// use texide_ast::AstArena;
+/// use texide_parser::ParserRegistry;
+///
+/// let arena = AstArena::new();
+/// let registry = ParserRegistry::new();
+/// let ast = registry.parse_with("markdown", &arena, "# Hello\n").unwrap();
+/// assert_eq!(ast.children.len(), 1);
+/// ```
+pub struct ParserRegistry {
+    parsers: Vec<Box<dyn Parser + Send + Sync>>,
+}
+
+impl ParserRegistry {
+    /// Creates a registry with the built-in `markdown`, `text` and `raw`
+    /// parsers.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            parsers: Vec::new(),
+        };
+        registry.register(MarkdownParser::new());
+        registry.register(PlainTextParser::new());
+        registry.register(RawParser::new());
+        registry
+    }
+
+    /// Registers a parser, replacing any parser with the same name.
+    pub fn register(&mut self, parser: impl Parser + Send + Sync + 'static) {
+        self.parsers.retain(|p| p.name() != parser.name());
+        self.parsers.push(Box::new(parser));
+    }
+
+    /// Returns the parser named `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Parser> {
+        self.parsers
+            .iter()
+            .find(|parser| parser.name() == name)
+            .map(|parser| parser.as_ref() as &dyn Parser)
+    }
+
+    /// Returns the first registered parser that [can parse](Parser::can_parse)
+    /// files with `extension`.
+    pub fn for_extension(&self, extension: &str) -> Option<&dyn Parser> {
+        self.parsers
+            .iter()
+            .find(|parser| parser.can_parse(extension))
+            .map(|parser| parser.as_ref() as &dyn Parser)
+    }
+
+    /// Returns the registered parser names in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.parsers.iter().map(|parser| parser.name())
+    }
+
+    /// Parses `source` with the parser named `name`.
+    ///
+    /// Returns [`ParseError::UnknownParser`] if no parser has that name.
+    pub fn parse_with<'a>(
+        &self,
+        name: &str,
+        arena: &'a AstArena,
+        source: &str,
+    ) -> Result<TxtNode<'a>, ParseError> {
+        let parser = self.get(name).ok_or_else(|| ParseError::UnknownParser {
+            name: name.to_string(),
+            available: self.names().collect::<Vec<_>>().join(", "),
+        })?;
+        parser.parse(arena, source)
+    }
+}
+
+impl Default for ParserRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texide_ast::{NodeData, NodeType, Span};
+
+    /// Parses `.. note::` lines as `Admonition` nodes, as an
+    /// reStructuredText parser would.
+    struct DirectiveParser;
+
+    impl Parser for DirectiveParser {
+        fn name(&self) -> &str {
+            "directives"
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["rst"]
+        }
+
+        fn parse<'a>(&self, arena: &'a AstArena, source: &str) -> Result<TxtNode<'a>, ParseError> {
+            let mut nodes = Vec::new();
+            let mut offset = 0;
+            for line in source.split_inclusive('\n') {
+                let span = Span::new(offset as u32, (offset + line.trim_end().len()) as u32);
+                let node = match line.trim_end().strip_prefix(".. note:: ") {
+                    Some(text) => {
+                        let text_start = span.end - text.len() as u32;
+                        let children = arena.alloc_slice_copy(&[TxtNode::new_text(
+                            NodeType::Str,
+                            Span::new(text_start, span.end),
+                            arena.alloc_str(text),
+                        )]);
+                        let mut node = TxtNode::new_parent(NodeType::Custom, span, children);
+                        node.data = NodeData::custom("Admonition");
+                        node
+                    }
+                    None => {
+                        TxtNode::new_text(NodeType::Str, span, arena.alloc_str(line.trim_end()))
+                    }
+                };
+                nodes.push(node);
+                offset += line.len();
+            }
+            Ok(TxtNode::new_parent(
+                NodeType::Document,
+                Span::new(0, source.len() as u32),
+                arena.alloc_slice_copy(&nodes),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_parse_by_name() {
+        let arena = AstArena::new();
+        let registry = ParserRegistry::new();
+        let source = "# Title\n\nBody text.\n";
+
+        let markdown = registry.parse_with("markdown", &arena, source).unwrap();
+        assert_eq!(markdown.children[0].node_type, NodeType::Header);
+
+        let text = registry.parse_with("text", &arena, source).unwrap();
+        assert_eq!(text.node_type, NodeType::Document);
+        assert!(
+            text.children
+                .iter()
+                .all(|child| child.node_type == NodeType::Paragraph)
+        );
+    }
+
+    #[test]
+    fn test_for_extension() {
+        let mut registry = ParserRegistry::new();
+        registry.register(DirectiveParser);
+
+        let name = |extension| registry.for_extension(extension).map(|p| p.name());
+        assert_eq!(name("md"), Some("markdown"));
+        assert_eq!(name("TXT"), Some("text"));
+        assert_eq!(name("rst"), Some("directives"));
+        assert_eq!(name("png"), None);
+    }
+
+    #[test]
+    fn test_unknown_parser() {
+        let arena = AstArena::new();
+        let error = ParserRegistry::new()
+            .parse_with("asciidoc", &arena, "= Title\n")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown parser 'asciidoc' (available: markdown, text, raw)"
+        );
+    }
+
+    #[test]
+    fn test_register_replaces_by_name() {
+        let mut registry = ParserRegistry::new();
+        registry.register(PlainTextParser::with_paragraph_mode(
+            crate::ParagraphMode::SingleLine,
+        ));
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec!["markdown", "raw", "text"]
+        );
+
+        // The registry hands out trait objects
+        let parser: Box<dyn Parser> = Box::new(MarkdownParser::new());
+        assert!(registry.get(parser.name()).is_some());
+    }
+
+    #[test]
+    fn test_custom_node_types_from_registered_parser() {
+        let arena = AstArena::new();
+        let mut registry = ParserRegistry::new();
+        registry.register(DirectiveParser);
+        let source = "Intro\n.. note:: Back up first\n";
+
+        let ast = registry.parse_with("directives", &arena, source).unwrap();
+
+        let admonitions = ast.select("Admonition");
+        assert_eq!(admonitions.len(), 1);
+        assert_eq!(admonitions[0].text(source), ".. note:: Back up first");
+        assert_eq!(admonitions[0].select("Str")[0].value, Some("Back up first"));
+        assert!(ast.select("Custom").is_empty());
+
+        assert_eq!(
+            ast.tree().to_string(),
+            "Document [0..30]\n  Str [0..5] \"Intro\"\n  Admonition [6..29]\n    Str [16..29] \"Back up first\"\n"
+        );
+    }
+}
@@ -2,20 +2,113 @@
 //!
 //! This parser treats plain text as a simple document with paragraphs.
 
+use serde::{Deserialize, Serialize};
 use texide_ast::{AstArena, NodeType, Span, TxtNode};
 
 use crate::{ParseError, Parser};
 
+/// How plain text is split into paragraphs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ParagraphMode {
+    /// Paragraphs are separated by blank lines.
+    #[default]
+    BlankLine,
+    /// Every non-blank line is a paragraph of its own.
+    SingleLine,
+    /// Paragraphs are separated by blank lines, and the line breaks inside
+    /// a paragraph are replaced with spaces in its text.
+    ///
+    /// Each line terminator byte becomes one space, so offsets into the
+    /// text still match the source.
+    HardWrapJoin,
+    /// Paragraphs are separated by blank lines, and each line of a paragraph
+    /// becomes its own `Str` node with a `Break` node covering the line
+    /// terminator between lines.
+    PreserveBreaks,
+}
+
 /// Plain text parser implementation.
 ///
 /// Parses plain text files into TxtAST. The text is split into paragraphs
-/// by blank lines, and each paragraph contains text nodes.
-pub struct PlainTextParser;
+/// according to its [`ParagraphMode`] (by blank lines by default), and each
+/// paragraph contains text nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct PlainTextParser {
+    mode: ParagraphMode,
+}
 
 impl PlainTextParser {
     /// Creates a new plain text parser.
     pub fn new() -> Self {
-        Self
+        Self::with_paragraph_mode(ParagraphMode::default())
+    }
+
+    /// Creates a plain text parser that splits paragraphs using `mode`.
+    pub fn with_paragraph_mode(mode: ParagraphMode) -> Self {
+        Self { mode }
+    }
+
+    /// Returns the paragraph splitting mode.
+    pub fn paragraph_mode(&self) -> ParagraphMode {
+        self.mode
+    }
+
+    /// Creates a paragraph for `source[start..end]`, excluding trailing whitespace.
+    fn paragraph<'a>(
+        &self,
+        arena: &'a AstArena,
+        source: &str,
+        start: usize,
+        end: usize,
+    ) -> TxtNode<'a> {
+        let para_text = source[start..end].trim_end();
+        let span = Span::new(start as u32, (start + para_text.len()) as u32);
+
+        let children = match self.mode {
+            ParagraphMode::PreserveBreaks => Self::lines(arena, para_text, start),
+            ParagraphMode::HardWrapJoin => {
+                let value = arena.alloc_str(&para_text.replace(['\r', '\n'], " "));
+                arena.alloc_slice_copy(&[TxtNode::new_text(NodeType::Str, span, value)])
+            }
+            ParagraphMode::BlankLine | ParagraphMode::SingleLine => {
+                let value = arena.alloc_str(para_text);
+                arena.alloc_slice_copy(&[TxtNode::new_text(NodeType::Str, span, value)])
+            }
+        };
+        TxtNode::new_parent(NodeType::Paragraph, span, children)
+    }
+
+    /// Splits paragraph text starting at `offset` into per-line `Str` nodes
+    /// separated by `Break` nodes.
+    fn lines<'a>(arena: &'a AstArena, text: &str, offset: usize) -> &'a [TxtNode<'a>] {
+        let mut nodes = Vec::new();
+        let mut line_start = offset;
+
+        for line in text.split_inclusive('\n') {
+            let content = line
+                .strip_suffix('\n')
+                .map(|l| l.strip_suffix('\r').unwrap_or(l))
+                .unwrap_or(line);
+            let content_end = line_start + content.len();
+            let line_end = line_start + line.len();
+
+            nodes.push(TxtNode::new_text(
+                NodeType::Str,
+                Span::new(line_start as u32, content_end as u32),
+                arena.alloc_str(content),
+            ));
+            if content_end < line_end {
+                nodes.push(TxtNode::new_leaf(
+                    NodeType::Break,
+                    Span::new(content_end as u32, line_end as u32),
+                ));
+            }
+
+            line_start = line_end;
+        }
+
+        arena.alloc_slice_copy(&nodes)
     }
 }
 
@@ -36,67 +129,42 @@ impl Parser for PlainTextParser {
 
     fn parse<'a>(&self, arena: &'a AstArena, source: &str) -> Result<TxtNode<'a>, ParseError> {
         let mut paragraphs: Vec<TxtNode<'a>> = Vec::new();
-        let mut current_start: Option<usize> = None;
-        let mut current_end: usize = 0;
-
-        for (idx, line) in source.lines().enumerate() {
-            let line_start = if idx == 0 {
-                0
-            } else {
-                // Find the actual byte offset of this line
-                source[..current_end]
-                    .rfind('\n')
-                    .map(|p| p + 1)
-                    .unwrap_or(current_end)
-            };
-
-            // Update current_end to include this line
-            current_end = line_start + line.len();
-            if current_end < source.len() && source.as_bytes().get(current_end) == Some(&b'\n') {
-                current_end += 1;
-            }
-
-            if line.trim().is_empty() {
+        // Byte range of the paragraph being collected
+        let mut current: Option<(usize, usize)> = None;
+        // Skip a leading byte order mark while keeping offsets relative to `source`
+        let mut line_start = if source.starts_with('\u{FEFF}') {
+            '\u{FEFF}'.len_utf8()
+        } else {
+            0
+        };
+
+        for line in source[line_start..].split_inclusive('\n') {
+            // Exclude the line terminator ("\n" or "\r\n")
+            let content = line
+                .strip_suffix('\n')
+                .map(|l| l.strip_suffix('\r').unwrap_or(l))
+                .unwrap_or(line);
+            let line_end = line_start + content.len();
+
+            if content.trim().is_empty() {
                 // End of paragraph
-                if let Some(start) = current_start {
-                    let para_text = &source[start..line_start.saturating_sub(1).max(start)];
-                    if !para_text.trim().is_empty() {
-                        let text_node = arena.alloc(TxtNode::new_text(
-                            NodeType::Str,
-                            Span::new(start as u32, (start + para_text.len()) as u32),
-                            arena.alloc_str(para_text),
-                        ));
-                        let children = arena.alloc_slice_copy(&[*text_node]);
-                        paragraphs.push(TxtNode::new_parent(
-                            NodeType::Paragraph,
-                            Span::new(start as u32, (start + para_text.len()) as u32),
-                            children,
-                        ));
-                    }
-                    current_start = None;
+                if let Some((start, end)) = current.take() {
+                    paragraphs.push(self.paragraph(arena, source, start, end));
                 }
-            } else if current_start.is_none() {
-                // Start of new paragraph
-                current_start = Some(line_start);
+            } else if self.mode == ParagraphMode::SingleLine {
+                paragraphs.push(self.paragraph(arena, source, line_start, line_end));
+            } else {
+                // Start or extend the current paragraph
+                let start = current.map_or(line_start, |(start, _)| start);
+                current = Some((start, line_end));
             }
+
+            line_start += line.len();
         }
 
         // Handle final paragraph
-        if let Some(start) = current_start {
-            let para_text = source[start..].trim_end();
-            if !para_text.is_empty() {
-                let text_node = arena.alloc(TxtNode::new_text(
-                    NodeType::Str,
-                    Span::new(start as u32, (start + para_text.len()) as u32),
-                    arena.alloc_str(para_text),
-                ));
-                let children = arena.alloc_slice_copy(&[*text_node]);
-                paragraphs.push(TxtNode::new_parent(
-                    NodeType::Paragraph,
-                    Span::new(start as u32, (start + para_text.len()) as u32),
-                    children,
-                ));
-            }
+        if let Some((start, end)) = current {
+            paragraphs.push(self.paragraph(arena, source, start, end));
         }
 
         let children = arena.alloc_slice_clone(&paragraphs);
@@ -111,6 +179,7 @@ impl Parser for PlainTextParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::verify_spans;
 
     #[test]
     fn test_parse_simple_text() {
@@ -171,6 +240,35 @@ mod tests {
         assert!(ast.children.is_empty());
     }
 
+    #[test]
+    fn test_parse_blank_input_in_every_mode() {
+        let modes = [
+            ParagraphMode::BlankLine,
+            ParagraphMode::SingleLine,
+            ParagraphMode::HardWrapJoin,
+            ParagraphMode::PreserveBreaks,
+        ];
+        for mode in modes {
+            let parser = PlainTextParser::with_paragraph_mode(mode);
+            for source in [
+                "",
+                "\n",
+                "\r\n",
+                " \t ",
+                "\n\n  \r\n\t",
+                "\u{FEFF}",
+                "\u{FEFF}\n",
+            ] {
+                let arena = AstArena::new();
+                let ast = parser.parse(&arena, source).unwrap();
+
+                assert!(ast.children.is_empty(), "{:?} in {:?}", source, mode);
+                assert_eq!(ast.span, Span::new(0, source.len() as u32));
+                verify_spans(&ast, source).unwrap();
+            }
+        }
+    }
+
     #[test]
     fn test_parse_single_line() {
         let arena = AstArena::new();
@@ -266,8 +364,9 @@ mod tests {
 
     #[test]
     fn test_parser_default() {
-        let parser = PlainTextParser;
+        let parser = PlainTextParser::default();
         assert_eq!(parser.name(), "text");
+        assert_eq!(parser.paragraph_mode(), ParagraphMode::BlankLine);
     }
 
     #[test]
@@ -296,4 +395,138 @@ mod tests {
 
         assert_eq!(ast.children.len(), 2);
     }
+
+    #[test]
+    fn test_spans_match_source() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::new();
+        let source = "First line\nsecond line  \n\n  Indented\n\n\nLast 🎉\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        verify_spans(&ast, source).unwrap();
+        for paragraph in ast.children {
+            let text = &paragraph.children[0];
+            assert_eq!(
+                text.value,
+                Some(&source[text.span.start as usize..text.span.end as usize])
+            );
+        }
+        assert_eq!(ast.children[1].children[0].value, Some("  Indented"));
+    }
+
+    #[test]
+    fn test_spans_with_crlf() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::new();
+        let source = "Line 1\r\nLine 2\r\n\r\nLine 3\r\n";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        verify_spans(&ast, source).unwrap();
+        assert_eq!(ast.children.len(), 2);
+        assert_eq!(ast.children[0].span, Span::new(0, 14));
+        assert_eq!(ast.children[0].children[0].value, Some("Line 1\r\nLine 2"));
+        assert_eq!(ast.children[1].span, Span::new(18, 24));
+        assert_eq!(ast.children[1].children[0].value, Some("Line 3"));
+    }
+
+    #[test]
+    fn test_leading_bom_is_skipped() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::new();
+        let source = "\u{FEFF}Hello\n\nWorld";
+
+        let ast = parser.parse(&arena, source).unwrap();
+
+        verify_spans(&ast, source).unwrap();
+        assert_eq!(ast.span, Span::new(0, source.len() as u32));
+        assert_eq!(ast.children[0].span, Span::new(3, 8));
+        assert_eq!(ast.children[0].children[0].value, Some("Hello"));
+        assert_eq!(ast.children[1].span, Span::new(10, 15));
+    }
+
+    const WRAPPED: &str = "First line\nsecond line\n\nThird line\r\nfourth line\n";
+
+    fn paragraphs(mode: ParagraphMode) -> Vec<String> {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::with_paragraph_mode(mode);
+        let ast = parser.parse(&arena, WRAPPED).unwrap();
+        ast.children
+            .iter()
+            .map(|paragraph| paragraph.children[0].value.unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_paragraph_mode_blank_line() {
+        assert_eq!(
+            paragraphs(ParagraphMode::BlankLine),
+            vec!["First line\nsecond line", "Third line\r\nfourth line"]
+        );
+    }
+
+    #[test]
+    fn test_paragraph_mode_single_line() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::with_paragraph_mode(ParagraphMode::SingleLine);
+        let ast = parser.parse(&arena, WRAPPED).unwrap();
+
+        verify_spans(&ast, WRAPPED).unwrap();
+        assert_eq!(
+            paragraphs(ParagraphMode::SingleLine),
+            vec!["First line", "second line", "Third line", "fourth line"]
+        );
+    }
+
+    #[test]
+    fn test_paragraph_mode_hard_wrap_join() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::with_paragraph_mode(ParagraphMode::HardWrapJoin);
+        let ast = parser.parse(&arena, WRAPPED).unwrap();
+
+        assert_eq!(ast.children.len(), 2);
+        assert_eq!(
+            paragraphs(ParagraphMode::HardWrapJoin),
+            vec!["First line second line", "Third line  fourth line"]
+        );
+        // Joined text keeps the byte length of its span
+        for paragraph in ast.children {
+            let text = &paragraph.children[0];
+            assert_eq!(text.value.unwrap().len(), text.span.len() as usize);
+        }
+    }
+
+    #[test]
+    fn test_paragraph_mode_preserve_breaks() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::with_paragraph_mode(ParagraphMode::PreserveBreaks);
+        let ast = parser.parse(&arena, WRAPPED).unwrap();
+
+        verify_spans(&ast, WRAPPED).unwrap();
+        assert_eq!(ast.children.len(), 2);
+
+        let first = &ast.children[0];
+        let types: Vec<_> = first.children.iter().map(|node| node.node_type).collect();
+        assert_eq!(types, vec![NodeType::Str, NodeType::Break, NodeType::Str]);
+        assert_eq!(first.children[0].value, Some("First line"));
+        assert_eq!(first.children[1].span, Span::new(10, 11));
+        assert_eq!(first.children[2].value, Some("second line"));
+        assert_eq!(first.children[2].span, Span::new(11, 22));
+
+        let second = &ast.children[1];
+        assert_eq!(second.children.len(), 3);
+        assert_eq!(second.children[1].span, Span::new(34, 36));
+        assert_eq!(second.children[2].value, Some("fourth line"));
+    }
+
+    #[test]
+    fn test_paragraph_mode_preserve_breaks_single_line() {
+        let arena = AstArena::new();
+        let parser = PlainTextParser::with_paragraph_mode(ParagraphMode::PreserveBreaks);
+        let ast = parser.parse(&arena, "Only line\n").unwrap();
+
+        assert_eq!(ast.children[0].children.len(), 1);
+        assert_eq!(ast.children[0].children[0].value, Some("Only line"));
+    }
 }
@@ -0,0 +1,280 @@
+//! Span verification for parser output.
+//!
+//! Parsers must produce spans that refer to the text they describe.
+//! [`verify_spans`] checks this for a whole tree and is used by the parser
+//! tests to catch offset bugs.
+
+use std::borrow::Cow;
+
+use markdown::{decode_named, decode_numeric};
+use texide_ast::{NodeType, Span, TxtNode};
+use thiserror::Error;
+
+/// A node whose span does not match the source it was parsed from.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SpanError {
+    /// The span ends before it starts or lies outside the source.
+    #[error("{node_type} span {}..{} is out of bounds (source length {len})", span.start, span.end)]
+    OutOfBounds {
+        /// Type of the offending node.
+        node_type: NodeType,
+        /// The offending span.
+        span: Span,
+        /// Length of the source in bytes.
+        len: usize,
+    },
+
+    /// The span splits a UTF-8 character.
+    #[error("{node_type} span {}..{} is not on a char boundary", span.start, span.end)]
+    NotCharBoundary {
+        /// Type of the offending node.
+        node_type: NodeType,
+        /// The offending span.
+        span: Span,
+    },
+
+    /// The span is not contained in its parent's span.
+    #[error("{node_type} span {}..{} is outside its parent span {}..{}", span.start, span.end, parent.start, parent.end)]
+    OutsideParent {
+        /// Type of the offending node.
+        node_type: NodeType,
+        /// The offending span.
+        span: Span,
+        /// The parent's span.
+        parent: Span,
+    },
+
+    /// The span starts before the end of its previous sibling.
+    #[error("{node_type} span {}..{} overlaps its previous sibling {}..{}", span.start, span.end, previous.start, previous.end)]
+    OverlapsSibling {
+        /// Type of the offending node.
+        node_type: NodeType,
+        /// The offending span.
+        span: Span,
+        /// The previous sibling's span.
+        previous: Span,
+    },
+
+    /// The text value cannot be found in the source text of the span.
+    #[error("{node_type} value {value:?} does not match source text {text:?}")]
+    ValueMismatch {
+        /// Type of the offending node.
+        node_type: NodeType,
+        /// The node's value.
+        value: String,
+        /// The source text covered by the span.
+        text: String,
+    },
+}
+
+/// Verifies that every span in the tree refers to valid text in `source`.
+///
+/// Checks that each span is within bounds and on char boundaries, that
+/// children lie inside their parent in order without overlapping, and that
+/// text values relate to the source they cover: the non-whitespace
+/// characters of the value must appear, in order, in `&source[span]`.
+/// This allows for markup such as code fences, escapes, and blockquote
+/// markers between the characters of the value. Character references in
+/// the source (`&copy;`, `&#169;`) match the character they decode to.
+pub fn verify_spans(node: &TxtNode, source: &str) -> Result<(), SpanError> {
+    verify_node(node, source)?;
+
+    let mut previous: Option<Span> = None;
+    for child in node.children {
+        if !node.span.contains_span(&child.span) {
+            return Err(SpanError::OutsideParent {
+                node_type: child.node_type,
+                span: child.span,
+                parent: node.span,
+            });
+        }
+        if let Some(previous) = previous
+            && child.span.start < previous.end
+        {
+            return Err(SpanError::OverlapsSibling {
+                node_type: child.node_type,
+                span: child.span,
+                previous,
+            });
+        }
+        previous = Some(child.span);
+
+        verify_spans(child, source)?;
+    }
+
+    Ok(())
+}
+
+/// Verifies a single node's span and value.
+fn verify_node(node: &TxtNode, source: &str) -> Result<(), SpanError> {
+    let start = node.span.start as usize;
+    let end = node.span.end as usize;
+
+    if start > end || end > source.len() {
+        return Err(SpanError::OutOfBounds {
+            node_type: node.node_type,
+            span: node.span,
+            len: source.len(),
+        });
+    }
+
+    if !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+        return Err(SpanError::NotCharBoundary {
+            node_type: node.node_type,
+            span: node.span,
+        });
+    }
+
+    if let Some(value) = node.value {
+        let text = &source[start..end];
+        if !is_subsequence(value, &decode_references(text)) {
+            return Err(SpanError::ValueMismatch {
+                node_type: node.node_type,
+                value: value.to_string(),
+                text: text.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces HTML character references in `text` with the characters they
+/// stand for, leaving anything that is not a valid reference untouched.
+fn decode_references(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let reference = rest[1..]
+            .find(';')
+            .and_then(|semi| decode_reference(&rest[1..semi + 1]).map(|c| (c, semi + 2)));
+        match reference {
+            Some((character, len)) => {
+                decoded.push_str(&character);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    Cow::Owned(decoded)
+}
+
+/// Decodes the body of a character reference (between `&` and `;`).
+fn decode_reference(body: &str) -> Option<String> {
+    let numeric = |digits: &str, radix: u32| {
+        (!digits.is_empty() && digits.chars().all(|c| c.is_digit(radix)))
+            .then(|| decode_numeric(digits, radix))
+    };
+    match body.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => numeric(&hex[1..], 16),
+        Some(decimal) => numeric(decimal, 10),
+        None => decode_named(body, true),
+    }
+}
+
+/// Returns true if the non-whitespace chars of `value` appear in order in `text`.
+fn is_subsequence(value: &str, text: &str) -> bool {
+    let mut text_chars = text.chars();
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| text_chars.any(|t| t == c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texide_ast::AstArena;
+
+    #[test]
+    fn test_valid_tree() {
+        let arena = AstArena::new();
+        let source = "Hello world";
+        let text = arena.alloc(TxtNode::new_text(
+            NodeType::Str,
+            Span::new(0, 11),
+            "Hello world",
+        ));
+        let children = arena.alloc_slice_copy(&[*text]);
+        let root = TxtNode::new_parent(NodeType::Document, Span::new(0, 11), children);
+
+        assert_eq!(verify_spans(&root, source), Ok(()));
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let node = TxtNode::new_leaf(NodeType::Paragraph, Span::new(0, 20));
+
+        let result = verify_spans(&node, "short");
+        assert!(matches!(result, Err(SpanError::OutOfBounds { len: 5, .. })));
+    }
+
+    #[test]
+    fn test_not_char_boundary() {
+        let node = TxtNode::new_leaf(NodeType::Paragraph, Span::new(0, 1));
+
+        let result = verify_spans(&node, "日本");
+        assert!(matches!(result, Err(SpanError::NotCharBoundary { .. })));
+    }
+
+    #[test]
+    fn test_child_outside_parent() {
+        let arena = AstArena::new();
+        let child = arena.alloc(TxtNode::new_leaf(NodeType::Str, Span::new(3, 8)));
+        let children = arena.alloc_slice_copy(&[*child]);
+        let root = TxtNode::new_parent(NodeType::Paragraph, Span::new(0, 5), children);
+
+        let result = verify_spans(&root, "0123456789");
+        assert!(matches!(result, Err(SpanError::OutsideParent { .. })));
+    }
+
+    #[test]
+    fn test_overlapping_siblings() {
+        let arena = AstArena::new();
+        let first = arena.alloc(TxtNode::new_leaf(NodeType::Str, Span::new(0, 5)));
+        let second = arena.alloc(TxtNode::new_leaf(NodeType::Str, Span::new(4, 8)));
+        let children = arena.alloc_slice_copy(&[*first, *second]);
+        let root = TxtNode::new_parent(NodeType::Paragraph, Span::new(0, 10), children);
+
+        let result = verify_spans(&root, "0123456789");
+        assert!(matches!(result, Err(SpanError::OverlapsSibling { .. })));
+    }
+
+    #[test]
+    fn test_value_mismatch() {
+        let node = TxtNode::new_text(NodeType::Str, Span::new(0, 5), "world");
+
+        let result = verify_spans(&node, "hello world");
+        assert!(matches!(result, Err(SpanError::ValueMismatch { .. })));
+    }
+
+    #[test]
+    fn test_value_with_markup_between_chars() {
+        let node = TxtNode::new_text(NodeType::CodeBlock, Span::new(0, 16), "let x;");
+
+        assert_eq!(verify_spans(&node, "```rs\nlet x;\n```"), Ok(()));
+    }
+
+    #[test]
+    fn test_value_with_character_references() {
+        let node = TxtNode::new_text(NodeType::Str, Span::new(0, 19), "© ☃ & x");
+
+        assert_eq!(verify_spans(&node, "&copy; &#9731; & x;"), Ok(()));
+        assert_eq!(
+            decode_references("&bogus; &#xZZ; &amp"),
+            "&bogus; &#xZZ; &amp"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Snapshot tests of the Markdown parser over a representative document.
+//!
+//! The tree and JSON forms are both checked, so a change in node order,
+//! spans or node data shows up as a snapshot diff.
+
+use texide_ast::AstArena;
+use texide_parser::{MarkdownParser, Parser};
+
+const DOCUMENT: &str = r#"---
+title: Guide
+---
+
+# Getting *started*
+
+Install the [CLI](https://example.com/cli "Download") with `cargo`, then
+read the **guide**.[^note]
+
+1. First
+2. Second
+   - Nested
+
+> Quoted text
+
+```rust
+fn main() {}
+```
+
+| Name | Value |
+| :--- | ----: |
+| a    | 1     |
+
+![Diagram](diagram.png)
+
+<div>Raw HTML</div>
+
+---
+
+[^note]: A footnote.
+"#;
+
+#[test]
+fn markdown_tree_snapshot() {
+    let arena = AstArena::new();
+    let ast = MarkdownParser::new().parse(&arena, DOCUMENT).unwrap();
+
+    insta::assert_snapshot!(ast.tree().to_string());
+}
+
+#[test]
+fn markdown_json_snapshot() {
+    let arena = AstArena::new();
+    let ast = MarkdownParser::new().parse(&arena, DOCUMENT).unwrap();
+
+    insta::assert_json_snapshot!(ast);
+}
+
+#[test]
+fn parsing_is_deterministic() {
+    let first_arena = AstArena::new();
+    let first = MarkdownParser::new().parse(&first_arena, DOCUMENT).unwrap();
+
+    // A used arena allocates at different addresses than a fresh one
+    let second_arena = AstArena::new();
+    second_arena.alloc_str(DOCUMENT);
+    let second = MarkdownParser::new()
+        .parse(&second_arena, DOCUMENT)
+        .unwrap();
+
+    assert_eq!(first.tree().to_string(), second.tree().to_string());
+}
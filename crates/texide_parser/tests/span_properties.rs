@@ -0,0 +1,80 @@
+//! Property tests asserting that parsers produce valid spans.
+
+use proptest::prelude::*;
+use texide_ast::{ArenaPool, AstArena};
+use texide_parser::{MarkdownParser, Parser, PlainTextParser, verify_spans};
+
+/// A line of words, with optional leading/trailing spaces and non-ASCII text.
+fn line() -> impl Strategy<Value = String> {
+    prop::collection::vec("[a-zA-Z0-9,.!?]{1,8}|日本語|🎉|é", 1..8).prop_flat_map(|words| {
+        let text = words.join(" ");
+        ("[ \t]{0,2}", "[ \t]{0,2}").prop_map(move |(lead, trail)| format!("{lead}{text}{trail}"))
+    })
+}
+
+/// Paragraphs separated by blank lines, using LF or CRLF line endings,
+/// optionally prefixed with a byte order mark.
+fn document() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(prop::collection::vec(line(), 1..4), 0..6),
+        prop::bool::ANY,
+        "[ \t]{0,2}",
+        prop::bool::ANY,
+        prop::bool::ANY,
+    )
+        .prop_map(|(paragraphs, crlf, blank, trailing_newline, bom)| {
+            let newline = if crlf { "\r\n" } else { "\n" };
+            let separator = format!("{newline}{blank}{newline}");
+            let mut doc = paragraphs
+                .iter()
+                .map(|lines| lines.join(newline))
+                .collect::<Vec<_>>()
+                .join(&separator);
+            if trailing_newline {
+                doc.push_str(newline);
+            }
+            if bom {
+                doc.insert(0, '\u{FEFF}');
+            }
+            doc
+        })
+}
+
+proptest! {
+    #[test]
+    fn plain_text_spans_are_valid(source in document()) {
+        let arena = AstArena::new();
+        let ast = PlainTextParser::new().parse(&arena, &source).unwrap();
+
+        prop_assert_eq!(verify_spans(&ast, &source), Ok(()));
+        for paragraph in ast.children {
+            let text = &paragraph.children[0];
+            let slice = &source[text.span.start as usize..text.span.end as usize];
+            prop_assert_eq!(text.value, Some(slice));
+        }
+    }
+
+    #[test]
+    fn markdown_spans_are_valid(source in document()) {
+        let arena = AstArena::new();
+        let ast = MarkdownParser::new().parse(&arena, &source).unwrap();
+
+        prop_assert_eq!(verify_spans(&ast, &source), Ok(()));
+    }
+
+    #[test]
+    fn pooled_arena_parses_match_fresh_arena(sources in prop::collection::vec(document(), 1..8)) {
+        let pool = ArenaPool::new();
+
+        for source in &sources {
+            let fresh = AstArena::new();
+            let expected = MarkdownParser::new().parse(&fresh, source).unwrap();
+
+            let pooled = pool.get();
+            let ast = MarkdownParser::new().parse(&pooled, source).unwrap();
+
+            prop_assert_eq!(format!("{:?}", ast), format!("{:?}", expected));
+        }
+        prop_assert_eq!(pool.idle(), 1);
+    }
+}
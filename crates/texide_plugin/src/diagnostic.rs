@@ -1,5 +1,7 @@
 //! Diagnostic types for lint results.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use texide_ast::{Location, Span};
 
@@ -17,6 +19,29 @@ pub enum Severity {
 }
 
 /// A diagnostic message from a lint rule.
+///
+/// [`Diagnostic::new`] covers the common case; the `with_*` methods set the
+/// optional fields:
+///
+/// ```rust
+/// use texide_ast::Span;
+/// use texide_plugin::{Diagnostic, Fix, RelatedSpan, Severity};
+///
+/// let span = Span::new(0, 4);
+/// let diagnostic = Diagnostic::new("no-todo", "Found TODO", span)
+///     .with_severity(Severity::Warning)
+///     .with_fix(Fix::delete(span))
+///     .with_related(vec![RelatedSpan::new(Span::new(10, 14), "Also here")])
+///     .with_help_url("https://example.com/rules/no-todo");
+///
+/// assert_eq!(diagnostic.severity, Severity::Warning);
+/// assert_eq!(diagnostic.fix.unwrap().text, "");
+/// assert_eq!(diagnostic.related.len(), 1);
+/// assert_eq!(
+///     diagnostic.help_url.as_deref(),
+///     Some("https://example.com/rules/no-todo")
+/// );
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Diagnostic {
     /// The rule that generated this diagnostic.
@@ -25,6 +50,18 @@ pub struct Diagnostic {
     /// The diagnostic message.
     pub message: String,
 
+    /// Key of the message in locale bundles (e.g., "final-newline.missing").
+    ///
+    /// When the configured locale has a template for this key, it replaces
+    /// `message` where the diagnostic is shown; otherwise `message` is
+    /// used as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+
+    /// Values for the `{name}` placeholders in the message template.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub message_params: BTreeMap<String, String>,
+
     /// Byte span in the source.
     pub span: Span,
 
@@ -39,6 +76,19 @@ pub struct Diagnostic {
     /// Optional fix for this diagnostic.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fix: Option<Fix>,
+
+    /// Optional URL with more information about this diagnostic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
+
+    /// Categories of the diagnostic (e.g., "accessibility", "style"),
+    /// taken from the rule that reported it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Other places in the source involved in this diagnostic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedSpan>,
 }
 
 impl Diagnostic {
@@ -47,13 +97,30 @@ impl Diagnostic {
         Self {
             rule_id: rule_id.into(),
             message: message.into(),
+            message_key: None,
+            message_params: BTreeMap::new(),
             span,
             loc: None,
             severity: Severity::Error,
             fix: None,
+            help_url: None,
+            tags: Vec::new(),
+            related: Vec::new(),
         }
     }
 
+    /// Sets the key the message is looked up by in locale bundles.
+    pub fn with_message_key(mut self, key: impl Into<String>) -> Self {
+        self.message_key = Some(key.into());
+        self
+    }
+
+    /// Sets the value of the `{name}` placeholder in the message template.
+    pub fn with_message_param(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.message_params.insert(name.into(), value.to_string());
+        self
+    }
+
     /// Sets the severity level.
     pub fn with_severity(mut self, severity: Severity) -> Self {
         self.severity = severity;
@@ -71,6 +138,50 @@ impl Diagnostic {
         self.fix = Some(fix);
         self
     }
+
+    /// Sets the help URL.
+    pub fn with_help_url(mut self, help_url: impl Into<String>) -> Self {
+        self.help_url = Some(help_url.into());
+        self
+    }
+
+    /// Sets the help URL, like [`Diagnostic::with_help_url`].
+    pub fn with_help(self, help_url: impl Into<String>) -> Self {
+        self.with_help_url(help_url)
+    }
+
+    /// Sets the categories of the diagnostic.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets the other places involved in the diagnostic.
+    pub fn with_related(mut self, related: Vec<RelatedSpan>) -> Self {
+        self.related = related;
+        self
+    }
+}
+
+/// A place in the source related to a diagnostic, such as the other
+/// occurrence of something reported as a duplicate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedSpan {
+    /// Byte span in the source.
+    pub span: Span,
+
+    /// What is at this place.
+    pub message: String,
+}
+
+impl RelatedSpan {
+    /// Creates a new related span.
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
 }
 
 /// An auto-fix for a diagnostic.
@@ -131,6 +242,100 @@ mod tests {
         assert_eq!(diag.fix.as_ref().unwrap().text, "DONE");
     }
 
+    #[test]
+    fn test_diagnostic_with_help_url() {
+        let diag = Diagnostic::new("no-todo", "Found TODO", Span::new(0, 4))
+            .with_help_url("https://example.com/rules/no-todo");
+
+        assert_eq!(
+            diag.help_url.as_deref(),
+            Some("https://example.com/rules/no-todo")
+        );
+        let aliased = Diagnostic::new("no-todo", "Found TODO", Span::new(0, 4))
+            .with_help("https://example.com/rules/no-todo");
+        assert_eq!(aliased.help_url, diag.help_url);
+    }
+
+    #[test]
+    fn test_diagnostic_with_tags() {
+        let diag = Diagnostic::new("image-alt-text", "Missing alt text", Span::new(0, 4))
+            .with_tags(vec!["accessibility".to_string()]);
+
+        assert_eq!(diag.tags, vec!["accessibility"]);
+        let json = serde_json::to_value(&diag).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["accessibility"]));
+    }
+
+    #[test]
+    fn test_diagnostic_with_related() {
+        let diag = Diagnostic::new("rule", "Duplicate", Span::new(10, 15))
+            .with_related(vec![RelatedSpan::new(Span::new(0, 5), "First here")]);
+
+        let json = serde_json::to_value(&diag).unwrap();
+        assert_eq!(json["related"][0]["message"], "First here");
+        assert_eq!(json["related"][0]["span"]["start"], 0);
+
+        let plain = serde_json::to_value(Diagnostic::new("rule", "message", Span::new(0, 1)));
+        assert!(plain.unwrap().get("related").is_none());
+    }
+
+    #[test]
+    fn test_diagnostic_with_message_key() {
+        let diag = Diagnostic::new(
+            "max-line-length",
+            "Line is 90 columns wide",
+            Span::new(0, 90),
+        )
+        .with_message_key("max-line-length.too-long")
+        .with_message_param("width", 90);
+
+        assert_eq!(
+            diag.message_key.as_deref(),
+            Some("max-line-length.too-long")
+        );
+        let json = serde_json::to_value(&diag).unwrap();
+        assert_eq!(json["message_params"]["width"], "90");
+
+        let plain = serde_json::to_value(Diagnostic::new("rule", "message", Span::new(0, 1)));
+        let plain = plain.unwrap();
+        assert!(plain.get("message_key").is_none());
+        assert!(plain.get("message_params").is_none());
+    }
+
+    #[test]
+    fn test_diagnostic_builder_sets_every_field() {
+        use texide_ast::Position;
+        let loc = Location::new(Position::new(2, 5), Position::new(2, 9));
+        let diag = Diagnostic::new("no-todo", "Found TODO", Span::new(10, 14))
+            .with_message_key("no-todo.found")
+            .with_message_param("word", "TODO")
+            .with_location(loc)
+            .with_severity(Severity::Info)
+            .with_fix(Fix::new(Span::new(10, 14), "DONE"))
+            .with_help_url("https://example.com/rules/no-todo")
+            .with_tags(vec!["style".to_string()])
+            .with_related(vec![RelatedSpan::new(Span::new(0, 4), "Earlier TODO")]);
+
+        assert_eq!(diag.rule_id, "no-todo");
+        assert_eq!(diag.message, "Found TODO");
+        assert_eq!(diag.message_key.as_deref(), Some("no-todo.found"));
+        assert_eq!(diag.message_params["word"], "TODO");
+        assert_eq!(diag.span, Span::new(10, 14));
+        assert_eq!(diag.loc, Some(loc));
+        assert_eq!(diag.severity, Severity::Info);
+        let fix = diag.fix.as_ref().unwrap();
+        assert_eq!((fix.span, fix.text.as_str()), (Span::new(10, 14), "DONE"));
+        assert_eq!(
+            diag.help_url.as_deref(),
+            Some("https://example.com/rules/no-todo")
+        );
+        assert_eq!(diag.tags, vec!["style"]);
+        assert_eq!(
+            diag.related,
+            vec![RelatedSpan::new(Span::new(0, 4), "Earlier TODO")]
+        );
+    }
+
     #[test]
     fn test_fix_insert() {
         let fix = Fix::insert(10, "inserted");
@@ -15,6 +15,32 @@ pub struct LoadResult {
     pub manifest: RuleManifest,
 }
 
+/// Metadata about the file being linted, exposed to rules via host functions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileMetadata {
+    /// File path, if the content comes from a file.
+    pub path: Option<String>,
+    /// File extension without the leading dot.
+    pub extension: Option<String>,
+    /// Number of lines in the source.
+    pub line_count: u64,
+}
+
+impl FileMetadata {
+    /// Creates metadata for `source` read from `file_path`.
+    pub fn new(file_path: Option<&str>, source: &str) -> Self {
+        let extension = file_path
+            .and_then(|p| std::path::Path::new(p).extension())
+            .map(|e| e.to_string_lossy().into_owned());
+
+        Self {
+            path: file_path.map(str::to_string),
+            extension,
+            line_count: source.lines().count() as u64,
+        }
+    }
+}
+
 /// Trait for WASM rule execution.
 ///
 /// This trait abstracts the underlying WASM runtime, allowing
@@ -32,7 +58,29 @@ pub trait RuleExecutor {
     /// # Returns
     ///
     /// The rule name and manifest on success.
-    fn load(&mut self, wasm_bytes: &[u8]) -> Result<LoadResult, PluginError>;
+    fn load(&mut self, wasm_bytes: &[u8]) -> Result<LoadResult, PluginError> {
+        self.load_checked(wasm_bytes, &|_| Ok(()))
+    }
+
+    /// Loads a WASM rule from bytes if `check` accepts its manifest.
+    ///
+    /// The module is compiled once. If `check` returns an error, the rule
+    /// is dropped and the loaded rules, including one of the same name,
+    /// are left as they were.
+    ///
+    /// # Arguments
+    ///
+    /// * `wasm_bytes` - The WASM binary content
+    /// * `check` - Called with the rule's manifest before it is stored
+    ///
+    /// # Returns
+    ///
+    /// The rule name and manifest on success.
+    fn load_checked(
+        &mut self,
+        wasm_bytes: &[u8],
+        check: &dyn Fn(&RuleManifest) -> Result<(), PluginError>,
+    ) -> Result<LoadResult, PluginError>;
 
     /// Loads a WASM rule from a file path.
     ///
@@ -60,6 +108,20 @@ pub trait RuleExecutor {
     /// JSON-serialized LintResponse on success.
     fn call_lint(&mut self, rule_name: &str, input_json: &str) -> Result<String, PluginError>;
 
+    /// Sets the file metadata returned by host functions in subsequent calls.
+    ///
+    /// Executors without host function support ignore it.
+    fn set_file_metadata(&mut self, _metadata: FileMetadata) {}
+
+    /// Takes the log lines a rule emitted since the last call to this method.
+    ///
+    /// Logs are cleared at the start of every `call_lint`, so after a call
+    /// this returns only the output of that invocation. Executors without
+    /// log capture return nothing.
+    fn take_logs(&mut self, _rule_name: &str) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Unloads a rule.
     ///
     /// # Arguments
@@ -82,3 +144,24 @@ pub trait RuleExecutor {
         self.loaded_rules().contains(&rule_name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_metadata_new() {
+        let metadata = FileMetadata::new(Some("docs/README.md"), "# Title\n\nBody\n");
+        assert_eq!(metadata.path.as_deref(), Some("docs/README.md"));
+        assert_eq!(metadata.extension.as_deref(), Some("md"));
+        assert_eq!(metadata.line_count, 3);
+    }
+
+    #[test]
+    fn test_file_metadata_without_path() {
+        let metadata = FileMetadata::new(None, "text");
+        assert_eq!(metadata.path, None);
+        assert_eq!(metadata.extension, None);
+        assert_eq!(metadata.line_count, 1);
+    }
+}
@@ -6,16 +6,29 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use extism::{Manifest, Plugin, Wasm};
+use extism::{CurrentPlugin, Function, Manifest, PTR, Plugin, UserData, Val, ValType, Wasm};
 use tracing::{debug, info};
 
-use crate::executor::{LoadResult, RuleExecutor};
+use crate::executor::{FileMetadata, LoadResult, RuleExecutor};
 use crate::{PluginError, RuleManifest};
 
+/// Name of the host function rules call to emit log lines.
+///
+/// Rules declare it in the `extism:host/user` namespace and pass a string.
+const LOG_FUNCTION: &str = "texide_log";
+
+/// Log lines captured from a rule.
+type LogBuffer = UserData<Vec<String>>;
+
+/// Metadata of the file currently being linted, shared by all rules.
+type SharedMetadata = UserData<FileMetadata>;
+
 /// A loaded rule using Extism.
 struct LoadedRule {
     /// The Extism plugin instance.
     plugin: Plugin,
+    /// Log lines emitted by the rule since they were last taken.
+    logs: LogBuffer,
     /// The rule manifest (kept for potential future use).
     #[allow(dead_code)]
     manifest: RuleManifest,
@@ -29,6 +42,8 @@ struct LoadedRule {
 pub struct ExtismExecutor {
     /// Loaded rules by name.
     rules: HashMap<String, LoadedRule>,
+    /// Metadata returned by the file metadata host functions.
+    metadata: SharedMetadata,
 }
 
 impl ExtismExecutor {
@@ -36,8 +51,73 @@ impl ExtismExecutor {
     pub fn new() -> Self {
         Self {
             rules: HashMap::new(),
+            metadata: SharedMetadata::new(FileMetadata::default()),
         }
     }
+
+    /// Returns the host functions provided to a rule.
+    fn host_functions(&self, logs: &LogBuffer) -> Vec<Function> {
+        vec![
+            log_function(logs),
+            metadata_string_function("texide_file_path", &self.metadata, |m| m.path.clone()),
+            metadata_string_function("texide_file_extension", &self.metadata, |m| {
+                m.extension.clone()
+            }),
+            Function::new(
+                "texide_line_count",
+                [],
+                [ValType::I64],
+                self.metadata.clone(),
+                |_plugin: &mut CurrentPlugin,
+                 _inputs: &[Val],
+                 outputs: &mut [Val],
+                 metadata: SharedMetadata| {
+                    outputs[0] = Val::I64(metadata.get()?.lock().unwrap().line_count as i64);
+                    Ok(())
+                },
+            ),
+        ]
+    }
+}
+
+/// Creates the `texide_log` host function writing into `logs`.
+fn log_function(logs: &LogBuffer) -> Function {
+    Function::new(
+        LOG_FUNCTION,
+        [PTR],
+        [],
+        logs.clone(),
+        |plugin: &mut CurrentPlugin, inputs: &[Val], _outputs: &mut [Val], logs: LogBuffer| {
+            let line: String = plugin.memory_get_val(&inputs[0])?;
+            logs.get()?.lock().unwrap().push(line);
+            Ok(())
+        },
+    )
+}
+
+/// Creates a host function returning a string field of the file metadata.
+///
+/// Missing values are returned as an empty string.
+fn metadata_string_function(
+    name: &str,
+    metadata: &SharedMetadata,
+    field: fn(&FileMetadata) -> Option<String>,
+) -> Function {
+    Function::new(
+        name,
+        [],
+        [PTR],
+        metadata.clone(),
+        move |plugin: &mut CurrentPlugin,
+              _inputs: &[Val],
+              outputs: &mut [Val],
+              metadata: SharedMetadata| {
+            let value = field(&metadata.get()?.lock().unwrap()).unwrap_or_default();
+            let handle = plugin.memory_new(value)?;
+            outputs[0] = plugin.memory_to_val(handle);
+            Ok(())
+        },
+    )
 }
 
 impl Default for ExtismExecutor {
@@ -47,15 +127,20 @@ impl Default for ExtismExecutor {
 }
 
 impl RuleExecutor for ExtismExecutor {
-    fn load(&mut self, wasm_bytes: &[u8]) -> Result<LoadResult, PluginError> {
+    fn load_checked(
+        &mut self,
+        wasm_bytes: &[u8],
+        check: &dyn Fn(&RuleManifest) -> Result<(), PluginError>,
+    ) -> Result<LoadResult, PluginError> {
         info!("Loading WASM rule ({} bytes)", wasm_bytes.len());
 
         // Create the plugin manifest from bytes
         let wasm = Wasm::data(wasm_bytes.to_vec());
         let manifest = Manifest::new([wasm]);
 
-        // Create the plugin with WASI support
-        let mut plugin = Plugin::new(&manifest, [], true)
+        // Create the plugin with WASI support and log capture
+        let logs = LogBuffer::new(Vec::new());
+        let mut plugin = Plugin::new(&manifest, self.host_functions(&logs), true)
             .map_err(|e| PluginError::load(format!("Failed to create plugin: {}", e)))?;
 
         // Get the rule manifest by calling get_manifest()
@@ -66,6 +151,7 @@ impl RuleExecutor for ExtismExecutor {
         let rule_manifest: RuleManifest = serde_json::from_str(&manifest_json)
             .map_err(|e| PluginError::invalid_manifest(e.to_string()))?;
 
+        check(&rule_manifest)?;
         debug!(
             "Loaded rule: {} v{}",
             rule_manifest.name, rule_manifest.version
@@ -76,6 +162,7 @@ impl RuleExecutor for ExtismExecutor {
             name.clone(),
             LoadedRule {
                 plugin,
+                logs,
                 manifest: rule_manifest.clone(),
             },
         );
@@ -93,8 +180,9 @@ impl RuleExecutor for ExtismExecutor {
         let wasm = Wasm::file(path);
         let manifest = Manifest::new([wasm]);
 
-        // Create the plugin with WASI support
-        let mut plugin = Plugin::new(&manifest, [], true)
+        // Create the plugin with WASI support and log capture
+        let logs = LogBuffer::new(Vec::new());
+        let mut plugin = Plugin::new(&manifest, self.host_functions(&logs), true)
             .map_err(|e| PluginError::load(format!("Failed to create plugin: {}", e)))?;
 
         // Get the rule manifest
@@ -115,6 +203,7 @@ impl RuleExecutor for ExtismExecutor {
             name.clone(),
             LoadedRule {
                 plugin,
+                logs,
                 manifest: rule_manifest.clone(),
             },
         );
@@ -131,6 +220,10 @@ impl RuleExecutor for ExtismExecutor {
             .get_mut(rule_name)
             .ok_or_else(|| PluginError::not_found(rule_name))?;
 
+        if let Ok(logs) = rule.logs.get() {
+            logs.lock().unwrap().clear();
+        }
+
         let response_json: String = rule
             .plugin
             .call("lint", input_json)
@@ -139,6 +232,20 @@ impl RuleExecutor for ExtismExecutor {
         Ok(response_json)
     }
 
+    fn set_file_metadata(&mut self, metadata: FileMetadata) {
+        if let Ok(current) = self.metadata.get() {
+            *current.lock().unwrap() = metadata;
+        }
+    }
+
+    fn take_logs(&mut self, rule_name: &str) -> Vec<String> {
+        self.rules
+            .get(rule_name)
+            .and_then(|rule| rule.logs.get().ok())
+            .map(|logs| std::mem::take(&mut *logs.lock().unwrap()))
+            .unwrap_or_default()
+    }
+
     fn unload(&mut self, rule_name: &str) -> bool {
         self.rules.remove(rule_name).is_some()
     }
@@ -3,13 +3,26 @@
 //! This module provides WASM execution using wasmi, a pure Rust
 //! WebAssembly interpreter that can itself be compiled to WASM,
 //! enabling "WASM-in-WASM" execution for browser environments.
+//!
+//! Rules share the rule's own linear memory with the host instead of
+//! Extism's, so the `extism:host/user` functions take pointers into it:
+//!
+//! | Function | Signature |
+//! |----------|-----------|
+//! | `texide_log` | `(ptr: i32, len: i32)` |
+//! | `texide_file_path` | `(ptr: i32, capacity: i32) -> i32` |
+//! | `texide_file_extension` | `(ptr: i32, capacity: i32) -> i32` |
+//! | `texide_line_count` | `() -> i64` |
+//!
+//! The string functions write at most `capacity` bytes to `ptr` and return
+//! the full length, so a rule can retry with a larger buffer.
 
 use std::collections::HashMap;
 
 use tracing::{debug, info};
 use wasmi::{Caller, Config, Engine, Extern, Linker, Memory, Module, Store, TypedFunc};
 
-use crate::executor::{LoadResult, RuleExecutor};
+use crate::executor::{FileMetadata, LoadResult, RuleExecutor};
 use crate::{PluginError, RuleManifest};
 
 /// Host state for wasmi store.
@@ -20,6 +33,10 @@ struct HostState {
     output_buffer: Vec<u8>,
     /// Memory instance (set after instantiation).
     memory: Option<Memory>,
+    /// Metadata returned by the file metadata host functions.
+    metadata: FileMetadata,
+    /// Log lines emitted by the rule since they were last taken.
+    logs: Vec<String>,
 }
 
 impl HostState {
@@ -28,6 +45,8 @@ impl HostState {
             input_buffer: Vec::new(),
             output_buffer: Vec::new(),
             memory: None,
+            metadata: FileMetadata::default(),
+            logs: Vec::new(),
         }
     }
 }
@@ -57,6 +76,9 @@ pub struct WasmiExecutor {
     engine: Engine,
     /// Loaded rules by name.
     rules: HashMap<String, LoadedRule>,
+    /// Metadata of the file currently being linted, given to each rule
+    /// before it is called.
+    metadata: FileMetadata,
 }
 
 impl WasmiExecutor {
@@ -68,9 +90,81 @@ impl WasmiExecutor {
         Self {
             engine,
             rules: HashMap::new(),
+            metadata: FileMetadata::default(),
         }
     }
 
+    /// Adds the `texide_*` host functions to `linker`.
+    fn add_texide_functions(linker: &mut Linker<HostState>) -> Result<(), PluginError> {
+        linker
+            .func_wrap(
+                "extism:host/user",
+                "texide_log",
+                |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                    let Some(memory) = caller.data().memory else {
+                        return;
+                    };
+                    let line = memory
+                        .data(&caller)
+                        .get(ptr as usize..(ptr as usize).saturating_add(len as usize))
+                        .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+                    if let Some(line) = line {
+                        caller.data_mut().logs.push(line);
+                    }
+                },
+            )
+            .map_err(|e| PluginError::load(format!("Failed to add texide_log: {}", e)))?;
+
+        Self::add_metadata_string_function(linker, "texide_file_path", |m| m.path.as_deref())?;
+        Self::add_metadata_string_function(linker, "texide_file_extension", |m| {
+            m.extension.as_deref()
+        })?;
+
+        linker
+            .func_wrap(
+                "extism:host/user",
+                "texide_line_count",
+                |caller: Caller<'_, HostState>| -> i64 { caller.data().metadata.line_count as i64 },
+            )
+            .map_err(|e| PluginError::load(format!("Failed to add texide_line_count: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Adds a host function copying a string field of the file metadata
+    /// into a buffer of the rule and returning its full length.
+    ///
+    /// Missing values are returned as an empty string.
+    fn add_metadata_string_function(
+        linker: &mut Linker<HostState>,
+        name: &str,
+        field: fn(&FileMetadata) -> Option<&str>,
+    ) -> Result<(), PluginError> {
+        linker
+            .func_wrap(
+                "extism:host/user",
+                name,
+                move |mut caller: Caller<'_, HostState>, ptr: i32, capacity: i32| -> i32 {
+                    let value = field(&caller.data().metadata)
+                        .unwrap_or_default()
+                        .as_bytes()
+                        .to_vec();
+                    if let Some(memory) = caller.data().memory {
+                        let len = value.len().min(capacity.max(0) as usize);
+                        if let Some(buffer) = memory
+                            .data_mut(&mut caller)
+                            .get_mut(ptr as usize..(ptr as usize).saturating_add(len))
+                        {
+                            buffer.copy_from_slice(&value[..len]);
+                        }
+                    }
+                    value.len() as i32
+                },
+            )
+            .map_err(|e| PluginError::load(format!("Failed to add {}: {}", name, e)))?;
+        Ok(())
+    }
+
     /// Reads a string from WASM memory.
     fn read_string(store: &Store<HostState>, ptr: i32, len: i32) -> Result<String, PluginError> {
         let memory = store
@@ -124,7 +218,11 @@ impl Default for WasmiExecutor {
 }
 
 impl RuleExecutor for WasmiExecutor {
-    fn load(&mut self, wasm_bytes: &[u8]) -> Result<LoadResult, PluginError> {
+    fn load_checked(
+        &mut self,
+        wasm_bytes: &[u8],
+        check: &dyn Fn(&RuleManifest) -> Result<(), PluginError>,
+    ) -> Result<LoadResult, PluginError> {
         info!("Loading WASM rule ({} bytes) with wasmi", wasm_bytes.len());
 
         // Compile the module
@@ -189,6 +287,8 @@ impl RuleExecutor for WasmiExecutor {
             )
             .map_err(|e| PluginError::load(format!("Failed to add output_set: {}", e)))?;
 
+        Self::add_texide_functions(&mut linker)?;
+
         // Instantiate the module
         let instance = linker
             .instantiate_and_start(&mut store, &module)
@@ -230,6 +330,7 @@ impl RuleExecutor for WasmiExecutor {
         let rule_manifest: RuleManifest = serde_json::from_str(&manifest_json)
             .map_err(|e| PluginError::invalid_manifest(e.to_string()))?;
 
+        check(&rule_manifest)?;
         debug!(
             "Loaded rule: {} v{}",
             rule_manifest.name, rule_manifest.version
@@ -259,6 +360,10 @@ impl RuleExecutor for WasmiExecutor {
             .get_mut(rule_name)
             .ok_or_else(|| PluginError::not_found(rule_name))?;
 
+        let state = rule.store.data_mut();
+        state.metadata = self.metadata.clone();
+        state.logs.clear();
+
         // Write input to WASM memory
         let (input_ptr, input_len) =
             Self::write_string(&mut rule.store, &rule.alloc_fn, input_json)?;
@@ -275,6 +380,17 @@ impl RuleExecutor for WasmiExecutor {
         Ok(response_json)
     }
 
+    fn set_file_metadata(&mut self, metadata: FileMetadata) {
+        self.metadata = metadata;
+    }
+
+    fn take_logs(&mut self, rule_name: &str) -> Vec<String> {
+        self.rules
+            .get_mut(rule_name)
+            .map(|rule| std::mem::take(&mut rule.store.data_mut().logs))
+            .unwrap_or_default()
+    }
+
     fn unload(&mut self, rule_name: &str) -> bool {
         self.rules.remove(rule_name).is_some()
     }
@@ -445,6 +561,56 @@ mod tests {
         assert!(err_msg.contains("failed") || err_msg.contains("Trap"));
     }
 
+    #[test]
+    fn test_executor_host_functions() {
+        // Logs a line and reports the file path, extension and line count
+        // as the output `<path> <extension> <line count>`
+        let json = r#"{"name":"host-rule","version":"1.0.0"}"#;
+        let wasm = wat_to_wasm(&format!(
+            r#"
+            (module
+                (import "extism:host/user" "texide_log" (func $log (param i32 i32)))
+                (import "extism:host/user" "texide_file_path" (func $path (param i32 i32) (result i32)))
+                (import "extism:host/user" "texide_file_extension" (func $ext (param i32 i32) (result i32)))
+                (import "extism:host/user" "texide_line_count" (func $lines (result i64)))
+                (memory (export "memory") 1)
+                (func (export "get_manifest") (result i32 i32)
+                    (i32.const 0)
+                    (i32.const {len}))
+                (func (export "lint") (param i32 i32) (result i32 i32)
+                    (local $path_len i32) (local $ext_len i32)
+                    (call $log (i32.const 200) (i32.const 5))
+                    (local.set $path_len (call $path (i32.const 300) (i32.const 64)))
+                    (i32.store8 (i32.add (i32.const 300) (local.get $path_len)) (i32.const 32))
+                    (local.set $ext_len
+                        (call $ext (i32.add (i32.const 301) (local.get $path_len)) (i32.const 64)))
+                    (i32.store8
+                        (i32.add (i32.const 301) (i32.add (local.get $path_len) (local.get $ext_len)))
+                        (i32.const 32))
+                    (i32.store8
+                        (i32.add (i32.const 302) (i32.add (local.get $path_len) (local.get $ext_len)))
+                        (i32.add (i32.const 48) (i32.wrap_i64 (call $lines))))
+                    (i32.const 300)
+                    (i32.add (i32.const 3) (i32.add (local.get $path_len) (local.get $ext_len))))
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (data (i32.const 0) "{json}")
+                (data (i32.const 200) "hello")
+            )
+            "#,
+            len = json.len(),
+            json = json.replace("\"", "\\\"")
+        ));
+
+        let mut executor = WasmiExecutor::new();
+        executor.load(&wasm).expect("Failed to load rule");
+        executor.set_file_metadata(FileMetadata::new(Some("docs/a.md"), "one\ntwo\n"));
+
+        let output = executor.call_lint("host-rule", "{}").unwrap();
+        assert_eq!(output, "docs/a.md md 2");
+        assert_eq!(executor.take_logs("host-rule"), vec!["hello"]);
+        assert!(executor.take_logs("host-rule").is_empty());
+    }
+
     #[test]
     fn test_executor_large_input() {
         // Test handling of larger input (simulating a real file)
@@ -5,15 +5,15 @@
 //! the target environment.
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-use serde::{Deserialize, Serialize};
-use tracing::warn;
+use tracing::{trace, warn};
 
 // RuleExecutor trait is used by the Executor type alias
 #[allow(unused_imports)]
 use crate::executor::RuleExecutor;
-use crate::{Diagnostic, PluginError, RuleManifest};
+use crate::{Diagnostic, FileMetadata, LintResponse, PluginError, RuleManifest, RuleProfiler};
 
 #[cfg(feature = "native")]
 use crate::executor_extism::ExtismExecutor;
@@ -35,24 +35,61 @@ type Executor = WasmiExecutor;
 #[cfg(not(any(feature = "native", feature = "browser")))]
 compile_error!("Either 'native' or 'browser' feature must be enabled.");
 
-/// Request sent to a rule's lint function.
-#[derive(Debug, Serialize)]
-struct LintRequest<'a> {
-    /// The node to lint (serialized).
-    node: &'a serde_json::Value,
-    /// Rule configuration.
-    config: serde_json::Value,
-    /// Source text.
-    source: &'a str,
-    /// File path (if available).
-    file_path: Option<&'a str>,
+/// A node to lint, serialized once and shared by every rule run on it.
+///
+/// Rules receive a JSON request `{"node", "config", "source", "file_path"}`.
+/// Only `config` differs between rules, so the rest of the request is
+/// serialized when the input is created and each rule's request is put
+/// together from the prepared parts and its configuration.
+#[derive(Debug, Clone)]
+pub struct LintInput {
+    /// `{"node":<node>,"config":`
+    head: String,
+    /// `,"source":<source>,"file_path":<file_path>}`
+    tail: String,
+    /// Metadata exposed to rules through host functions.
+    metadata: FileMetadata,
 }
 
-/// Response from a rule's lint function.
-#[derive(Debug, Deserialize)]
-struct LintResponse {
-    /// Diagnostics reported by the rule.
-    diagnostics: Vec<Diagnostic>,
+impl LintInput {
+    /// Serializes a node, its source text and the file path.
+    pub fn new(
+        node: &serde_json::Value,
+        source: &str,
+        file_path: Option<&str>,
+    ) -> Result<Self, PluginError> {
+        let head = format!(r#"{{"node":{},"config":"#, serde_json::to_string(node)?);
+        let tail = format!(
+            r#","source":{},"file_path":{}}}"#,
+            serde_json::to_string(source)?,
+            serde_json::to_string(&file_path)?
+        );
+        Ok(Self {
+            head,
+            tail,
+            metadata: FileMetadata::new(file_path, source),
+        })
+    }
+
+    /// Returns the input for another node of the same file, reusing the
+    /// serialized source text.
+    pub fn with_node(&self, node: &serde_json::Value) -> Result<Self, PluginError> {
+        Ok(Self {
+            head: format!(r#"{{"node":{},"config":"#, serde_json::to_string(node)?),
+            tail: self.tail.clone(),
+            metadata: self.metadata.clone(),
+        })
+    }
+
+    /// Returns the request for a rule with the given configuration.
+    fn request(&self, config: &serde_json::Value) -> Result<String, PluginError> {
+        let config = serde_json::to_string(config)?;
+        let mut request = String::with_capacity(self.head.len() + config.len() + self.tail.len());
+        request.push_str(&self.head);
+        request.push_str(&config);
+        request.push_str(&self.tail);
+        Ok(request)
+    }
 }
 
 /// Host for loading and executing WASM rule plugins.
@@ -77,6 +114,16 @@ pub struct PluginHost {
     manifests: HashMap<String, RuleManifest>,
     /// Rule configurations by name.
     configs: HashMap<String, serde_json::Value>,
+    /// Source paths of rules loaded from files, by name.
+    sources: HashMap<String, PathBuf>,
+    /// Number of times each rule has been reloaded, by name.
+    revisions: HashMap<String, u32>,
+    /// BLAKE3 hashes of the WASM bytes each rule was loaded from, by name.
+    hashes: HashMap<String, String>,
+    /// Log lines emitted by each rule during its last invocation, by name.
+    logs: HashMap<String, Vec<String>>,
+    /// Time spent in each rule, when profiling is enabled.
+    profiler: Option<RuleProfiler>,
 }
 
 impl PluginHost {
@@ -86,6 +133,11 @@ impl PluginHost {
             executor: Executor::new(),
             manifests: HashMap::new(),
             configs: HashMap::new(),
+            sources: HashMap::new(),
+            revisions: HashMap::new(),
+            hashes: HashMap::new(),
+            logs: HashMap::new(),
+            profiler: None,
         }
     }
 
@@ -99,16 +151,102 @@ impl PluginHost {
     ///
     /// The rule manifest on success.
     pub fn load_rule(&mut self, path: impl AsRef<Path>) -> Result<RuleManifest, PluginError> {
-        let result = self.executor.load_file(path.as_ref())?;
+        let path = path.as_ref();
+        let wasm_bytes = std::fs::read(path)?;
+        let result = self.executor.load(&wasm_bytes)?;
 
         self.manifests
             .insert(result.name.clone(), result.manifest.clone());
         self.configs
             .insert(result.name.clone(), serde_json::Value::Null);
+        self.sources.insert(result.name.clone(), path.to_path_buf());
+        self.hashes
+            .insert(result.name.clone(), hash_bytes(&wasm_bytes));
+
+        Ok(result.manifest)
+    }
+
+    /// Reloads a rule from the file it was originally loaded from.
+    ///
+    /// The new instance replaces the old one and the rule's revision is
+    /// bumped so that cached results are invalidated. The rule keeps its
+    /// configuration. If the file cannot be loaded, the old instance is
+    /// kept and an error is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Rule name
+    ///
+    /// # Returns
+    ///
+    /// The new rule manifest on success.
+    pub fn reload_rule(&mut self, name: &str) -> Result<RuleManifest, PluginError> {
+        let path = self
+            .sources
+            .get(name)
+            .cloned()
+            .ok_or_else(|| PluginError::not_found(name))?;
+
+        let wasm_bytes = std::fs::read(&path)?;
+        // Checked before it is stored, so a file renamed to another loaded
+        // rule cannot replace that rule
+        let result = self.executor.load_checked(&wasm_bytes, &|manifest| {
+            if manifest.name == name {
+                return Ok(());
+            }
+            Err(PluginError::load(format!(
+                "Reloaded rule from {} is named '{}', expected '{}'",
+                path.display(),
+                manifest.name,
+                name
+            )))
+        })?;
+
+        self.manifests
+            .insert(result.name.clone(), result.manifest.clone());
+        self.hashes
+            .insert(result.name.clone(), hash_bytes(&wasm_bytes));
+        *self.revisions.entry(result.name).or_insert(0) += 1;
 
         Ok(result.manifest)
     }
 
+    /// Returns the path a rule was loaded from, if it was loaded from a file.
+    pub fn rule_source(&self, name: &str) -> Option<&Path> {
+        self.sources.get(name).map(PathBuf::as_path)
+    }
+
+    /// Returns the BLAKE3 hash of the WASM bytes a rule was loaded from.
+    ///
+    /// Unlike the manifest version, the hash changes whenever the rule is
+    /// rebuilt, so it can tell apart builds of a rule under development.
+    pub fn rule_hash(&self, name: &str) -> Option<&str> {
+        self.hashes.get(name).map(String::as_str)
+    }
+
+    /// Returns the log lines emitted by a rule during its last invocation.
+    pub fn rule_logs(&self, name: &str) -> &[String] {
+        self.logs.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns how many times a rule has been reloaded.
+    pub fn rule_revision(&self, name: &str) -> u32 {
+        self.revisions.get(name).copied().unwrap_or(0)
+    }
+
+    /// Enables or disables timing of rule calls.
+    ///
+    /// Enabling profiling starts from an empty profile; disabling it drops
+    /// the recorded profile.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiler = enabled.then(RuleProfiler::new);
+    }
+
+    /// Returns the time spent in each rule since profiling was enabled.
+    pub fn profiler(&self) -> Option<&RuleProfiler> {
+        self.profiler.as_ref()
+    }
+
     /// Loads a rule from WASM bytes.
     ///
     /// # Arguments
@@ -125,6 +263,8 @@ impl PluginHost {
             .insert(result.name.clone(), result.manifest.clone());
         self.configs
             .insert(result.name.clone(), serde_json::Value::Null);
+        self.hashes
+            .insert(result.name.clone(), hash_bytes(wasm_bytes));
 
         Ok(result.manifest)
     }
@@ -177,26 +317,40 @@ impl PluginHost {
         source: &str,
         file_path: Option<&str>,
     ) -> Result<Vec<Diagnostic>, PluginError> {
-        let config = self
-            .configs
-            .get(name)
-            .cloned()
-            .unwrap_or(serde_json::Value::Null);
+        let input = LintInput::new(node, source, file_path)?;
+        self.run_rule_on(name, &input)
+    }
 
-        let request = LintRequest {
-            node,
-            config,
-            source,
-            file_path,
-        };
+    /// Runs a rule on a prepared input.
+    ///
+    /// Prepare the input once with [`LintInput::new`] to run several rules
+    /// on the same node without serializing it for each of them.
+    pub fn run_rule_on(
+        &mut self,
+        name: &str,
+        input: &LintInput,
+    ) -> Result<Vec<Diagnostic>, PluginError> {
+        let start = self.profiler.is_some().then(Instant::now);
+        let request_json =
+            input.request(self.configs.get(name).unwrap_or(&serde_json::Value::Null))?;
+        self.executor.set_file_metadata(input.metadata.clone());
+        let result = self.executor.call_lint(name, &request_json);
+        if let (Some(profiler), Some(start)) = (&mut self.profiler, start) {
+            profiler.record(name, start.elapsed());
+        }
+
+        // Surface rule logs even when the call failed
+        let logs = self.executor.take_logs(name);
+        for line in &logs {
+            trace!("[{}] {}", name, line);
+        }
+        self.logs.insert(name.to_string(), logs);
 
-        let request_json = serde_json::to_string(&request)?;
-        let response_json = self.executor.call_lint(name, &request_json)?;
+        let response_json = result?;
 
-        let response: LintResponse = serde_json::from_str(&response_json)
-            .map_err(|e| PluginError::call(format!("Invalid response from '{}': {}", name, e)))?;
+        let response = LintResponse::from_json(name, &response_json)?;
 
-        Ok(response.diagnostics)
+        Ok(response.into_diagnostics(name))
     }
 
     /// Runs all loaded rules on a node.
@@ -222,10 +376,41 @@ impl PluginHost {
             .into_iter()
             .map(|s: &str| s.to_string())
             .collect();
+
+        self.run_rules(&rule_names, node, source, file_path)
+    }
+
+    /// Runs the given rules on a node.
+    ///
+    /// Each rule receives the configuration set via [`PluginHost::configure_rule`].
+    /// The node is serialized once for all of them. Rules that fail are
+    /// logged and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the rules to run
+    /// * `node` - The AST node (serialized as JSON)
+    /// * `source` - The source text
+    /// * `file_path` - Optional file path
+    ///
+    /// # Returns
+    ///
+    /// All diagnostics from the given rules.
+    pub fn run_rules(
+        &mut self,
+        names: &[String],
+        node: &serde_json::Value,
+        source: &str,
+        file_path: Option<&str>,
+    ) -> Result<Vec<Diagnostic>, PluginError> {
         let mut all_diagnostics = Vec::new();
+        if names.is_empty() {
+            return Ok(all_diagnostics);
+        }
 
-        for name in &rule_names {
-            match self.run_rule(name, node, source, file_path) {
+        let input = LintInput::new(node, source, file_path)?;
+        for name in names {
+            match self.run_rule_on(name, &input) {
                 Ok(diagnostics) => {
                     all_diagnostics.extend(diagnostics);
                 }
@@ -242,6 +427,10 @@ impl PluginHost {
     pub fn unload_rule(&mut self, name: &str) -> bool {
         self.manifests.remove(name);
         self.configs.remove(name);
+        self.sources.remove(name);
+        self.revisions.remove(name);
+        self.hashes.remove(name);
+        self.logs.remove(name);
         self.executor.unload(name)
     }
 
@@ -249,6 +438,10 @@ impl PluginHost {
     pub fn unload_all(&mut self) {
         self.manifests.clear();
         self.configs.clear();
+        self.sources.clear();
+        self.revisions.clear();
+        self.hashes.clear();
+        self.logs.clear();
         self.executor.unload_all();
     }
 }
@@ -259,6 +452,11 @@ impl Default for PluginHost {
     }
 }
 
+/// Returns the hex BLAKE3 hash of `bytes`.
+fn hash_bytes(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +481,364 @@ mod tests {
         let result = host.configure_rule("nonexistent", serde_json::json!({}));
         assert!(matches!(result, Err(PluginError::NotFound(_))));
     }
+
+    /// Builds an Extism rule named `strict-rule` that reports a diagnostic
+    /// only when its request contains `needle`.
+    ///
+    /// The rule logs `checking for <needle>` through `texide_log` on every call.
+    #[cfg(feature = "native")]
+    fn needle_rule_wasm(needle: &str) -> Vec<u8> {
+        crate::test_support::RuleFixture::new("strict-rule")
+            .reports(r#"[{"message":"Strict violation","span":{"start":0,"end":1}}]"#)
+            .when_request_contains(needle)
+            .logs(&format!("checking for {}", needle))
+            .build()
+    }
+
+    /// Builds a rule that reports a diagnostic when configured with `"strict": true`.
+    #[cfg(feature = "native")]
+    fn strict_rule_wasm() -> Vec<u8> {
+        needle_rule_wasm(r#""strict":true"#)
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_configured_options_are_passed_to_rule() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&strict_rule_wasm()).unwrap();
+        let node = serde_json::json!({ "type": "Document", "children": [] });
+
+        let diagnostics = host.run_rule("strict-rule", &node, "text", None).unwrap();
+        assert!(diagnostics.is_empty());
+
+        host.configure_rule("strict-rule", serde_json::json!({ "strict": true }))
+            .unwrap();
+        let diagnostics = host.run_rule("strict-rule", &node, "text", None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "strict-rule");
+
+        host.configure_rule("strict-rule", serde_json::json!({ "strict": false }))
+            .unwrap();
+        let diagnostics = host.run_all_rules(&node, "text", None).unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_run_rules_only_runs_given_rules() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&strict_rule_wasm()).unwrap();
+        host.configure_rule("strict-rule", serde_json::json!({ "strict": true }))
+            .unwrap();
+        let node = serde_json::json!({});
+
+        let diagnostics = host.run_rules(&[], &node, "text", None).unwrap();
+        assert!(diagnostics.is_empty());
+
+        let diagnostics = host
+            .run_rules(&["strict-rule".to_string()], &node, "text", None)
+            .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    /// The request every rule has received, serialized in one go.
+    #[derive(serde::Serialize)]
+    struct LintRequest<'a> {
+        node: &'a serde_json::Value,
+        config: serde_json::Value,
+        source: &'a str,
+        file_path: Option<&'a str>,
+    }
+
+    #[test]
+    fn test_lint_input_request_matches_wire_format() {
+        let node = serde_json::json!({ "type": "Str", "range": [0, 13], "children": [] });
+        let source = "Say \"hello\"\n";
+        let config = serde_json::json!({ "strict": true, "words": ["a\\b"] });
+
+        for file_path in [None, Some("docs/\"quoted\".md")] {
+            let input = LintInput::new(&node, source, file_path).unwrap();
+            let expected = serde_json::to_string(&LintRequest {
+                node: &node,
+                config: config.clone(),
+                source,
+                file_path,
+            })
+            .unwrap();
+            assert_eq!(input.request(&config).unwrap(), expected);
+        }
+
+        // Another node of the same file
+        let other = serde_json::json!({ "type": "Paragraph" });
+        let input = LintInput::new(&node, source, None).unwrap();
+        let expected = serde_json::to_string(&LintRequest {
+            node: &other,
+            config: serde_json::Value::Null,
+            source,
+            file_path: None,
+        })
+        .unwrap();
+        let request = input
+            .with_node(&other)
+            .unwrap()
+            .request(&serde_json::Value::Null);
+        assert_eq!(request.unwrap(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_shared_input_gives_same_diagnostics_as_separate_runs() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&strict_rule_wasm()).unwrap();
+        host.load_rule_bytes(&file_path_rule_wasm()).unwrap();
+        host.configure_rule("strict-rule", serde_json::json!({ "strict": true }))
+            .unwrap();
+        let node = serde_json::json!({ "type": "Document", "children": [] });
+        let names = ["file-path-rule".to_string(), "strict-rule".to_string()];
+
+        let mut separate = Vec::new();
+        for name in &names {
+            separate.extend(host.run_rule(name, &node, "text", Some("a.md")).unwrap());
+        }
+        let shared = host.run_rules(&names, &node, "text", Some("a.md")).unwrap();
+
+        assert_eq!(separate.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&shared).unwrap(),
+            serde_json::to_value(&separate).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_reload_rule_picks_up_new_wasm() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("strict-rule.wasm");
+        std::fs::write(&path, strict_rule_wasm()).unwrap();
+
+        let mut host = PluginHost::new();
+        host.load_rule(&path).unwrap();
+        assert_eq!(host.rule_source("strict-rule"), Some(path.as_path()));
+        assert_eq!(host.rule_revision("strict-rule"), 0);
+
+        let node = serde_json::json!({ "type": "Document" });
+        let diagnostics = host.run_rule("strict-rule", &node, "text", None).unwrap();
+        assert!(diagnostics.is_empty());
+
+        // The new version reports on every document node.
+        std::fs::write(&path, needle_rule_wasm(r#""type":"Document""#)).unwrap();
+        let hash = host.rule_hash("strict-rule").unwrap().to_string();
+        host.reload_rule("strict-rule").unwrap();
+        assert_eq!(host.rule_revision("strict-rule"), 1);
+        assert_ne!(host.rule_hash("strict-rule"), Some(hash.as_str()));
+
+        let diagnostics = host.run_rule("strict-rule", &node, "text", None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_reload_rule_failure_keeps_old_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("strict-rule.wasm");
+        std::fs::write(&path, strict_rule_wasm()).unwrap();
+
+        let mut host = PluginHost::new();
+        host.load_rule(&path).unwrap();
+        host.configure_rule("strict-rule", serde_json::json!({ "strict": true }))
+            .unwrap();
+
+        std::fs::write(&path, b"not wasm").unwrap();
+        assert!(host.reload_rule("strict-rule").is_err());
+        assert_eq!(host.rule_revision("strict-rule"), 0);
+
+        let node = serde_json::json!({});
+        let diagnostics = host.run_rule("strict-rule", &node, "text", None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_reload_rule_renamed_to_other_loaded_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let strict_path = dir.path().join("strict-rule.wasm");
+        let file_path_path = dir.path().join("file-path-rule.wasm");
+        std::fs::write(&strict_path, strict_rule_wasm()).unwrap();
+        std::fs::write(&file_path_path, file_path_rule_wasm()).unwrap();
+
+        let mut host = PluginHost::new();
+        host.load_rule(&strict_path).unwrap();
+        host.load_rule(&file_path_path).unwrap();
+
+        // strict-rule.wasm now declares the name of the other rule
+        std::fs::write(&strict_path, file_path_rule_wasm()).unwrap();
+        assert!(host.reload_rule("strict-rule").is_err());
+
+        let mut loaded = host.loaded_rules();
+        loaded.sort_unstable();
+        assert_eq!(loaded, vec!["file-path-rule", "strict-rule"]);
+        assert_eq!(host.rule_revision("file-path-rule"), 0);
+        let node = serde_json::json!({});
+        let diagnostics = host
+            .run_rule("file-path-rule", &node, "text", Some("a.md"))
+            .unwrap();
+        assert_eq!(diagnostics[0].message, "File: a.md");
+        assert!(
+            host.run_rule("strict-rule", &node, "text", None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_reload_rule_not_loaded_from_file() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&strict_rule_wasm()).unwrap();
+
+        let result = host.reload_rule("strict-rule");
+        assert!(matches!(result, Err(PluginError::NotFound(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_rule_logs_are_captured_per_call() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&strict_rule_wasm()).unwrap();
+        assert!(host.rule_logs("strict-rule").is_empty());
+
+        let node = serde_json::json!({});
+        host.run_rule("strict-rule", &node, "text", None).unwrap();
+        host.run_rule("strict-rule", &node, "text", None).unwrap();
+
+        assert_eq!(
+            host.rule_logs("strict-rule"),
+            [r#"checking for "strict":true"#]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_profiling_records_each_call() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&strict_rule_wasm()).unwrap();
+        let node = serde_json::json!({});
+
+        host.run_all_rules(&node, "text", None).unwrap();
+        assert!(host.profiler().is_none());
+
+        host.set_profiling(true);
+        host.run_all_rules(&node, "text", None).unwrap();
+        host.run_all_rules(&node, "text", None).unwrap();
+
+        let profile = host.profiler().unwrap().get("strict-rule").unwrap();
+        assert_eq!(profile.invocations, 2);
+    }
+
+    /// Builds an Extism rule named `file-path-rule` that reports the path
+    /// returned by the `texide_file_path` host function.
+    #[cfg(feature = "native")]
+    fn file_path_rule_wasm() -> Vec<u8> {
+        fn escape(s: &str) -> String {
+            s.replace('"', "\\\"")
+        }
+
+        let manifest = r#"{"name":"file-path-rule","version":"1.0.0"}"#;
+        let prefix = r#"{"diagnostics":[{"message":"File: "#;
+        let suffix = r#"","span":{"start":0,"end":0}}]}"#;
+
+        let wat = format!(
+            r#"
+            (module
+                (import "extism:host/env" "alloc" (func $alloc (param i64) (result i64)))
+                (import "extism:host/env" "length" (func $length (param i64) (result i64)))
+                (import "extism:host/env" "load_u8" (func $load_u8 (param i64) (result i32)))
+                (import "extism:host/env" "store_u8" (func $store_u8 (param i64 i32)))
+                (import "extism:host/env" "output_set" (func $output_set (param i64 i64)))
+                (import "extism:host/user" "texide_file_path" (func $texide_file_path (result i64)))
+                (memory 1)
+                (data (i32.const 0) "{manifest}")
+                (data (i32.const 256) "{prefix}")
+                (data (i32.const 512) "{suffix}")
+
+                ;; Copies local memory to Extism memory at `dest`.
+                (func $copy_local (param $dest i64) (param $ptr i32) (param $len i32)
+                    (local $i i32)
+                    (block $done
+                        (loop $copy
+                            (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                            (call $store_u8
+                                (i64.add (local.get $dest) (i64.extend_i32_u (local.get $i)))
+                                (i32.load8_u (i32.add (local.get $ptr) (local.get $i))))
+                            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                            (br $copy))))
+
+                ;; Copies `len` bytes of Extism memory from `src` to `dest`.
+                (func $copy_extism (param $dest i64) (param $src i64) (param $len i64)
+                    (local $i i64)
+                    (block $done
+                        (loop $copy
+                            (br_if $done (i64.ge_u (local.get $i) (local.get $len)))
+                            (call $store_u8
+                                (i64.add (local.get $dest) (local.get $i))
+                                (call $load_u8 (i64.add (local.get $src) (local.get $i))))
+                            (local.set $i (i64.add (local.get $i) (i64.const 1)))
+                            (br $copy))))
+
+                (func (export "get_manifest") (result i32)
+                    (local $offset i64)
+                    (local.set $offset (call $alloc (i64.const {manifest_len})))
+                    (call $copy_local (local.get $offset) (i32.const 0) (i32.const {manifest_len}))
+                    (call $output_set (local.get $offset) (i64.const {manifest_len}))
+                    (i32.const 0))
+
+                (func (export "lint") (result i32)
+                    (local $path i64) (local $path_len i64) (local $out i64) (local $out_len i64)
+                    (local.set $path (call $texide_file_path))
+                    (local.set $path_len (call $length (local.get $path)))
+                    (local.set $out_len
+                        (i64.add (local.get $path_len) (i64.const {affix_len})))
+                    (local.set $out (call $alloc (local.get $out_len)))
+                    (call $copy_local (local.get $out) (i32.const 256) (i32.const {prefix_len}))
+                    (call $copy_extism
+                        (i64.add (local.get $out) (i64.const {prefix_len}))
+                        (local.get $path)
+                        (local.get $path_len))
+                    (call $copy_local
+                        (i64.add (local.get $out) (i64.add (local.get $path_len) (i64.const {prefix_len})))
+                        (i32.const 512)
+                        (i32.const {suffix_len}))
+                    (call $output_set (local.get $out) (local.get $out_len))
+                    (i32.const 0))
+            )
+            "#,
+            manifest = escape(manifest),
+            manifest_len = manifest.len(),
+            prefix = escape(prefix),
+            prefix_len = prefix.len(),
+            suffix = escape(suffix),
+            suffix_len = suffix.len(),
+            affix_len = prefix.len() + suffix.len(),
+        );
+
+        wat::parse_str(wat).expect("Invalid WAT")
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_rule_reads_file_path_through_host_function() {
+        let mut host = PluginHost::new();
+        host.load_rule_bytes(&file_path_rule_wasm()).unwrap();
+        let node = serde_json::json!({});
+
+        let diagnostics = host
+            .run_rule("file-path-rule", &node, "text", Some("docs/guide.md"))
+            .unwrap();
+        assert_eq!(diagnostics[0].message, "File: docs/guide.md");
+
+        let diagnostics = host
+            .run_rule("file-path-rule", &node, "text", None)
+            .unwrap();
+        assert_eq!(diagnostics[0].message, "File: ");
+    }
 }
@@ -20,6 +20,8 @@
 //!
 //! - `native` (default): Enable Extism backend for native environments
 //! - `browser`: Enable wasmi backend for browser/WASM environments
+//! - `test-support`: Enable [`test_support`], WASM rules for tests of
+//!   crates using this one
 //!
 //! ## Example
 //!
@@ -37,6 +39,10 @@ mod error;
 mod executor;
 mod host;
 mod manifest;
+mod profile;
+mod protocol;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 #[cfg(feature = "native")]
 mod executor_extism;
@@ -44,8 +50,10 @@ mod executor_extism;
 #[cfg(all(feature = "browser", not(feature = "native")))]
 mod executor_wasmi;
 
-pub use diagnostic::{Diagnostic, Fix, Severity};
+pub use diagnostic::{Diagnostic, Fix, RelatedSpan, Severity};
 pub use error::PluginError;
-pub use executor::{LoadResult, RuleExecutor};
-pub use host::PluginHost;
+pub use executor::{FileMetadata, LoadResult, RuleExecutor};
+pub use host::{LintInput, PluginHost};
 pub use manifest::{IsolationLevel, RuleManifest};
+pub use profile::{RuleProfile, RuleProfiler};
+pub use protocol::{LintResponse, WIRE_FORMAT_VERSION, WireDiagnostic};
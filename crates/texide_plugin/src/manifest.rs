@@ -46,6 +46,15 @@ pub struct RuleManifest {
     /// JSON Schema for rule options.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<serde_json::Value>,
+
+    /// Categories of the rule's diagnostics (e.g., "accessibility", "style").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Documentation URL given to the rule's diagnostics that have none.
+    /// `{rule_id}` is replaced with the diagnostic's rule ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
 }
 
 impl RuleManifest {
@@ -59,6 +68,8 @@ impl RuleManifest {
             node_types: Vec::new(),
             isolation_level: IsolationLevel::Global,
             schema: None,
+            tags: Vec::new(),
+            help_url: None,
         }
     }
 
@@ -85,6 +96,18 @@ impl RuleManifest {
         self.isolation_level = isolation_level;
         self
     }
+
+    /// Sets the categories of the rule's diagnostics.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets the documentation URL template for the rule's diagnostics.
+    pub fn with_help_url(mut self, help_url: impl Into<String>) -> Self {
+        self.help_url = Some(help_url.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -107,7 +130,8 @@ mod tests {
             .with_description("Disallow TODO comments")
             .with_fixable(true)
             .with_node_types(vec!["Str".to_string()])
-            .with_isolation_level(IsolationLevel::Block);
+            .with_isolation_level(IsolationLevel::Block)
+            .with_tags(vec!["style".to_string()]);
 
         assert_eq!(
             manifest.description,
@@ -116,6 +140,7 @@ mod tests {
         assert!(manifest.fixable);
         assert_eq!(manifest.node_types, vec!["Str"]);
         assert_eq!(manifest.isolation_level, IsolationLevel::Block);
+        assert_eq!(manifest.tags, vec!["style"]);
     }
 
     #[test]
@@ -137,5 +162,6 @@ mod tests {
 
         let manifest: RuleManifest = serde_json::from_str(json).unwrap();
         assert_eq!(manifest.isolation_level, IsolationLevel::Global);
+        assert!(manifest.tags.is_empty());
     }
 }
@@ -0,0 +1,103 @@
+//! Rule execution profiling.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Accumulated execution time of one rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleProfile {
+    /// Number of times the rule ran.
+    pub invocations: u64,
+    /// Total time spent in the rule.
+    pub total: Duration,
+}
+
+impl RuleProfile {
+    /// Returns the average time per invocation.
+    pub fn average(&self) -> Duration {
+        match u32::try_from(self.invocations) {
+            Ok(0) => Duration::ZERO,
+            Ok(invocations) => self.total / invocations,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.invocations as f64),
+        }
+    }
+}
+
+/// Execution times of rules, accumulated by rule name.
+#[derive(Debug, Clone, Default)]
+pub struct RuleProfiler {
+    rules: HashMap<String, RuleProfile>,
+}
+
+impl RuleProfiler {
+    /// Creates an empty profiler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one invocation of `rule` that took `duration`.
+    pub fn record(&mut self, rule: &str, duration: Duration) {
+        let profile = self.rules.entry(rule.to_string()).or_default();
+        profile.invocations += 1;
+        profile.total += duration;
+    }
+
+    /// Adds the invocations recorded by `other`.
+    pub fn merge(&mut self, other: &RuleProfiler) {
+        for (rule, other) in &other.rules {
+            let profile = self.rules.entry(rule.clone()).or_default();
+            profile.invocations += other.invocations;
+            profile.total += other.total;
+        }
+    }
+
+    /// Returns the profile of `rule`, if it ran.
+    pub fn get(&self, rule: &str) -> Option<&RuleProfile> {
+        self.rules.get(rule)
+    }
+
+    /// Returns every profiled rule, slowest (by total time) first.
+    pub fn sorted(&self) -> Vec<(&str, RuleProfile)> {
+        let mut rules: Vec<_> = self
+            .rules
+            .iter()
+            .map(|(rule, profile)| (rule.as_str(), *profile))
+            .collect();
+        rules.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_average() {
+        let mut profiler = RuleProfiler::new();
+        profiler.record("no-todo", Duration::from_millis(10));
+        profiler.record("no-todo", Duration::from_millis(30));
+
+        let profile = profiler.get("no-todo").unwrap();
+        assert_eq!(profile.invocations, 2);
+        assert_eq!(profile.total, Duration::from_millis(40));
+        assert_eq!(profile.average(), Duration::from_millis(20));
+        assert_eq!(RuleProfile::default().average(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_merge_and_sorted() {
+        let mut profiler = RuleProfiler::new();
+        profiler.record("fast", Duration::from_millis(1));
+
+        let mut other = RuleProfiler::new();
+        other.record("slow", Duration::from_millis(50));
+        other.record("fast", Duration::from_millis(1));
+        profiler.merge(&other);
+
+        let sorted = profiler.sorted();
+        assert_eq!(sorted[0].0, "slow");
+        assert_eq!(sorted[1].0, "fast");
+        assert_eq!(sorted[1].1.invocations, 2);
+    }
+}
@@ -0,0 +1,237 @@
+//! Wire format for responses returned by WASM rules.
+//!
+//! Rules serialize a [`LintResponse`] as JSON from their `lint` export.
+//! The response carries a `version` field so the host can evolve the
+//! format without breaking rules built against older PDKs. Responses
+//! without a version are treated as the legacy (version 0) shape, which
+//! is a strict subset of the current one.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use texide_ast::{Location, Span};
+
+use crate::{Diagnostic, Fix, PluginError, Severity};
+
+/// The newest wire format version understood by this host.
+pub const WIRE_FORMAT_VERSION: u32 = 1;
+
+/// Response from a rule's lint function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintResponse {
+    /// Wire format version (0 when omitted by legacy rules).
+    #[serde(default)]
+    pub version: u32,
+
+    /// Diagnostics reported by the rule.
+    pub diagnostics: Vec<WireDiagnostic>,
+}
+
+/// A diagnostic as serialized by a WASM rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WireDiagnostic {
+    /// The rule that generated this diagnostic.
+    ///
+    /// If empty, the name of the rule that was called is used.
+    #[serde(default)]
+    pub rule_id: String,
+
+    /// The diagnostic message.
+    pub message: String,
+
+    /// Key of the message in locale bundles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+
+    /// Values for the placeholders in the message template.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub message_params: BTreeMap<String, String>,
+
+    /// Byte span in the source.
+    pub span: Span,
+
+    /// Line/column location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loc: Option<Location>,
+
+    /// Severity level.
+    #[serde(default)]
+    pub severity: Severity,
+
+    /// Optional fix for this diagnostic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+
+    /// Optional URL with more information about the diagnostic (since version 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
+}
+
+impl LintResponse {
+    /// Parses a response returned by the rule `rule_name`.
+    ///
+    /// Fails if the JSON is malformed or if the rule speaks a newer
+    /// wire format version than this host supports.
+    pub fn from_json(rule_name: &str, json: &str) -> Result<Self, PluginError> {
+        let response: LintResponse = serde_json::from_str(json).map_err(|e| {
+            PluginError::call(format!("Invalid response from '{}': {}", rule_name, e))
+        })?;
+
+        if response.version > WIRE_FORMAT_VERSION {
+            return Err(PluginError::call(format!(
+                "Rule '{}' uses wire format version {}, but this host supports up to {}",
+                rule_name, response.version, WIRE_FORMAT_VERSION
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Converts the wire diagnostics into host diagnostics.
+    pub fn into_diagnostics(self, rule_name: &str) -> Vec<Diagnostic> {
+        self.diagnostics
+            .into_iter()
+            .map(|d| d.into_diagnostic(rule_name))
+            .collect()
+    }
+}
+
+impl WireDiagnostic {
+    /// Converts this wire diagnostic into a host diagnostic.
+    ///
+    /// `rule_name` is used as the rule ID when the rule did not set one.
+    pub fn into_diagnostic(self, rule_name: &str) -> Diagnostic {
+        let rule_id = if self.rule_id.is_empty() {
+            rule_name.to_string()
+        } else {
+            self.rule_id
+        };
+
+        Diagnostic {
+            rule_id,
+            message: self.message,
+            message_key: self.message_key,
+            message_params: self.message_params,
+            span: self.span,
+            loc: self.loc,
+            severity: self.severity,
+            fix: self.fix,
+            help_url: self.help_url,
+            tags: Vec::new(),
+            related: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_response_deserialization() {
+        let json = r#"{
+            "diagnostics": [
+                {
+                    "rule_id": "no-todo",
+                    "message": "Found TODO",
+                    "span": { "start": 0, "end": 4 },
+                    "severity": "warning"
+                }
+            ]
+        }"#;
+
+        let response = LintResponse::from_json("no-todo", json).unwrap();
+        assert_eq!(response.version, 0);
+
+        let diagnostics = response.into_diagnostics("no-todo");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "no-todo");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].fix.is_none());
+        assert!(diagnostics[0].help_url.is_none());
+    }
+
+    #[test]
+    fn test_v1_response_deserialization() {
+        let json = r#"{
+            "version": 1,
+            "diagnostics": [
+                {
+                    "rule_id": "no-todo",
+                    "message": "Found TODO",
+                    "span": { "start": 0, "end": 4 },
+                    "severity": "info",
+                    "fix": { "span": { "start": 0, "end": 4 }, "text": "DONE" },
+                    "help_url": "https://example.com/rules/no-todo"
+                }
+            ]
+        }"#;
+
+        let response = LintResponse::from_json("no-todo", json).unwrap();
+        assert_eq!(response.version, 1);
+
+        let diagnostics = response.into_diagnostics("no-todo");
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+        assert_eq!(diagnostics[0].fix.as_ref().unwrap().text, "DONE");
+        assert_eq!(
+            diagnostics[0].help_url.as_deref(),
+            Some("https://example.com/rules/no-todo")
+        );
+    }
+
+    #[test]
+    fn test_missing_rule_id_defaults_to_rule_name() {
+        let json = r#"{
+            "version": 1,
+            "diagnostics": [
+                { "message": "Too long", "span": { "start": 2, "end": 8 } }
+            ]
+        }"#;
+
+        let diagnostics = LintResponse::from_json("sentence-length", json)
+            .unwrap()
+            .into_diagnostics("sentence-length");
+
+        assert_eq!(diagnostics[0].rule_id, "sentence-length");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_message_key_and_params() {
+        let json = r#"{
+            "version": 1,
+            "diagnostics": [
+                {
+                    "message": "Sentence has 42 words",
+                    "message_key": "sentence-length.too-long",
+                    "message_params": { "words": "42" },
+                    "span": { "start": 0, "end": 10 }
+                }
+            ]
+        }"#;
+
+        let diagnostics = LintResponse::from_json("sentence-length", json)
+            .unwrap()
+            .into_diagnostics("sentence-length");
+
+        assert_eq!(
+            diagnostics[0].message_key.as_deref(),
+            Some("sentence-length.too-long")
+        );
+        assert_eq!(diagnostics[0].message_params["words"], "42");
+    }
+
+    #[test]
+    fn test_unsupported_version_is_rejected() {
+        let json = r#"{ "version": 99, "diagnostics": [] }"#;
+
+        let result = LintResponse::from_json("future-rule", json);
+        assert!(matches!(result, Err(PluginError::CallError(_))));
+    }
+
+    #[test]
+    fn test_malformed_response_is_rejected() {
+        let result = LintResponse::from_json("broken-rule", "[]");
+        assert!(matches!(result, Err(PluginError::CallError(_))));
+    }
+}
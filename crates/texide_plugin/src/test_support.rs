@@ -0,0 +1,181 @@
+//! WASM rules for tests and benchmarks.
+//!
+//! Compiles small Extism rules from WAT, so tests can load real rules
+//! without a WASM toolchain. Enabled by the `test-support` feature.
+//!
+//! ```rust
+//! use texide_plugin::test_support::RuleFixture;
+//!
+//! let wasm = RuleFixture::new("no-todo")
+//!     .reports(r#"[{"message":"Found TODO","span":{"start":0,"end":4}}]"#)
+//!     .when_request_contains("TODO")
+//!     .build();
+//! assert!(wasm.starts_with(b"\0asm"));
+//! ```
+
+use std::fmt::Write as _;
+
+/// An Extism rule that reports fixed diagnostics.
+#[derive(Debug, Clone)]
+pub struct RuleFixture {
+    /// Name in the rule's manifest.
+    name: String,
+    /// JSON array of the wire diagnostics reported.
+    diagnostics: String,
+    /// Text the lint request must contain for the rule to report.
+    needle: String,
+    /// Message logged through `texide_log` on every lint call.
+    log: Option<String>,
+}
+
+impl RuleFixture {
+    /// Creates a rule called `name`, version `1.0.0`, that reports nothing.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            diagnostics: "[]".to_string(),
+            needle: String::new(),
+            log: None,
+        }
+    }
+
+    /// Reports `diagnostics`, a JSON array of wire diagnostics, on every
+    /// lint call.
+    pub fn reports(mut self, diagnostics: &str) -> Self {
+        self.diagnostics = diagnostics.to_string();
+        self
+    }
+
+    /// Reports only when the lint request (the node, config, source and
+    /// file path as JSON) contains `needle`, and nothing otherwise.
+    pub fn when_request_contains(mut self, needle: &str) -> Self {
+        self.needle = needle.to_string();
+        self
+    }
+
+    /// Logs `message` through `texide_log` on every lint call.
+    pub fn logs(mut self, message: &str) -> Self {
+        self.log = Some(message.to_string());
+        self
+    }
+
+    /// Compiles the rule to WASM.
+    pub fn build(&self) -> Vec<u8> {
+        let manifest = format!(r#"{{"name":"{}","version":"1.0.0"}}"#, self.name);
+        let hit = format!(r#"{{"version":1,"diagnostics":{}}}"#, self.diagnostics);
+        let empty = r#"{"version":1,"diagnostics":[]}"#;
+        let log = self.log.as_deref().unwrap_or_default();
+
+        // Lays the strings out one after another in local memory
+        let mut data = String::new();
+        let mut offset = 0;
+        let mut place = |text: &str| {
+            let at = offset;
+            writeln!(
+                data,
+                r#"(data (i32.const {}) "{}")"#,
+                at,
+                text.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+            .unwrap();
+            offset += text.len().next_multiple_of(8);
+            (at, text.len())
+        };
+        let manifest = place(&manifest);
+        let needle = place(&self.needle);
+        let hit = place(&hit);
+        let empty = place(empty);
+        let log = place(log);
+        let pages = offset.div_ceil(65536).max(1);
+
+        let (log_import, log_call) = match self.log {
+            Some(_) => (
+                r#"(import "extism:host/user" "texide_log" (func $texide_log (param i64)))"#,
+                format!(
+                    "(call $texide_log (call $copy_out (i32.const {}) (i32.const {})))",
+                    log.0, log.1
+                ),
+            ),
+            None => ("", String::new()),
+        };
+
+        let wat = format!(
+            r#"
+            (module
+                (import "extism:host/env" "input_length" (func $input_length (result i64)))
+                (import "extism:host/env" "input_load_u8" (func $input_load_u8 (param i64) (result i32)))
+                (import "extism:host/env" "alloc" (func $alloc (param i64) (result i64)))
+                (import "extism:host/env" "store_u8" (func $store_u8 (param i64 i32)))
+                (import "extism:host/env" "output_set" (func $output_set (param i64 i64)))
+                {log_import}
+                (memory {pages})
+                {data}
+
+                ;; Copies local memory into Extism memory and returns its offset.
+                (func $copy_out (param $ptr i32) (param $len i32) (result i64)
+                    (local $offset i64) (local $i i32)
+                    (local.set $offset (call $alloc (i64.extend_i32_u (local.get $len))))
+                    (block $done
+                        (loop $copy
+                            (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                            (call $store_u8
+                                (i64.add (local.get $offset) (i64.extend_i32_u (local.get $i)))
+                                (i32.load8_u (i32.add (local.get $ptr) (local.get $i))))
+                            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                            (br $copy)))
+                    (local.get $offset))
+
+                ;; Sets local memory as the output.
+                (func $output (param $ptr i32) (param $len i32)
+                    (call $output_set
+                        (call $copy_out (local.get $ptr) (local.get $len))
+                        (i64.extend_i32_u (local.get $len))))
+
+                ;; Returns 1 if the input contains the needle.
+                (func $contains_needle (result i32)
+                    (local $start i64) (local $end i64) (local $j i32)
+                    (local.set $end (i64.sub (call $input_length) (i64.const {needle_len})))
+                    (block $not_found
+                        (loop $scan
+                            (br_if $not_found (i64.gt_s (local.get $start) (local.get $end)))
+                            (local.set $j (i32.const 0))
+                            (block $mismatch
+                                (loop $compare
+                                    (if (i32.eq (local.get $j) (i32.const {needle_len}))
+                                        (then (return (i32.const 1))))
+                                    (br_if $mismatch
+                                        (i32.ne
+                                            (call $input_load_u8
+                                                (i64.add (local.get $start) (i64.extend_i32_u (local.get $j))))
+                                            (i32.load8_u (i32.add (i32.const {needle_at}) (local.get $j)))))
+                                    (local.set $j (i32.add (local.get $j) (i32.const 1)))
+                                    (br $compare)))
+                            (local.set $start (i64.add (local.get $start) (i64.const 1)))
+                            (br $scan)))
+                    (i32.const 0))
+
+                (func (export "get_manifest") (result i32)
+                    (call $output (i32.const {manifest_at}) (i32.const {manifest_len}))
+                    (i32.const 0))
+
+                (func (export "lint") (result i32)
+                    {log_call}
+                    (if (call $contains_needle)
+                        (then (call $output (i32.const {hit_at}) (i32.const {hit_len})))
+                        (else (call $output (i32.const {empty_at}) (i32.const {empty_len}))))
+                    (i32.const 0))
+            )
+            "#,
+            manifest_at = manifest.0,
+            manifest_len = manifest.1,
+            needle_at = needle.0,
+            needle_len = needle.1,
+            hit_at = hit.0,
+            hit_len = hit.1,
+            empty_at = empty.0,
+            empty_len = empty.1,
+        );
+
+        wat::parse_str(wat).expect("Invalid WAT")
+    }
+}
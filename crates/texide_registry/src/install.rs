@@ -0,0 +1,773 @@
+//! Concurrent plugin installation into the download cache.
+//!
+//! Each plugin is installed by fetching its `texide-rule.json` manifest,
+//! downloading the WASM artifact it points to, and verifying the artifact's
+//! SHA-256 before writing both into the cache:
+//!
+//! ```text
+//! <cache_dir>/<name>/<version>/
+//! ├── <artifact>.wasm
+//! └── texide-rule.json
+//! ```
+//!
+//! Downloads go through the proxies named by `HTTP_PROXY`, `HTTPS_PROXY`,
+//! and `ALL_PROXY` (minus `NO_PROXY`), and URLs can be redirected to an
+//! internal mirror with [`Installer::with_mirrors`].
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, warn};
+
+use crate::manifest::{ManifestError, validate_manifest};
+
+/// Default number of plugins downloaded at the same time.
+pub const DEFAULT_JOBS: usize = 4;
+
+/// Default number of retries after a transient network failure.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default delay before the first retry; later retries double it.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Error type for plugin installation.
+#[derive(Debug, Error)]
+pub enum InstallError {
+    #[error("Invalid URL '{0}'")]
+    InvalidUrl(String),
+    #[error("Failed to download {url}: {source}")]
+    Http {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Failed to download {url}: HTTP {status}")]
+    Status { url: String, status: u16 },
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+    #[error("Checksum mismatch for {url}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Failed to write {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Installation task for '{0}' panicked")]
+    Panicked(String),
+    #[error("{}", format_failures(.0))]
+    Failed(Vec<PluginFailure>),
+}
+
+/// A plugin that failed to install.
+#[derive(Debug)]
+pub struct PluginFailure {
+    pub name: String,
+    pub error: InstallError,
+}
+
+fn format_failures(failures: &[PluginFailure]) -> String {
+    let mut message = format!("{} plugin(s) failed to install:", failures.len());
+    for failure in failures {
+        let _ = write!(message, "\n  {}: {}", failure.name, failure.error);
+    }
+    message
+}
+
+/// A plugin to install, identified by the URL of its manifest.
+#[derive(Debug, Clone)]
+pub struct PluginSource {
+    /// Name used for the cache directory (the rule alias).
+    pub name: String,
+    /// URL of the plugin's `texide-rule.json`.
+    pub manifest_url: String,
+}
+
+/// A plugin that was installed into the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPlugin {
+    pub name: String,
+    pub version: String,
+    /// Path of the verified WASM artifact.
+    pub wasm_path: PathBuf,
+}
+
+/// Progress of a bulk installation, reported as each plugin completes.
+#[derive(Debug)]
+pub struct InstallProgress<'a> {
+    pub name: &'a str,
+    /// Number of plugins finished so far, including this one.
+    pub completed: usize,
+    pub total: usize,
+    pub result: Result<&'a InstalledPlugin, &'a InstallError>,
+}
+
+type ProgressFn = dyn Fn(&InstallProgress<'_>) + Send + Sync;
+
+/// Installs plugins into the download cache, several at a time.
+pub struct Installer {
+    fetcher: Fetcher,
+    cache_dir: PathBuf,
+    jobs: usize,
+    progress: Option<Arc<ProgressFn>>,
+}
+
+impl Installer {
+    /// Creates an installer writing into `cache_dir`.
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            fetcher: Fetcher {
+                client: build_client(|key| std::env::var(key).ok()),
+                mirrors: Arc::new(Vec::new()),
+                max_retries: DEFAULT_MAX_RETRIES,
+                base_delay: DEFAULT_RETRY_DELAY,
+            },
+            cache_dir: cache_dir.into(),
+            jobs: DEFAULT_JOBS,
+            progress: None,
+        }
+    }
+
+    /// Returns the global download cache (`~/.texide/cache/plugins`).
+    pub fn default_cache_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".texide").join("cache").join("plugins"))
+    }
+
+    /// Sets how many plugins are downloaded at the same time (at least 1).
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Sets how often a download is retried after a 5xx response, a failed
+    /// connection or a timeout, and the delay before the first retry.
+    ///
+    /// Each further retry doubles the delay and adds random jitter of up to
+    /// `base_delay`. Other failures, such as 404s, are never retried.
+    pub fn with_retries(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        self.fetcher.max_retries = max_retries;
+        self.fetcher.base_delay = base_delay;
+        self
+    }
+
+    /// Sets URL prefix rewrites, mapping an upstream prefix to the mirror
+    /// prefix that replaces it. The longest matching prefix wins.
+    pub fn with_mirrors(mut self, mirrors: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut mirrors: Vec<_> = mirrors.into_iter().collect();
+        mirrors.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self.fetcher.mirrors = Arc::new(mirrors);
+        self
+    }
+
+    /// Sets a callback invoked as each plugin finishes, successfully or not.
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(&InstallProgress<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Installs all `sources`.
+    ///
+    /// A failing plugin does not stop the others; once every download has
+    /// finished, all failures are returned together as [`InstallError::Failed`].
+    pub async fn install_all(
+        &self,
+        sources: Vec<PluginSource>,
+    ) -> Result<Vec<InstalledPlugin>, InstallError> {
+        let total = sources.len();
+        let permits = Arc::new(Semaphore::new(self.jobs));
+        let mut tasks = JoinSet::new();
+        let mut names = HashMap::new();
+
+        for source in sources {
+            let fetcher = self.fetcher.clone();
+            let cache_dir = self.cache_dir.clone();
+            let permits = Arc::clone(&permits);
+            let name = source.name.clone();
+
+            let handle = tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                install_one(&fetcher, &cache_dir, &source).await
+            });
+            names.insert(handle.id(), name);
+        }
+
+        let mut installed = Vec::with_capacity(total);
+        let mut failures = Vec::new();
+
+        while let Some(joined) = tasks.join_next_with_id().await {
+            let (name, result) = match joined {
+                Ok((id, result)) => (names.remove(&id).unwrap_or_default(), result),
+                Err(e) => {
+                    let name = names.remove(&e.id()).unwrap_or_default();
+                    (name.clone(), Err(InstallError::Panicked(name)))
+                }
+            };
+
+            if let Some(progress) = &self.progress {
+                progress(&InstallProgress {
+                    name: &name,
+                    completed: installed.len() + failures.len() + 1,
+                    total,
+                    result: result.as_ref(),
+                });
+            }
+
+            match result {
+                Ok(plugin) => installed.push(plugin),
+                Err(error) => failures.push(PluginFailure { name, error }),
+            }
+        }
+
+        if !failures.is_empty() {
+            failures.sort_by(|a, b| a.name.cmp(&b.name));
+            return Err(InstallError::Failed(failures));
+        }
+
+        installed.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(installed)
+    }
+}
+
+/// Fetches, verifies, and caches a single plugin.
+async fn install_one(
+    fetcher: &Fetcher,
+    cache_dir: &Path,
+    source: &PluginSource,
+) -> Result<InstalledPlugin, InstallError> {
+    let manifest_url = fetcher.url(&source.manifest_url)?;
+    let manifest_bytes = fetcher.get(&manifest_url).await?;
+    let manifest_json = String::from_utf8_lossy(&manifest_bytes);
+    let manifest = validate_manifest(&manifest_json)?;
+
+    let wasm_url = fetcher.url(&manifest.artifacts.wasm)?;
+    let wasm = fetcher.get(&wasm_url).await?;
+
+    let actual = hex(&Sha256::digest(&wasm));
+    if !actual.eq_ignore_ascii_case(&manifest.artifacts.sha256) {
+        return Err(InstallError::ChecksumMismatch {
+            url: wasm_url.to_string(),
+            expected: manifest.artifacts.sha256,
+            actual,
+        });
+    }
+
+    let dir = cache_dir.join(&source.name).join(&manifest.rule.version);
+    let file_name = wasm_url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}.wasm", source.name));
+    let wasm_path = dir.join(file_name);
+
+    write(&dir, &wasm_path, &wasm).await?;
+    write(&dir, &dir.join("texide-rule.json"), &manifest_bytes).await?;
+    debug!("Installed {} to {}", source.name, wasm_path.display());
+
+    Ok(InstalledPlugin {
+        name: source.name.clone(),
+        version: manifest.rule.version,
+        wasm_path,
+    })
+}
+
+/// Builds an HTTP client using the proxy variables returned by `env`.
+fn build_client(env: impl Fn(&str) -> Option<String>) -> reqwest::Client {
+    let var = |name: &str| env(name).or_else(|| env(&name.to_lowercase()));
+    let mut builder = reqwest::Client::builder().no_proxy();
+    let no_proxy = var("NO_PROXY").and_then(|list| reqwest::NoProxy::from_string(&list));
+
+    for name in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+        let Some(url) = var(name) else { continue };
+        let proxy = match name {
+            "HTTP_PROXY" => reqwest::Proxy::http(&url),
+            "HTTPS_PROXY" => reqwest::Proxy::https(&url),
+            _ => reqwest::Proxy::all(&url),
+        };
+        match proxy {
+            Ok(proxy) => builder = builder.proxy(proxy.no_proxy(no_proxy.clone())),
+            Err(e) => warn!("Ignoring invalid {} '{}': {}", name, url, e),
+        }
+    }
+
+    builder.build().unwrap_or_else(|e| {
+        warn!("Failed to configure HTTP client, using defaults: {}", e);
+        reqwest::Client::new()
+    })
+}
+
+/// HTTP client that rewrites URLs to mirrors and retries transient failures
+/// with exponential backoff.
+#[derive(Clone)]
+struct Fetcher {
+    client: reqwest::Client,
+    /// Prefix rewrites, longest prefix first.
+    mirrors: Arc<Vec<(String, String)>>,
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl Fetcher {
+    /// Parses `url` after applying the first matching mirror rewrite.
+    fn url(&self, url: &str) -> Result<Url, InstallError> {
+        let rewritten = self
+            .mirrors
+            .iter()
+            .find_map(|(prefix, mirror)| {
+                url.strip_prefix(prefix.as_str())
+                    .map(|rest| format!("{}{}", mirror, rest))
+            })
+            .unwrap_or_else(|| url.to_string());
+
+        if rewritten != url {
+            debug!("Rewrote {} to {}", url, rewritten);
+        }
+        Url::parse(&rewritten).map_err(|_| InstallError::InvalidUrl(rewritten))
+    }
+
+    async fn get(&self, url: &Url) -> Result<Vec<u8>, InstallError> {
+        let mut attempt = 0;
+        loop {
+            match self.get_once(url).await {
+                Err(e) if is_transient(&e) && attempt < self.max_retries => {
+                    let delay = self.backoff(attempt);
+                    attempt += 1;
+                    debug!(
+                        "Retrying {} in {:?} (attempt {}/{}): {}",
+                        url, delay, attempt, self.max_retries, e
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn get_once(&self, url: &Url) -> Result<Vec<u8>, InstallError> {
+        let http_error = |source| InstallError::Http {
+            url: url.to_string(),
+            source,
+        };
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(http_error)?;
+        if !response.status().is_success() {
+            return Err(InstallError::Status {
+                url: url.to_string(),
+                status: response.status().as_u16(),
+            });
+        }
+
+        Ok(response.bytes().await.map_err(http_error)?.to_vec())
+    }
+
+    /// Returns `base_delay * 2^attempt` plus up to `base_delay` of jitter.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.base_delay.saturating_mul(1 << attempt.min(16));
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos());
+        let jitter = self.base_delay.mul_f64(f64::from(nanos) / 1e9);
+        exponential + jitter
+    }
+}
+
+/// Returns true for failures worth retrying: 5xx responses, failed
+/// connections and timeouts.
+fn is_transient(error: &InstallError) -> bool {
+    match error {
+        InstallError::Status { status, .. } => *status >= 500,
+        InstallError::Http { source, .. } => source.is_connect() || source.is_timeout(),
+        _ => false,
+    }
+}
+
+async fn write(dir: &Path, path: &Path, contents: &[u8]) -> Result<(), InstallError> {
+    let io_error = |source| InstallError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    tokio::fs::create_dir_all(dir).await.map_err(io_error)?;
+    tokio::fs::write(path, contents).await.map_err(io_error)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{:02x}", b);
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Binds a local port and returns it with its base URL.
+    async fn bind() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    /// Serves `files` over HTTP on `listener`, keyed by request path.
+    fn serve(listener: TcpListener, files: HashMap<String, Vec<u8>>) {
+        serve_with(listener, move |path| match files.get(path) {
+            Some(body) => (200, body.clone()),
+            None => (404, Vec::new()),
+        });
+    }
+
+    /// Serves HTTP on `listener`, answering each request path with the
+    /// status and body returned by `handler`.
+    fn serve_with(
+        listener: TcpListener,
+        handler: impl Fn(&str) -> (u16, Vec<u8>) + Send + Sync + 'static,
+    ) {
+        let handler = Arc::new(handler);
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let handler = Arc::clone(&handler);
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+                    let (status, body) = handler(path);
+                    let mut response = format!(
+                        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        status,
+                        body.len()
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(&body);
+                    let _ = stream.write_all(&response).await;
+                });
+            }
+        });
+    }
+
+    /// Adds a plugin named `name` to `files` and returns its source.
+    fn fixture(
+        files: &mut HashMap<String, Vec<u8>>,
+        base: &str,
+        name: &str,
+        sha256: Option<&str>,
+    ) -> PluginSource {
+        let wasm = format!("wasm for {}", name).into_bytes();
+        let sha256 = sha256.map_or_else(|| hex(&Sha256::digest(&wasm)), str::to_string);
+        let manifest = format!(
+            r#"{{
+                "rule": {{ "name": "{name}", "version": "1.0.0" }},
+                "artifacts": {{ "wasm": "{base}/{name}/{name}.wasm", "sha256": "{sha256}" }}
+            }}"#
+        );
+
+        files.insert(format!("/{name}/{name}.wasm"), wasm);
+        files.insert(format!("/{}/texide-rule.json", name), manifest.into_bytes());
+        PluginSource {
+            name: name.to_string(),
+            manifest_url: format!("{}/{}/texide-rule.json", base, name),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_install_all_concurrently() {
+        let (listener, base) = bind().await;
+        let mut files = HashMap::new();
+        let sources: Vec<PluginSource> = ["alpha", "beta", "gamma"]
+            .iter()
+            .map(|name| fixture(&mut files, &base, name, None))
+            .collect();
+        serve(listener, files);
+        let cache = tempfile::tempdir().unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&seen);
+        let installer = Installer::new(cache.path())
+            .with_jobs(3)
+            .with_progress(move |progress| {
+                assert_eq!(progress.total, 3);
+                assert!(progress.result.is_ok());
+                recorded.lock().unwrap().push(progress.completed);
+            });
+
+        let installed = installer.install_all(sources).await.unwrap();
+
+        let names: Vec<&str> = installed.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta", "gamma"]);
+        for name in names {
+            let dir = cache.path().join(name).join("1.0.0");
+            assert_eq!(
+                std::fs::read(dir.join(format!("{}.wasm", name))).unwrap(),
+                format!("wasm for {}", name).into_bytes()
+            );
+            assert!(dir.join("texide-rule.json").is_file());
+        }
+
+        let mut completed = seen.lock().unwrap().clone();
+        completed.sort();
+        assert_eq!(completed, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_install_all_reports_failures_after_others_finish() {
+        let (listener, base) = bind().await;
+        let mut files = HashMap::new();
+        let bad_hash = "0".repeat(64);
+        let sources = vec![
+            fixture(&mut files, &base, "good", None),
+            fixture(&mut files, &base, "bad", Some(&bad_hash)),
+        ];
+        serve(listener, files);
+        let cache = tempfile::tempdir().unwrap();
+
+        let error = Installer::new(cache.path())
+            .install_all(sources)
+            .await
+            .unwrap_err();
+
+        let InstallError::Failed(failures) = error else {
+            panic!("Expected aggregate error, got {:?}", error);
+        };
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "bad");
+        assert!(matches!(
+            failures[0].error,
+            InstallError::ChecksumMismatch { .. }
+        ));
+        assert!(cache.path().join("good/1.0.0/good.wasm").is_file());
+        assert!(!cache.path().join("bad").exists());
+    }
+
+    /// Serves one fixture plugin whose WASM request fails with `status`
+    /// `failures` times before succeeding, and returns the request counter.
+    async fn flaky_plugin(status: u16, failures: usize) -> (PluginSource, Arc<AtomicUsize>) {
+        let (listener, base) = bind().await;
+        let mut files = HashMap::new();
+        let source = fixture(&mut files, &base, "flaky", None);
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&requests);
+        serve_with(listener, move |path| {
+            if path.ends_with(".wasm") && counter.fetch_add(1, Ordering::SeqCst) < failures {
+                return (status, Vec::new());
+            }
+            files
+                .get(path)
+                .map_or((404, Vec::new()), |body| (200, body.clone()))
+        });
+
+        (source, requests)
+    }
+
+    #[tokio::test]
+    async fn test_install_retries_transient_failures() {
+        let (source, requests) = flaky_plugin(503, 2).await;
+        let cache = tempfile::tempdir().unwrap();
+
+        let installed = Installer::new(cache.path())
+            .with_retries(3, Duration::from_millis(1))
+            .install_all(vec![source])
+            .await
+            .unwrap();
+
+        assert_eq!(installed[0].name, "flaky");
+        assert!(installed[0].wasm_path.is_file());
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_install_gives_up_after_max_retries() {
+        let (source, requests) = flaky_plugin(503, 5).await;
+        let cache = tempfile::tempdir().unwrap();
+
+        let error = Installer::new(cache.path())
+            .with_retries(2, Duration::from_millis(1))
+            .install_all(vec![source])
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("HTTP 503"));
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_install_does_not_retry_not_found() {
+        let (source, requests) = flaky_plugin(404, 1).await;
+        let cache = tempfile::tempdir().unwrap();
+
+        let error = Installer::new(cache.path())
+            .with_retries(3, Duration::from_millis(1))
+            .install_all(vec![source])
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("HTTP 404"));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially() {
+        let fetcher = Installer::new("unused")
+            .with_retries(3, Duration::from_millis(100))
+            .fetcher;
+
+        for attempt in 0..3 {
+            let delay = fetcher.backoff(attempt);
+            let floor = Duration::from_millis(100 << attempt);
+            assert!(delay >= floor && delay < floor + Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_mirror_rewrites_longest_prefix() {
+        let fetcher = Installer::new("unused")
+            .with_mirrors([
+                (
+                    "https://github.com/".to_string(),
+                    "https://mirror.example.com/github/".to_string(),
+                ),
+                (
+                    "https://github.com/simorgh3196/".to_string(),
+                    "https://mirror.example.com/trusted/".to_string(),
+                ),
+            ])
+            .fetcher;
+
+        assert_eq!(
+            fetcher
+                .url("https://github.com/alice/rule/releases/download/v1/rule.wasm")
+                .unwrap()
+                .as_str(),
+            "https://mirror.example.com/github/alice/rule/releases/download/v1/rule.wasm"
+        );
+        assert_eq!(
+            fetcher
+                .url("https://github.com/simorgh3196/rule/texide-rule.json")
+                .unwrap()
+                .as_str(),
+            "https://mirror.example.com/trusted/rule/texide-rule.json"
+        );
+        assert_eq!(
+            fetcher
+                .url("https://example.com/rule.wasm")
+                .unwrap()
+                .as_str(),
+            "https://example.com/rule.wasm"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_install_through_mirror() {
+        let (listener, base) = bind().await;
+        let mut files = HashMap::new();
+        let mut source = fixture(&mut files, "https://github.com", "mirrored", None);
+        // The fixture's manifest points at github.com; serve it locally instead
+        let files = files
+            .into_iter()
+            .map(|(path, body)| {
+                let body = String::from_utf8(body)
+                    .unwrap()
+                    .replace("https://github.com", &base);
+                (path, body.into_bytes())
+            })
+            .collect();
+        serve(listener, files);
+        source.manifest_url = "https://github.com/mirrored/texide-rule.json".to_string();
+        let cache = tempfile::tempdir().unwrap();
+
+        let installed = Installer::new(cache.path())
+            .with_mirrors([("https://github.com".to_string(), base.clone())])
+            .install_all(vec![source])
+            .await
+            .unwrap();
+
+        assert!(installed[0].wasm_path.is_file());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_env_configures_client() {
+        let (listener, proxy) = bind().await;
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requested);
+        serve_with(listener, move |target| {
+            seen.lock().unwrap().push(target.to_string());
+            (200, b"proxied".to_vec())
+        });
+
+        let client = build_client(|key| (key == "HTTP_PROXY").then(|| proxy.clone()));
+        let body = client
+            .get("http://plugins.invalid/rule/texide-rule.json")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        assert_eq!(body, "proxied");
+        assert_eq!(
+            *requested.lock().unwrap(),
+            ["http://plugins.invalid/rule/texide-rule.json"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_proxy_bypasses_proxy() {
+        let (proxy_listener, proxy) = bind().await;
+        serve_with(proxy_listener, |_| (200, b"proxied".to_vec()));
+        let (listener, base) = bind().await;
+        serve_with(listener, |_| (200, b"direct".to_vec()));
+
+        let client = build_client(|key| match key {
+            "HTTP_PROXY" => Some(proxy.clone()),
+            "no_proxy" => Some("127.0.0.1".to_string()),
+            _ => None,
+        });
+        let body = client
+            .get(format!("{}/rule.wasm", base))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        assert_eq!(body, "direct");
+    }
+}
@@ -1,5 +1,7 @@
 //! Texide Plugin Registry and Manifest Management.
 
+pub mod install;
 pub mod manifest;
 
+pub use install::{InstallError, InstalledPlugin, Installer, PluginSource};
 pub use manifest::{ExternalRuleManifest, ManifestError};
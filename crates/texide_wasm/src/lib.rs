@@ -230,6 +230,7 @@ struct JsDiagnostic {
     end_column: Option<u32>,
     severity: String,
     fix: Option<JsFix>,
+    help_url: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -261,6 +262,7 @@ impl From<Diagnostic> for JsDiagnostic {
                 end: f.span.end,
                 text: f.text,
             }),
+            help_url: d.help_url,
         }
     }
 }
@@ -271,7 +273,7 @@ fn ast_to_json(node: &texide_ast::TxtNode) -> serde_json::Value {
 
     obj.insert(
         "type".to_string(),
-        serde_json::Value::String(format!("{:?}", node.node_type)),
+        serde_json::Value::String(node.type_name().to_string()),
     );
 
     obj.insert(
@@ -304,18 +306,48 @@ fn ast_to_json(node: &texide_ast::TxtNode) -> serde_json::Value {
             serde_json::Value::String(title.to_string()),
         );
     }
+    if let Some(alt) = node.data.alt {
+        obj.insert(
+            "alt".to_string(),
+            serde_json::Value::String(alt.to_string()),
+        );
+    }
     if let Some(depth) = node.data.depth {
         obj.insert("depth".to_string(), serde_json::Value::Number(depth.into()));
     }
     if let Some(ordered) = node.data.ordered {
         obj.insert("ordered".to_string(), serde_json::Value::Bool(ordered));
     }
+    if let Some(start) = node.data.start {
+        obj.insert("start".to_string(), serde_json::Value::Number(start.into()));
+    }
+    if let Some(ordinal) = node.data.ordinal {
+        obj.insert(
+            "ordinal".to_string(),
+            serde_json::Value::Number(ordinal.into()),
+        );
+    }
     if let Some(lang) = node.data.lang {
         obj.insert(
             "lang".to_string(),
             serde_json::Value::String(lang.to_string()),
         );
     }
+    if let Some(info) = node.data.info {
+        obj.insert(
+            "info".to_string(),
+            serde_json::Value::String(info.to_string()),
+        );
+    }
+    if let Some(fence) = node.data.fence {
+        obj.insert(
+            "fence".to_string(),
+            serde_json::Value::String(fence.to_string()),
+        );
+    }
+    if let Some(align) = node.data.align {
+        obj.insert("align".to_string(), serde_json::json!(align));
+    }
 
     serde_json::Value::Object(obj)
 }
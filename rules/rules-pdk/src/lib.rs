@@ -2,6 +2,8 @@
 //!
 //! This crate provides shared type definitions used across all rule implementations.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Request sent to a rule's lint function.
@@ -11,7 +13,7 @@ pub struct LintRequest {
     pub node: serde_json::Value,
     /// Rule configuration.
     pub config: serde_json::Value,
-    /// Full source text.
+    /// Full source text, byte for byte; node ranges are byte offsets into it.
     pub source: String,
     /// File path (if available).
     pub file_path: Option<String>,
@@ -134,6 +136,12 @@ pub struct Diagnostic {
     pub rule_id: String,
     /// The diagnostic message.
     pub message: String,
+    /// Key of the message in locale bundles, used to translate it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+    /// Values for the `{name}` placeholders in the message template.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub message_params: BTreeMap<String, String>,
     /// Byte span in the source.
     pub span: Span,
     /// Severity level.
@@ -142,6 +150,9 @@ pub struct Diagnostic {
     /// Optional fix for this diagnostic.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fix: Option<Fix>,
+    /// Optional URL with more information about this diagnostic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
 }
 
 impl Diagnostic {
@@ -150,9 +161,12 @@ impl Diagnostic {
         Self {
             rule_id: rule_id.into(),
             message: message.into(),
+            message_key: None,
+            message_params: BTreeMap::new(),
             span,
             severity: Severity::Error,
             fix: None,
+            help_url: None,
         }
     }
 
@@ -161,9 +175,12 @@ impl Diagnostic {
         Self {
             rule_id: rule_id.into(),
             message: message.into(),
+            message_key: None,
+            message_params: BTreeMap::new(),
             span,
             severity: Severity::Warning,
             fix: None,
+            help_url: None,
         }
     }
 
@@ -178,6 +195,24 @@ impl Diagnostic {
         self.fix = Some(fix);
         self
     }
+
+    /// Sets the help URL.
+    pub fn with_help_url(mut self, help_url: impl Into<String>) -> Self {
+        self.help_url = Some(help_url.into());
+        self
+    }
+
+    /// Sets the key the message is translated by.
+    pub fn with_message_key(mut self, key: impl Into<String>) -> Self {
+        self.message_key = Some(key.into());
+        self
+    }
+
+    /// Sets the value of the `{name}` placeholder in the message template.
+    pub fn with_message_param(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.message_params.insert(name.into(), value.to_string());
+        self
+    }
 }
 
 /// Byte span in source text.
@@ -270,6 +305,13 @@ pub struct RuleManifest {
     /// Node types this rule is interested in.
     #[serde(default)]
     pub node_types: Vec<String>,
+    /// Categories of the rule's diagnostics (e.g., "accessibility").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Documentation URL for the rule's diagnostics; `{rule_id}` is
+    /// replaced with the diagnostic's rule ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
 }
 
 impl RuleManifest {
@@ -281,6 +323,8 @@ impl RuleManifest {
             description: None,
             fixable: false,
             node_types: Vec::new(),
+            tags: Vec::new(),
+            help_url: None,
         }
     }
 
@@ -301,6 +345,18 @@ impl RuleManifest {
         self.node_types = node_types;
         self
     }
+
+    /// Sets the categories of the rule's diagnostics.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets the documentation URL template for the rule's diagnostics.
+    pub fn with_help_url(mut self, help_url: impl Into<String>) -> Self {
+        self.help_url = Some(help_url.into());
+        self
+    }
 }
 
 /// Helper to extract text range from a node.